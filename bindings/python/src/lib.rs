@@ -335,10 +335,15 @@ impl EntryMode {
         self.0.is_dir()
     }
 
+    pub fn is_symlink(&self) -> bool {
+        self.0.is_symlink()
+    }
+
     pub fn __repr__(&self) -> &'static str {
         match self.0 {
             od::EntryMode::FILE => "EntryMode.FILE",
             od::EntryMode::DIR => "EntryMode.DIR",
+            od::EntryMode::SYMLINK => "EntryMode.SYMLINK",
             od::EntryMode::Unknown => "EntryMode.UNKNOWN",
         }
     }
@@ -0,0 +1,569 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use async_trait::async_trait;
+use log::warn;
+use serde::Deserialize;
+use serde::Serialize;
+use time::OffsetDateTime;
+
+use crate::ops::*;
+use crate::raw::oio::ReadExt;
+use crate::raw::*;
+use crate::*;
+
+/// Add read through cache for the underlying service.
+///
+/// Objects fetched from the underlying service (the origin) will be stored
+/// in the cache operator, for example a local fs. Cached objects will
+/// always be revalidated with their `ETag` or `Last-Modified` before being
+/// served:
+///
+/// - If the origin supports conditional stat, a stat with `If-None-Match`
+///   or `If-Modified-Since` will be sent, and the cached object will be
+///   served while the origin returns `ConditionNotMatch` (`304`).
+/// - Otherwise, a plain stat will be sent and the validators will be
+///   compared locally.
+///
+/// # Notes
+///
+/// - Only full reads will be cached, range reads always bypass the cache.
+/// - Reads with version, encryption, headers or decompress always bypass
+///   the cache since their content depends on the args.
+/// - Cached objects will be loaded into memory as a whole, please use this
+///   layer for small objects only.
+/// - Objects without validators will never be served from the cache.
+/// - Failures from the cache operator will be logged and ignored.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::CacheLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let cache = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .finish();
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(CacheLayer::new(cache))
+///     .finish();
+/// ```
+#[derive(Debug, Clone)]
+pub struct CacheLayer {
+    cache: Operator,
+}
+
+impl CacheLayer {
+    /// Create a new cache layer that stores objects in given operator.
+    pub fn new(cache: Operator) -> Self {
+        Self { cache }
+    }
+}
+
+impl<A: Accessor> Layer<A> for CacheLayer {
+    type LayeredAccessor = CacheAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        let conditional = inner
+            .info()
+            .capabilities()
+            .contains(AccessorCapability::ConditionalStat);
+
+        CacheAccessor {
+            inner,
+            cache: self.cache.clone(),
+            conditional,
+        }
+    }
+}
+
+/// Validators of a cached object.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheMeta {
+    etag: Option<String>,
+    last_modified: Option<OffsetDateTime>,
+}
+
+impl CacheMeta {
+    fn new(meta: &Metadata) -> Self {
+        let bit = meta.bit();
+        let complete = bit.contains(Metakey::Complete);
+
+        CacheMeta {
+            etag: if complete || bit.contains(Metakey::Etag) {
+                meta.etag().map(|v| v.to_string())
+            } else {
+                None
+            },
+            last_modified: if complete || bit.contains(Metakey::LastModified) {
+                meta.last_modified()
+            } else {
+                None
+            },
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+
+    /// Check if the origin object is still the same as cached one.
+    fn is_fresh(&self, current: &CacheMeta) -> bool {
+        match (&self.etag, &current.etag) {
+            (Some(cached), Some(current)) => cached == current,
+            _ => match (self.last_modified, current.last_modified) {
+                (Some(cached), Some(current)) => cached == current,
+                _ => false,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CacheAccessor<A: Accessor> {
+    inner: A,
+    cache: Operator,
+    conditional: bool,
+}
+
+impl<A: Accessor> CacheAccessor<A> {
+    fn data_path(path: &str) -> String {
+        format!("data/{path}")
+    }
+
+    fn meta_path(path: &str) -> String {
+        format!("meta/{path}")
+    }
+
+    /// Load cached object, returns `None` if not cached or broken.
+    async fn load(&self, path: &str) -> Option<(CacheMeta, Vec<u8>)> {
+        let meta = match self.cache.read(&Self::meta_path(path)).await {
+            Ok(bs) => bs,
+            Err(err) if err.kind() == ErrorKind::NotFound => return None,
+            Err(err) => {
+                warn!("cache layer load meta of {path} failed: {err:?}");
+                return None;
+            }
+        };
+        let meta: CacheMeta = match serde_json::from_slice(&meta) {
+            Ok(meta) => meta,
+            Err(err) => {
+                warn!("cache layer parse meta of {path} failed: {err:?}");
+                return None;
+            }
+        };
+
+        match self.cache.read(&Self::data_path(path)).await {
+            Ok(bs) => Some((meta, bs)),
+            Err(err) => {
+                if err.kind() != ErrorKind::NotFound {
+                    warn!("cache layer load data of {path} failed: {err:?}");
+                }
+                None
+            }
+        }
+    }
+
+    /// Store object into cache.
+    ///
+    /// Data will be written before meta so that we never serve a meta
+    /// without data.
+    async fn store(&self, path: &str, meta: &CacheMeta, bs: Vec<u8>) {
+        if meta.is_empty() {
+            return;
+        }
+
+        let meta = match serde_json::to_vec(meta) {
+            Ok(meta) => meta,
+            Err(err) => {
+                warn!("cache layer serialize meta of {path} failed: {err:?}");
+                return;
+            }
+        };
+
+        let res = match self.cache.write(&Self::data_path(path), bs).await {
+            Ok(()) => self.cache.write(&Self::meta_path(path), meta).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = res {
+            warn!("cache layer store {path} failed: {err:?}");
+        }
+    }
+
+    /// Remove object from cache.
+    ///
+    /// Meta will be removed before data so that we never serve a meta
+    /// without data.
+    async fn invalidate(&self, path: &str) {
+        let res = match self.cache.delete(&Self::meta_path(path)).await {
+            Ok(()) => self.cache.delete(&Self::data_path(path)).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = res {
+            warn!("cache layer invalidate {path} failed: {err:?}");
+        }
+    }
+
+    /// Revalidate cached object with origin.
+    ///
+    /// Returns `true` if cached object is still fresh.
+    async fn revalidate(&self, path: &str, cached: &CacheMeta) -> Result<bool> {
+        let mut args = OpStat::new();
+        if self.conditional {
+            if let Some(etag) = &cached.etag {
+                args = args.with_if_none_match(etag);
+            } else if let Some(t) = cached.last_modified {
+                args = args.with_if_modified_since(t);
+            }
+        }
+
+        match self.inner.stat(path, args).await {
+            Ok(rp) => Ok(cached.is_fresh(&CacheMeta::new(&rp.into_metadata()))),
+            Err(err) if err.kind() == ErrorKind::ConditionNotMatch => Ok(true),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Read the whole object from origin, returns the validators carried
+    /// by the read response and the content.
+    async fn read_all(&self, path: &str, args: OpRead) -> Result<(CacheMeta, Vec<u8>)> {
+        let (rp, mut r) = self.inner.read(path, args).await?;
+        let meta = rp.into_metadata();
+
+        let mut bs = Vec::with_capacity(meta.content_length_raw().unwrap_or_default() as usize);
+        let mut buf = vec![0; 64 * 1024];
+        loop {
+            match r.read(&mut buf).await? {
+                0 => break,
+                n => bs.extend_from_slice(&buf[..n]),
+            }
+        }
+
+        Ok((CacheMeta::new(&meta), bs))
+    }
+
+    /// Check if the read could be served from cache.
+    ///
+    /// Content of reads with any of those args depends on the args, and
+    /// the conditional stat won't carry them either.
+    fn is_cacheable(args: &OpRead) -> bool {
+        args.range().is_full()
+            && args.version().is_none()
+            && args.encryption().is_none()
+            && args.headers().is_empty()
+            && !args.decompress()
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for CacheAccessor<A> {
+    type Inner = A;
+    type Reader = oio::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        if !Self::is_cacheable(&args) {
+            return self
+                .inner
+                .read(path, args)
+                .await
+                .map(|(rp, r)| (rp, Box::new(r) as oio::Reader));
+        }
+
+        if let Some((cached, bs)) = self.load(path).await {
+            match self.revalidate(path, &cached).await {
+                Ok(true) => {
+                    return Ok((
+                        RpRead::new(bs.len() as u64),
+                        Box::new(oio::Cursor::from(bs)) as oio::Reader,
+                    ))
+                }
+                Ok(false) => {}
+                Err(err) => {
+                    if err.kind() == ErrorKind::NotFound {
+                        self.invalidate(path).await;
+                    }
+                    return Err(err);
+                }
+            }
+        }
+
+        let (current, bs) = self.read_all(path, args).await?;
+        let size = bs.len() as u64;
+        self.store(path, &current, bs.clone()).await;
+
+        Ok((
+            RpRead::new(size),
+            Box::new(oio::Cursor::from(bs)) as oio::Reader,
+        ))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.invalidate(path).await;
+        self.inner.write(path, args).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let rp = self.inner.delete(path, args).await?;
+        self.invalidate(path).await;
+        Ok(rp)
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+        self.inner.scan(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner.blocking_list(path, args)
+    }
+
+    fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        self.inner.blocking_scan(path, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    use bytes::Bytes;
+
+    use super::*;
+    use crate::services;
+
+    type Objects = Arc<Mutex<HashMap<String, (Bytes, String)>>>;
+
+    #[derive(Default, Clone)]
+    struct MockBuilder {
+        objects: Objects,
+        read_count: Arc<AtomicUsize>,
+        stat_count: Arc<AtomicUsize>,
+    }
+
+    impl Builder for MockBuilder {
+        const SCHEME: Scheme = Scheme::Custom("mock");
+        type Accessor = MockService;
+
+        fn from_map(_: HashMap<String, String>) -> Self {
+            Self::default()
+        }
+
+        fn build(&mut self) -> Result<Self::Accessor> {
+            Ok(MockService {
+                objects: self.objects.clone(),
+                read_count: self.read_count.clone(),
+                stat_count: self.stat_count.clone(),
+            })
+        }
+    }
+
+    /// MockService is an origin that supports conditional stat with etag.
+    #[derive(Debug, Clone)]
+    struct MockService {
+        objects: Objects,
+        read_count: Arc<AtomicUsize>,
+        stat_count: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Accessor for MockService {
+        type Reader = oio::Cursor;
+        type BlockingReader = ();
+        type Writer = ();
+        type BlockingWriter = ();
+        type Pager = ();
+        type BlockingPager = ();
+
+        fn info(&self) -> AccessorInfo {
+            let mut am = AccessorInfo::default();
+            am.set_capabilities(
+                AccessorCapability::Read
+                    | AccessorCapability::ReadWithHeader
+                    | AccessorCapability::ConditionalStat,
+            );
+            am
+        }
+
+        async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            self.read_count.fetch_add(1, Ordering::SeqCst);
+
+            let objects = self.objects.lock().unwrap();
+            let (bs, etag) = objects
+                .get(path)
+                .ok_or_else(|| Error::new(ErrorKind::NotFound, "not found"))?;
+            let bs = args.range().apply_on_bytes(bs.clone());
+
+            Ok((
+                RpRead::with_metadata(
+                    Metadata::new(EntryMode::FILE)
+                        .with_content_length(bs.len() as u64)
+                        .with_etag(etag.clone()),
+                ),
+                oio::Cursor::from(bs.to_vec()),
+            ))
+        }
+
+        async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+            self.stat_count.fetch_add(1, Ordering::SeqCst);
+
+            let objects = self.objects.lock().unwrap();
+            let (bs, etag) = objects
+                .get(path)
+                .ok_or_else(|| Error::new(ErrorKind::NotFound, "not found"))?;
+
+            if args.if_none_match() == Some(etag.as_str()) {
+                return Err(Error::new(ErrorKind::ConditionNotMatch, "not modified"));
+            }
+
+            Ok(RpStat::new(
+                Metadata::new(EntryMode::FILE)
+                    .with_content_length(bs.len() as u64)
+                    .with_etag(etag.clone()),
+            ))
+        }
+    }
+
+    fn new_operator() -> (MockBuilder, Operator, Operator) {
+        let builder = MockBuilder::default();
+        let cache = Operator::new(services::Memory::default())
+            .expect("must init")
+            .finish();
+        let op = Operator::new(builder.clone())
+            .expect("must init")
+            .layer(CacheLayer::new(cache.clone()))
+            .finish();
+
+        (builder, op, cache)
+    }
+
+    fn put(builder: &MockBuilder, path: &str, content: &'static str, etag: &str) {
+        builder.objects.lock().unwrap().insert(
+            path.to_string(),
+            (Bytes::from_static(content.as_bytes()), etag.to_string()),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cache_miss_then_hit() {
+        let (builder, op, cache) = new_operator();
+        put(&builder, "test", "Hello, World!", "\"v1\"");
+
+        let bs = op.read("test").await.expect("must read");
+        assert_eq!(bs, b"Hello, World!");
+        assert_eq!(builder.read_count.load(Ordering::SeqCst), 1);
+        // Validators of miss are taken from the read response.
+        assert_eq!(builder.stat_count.load(Ordering::SeqCst), 0);
+        assert_eq!(
+            cache.read("data/test").await.expect("must cached"),
+            b"Hello, World!"
+        );
+
+        // Revalidation unchanged, cached data will be served.
+        let bs = op.read("test").await.expect("must read");
+        assert_eq!(bs, b"Hello, World!");
+        assert_eq!(builder.read_count.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cache_revalidate_changed() {
+        let (builder, op, cache) = new_operator();
+        put(&builder, "test", "Hello, World!", "\"v1\"");
+
+        op.read("test").await.expect("must read");
+
+        put(&builder, "test", "Hello, OpenDAL!", "\"v2\"");
+        let bs = op.read("test").await.expect("must read");
+        assert_eq!(bs, b"Hello, OpenDAL!");
+        assert_eq!(builder.read_count.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            cache.read("data/test").await.expect("must cached"),
+            b"Hello, OpenDAL!"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cache_origin_deleted() {
+        let (builder, op, cache) = new_operator();
+        put(&builder, "test", "Hello, World!", "\"v1\"");
+
+        op.read("test").await.expect("must read");
+
+        builder.objects.lock().unwrap().remove("test");
+        let err = op.read("test").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        assert!(!cache.is_exist("meta/test").await.unwrap());
+        assert!(!cache.is_exist("data/test").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_cache_range_read_bypass() {
+        let (builder, op, cache) = new_operator();
+        put(&builder, "test", "Hello, World!", "\"v1\"");
+
+        let bs = op.range_read("test", 0..5).await.expect("must read");
+        assert_eq!(bs, b"Hello");
+        assert!(!cache.is_exist("data/test").await.unwrap());
+
+        op.read("test").await.expect("must read");
+        let bs = op.range_read("test", 7..12).await.expect("must read");
+        assert_eq!(bs, b"World");
+        assert_eq!(builder.read_count.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_cache_read_with_header_bypass() {
+        let (builder, op, cache) = new_operator();
+        put(&builder, "test", "Hello, World!", "\"v1\"");
+
+        let args = OpRead::new().with_header("x-test", "test");
+        let bs = op.read_with("test", args.clone()).await.expect("must read");
+        assert_eq!(bs, b"Hello, World!");
+        assert!(!cache.is_exist("data/test").await.unwrap());
+
+        op.read_with("test", args).await.expect("must read");
+        assert_eq!(builder.read_count.load(Ordering::SeqCst), 2);
+        assert_eq!(builder.stat_count.load(Ordering::SeqCst), 0);
+    }
+}
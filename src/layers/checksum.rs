@@ -0,0 +1,442 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use base64::engine::general_purpose;
+use base64::Engine;
+use bytes::Bytes;
+use futures::ready;
+use log::warn;
+use md5::Digest;
+use md5::Md5;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::*;
+
+/// Verify the content of reads against checksums returned by services.
+///
+/// # Notes
+///
+/// If the metadata returned by `read` carries a `content_crc32c` or
+/// `content_md5`, the digest will be computed incrementally while data
+/// streams in. A mismatch will be returned as [`ErrorKind::Unexpected`]
+/// at the end of stream.
+///
+/// - `content_crc32c` will be preferred if both of them are returned.
+/// - Checksums cover the whole object, so range reads will not be
+///   verified.
+/// - Verification will be skipped once the reader has been seeked.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::ReadChecksumLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(ReadChecksumLayer::new())
+///     .finish();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ReadChecksumLayer;
+
+impl ReadChecksumLayer {
+    /// Create a new read checksum layer.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<A: Accessor> Layer<A> for ReadChecksumLayer {
+    type LayeredAccessor = ReadChecksumAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        ReadChecksumAccessor { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReadChecksumAccessor<A: Accessor> {
+    inner: A,
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for ReadChecksumAccessor<A> {
+    type Inner = A;
+    type Reader = ChecksumReader<A::Reader>;
+    type BlockingReader = ChecksumReader<A::BlockingReader>;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let full = args.range().is_full();
+        let (rp, r) = self.inner.read(path, args).await?;
+
+        let checksum = if full {
+            Checksum::new(path, rp.metadata())
+        } else {
+            None
+        };
+        Ok((rp, ChecksumReader::new(r, path, checksum)))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+        self.inner.scan(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let full = args.range().is_full();
+        let (rp, r) = self.inner.blocking_read(path, args)?;
+
+        let checksum = if full {
+            Checksum::new(path, rp.metadata())
+        } else {
+            None
+        };
+        Ok((rp, ChecksumReader::new(r, path, checksum)))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner.blocking_list(path, args)
+    }
+
+    fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        self.inner.blocking_scan(path, args)
+    }
+}
+
+/// Incremental checksum with the expected digest.
+enum Checksum {
+    Crc32c { expected: u32, state: u32 },
+    Md5 { expected: Vec<u8>, state: Md5 },
+}
+
+impl Checksum {
+    /// Build checksum from metadata, returns `None` if there is no valid
+    /// checksum.
+    fn new(path: &str, meta: &Metadata) -> Option<Self> {
+        let bit = meta.bit();
+        let complete = bit.contains(Metakey::Complete);
+
+        if complete || bit.contains(Metakey::ContentCrc32c) {
+            if let Some(v) = meta.content_crc32c() {
+                match general_purpose::STANDARD.decode(v) {
+                    Ok(bs) if bs.len() == 4 => {
+                        return Some(Checksum::Crc32c {
+                            expected: u32::from_be_bytes([bs[0], bs[1], bs[2], bs[3]]),
+                            state: 0,
+                        })
+                    }
+                    _ => warn!("read checksum layer ignores invalid crc32c {v} of {path}"),
+                }
+            }
+        }
+
+        if complete || bit.contains(Metakey::ContentMd5) {
+            if let Some(v) = meta.content_md5() {
+                match general_purpose::STANDARD.decode(v) {
+                    Ok(bs) if bs.len() == 16 => {
+                        return Some(Checksum::Md5 {
+                            expected: bs,
+                            state: Md5::new(),
+                        })
+                    }
+                    _ => warn!("read checksum layer ignores invalid md5 {v} of {path}"),
+                }
+            }
+        }
+
+        None
+    }
+
+    fn update(&mut self, bs: &[u8]) {
+        match self {
+            Checksum::Crc32c { state, .. } => *state = crc32c::crc32c_append(*state, bs),
+            Checksum::Md5 { state, .. } => state.update(bs),
+        }
+    }
+
+    fn verify(&self, path: &str) -> Result<()> {
+        let (name, expected, actual) = match self {
+            Checksum::Crc32c { expected, state } => (
+                "crc32c",
+                general_purpose::STANDARD.encode(expected.to_be_bytes()),
+                general_purpose::STANDARD.encode(state.to_be_bytes()),
+            ),
+            Checksum::Md5 { expected, state } => (
+                "md5",
+                general_purpose::STANDARD.encode(expected),
+                general_purpose::STANDARD.encode(state.clone().finalize()),
+            ),
+        };
+
+        if expected == actual {
+            return Ok(());
+        }
+
+        Err(Error::new(
+            ErrorKind::Unexpected,
+            "checksum of read content is not match",
+        )
+        .with_context("path", path)
+        .with_context("checksum", name)
+        .with_context("expect", expected)
+        .with_context("actual", actual))
+    }
+}
+
+pub struct ChecksumReader<R> {
+    inner: R,
+    path: String,
+    checksum: Option<Checksum>,
+}
+
+impl<R> ChecksumReader<R> {
+    fn new(inner: R, path: &str, checksum: Option<Checksum>) -> Self {
+        Self {
+            inner,
+            path: path.to_string(),
+            checksum,
+        }
+    }
+
+    fn update(&mut self, bs: &[u8]) {
+        if let Some(checksum) = self.checksum.as_mut() {
+            checksum.update(bs);
+        }
+    }
+
+    /// Verify checksum while reaching the end of stream.
+    ///
+    /// The checksum will be kept on mismatch so that following reads
+    /// still fail instead of returning a clean EOF.
+    fn finish(&mut self) -> Result<()> {
+        if let Some(checksum) = &self.checksum {
+            checksum.verify(&self.path)?;
+        }
+        self.checksum = None;
+        Ok(())
+    }
+}
+
+impl<R: oio::Read> oio::Read for ChecksumReader<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        let n = ready!(self.inner.poll_read(cx, buf))?;
+        if n == 0 && !buf.is_empty() {
+            self.finish()?;
+        } else {
+            self.update(&buf[..n]);
+        }
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: io::SeekFrom) -> Poll<Result<u64>> {
+        let n = ready!(self.inner.poll_seek(cx, pos))?;
+        self.checksum = None;
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        match ready!(self.inner.poll_next(cx)) {
+            Some(Ok(bs)) => {
+                self.update(&bs);
+                Poll::Ready(Some(Ok(bs)))
+            }
+            Some(Err(err)) => Poll::Ready(Some(Err(err))),
+            None => match self.finish() {
+                Ok(()) => Poll::Ready(None),
+                Err(err) => Poll::Ready(Some(Err(err))),
+            },
+        }
+    }
+}
+
+impl<R: oio::BlockingRead> oio::BlockingRead for ChecksumReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        if n == 0 && !buf.is_empty() {
+            self.finish()?;
+        } else {
+            self.update(&buf[..n]);
+        }
+        Ok(n)
+    }
+
+    fn seek(&mut self, pos: io::SeekFrom) -> Result<u64> {
+        let n = self.inner.seek(pos)?;
+        self.checksum = None;
+        Ok(n)
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        match self.inner.next() {
+            Some(Ok(bs)) => {
+                self.update(&bs);
+                Some(Ok(bs))
+            }
+            Some(Err(err)) => Some(Err(err)),
+            None => self.finish().err().map(Err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[derive(Default, Clone)]
+    struct MockBuilder {
+        meta: Option<Metadata>,
+    }
+
+    impl Builder for MockBuilder {
+        const SCHEME: Scheme = Scheme::Custom("mock");
+        type Accessor = MockService;
+
+        fn from_map(_: HashMap<String, String>) -> Self {
+            Self::default()
+        }
+
+        fn build(&mut self) -> Result<Self::Accessor> {
+            Ok(MockService {
+                meta: self.meta.clone().expect("meta must be set"),
+            })
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    struct MockService {
+        meta: Metadata,
+    }
+
+    #[async_trait]
+    impl Accessor for MockService {
+        type Reader = oio::Cursor;
+        type BlockingReader = oio::Cursor;
+        type Writer = ();
+        type BlockingWriter = ();
+        type Pager = ();
+        type BlockingPager = ();
+
+        fn info(&self) -> AccessorInfo {
+            let mut am = AccessorInfo::default();
+            am.set_capabilities(AccessorCapability::Read | AccessorCapability::Blocking)
+                .set_hints(AccessorHint::ReadSeekable);
+
+            am
+        }
+
+        async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            self.blocking_read(path, args)
+        }
+
+        fn blocking_read(&self, _: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+            let bs = args
+                .range()
+                .apply_on_bytes(Bytes::from_static(b"Hello, World!"));
+            let mut meta = self.meta.clone();
+            meta.set_content_length(bs.len() as u64);
+            Ok((RpRead::with_metadata(meta), oio::Cursor::from(bs)))
+        }
+    }
+
+    fn new_operator(meta: Metadata) -> Operator {
+        let builder = MockBuilder { meta: Some(meta) };
+        Operator::new(builder)
+            .unwrap()
+            .layer(ReadChecksumLayer::new())
+            .finish()
+    }
+
+    fn content_md5(bs: &[u8]) -> String {
+        general_purpose::STANDARD.encode(Md5::digest(bs))
+    }
+
+    fn content_crc32c(bs: &[u8]) -> String {
+        general_purpose::STANDARD.encode(crc32c::crc32c(bs).to_be_bytes())
+    }
+
+    #[tokio::test]
+    async fn test_read_checksum_match() {
+        let meta = Metadata::new(EntryMode::FILE)
+            .with_content_length(13)
+            .with_content_md5(content_md5(b"Hello, World!"));
+        let op = new_operator(meta);
+        let bs = op.read("file").await.expect("must read");
+        assert_eq!(bs, b"Hello, World!");
+
+        let meta = Metadata::new(EntryMode::FILE)
+            .with_content_length(13)
+            .with_content_crc32c(content_crc32c(b"Hello, World!"));
+        let op = new_operator(meta);
+        let bs = op.read("file").await.expect("must read");
+        assert_eq!(bs, b"Hello, World!");
+        let bs = op.blocking().read("file").expect("must read");
+        assert_eq!(bs, b"Hello, World!");
+    }
+
+    #[tokio::test]
+    async fn test_read_checksum_mismatch() {
+        let meta = Metadata::new(EntryMode::FILE)
+            .with_content_length(13)
+            .with_content_md5(content_md5(b"Hello, Corrupted!"));
+        let op = new_operator(meta);
+        let err = op.read("file").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        let meta = Metadata::new(EntryMode::FILE)
+            .with_content_length(13)
+            .with_content_crc32c(content_crc32c(b"Hello, Corrupted!"));
+        let op = new_operator(meta);
+        let err = op.read("file").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        let err = op.blocking().read("file").expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        // Range reads are not covered by checksum.
+        let bs = op.range_read("file", 0..5).await.expect("must read");
+        assert_eq!(bs, b"Hello");
+    }
+}
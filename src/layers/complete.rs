@@ -25,6 +25,7 @@ use std::task::Poll;
 use async_trait::async_trait;
 
 use crate::ops::*;
+use crate::raw::oio::into_blocking_reader::RangeReader as BlockingRangeReader;
 use crate::raw::oio::into_reader::RangeReader;
 use crate::raw::oio::to_flat_pager;
 use crate::raw::oio::to_hierarchy_pager;
@@ -60,7 +61,8 @@ use crate::*;
 ///
 /// - If both `seekable` and `streamable`, return directly.
 /// - If not `streamable`, with [`oio::into_streamable_reader`].
-/// - If not `seekable`, with [`oio::into_reader::by_range`]
+/// - If not `seekable`, with [`oio::into_reader::by_range`] or
+///   [`oio::into_blocking_reader::by_range`] for blocking readers.
 /// - If neither not supported, wrap both by_range and into_streamable.
 ///
 /// All implementations of Reader should be `zero cost`. In our cases,
@@ -110,15 +112,19 @@ use crate::*;
 /// There are two styles of list, but not all services support both of
 /// them. CompleteLayer will add those capabilities in a zero cost way.
 ///
-/// Underlying services will return [`AccessorHint`] to indicate the
-/// features that returning pagers support.
+/// Underlying services will return [`AccessorCapability::List`] and
+/// [`AccessorCapability::Scan`] to indicate whether they support
+/// hierarchy (delimited by `/`) and flat listing natively.
 ///
 /// - If both `flat` and `hierarchy`, return directly.
-/// - If only `flat`, with [`oio::to_flat_pager`].
-/// - if only `hierarchy`, with [`oio::to_hierarchy_pager`].
+/// - If only `hierarchy`, flat listing will be emulated with
+///   [`oio::to_flat_pager`].
+/// - If only `flat`, hierarchy listing will be emulated with
+///   [`oio::to_hierarchy_pager`] which splits keys on `/` and returns
+///   every common prefix as a dir entry only once.
+///   `OpList::with_start_after` and `OpList::with_limit` will be applied
+///   while converting.
 /// - If neither not supported, something must be wrong.
-///
-/// [`AccessorHint`]: crate::raw::AccessorHint
 pub struct CompleteLayer;
 
 impl<A: Accessor> Layer<A> for CompleteLayer {
@@ -133,6 +139,14 @@ impl<A: Accessor> Layer<A> for CompleteLayer {
     }
 }
 
+/// Build the args of `scan` which is used to emulate `list`.
+fn list_to_scan(args: &OpList) -> OpScan {
+    match args.limit() {
+        Some(limit) => OpScan::new().with_limit(limit),
+        None => OpScan::new(),
+    }
+}
+
 /// Provide reader wrapper for backend.
 pub struct CompleteReaderAccessor<A: Accessor> {
     meta: AccessorInfo,
@@ -157,7 +171,8 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
         );
 
         let range = args.range();
-        let (rp, r) = self.inner.read(path, args).await?;
+        let seek_buffer = args.seek_buffer();
+        let (rp, r) = self.inner.read(path, args.clone()).await?;
         let content_length = rp.metadata().content_length();
 
         match (seekable, streamable) {
@@ -173,7 +188,7 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
                     (None, Some(size)) => {
                         // TODO: we can read content range to calculate
                         // the total content length.
-                        let om = self.inner.stat(path, OpStat::new()).await?.into_metadata();
+                        let om = self.inner.stat(path, args.to_stat()).await?.into_metadata();
                         let total_size = om.content_length();
                         let (offset, size) = if size > total_size {
                             (0, total_size)
@@ -184,7 +199,9 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
                         (offset, size)
                     }
                 };
-                let r = oio::into_reader::by_range(self.inner.clone(), path, r, offset, size);
+                let r = oio::into_reader::by_range(self.inner.clone(), path, r, offset, size)
+                    .with_buffer_size(seek_buffer)
+                    .with_args(args);
 
                 if streamable {
                     Ok((rp, CompleteReader::NeedSeekable(r)))
@@ -200,24 +217,54 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
         &self,
         path: &str,
         args: OpRead,
-    ) -> Result<(RpRead, CompleteReader<A, A::BlockingReader>)> {
+    ) -> Result<(RpRead, CompleteBlockingReader<A, A::BlockingReader>)> {
         let (seekable, streamable) = (
             self.meta.hints().contains(AccessorHint::ReadSeekable),
             self.meta.hints().contains(AccessorHint::ReadStreamable),
         );
 
-        let (rp, r) = self.inner.blocking_read(path, args)?;
+        let range = args.range();
+        let (rp, r) = self.inner.blocking_read(path, args.clone())?;
+        let content_length = rp.metadata().content_length();
 
         match (seekable, streamable) {
-            (true, true) => Ok((rp, CompleteReader::AlreadyComplete(r))),
+            (true, true) => Ok((rp, CompleteBlockingReader::AlreadyComplete(r))),
             (true, false) => {
                 let r = oio::into_streamable_reader(r, 256 * 1024);
-                Ok((rp, CompleteReader::NeedStreamable(r)))
+                Ok((rp, CompleteBlockingReader::NeedStreamable(r)))
+            }
+            _ => {
+                let (offset, size) = match (range.offset(), range.size()) {
+                    (Some(offset), _) => (offset, content_length),
+                    (None, None) => (0, content_length),
+                    (None, Some(size)) => {
+                        // TODO: we can read content range to calculate
+                        // the total content length.
+                        let om = self
+                            .inner
+                            .blocking_stat(path, args.to_stat())?
+                            .into_metadata();
+                        let total_size = om.content_length();
+                        let (offset, size) = if size > total_size {
+                            (0, total_size)
+                        } else {
+                            (total_size - size, size)
+                        };
+
+                        (offset, size)
+                    }
+                };
+                let r =
+                    oio::into_blocking_reader::by_range(self.inner.clone(), path, r, offset, size)
+                        .with_args(args);
+
+                if streamable {
+                    Ok((rp, CompleteBlockingReader::NeedSeekable(r)))
+                } else {
+                    let r = oio::into_streamable_reader(r, 256 * 1024);
+                    Ok((rp, CompleteBlockingReader::NeedBoth(r)))
+                }
             }
-            (false, _) => Err(Error::new(
-                ErrorKind::Unsupported,
-                "non seekable blocking reader is not supported",
-            )),
         }
     }
 
@@ -235,8 +282,8 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
             let (rp, p) = self.inner.list(path, args).await?;
             Ok((rp, CompletePager::AlreadyComplete(p)))
         } else if can_scan {
-            let (_, p) = self.inner.scan(path, OpScan::new()).await?;
-            let p = to_hierarchy_pager(p, path);
+            let (_, p) = self.inner.scan(path, list_to_scan(&args)).await?;
+            let p = to_hierarchy_pager(p, path).with_start_after(args.start_after());
             Ok((RpList::default(), CompletePager::NeedHierarchy(p)))
         } else {
             Err(
@@ -261,8 +308,8 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
             let (rp, p) = self.inner.blocking_list(path, args)?;
             Ok((rp, CompletePager::AlreadyComplete(p)))
         } else if can_scan {
-            let (_, p) = self.inner.blocking_scan(path, OpScan::new())?;
-            let p = to_hierarchy_pager(p, path);
+            let (_, p) = self.inner.blocking_scan(path, list_to_scan(&args))?;
+            let p = to_hierarchy_pager(p, path).with_start_after(args.start_after());
             Ok((RpList::default(), CompletePager::NeedHierarchy(p)))
         } else {
             Err(
@@ -328,7 +375,7 @@ impl<A: Accessor> CompleteReaderAccessor<A> {
 impl<A: Accessor> LayeredAccessor for CompleteReaderAccessor<A> {
     type Inner = A;
     type Reader = CompleteReader<A, A::Reader>;
-    type BlockingReader = CompleteReader<A, A::BlockingReader>;
+    type BlockingReader = CompleteBlockingReader<A, A::BlockingReader>;
     type Writer = A::Writer;
     type BlockingWriter = A::BlockingWriter;
     type Pager = CompletePager<A, A::Pager>;
@@ -435,38 +482,48 @@ where
     }
 }
 
-impl<A, R> oio::BlockingRead for CompleteReader<A, R>
+pub enum CompleteBlockingReader<A: Accessor, R> {
+    AlreadyComplete(R),
+    NeedSeekable(BlockingRangeReader<A>),
+    NeedStreamable(IntoStreamableReader<R>),
+    NeedBoth(IntoStreamableReader<BlockingRangeReader<A>>),
+}
+
+impl<A, R> oio::BlockingRead for CompleteBlockingReader<A, R>
 where
     A: Accessor<BlockingReader = R>,
     R: oio::BlockingRead,
 {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
-        use CompleteReader::*;
+        use CompleteBlockingReader::*;
 
         match self {
             AlreadyComplete(r) => r.read(buf),
+            NeedSeekable(r) => r.read(buf),
             NeedStreamable(r) => r.read(buf),
-            _ => unreachable!("not supported types of complete reader"),
+            NeedBoth(r) => r.read(buf),
         }
     }
 
     fn seek(&mut self, pos: io::SeekFrom) -> Result<u64> {
-        use CompleteReader::*;
+        use CompleteBlockingReader::*;
 
         match self {
             AlreadyComplete(r) => r.seek(pos),
+            NeedSeekable(r) => r.seek(pos),
             NeedStreamable(r) => r.seek(pos),
-            _ => unreachable!("not supported types of complete reader"),
+            NeedBoth(r) => r.seek(pos),
         }
     }
 
     fn next(&mut self) -> Option<Result<bytes::Bytes>> {
-        use CompleteReader::*;
+        use CompleteBlockingReader::*;
 
         match self {
             AlreadyComplete(r) => r.next(),
+            NeedSeekable(r) => r.next(),
             NeedStreamable(r) => r.next(),
-            _ => unreachable!("not supported types of complete reader"),
+            NeedBoth(r) => r.next(),
         }
     }
 }
@@ -509,3 +566,180 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::collections::HashMap;
+
+    use futures::TryStreamExt;
+
+    use super::*;
+
+    const KEYS: &[&str] = &["a", "b/", "b/c", "b/d/e", "f/g/h", "f/i", "j/", "k/l"];
+
+    #[derive(Default)]
+    struct MockBuilder {
+        native: bool,
+    }
+
+    impl Builder for MockBuilder {
+        const SCHEME: Scheme = Scheme::Custom("mock");
+        type Accessor = MockService;
+
+        fn from_map(_: HashMap<String, String>) -> Self {
+            Self::default()
+        }
+
+        fn build(&mut self) -> Result<Self::Accessor> {
+            Ok(MockService {
+                native: self.native,
+            })
+        }
+    }
+
+    /// MockService supports flat listing natively, and hierarchy listing
+    /// only if `native` is true.
+    #[derive(Debug)]
+    struct MockService {
+        native: bool,
+    }
+
+    #[async_trait]
+    impl Accessor for MockService {
+        type Reader = ();
+        type BlockingReader = ();
+        type Writer = ();
+        type BlockingWriter = ();
+        type Pager = MockPager;
+        type BlockingPager = MockPager;
+
+        fn info(&self) -> AccessorInfo {
+            let mut cap = AccessorCapability::Scan | AccessorCapability::Blocking;
+            if self.native {
+                cap |= AccessorCapability::List;
+            }
+
+            let mut am = AccessorInfo::default();
+            am.set_capabilities(cap);
+            am
+        }
+
+        async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+            self.blocking_list(path, args)
+        }
+
+        async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+            self.blocking_scan(path, args)
+        }
+
+        fn blocking_list(&self, path: &str, _: OpList) -> Result<(RpList, Self::BlockingPager)> {
+            assert!(self.native, "list must not be called if not supported");
+            let path = path.trim_start_matches('/');
+
+            let mut entries = BTreeSet::new();
+            for key in KEYS.iter().filter(|k| k.starts_with(path) && **k != path) {
+                let entry = match key[path.len()..].find('/') {
+                    Some(idx) => &key[..path.len() + idx + 1],
+                    None => key,
+                };
+                entries.insert(entry.to_string());
+            }
+
+            Ok((RpList::default(), MockPager::new(entries)))
+        }
+
+        fn blocking_scan(&self, path: &str, _: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+            let path = path.trim_start_matches('/');
+            let entries = KEYS
+                .iter()
+                .filter(|k| k.starts_with(path) && **k != path)
+                .map(|k| k.to_string());
+
+            Ok((RpScan::default(), MockPager::new(entries)))
+        }
+    }
+
+    struct MockPager {
+        entries: Option<Vec<oio::Entry>>,
+    }
+
+    impl MockPager {
+        fn new(paths: impl IntoIterator<Item = String>) -> Self {
+            let entries = paths
+                .into_iter()
+                .map(|p| {
+                    let mode = if p.ends_with('/') {
+                        EntryMode::DIR
+                    } else {
+                        EntryMode::FILE
+                    };
+                    oio::Entry::new(&p, Metadata::new(mode).with_content_length(0))
+                })
+                .collect();
+
+            Self {
+                entries: Some(entries),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl oio::Page for MockPager {
+        async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+            Ok(self.entries.take())
+        }
+    }
+
+    impl oio::BlockingPage for MockPager {
+        fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+            Ok(self.entries.take())
+        }
+    }
+
+    async fn list(op: &Operator, path: &str) -> BTreeSet<String> {
+        op.list(path)
+            .await
+            .expect("list must succeed")
+            .map_ok(|e| e.path().to_string())
+            .try_collect()
+            .await
+            .expect("list must succeed")
+    }
+
+    fn blocking_list(op: &BlockingOperator, path: &str) -> BTreeSet<String> {
+        op.list(path)
+            .expect("list must succeed")
+            .map(|e| e.map(|e| e.path().to_string()))
+            .collect::<Result<_>>()
+            .expect("list must succeed")
+    }
+
+    #[tokio::test]
+    async fn test_list_native_and_emulated() {
+        let native = Operator::new(MockBuilder { native: true })
+            .unwrap()
+            .finish();
+        let emulated = Operator::new(MockBuilder { native: false })
+            .unwrap()
+            .finish();
+
+        // Dirs are returned with trailing `/` and only once.
+        let expected: BTreeSet<_> = ["a", "b/", "f/", "j/", "k/"]
+            .into_iter()
+            .map(|p| p.to_string())
+            .collect();
+        assert_eq!(list(&native, "/").await, expected);
+        assert_eq!(list(&emulated, "/").await, expected);
+
+        for path in ["b/", "f/", "f/g/", "j/"] {
+            let expected = list(&native, path).await;
+            assert_eq!(list(&emulated, path).await, expected, "{path}");
+            assert_eq!(
+                blocking_list(&emulated.blocking(), path),
+                expected,
+                "{path}"
+            );
+        }
+    }
+}
@@ -37,6 +37,11 @@ use crate::*;
 /// Users can control how many concurrent connections could be established
 /// between OpenDAL and underlying storage services.
 ///
+/// Besides the total permits, users can also limit read and write
+/// operations independently via [`ConcurrentLimitLayer::with_read_limit`]
+/// and [`ConcurrentLimitLayer::with_write_limit`]. Other operations like
+/// `stat` and `list` will only count against the total permits.
+///
 /// # Examples
 ///
 /// ```
@@ -54,12 +59,36 @@ use crate::*;
 #[derive(Clone)]
 pub struct ConcurrentLimitLayer {
     permits: usize,
+    read_permits: Option<usize>,
+    write_permits: Option<usize>,
 }
 
 impl ConcurrentLimitLayer {
     /// Create a new ConcurrentLimitLayer will specify permits
     pub fn new(permits: usize) -> Self {
-        Self { permits }
+        Self {
+            permits,
+            read_permits: None,
+            write_permits: None,
+        }
+    }
+
+    /// Set the permits of concurrent read operations.
+    ///
+    /// Read operations will count against both read permits and total
+    /// permits. The permit will be held until the reader is dropped.
+    pub fn with_read_limit(mut self, read_permits: usize) -> Self {
+        self.read_permits = Some(read_permits);
+        self
+    }
+
+    /// Set the permits of concurrent write operations.
+    ///
+    /// Write operations will count against both write permits and total
+    /// permits. The permit will be held until the writer is dropped.
+    pub fn with_write_limit(mut self, write_permits: usize) -> Self {
+        self.write_permits = Some(write_permits);
+        self
     }
 }
 
@@ -70,6 +99,8 @@ impl<A: Accessor> Layer<A> for ConcurrentLimitLayer {
         ConcurrentLimitAccessor {
             inner,
             semaphore: Arc::new(Semaphore::new(self.permits)),
+            read_semaphore: self.read_permits.map(|v| Arc::new(Semaphore::new(v))),
+            write_semaphore: self.write_permits.map(|v| Arc::new(Semaphore::new(v))),
         }
     }
 }
@@ -78,6 +109,32 @@ impl<A: Accessor> Layer<A> for ConcurrentLimitLayer {
 pub struct ConcurrentLimitAccessor<A: Accessor> {
     inner: A,
     semaphore: Arc<Semaphore>,
+    read_semaphore: Option<Arc<Semaphore>>,
+    write_semaphore: Option<Arc<Semaphore>>,
+}
+
+/// Acquire an owned permit from an optional semaphore.
+async fn acquire_optional(semaphore: &Option<Arc<Semaphore>>) -> Option<OwnedSemaphorePermit> {
+    match semaphore {
+        Some(semaphore) => Some(
+            semaphore
+                .clone()
+                .acquire_owned()
+                .await
+                .expect("semaphore must be valid"),
+        ),
+        None => None,
+    }
+}
+
+/// Try to acquire an owned permit from an optional semaphore.
+fn try_acquire_optional(semaphore: &Option<Arc<Semaphore>>) -> Option<OwnedSemaphorePermit> {
+    semaphore.as_ref().map(|semaphore| {
+        semaphore
+            .clone()
+            .try_acquire_owned()
+            .expect("semaphore must be valid")
+    })
 }
 
 #[async_trait]
@@ -105,6 +162,9 @@ impl<A: Accessor> LayeredAccessor for ConcurrentLimitAccessor<A> {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        // Acquire the read permit first, so that reads waiting for it
+        // will not hold global permits and block other operations.
+        let read_permit = acquire_optional(&self.read_semaphore).await;
         let permit = self
             .semaphore
             .clone()
@@ -115,10 +175,13 @@ impl<A: Accessor> LayeredAccessor for ConcurrentLimitAccessor<A> {
         self.inner
             .read(path, args)
             .await
-            .map(|(rp, r)| (rp, ConcurrentLimitWrapper::new(r, permit)))
+            .map(|(rp, r)| (rp, ConcurrentLimitWrapper::new(r, permit, read_permit)))
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        // Acquire the write permit first, so that writes waiting for it
+        // will not hold global permits and block other operations.
+        let write_permit = acquire_optional(&self.write_semaphore).await;
         let permit = self
             .semaphore
             .clone()
@@ -129,7 +192,7 @@ impl<A: Accessor> LayeredAccessor for ConcurrentLimitAccessor<A> {
         self.inner
             .write(path, args)
             .await
-            .map(|(rp, w)| (rp, ConcurrentLimitWrapper::new(w, permit)))
+            .map(|(rp, w)| (rp, ConcurrentLimitWrapper::new(w, permit, write_permit)))
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
@@ -163,7 +226,7 @@ impl<A: Accessor> LayeredAccessor for ConcurrentLimitAccessor<A> {
         self.inner
             .list(path, args)
             .await
-            .map(|(rp, s)| (rp, ConcurrentLimitWrapper::new(s, permit)))
+            .map(|(rp, s)| (rp, ConcurrentLimitWrapper::new(s, permit, None)))
     }
 
     async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
@@ -177,7 +240,7 @@ impl<A: Accessor> LayeredAccessor for ConcurrentLimitAccessor<A> {
         self.inner
             .scan(path, args)
             .await
-            .map(|(rp, s)| (rp, ConcurrentLimitWrapper::new(s, permit)))
+            .map(|(rp, s)| (rp, ConcurrentLimitWrapper::new(s, permit, None)))
     }
 
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
@@ -200,6 +263,7 @@ impl<A: Accessor> LayeredAccessor for ConcurrentLimitAccessor<A> {
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let read_permit = try_acquire_optional(&self.read_semaphore);
         let permit = self
             .semaphore
             .clone()
@@ -208,10 +272,11 @@ impl<A: Accessor> LayeredAccessor for ConcurrentLimitAccessor<A> {
 
         self.inner
             .blocking_read(path, args)
-            .map(|(rp, r)| (rp, ConcurrentLimitWrapper::new(r, permit)))
+            .map(|(rp, r)| (rp, ConcurrentLimitWrapper::new(r, permit, read_permit)))
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let write_permit = try_acquire_optional(&self.write_semaphore);
         let permit = self
             .semaphore
             .clone()
@@ -220,7 +285,7 @@ impl<A: Accessor> LayeredAccessor for ConcurrentLimitAccessor<A> {
 
         self.inner
             .blocking_write(path, args)
-            .map(|(rp, w)| (rp, ConcurrentLimitWrapper::new(w, permit)))
+            .map(|(rp, w)| (rp, ConcurrentLimitWrapper::new(w, permit, write_permit)))
     }
 
     fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
@@ -250,7 +315,7 @@ impl<A: Accessor> LayeredAccessor for ConcurrentLimitAccessor<A> {
 
         self.inner
             .blocking_list(path, args)
-            .map(|(rp, it)| (rp, ConcurrentLimitWrapper::new(it, permit)))
+            .map(|(rp, it)| (rp, ConcurrentLimitWrapper::new(it, permit, None)))
     }
 
     fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
@@ -262,7 +327,7 @@ impl<A: Accessor> LayeredAccessor for ConcurrentLimitAccessor<A> {
 
         self.inner
             .blocking_scan(path, args)
-            .map(|(rp, it)| (rp, ConcurrentLimitWrapper::new(it, permit)))
+            .map(|(rp, it)| (rp, ConcurrentLimitWrapper::new(it, permit, None)))
     }
 }
 
@@ -271,13 +336,20 @@ pub struct ConcurrentLimitWrapper<R> {
 
     // Hold on this permit until this reader has been dropped.
     _permit: OwnedSemaphorePermit,
+    // Hold on the read/write permit until this reader has been dropped.
+    _io_permit: Option<OwnedSemaphorePermit>,
 }
 
 impl<R> ConcurrentLimitWrapper<R> {
-    fn new(inner: R, permit: OwnedSemaphorePermit) -> Self {
+    fn new(
+        inner: R,
+        permit: OwnedSemaphorePermit,
+        io_permit: Option<OwnedSemaphorePermit>,
+    ) -> Self {
         Self {
             inner,
             _permit: permit,
+            _io_permit: io_permit,
         }
     }
 }
@@ -323,6 +395,14 @@ impl<R: oio::Write> oio::Write for ConcurrentLimitWrapper<R> {
     async fn close(&mut self) -> Result<()> {
         self.inner.close().await
     }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+
+    fn state(&self) -> Result<MultipartState> {
+        self.inner.state()
+    }
 }
 
 impl<R: oio::BlockingWrite> oio::BlockingWrite for ConcurrentLimitWrapper<R> {
@@ -337,6 +417,10 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for ConcurrentLimitWrapper<R> {
     fn close(&mut self) -> Result<()> {
         self.inner.close()
     }
+
+    fn abort(&mut self) -> Result<()> {
+        self.inner.abort()
+    }
 }
 
 #[async_trait]
@@ -351,3 +435,73 @@ impl<R: oio::BlockingPage> oio::BlockingPage for ConcurrentLimitWrapper<R> {
         self.inner.next()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::services;
+
+    #[tokio::test]
+    async fn test_write_limit_does_not_block_read() {
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(ConcurrentLimitLayer::new(1024).with_write_limit(1))
+            .finish();
+
+        op.write("exist", "Hello, World!")
+            .await
+            .expect("write must succeed");
+
+        // Hold the only write permit.
+        let _w = op.writer("writing").await.expect("writer must be created");
+
+        let res = tokio::time::timeout(Duration::from_millis(100), op.writer("blocked")).await;
+        assert!(res.is_err(), "write must be blocked by write limit");
+
+        let bs = tokio::time::timeout(Duration::from_millis(100), op.read("exist"))
+            .await
+            .expect("read must not be blocked by write limit")
+            .expect("read must succeed");
+        assert_eq!(bs, b"Hello, World!");
+
+        tokio::time::timeout(Duration::from_millis(100), op.stat("exist"))
+            .await
+            .expect("stat must not be blocked by write limit")
+            .expect("stat must succeed");
+    }
+
+    #[tokio::test]
+    async fn test_waiting_write_does_not_hold_global_permit() {
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(ConcurrentLimitLayer::new(2).with_write_limit(1))
+            .finish();
+
+        op.write("exist", "Hello, World!")
+            .await
+            .expect("write must succeed");
+
+        // Hold the only write permit and a global permit.
+        let _w = op.writer("writing").await.expect("writer must be created");
+
+        // Wait for the write permit in background.
+        let blocked = tokio::spawn({
+            let op = op.clone();
+            async move { op.writer("blocked").await.map(|_| ()) }
+        });
+        tokio::task::yield_now().await;
+        assert!(
+            !blocked.is_finished(),
+            "write must be blocked by write limit"
+        );
+
+        // The last global permit must still be available.
+        tokio::time::timeout(Duration::from_millis(100), op.read("exist"))
+            .await
+            .expect("read must not be blocked by waiting write")
+            .expect("read must succeed");
+        blocked.abort();
+    }
+}
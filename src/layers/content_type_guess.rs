@@ -0,0 +1,270 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use async_trait::async_trait;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::*;
+
+/// Guess content type from path extension on write.
+///
+/// # Notes
+///
+/// - Content type will only be set if the caller doesn't specify one, an
+///   explicit content type will never be overridden.
+/// - Paths with unknown or without extension will be written as is.
+/// - Services that don't support content type will ignore it.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::ContentTypeGuessLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(ContentTypeGuessLayer::new())
+///     .finish();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ContentTypeGuessLayer;
+
+impl ContentTypeGuessLayer {
+    /// Create a new content type guess layer.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<A: Accessor> Layer<A> for ContentTypeGuessLayer {
+    type LayeredAccessor = ContentTypeGuessAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        ContentTypeGuessAccessor { inner }
+    }
+}
+
+#[derive(Debug)]
+pub struct ContentTypeGuessAccessor<A: Accessor> {
+    inner: A,
+}
+
+impl<A: Accessor> ContentTypeGuessAccessor<A> {
+    fn guess(path: &str, args: OpWrite) -> OpWrite {
+        if args.content_type().is_some() {
+            return args;
+        }
+
+        match guess_content_type(path) {
+            Some(mime) => args.with_content_type(mime),
+            None => args,
+        }
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for ContentTypeGuessAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(path, Self::guess(path, args)).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+        self.inner.scan(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(path, Self::guess(path, args))
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner.blocking_list(path, args)
+    }
+
+    fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        self.inner.blocking_scan(path, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// Record the content type of writes and return it on stat, so that we
+    /// can test with the memory service.
+    #[derive(Clone, Default)]
+    struct MockContentTypeLayer {
+        content_types: Arc<Mutex<HashMap<String, String>>>,
+    }
+
+    impl<A: Accessor> Layer<A> for MockContentTypeLayer {
+        type LayeredAccessor = MockContentTypeAccessor<A>;
+
+        fn layer(&self, inner: A) -> Self::LayeredAccessor {
+            MockContentTypeAccessor {
+                inner,
+                content_types: self.content_types.clone(),
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct MockContentTypeAccessor<A: Accessor> {
+        inner: A,
+        content_types: Arc<Mutex<HashMap<String, String>>>,
+    }
+
+    #[async_trait]
+    impl<A: Accessor> LayeredAccessor for MockContentTypeAccessor<A> {
+        type Inner = A;
+        type Reader = A::Reader;
+        type BlockingReader = A::BlockingReader;
+        type Writer = A::Writer;
+        type BlockingWriter = A::BlockingWriter;
+        type Pager = A::Pager;
+        type BlockingPager = A::BlockingPager;
+
+        fn inner(&self) -> &Self::Inner {
+            &self.inner
+        }
+
+        async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            self.inner.read(path, args).await
+        }
+
+        async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+            {
+                let mut content_types = self.content_types.lock().unwrap();
+                match args.content_type() {
+                    Some(v) => content_types.insert(path.to_string(), v.to_string()),
+                    None => content_types.remove(path),
+                };
+            }
+
+            self.inner.write(path, args).await
+        }
+
+        async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+            let meta = self.inner.stat(path, args).await?.into_metadata();
+            let meta = match self.content_types.lock().unwrap().get(path) {
+                Some(v) => meta.with_content_type(v.clone()),
+                None => meta,
+            };
+            Ok(RpStat::new(meta))
+        }
+
+        async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+            self.inner.list(path, args).await
+        }
+
+        async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+            self.inner.scan(path, args).await
+        }
+
+        fn blocking_read(
+            &self,
+            path: &str,
+            args: OpRead,
+        ) -> Result<(RpRead, Self::BlockingReader)> {
+            self.inner.blocking_read(path, args)
+        }
+
+        fn blocking_write(
+            &self,
+            path: &str,
+            args: OpWrite,
+        ) -> Result<(RpWrite, Self::BlockingWriter)> {
+            self.inner.blocking_write(path, args)
+        }
+
+        fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+            self.inner.blocking_list(path, args)
+        }
+
+        fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+            self.inner.blocking_scan(path, args)
+        }
+    }
+
+    fn new_operator() -> Operator {
+        Operator::new(services::Memory::default())
+            .expect("must init")
+            .layer(MockContentTypeLayer::default())
+            .layer(ContentTypeGuessLayer::new())
+            .finish()
+    }
+
+    #[tokio::test]
+    async fn test_guess_content_type() {
+        let op = new_operator();
+
+        op.write("foo.json", "{}").await.expect("must write");
+        let meta = op.stat("foo.json").await.expect("must stat");
+        assert_eq!(meta.content_type(), Some("application/json"));
+
+        op.write("bar.bin", "Hello, World!")
+            .await
+            .expect("must write");
+        let meta = op.stat("bar.bin").await.expect("must stat");
+        assert_eq!(meta.content_type(), None);
+    }
+
+    #[tokio::test]
+    async fn test_explicit_content_type() {
+        let op = new_operator();
+
+        op.write_with(
+            "foo.json",
+            OpWrite::new().with_content_type("text/plain"),
+            "{}",
+        )
+        .await
+        .expect("must write");
+        let meta = op.stat("foo.json").await.expect("must stat");
+        assert_eq!(meta.content_type(), Some("text/plain"));
+    }
+}
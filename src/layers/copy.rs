@@ -0,0 +1,552 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use log::warn;
+
+use crate::ops::*;
+use crate::raw::oio::BlockingRead;
+use crate::raw::oio::BlockingWrite;
+use crate::raw::oio::ReadExt;
+use crate::raw::oio::Write;
+use crate::raw::*;
+use crate::*;
+
+/// Add copy support for services that can't copy on the server side.
+///
+/// # Notes
+///
+/// If the underlying service supports copy natively, the request will be
+/// forwarded as is. Otherwise, this layer will read from `from` and
+/// append the data into `to` chunk by chunk:
+///
+/// - `content_type` of the source will be preserved if known.
+/// - The number of copied bytes will be checked against the source's
+///   `content_length` if known. The destination will not be committed if
+///   they don't match.
+/// - Both paths are always inside the same operator, so copying between
+///   different services is not supported by this layer.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::CopyLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(CopyLayer::new())
+///     .finish();
+/// ```
+#[derive(Debug, Clone)]
+pub struct CopyLayer {
+    buffer_size: usize,
+}
+
+impl Default for CopyLayer {
+    fn default() -> Self {
+        Self {
+            buffer_size: 4 * 1024 * 1024,
+        }
+    }
+}
+
+impl CopyLayer {
+    /// Create a new copy layer with 4MiB buffer size.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the size of every chunk appended to the destination.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `buffer_size` is 0.
+    pub fn with_buffer_size(mut self, buffer_size: usize) -> Self {
+        assert!(buffer_size > 0, "buffer_size must be larger than 0");
+
+        self.buffer_size = buffer_size;
+        self
+    }
+}
+
+impl<A: Accessor> Layer<A> for CopyLayer {
+    type LayeredAccessor = CopyAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        let native = inner
+            .info()
+            .capabilities()
+            .contains(AccessorCapability::Copy);
+
+        CopyAccessor {
+            inner,
+            native,
+            buffer_size: self.buffer_size,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CopyAccessor<A: Accessor> {
+    inner: A,
+    native: bool,
+    buffer_size: usize,
+}
+
+impl<A: Accessor> CopyAccessor<A> {
+    /// Check the source and build the write args for destination.
+    ///
+    /// Returns the content length of the source if known.
+    fn prepare(
+        &self,
+        op: Operation,
+        from: &str,
+        to: &str,
+        args: &OpCopy,
+        rp: RpStat,
+    ) -> Result<(OpWrite, Option<u64>)> {
+        if from == to {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "copy source and destination are the same",
+            )
+            .with_operation(op)
+            .with_context("from", from)
+            .with_context("to", to));
+        }
+
+        let meta = rp.into_metadata();
+        if meta.mode().is_dir() {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "copy source is a directory")
+                    .with_operation(op)
+                    .with_context("from", from),
+            );
+        }
+
+        let mut wargs = OpWrite::new().with_append();
+        if meta.bit().contains(Metakey::ContentType) || meta.bit().contains(Metakey::Complete) {
+            if let Some(v) = meta.content_type() {
+                wargs = wargs.with_content_type(v);
+            }
+        }
+        // The condition will be checked while committing the destination.
+        if let Some(v) = args.if_none_match() {
+            wargs = wargs.with_if_none_match(v);
+        }
+
+        Ok((wargs, meta.content_length_raw()))
+    }
+
+    /// Don't allocate more than the source's content length.
+    fn buffer_len(&self, size: Option<u64>) -> usize {
+        match size {
+            Some(v) => self.buffer_size.min(v as usize).max(1),
+            None => self.buffer_size,
+        }
+    }
+
+    /// Check copied size against the source's content length.
+    fn check_size(op: Operation, from: &str, expected: Option<u64>, actual: u64) -> Result<()> {
+        match expected {
+            Some(expected) if expected != actual => Err(Error::new(
+                ErrorKind::Unexpected,
+                "copied size is not match with source content length",
+            )
+            .with_operation(op)
+            .with_context("from", from)
+            .with_context("expect", expected.to_string())
+            .with_context("actual", actual.to_string())),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for CopyAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn metadata(&self) -> AccessorInfo {
+        let mut meta = self.inner.info();
+        let mut cap = meta.capabilities();
+        if !self.native && cap.contains(AccessorCapability::Read | AccessorCapability::Write) {
+            cap |= AccessorCapability::Copy;
+            if cap.contains(AccessorCapability::WriteWithIfNoneMatch) {
+                cap |= AccessorCapability::CopyWithIfNoneMatch;
+            }
+            meta.set_capabilities(cap);
+        }
+
+        meta
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(path, args).await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        if self.native {
+            return self.inner.copy(from, to, args).await;
+        }
+
+        let rp = self
+            .inner
+            .stat(from, OpStat::new())
+            .await
+            .map_err(|err| err.with_operation(Operation::Copy))?;
+        let (wargs, size) = self.prepare(Operation::Copy, from, to, &args, rp)?;
+
+        let (_, mut r) = self.inner.read(from, OpRead::new()).await?;
+        let (_, mut w) = self.inner.write(to, wargs).await?;
+
+        let result = async {
+            let mut buf = vec![0; self.buffer_len(size)];
+            let mut copied = 0;
+            loop {
+                let mut filled = 0;
+                while filled < buf.len() {
+                    match r.read(&mut buf[filled..]).await? {
+                        0 => break,
+                        n => filled += n,
+                    }
+                }
+                if filled == 0 {
+                    break;
+                }
+
+                w.append(Bytes::copy_from_slice(&buf[..filled])).await?;
+                copied += filled as u64;
+            }
+
+            Self::check_size(Operation::Copy, from, size, copied)?;
+            w.close().await
+        }
+        .await;
+
+        // Clean up the uncommitted destination, for example the condition
+        // is not match while closing.
+        if let Err(err) = result {
+            if let Err(e) = w.abort().await {
+                warn!("abort writer of {to} failed: {e:?}");
+            }
+            return Err(err);
+        }
+
+        Ok(RpCopy::default())
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+        self.inner.scan(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        if self.native {
+            return self.inner.blocking_copy(from, to, args);
+        }
+
+        let rp = self
+            .inner
+            .blocking_stat(from, OpStat::new())
+            .map_err(|err| err.with_operation(Operation::BlockingCopy))?;
+        let (wargs, size) = self.prepare(Operation::BlockingCopy, from, to, &args, rp)?;
+
+        let (_, mut r) = self.inner.blocking_read(from, OpRead::new())?;
+        let (_, mut w) = self.inner.blocking_write(to, wargs)?;
+
+        let result = (|| {
+            let mut buf = vec![0; self.buffer_len(size)];
+            let mut copied = 0;
+            loop {
+                let mut filled = 0;
+                while filled < buf.len() {
+                    match r.read(&mut buf[filled..])? {
+                        0 => break,
+                        n => filled += n,
+                    }
+                }
+                if filled == 0 {
+                    break;
+                }
+
+                w.append(Bytes::copy_from_slice(&buf[..filled]))?;
+                copied += filled as u64;
+            }
+
+            Self::check_size(Operation::BlockingCopy, from, size, copied)?;
+            w.close()
+        })();
+
+        // Clean up the uncommitted destination, same as `copy`.
+        if let Err(err) = result {
+            if let Err(e) = w.abort() {
+                warn!("abort writer of {to} failed: {e:?}");
+            }
+            return Err(err);
+        }
+
+        Ok(RpCopy::default())
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner.blocking_list(path, args)
+    }
+
+    fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        self.inner.blocking_scan(path, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services;
+
+    fn new_operator() -> Operator {
+        Operator::new(services::Memory::default())
+            .expect("must init")
+            .layer(CopyLayer::new().with_buffer_size(4))
+            .finish()
+    }
+
+    #[tokio::test]
+    async fn test_copy_unsupported_without_layer() {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .finish();
+        assert!(!op.info().can_copy());
+        op.write("from", "Hello, World!").await.expect("must write");
+
+        let err = op.copy("from", "to").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[tokio::test]
+    async fn test_copy() {
+        let op = new_operator();
+        assert!(op.info().can_copy());
+
+        op.write("from", "Hello, World!").await.expect("must write");
+        op.write("to", "existing content")
+            .await
+            .expect("must write");
+
+        op.copy("from", "to").await.expect("must copy");
+
+        let bs = op.read("to").await.expect("must read");
+        assert_eq!(bs, b"Hello, World!");
+        let bs = op.read("from").await.expect("must read");
+        assert_eq!(bs, b"Hello, World!");
+    }
+
+    #[tokio::test]
+    async fn test_copy_empty_file() {
+        let op = new_operator();
+        op.write("from", "").await.expect("must write");
+
+        op.copy("from", "to").await.expect("must copy");
+
+        let bs = op.read("to").await.expect("must read");
+        assert!(bs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_copy_source_not_found() {
+        let op = new_operator();
+
+        let err = op.copy("not_exist", "to").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        assert!(!op.is_exist("to").await.expect("must check"));
+    }
+
+    #[tokio::test]
+    async fn test_copy_same_path() {
+        let op = new_operator();
+        op.write("from", "Hello, World!").await.expect("must write");
+
+        let err = op.copy("from", "/from").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        let bs = op.read("from").await.expect("must read");
+        assert_eq!(bs, b"Hello, World!");
+    }
+
+    #[tokio::test]
+    async fn test_copy_with_if_none_match_unsupported() {
+        let op = new_operator();
+        assert!(!op.info().can_copy_with_if_none_match());
+        op.write("from", "Hello, World!").await.expect("must write");
+
+        let err = op
+            .copy_with("from", "to", OpCopy::new().with_if_none_match("*"))
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert!(!op.is_exist("to").await.expect("must check"));
+    }
+
+    #[tokio::test]
+    async fn test_copy_with_if_none_match() {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let server = MockServer::start().await;
+        for m in ["HEAD", "GET"] {
+            Mock::given(method(m))
+                .and(path("/test/from"))
+                .respond_with(ResponseTemplate::new(200).set_body_string("Hello, World!"))
+                .mount(&server)
+                .await;
+        }
+        for (to, status) in [("fresh", 200), ("existing", 412)] {
+            Mock::given(method("POST"))
+                .and(path(format!("/test/{to}")))
+                .and(query_param("uploads", ""))
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    "<InitiateMultipartUploadResult><UploadId>upload</UploadId></InitiateMultipartUploadResult>",
+                ))
+                .mount(&server)
+                .await;
+            Mock::given(method("PUT"))
+                .and(path(format!("/test/{to}")))
+                .and(query_param("partNumber", "1"))
+                .respond_with(ResponseTemplate::new(200).insert_header("ETag", "\"etag\""))
+                .mount(&server)
+                .await;
+            Mock::given(method("POST"))
+                .and(path(format!("/test/{to}")))
+                .and(query_param("uploadId", "upload"))
+                .and(header("if-none-match", "*"))
+                .respond_with(ResponseTemplate::new(status))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        // The rejected upload must be aborted.
+        Mock::given(method("DELETE"))
+            .and(path("/test/existing"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut builder = services::S3::default();
+        builder
+            .bucket("test")
+            .endpoint(&server.uri())
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key")
+            .disable_config_load();
+        let op = Operator::new(builder)
+            .expect("must init")
+            .layer(CopyLayer::new())
+            .finish();
+        assert!(op.info().can_copy_with_if_none_match());
+
+        let args = OpCopy::new().with_if_none_match("*");
+        op.copy_with("from", "fresh", args.clone())
+            .await
+            .expect("must copy");
+        let err = op
+            .copy_with("from", "existing", args)
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+    }
+
+    #[test]
+    fn test_blocking_copy() {
+        let op = new_operator().blocking();
+        op.write("from", "Hello, World!").expect("must write");
+
+        op.copy("from", "to").expect("must copy");
+
+        let bs = op.read("to").expect("must read");
+        assert_eq!(bs, b"Hello, World!");
+
+        let err = op.copy("not_exist", "to").expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_blocking_copy_abort_on_size_mismatch() {
+        use std::env;
+
+        use uuid::Uuid;
+
+        use crate::layers::MockLayer;
+
+        let root = env::temp_dir().join(format!("opendal-{}", Uuid::new_v4()));
+        let mut builder = services::Fs::default();
+        builder.root(&root.to_string_lossy());
+        let op = Operator::new(builder)
+            .expect("must init")
+            .layer(MockLayer::default().with_metadata(|op, _, meta| {
+                if op == Operation::BlockingStat {
+                    meta.with_content_length(1024)
+                } else {
+                    meta
+                }
+            }))
+            .layer(CopyLayer::new().with_buffer_size(4))
+            .finish()
+            .blocking();
+        op.write("from", "Hello, World!").expect("must write");
+
+        let err = op.copy("from", "to").expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        // The partial destination created by copy must be removed.
+        assert!(!op.is_exist("to").expect("must check"));
+
+        std::fs::remove_dir_all(root).expect("must remove");
+    }
+}
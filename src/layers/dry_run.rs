@@ -0,0 +1,394 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use parking_lot::Mutex;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::*;
+
+/// Record mutations instead of executing them.
+///
+/// # Notes
+///
+/// This layer will:
+///
+/// - record `create`, `write`, `delete`, `copy` and `rename` (including
+///   batch delete and batch write), and return success without touching
+///   the underlying service.
+/// - forward all other operations like `read`, `stat` and `list` to the
+///   underlying service as is.
+///
+/// So reads will NOT observe the recorded mutations. For example, `stat`
+/// after a dry run `write` will still return `NotFound`.
+///
+/// Recorded operations can be collected via [`DryRunLayer::records`].
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::DryRunLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<()> {
+/// let layer = DryRunLayer::new();
+/// let op = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(layer.clone())
+///     .finish();
+///
+/// op.write("test", "Hello, World!").await?;
+/// for record in layer.records() {
+///     println!("{} {} {:?}", record.operation(), record.path(), record.size());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DryRunLayer {
+    records: Arc<Mutex<Vec<DryRunRecord>>>,
+}
+
+impl DryRunLayer {
+    /// Create a new DryRunLayer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get all operations recorded so far, in the order they are executed.
+    pub fn records(&self) -> Vec<DryRunRecord> {
+        self.records.lock().clone()
+    }
+
+    /// Take all operations recorded so far and clear the log.
+    pub fn take_records(&self) -> Vec<DryRunRecord> {
+        std::mem::take(&mut *self.records.lock())
+    }
+}
+
+impl<A: Accessor> Layer<A> for DryRunLayer {
+    type LayeredAccessor = DryRunAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        DryRunAccessor {
+            inner,
+            records: self.records.clone(),
+        }
+    }
+}
+
+/// A mutation recorded by [`DryRunLayer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DryRunRecord {
+    operation: Operation,
+    path: String,
+    target: Option<String>,
+    size: Option<u64>,
+}
+
+impl DryRunRecord {
+    fn new(operation: Operation, path: &str) -> Self {
+        Self {
+            operation,
+            path: path.to_string(),
+            target: None,
+            size: None,
+        }
+    }
+
+    /// The operation that would be executed.
+    pub fn operation(&self) -> Operation {
+        self.operation
+    }
+
+    /// The path of this operation, or the source path of `copy` and `rename`.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The target path of `copy` and `rename`.
+    pub fn target(&self) -> Option<&str> {
+        self.target.as_deref()
+    }
+
+    /// The total size of bytes that would be written by `write`.
+    pub fn size(&self) -> Option<u64> {
+        self.size
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DryRunAccessor<A: Accessor> {
+    inner: A,
+    records: Arc<Mutex<Vec<DryRunRecord>>>,
+}
+
+impl<A: Accessor> DryRunAccessor<A> {
+    fn record(&self, record: DryRunRecord) {
+        self.records.lock().push(record)
+    }
+
+    fn record_batch(&self, op: Operation, args: OpBatch) -> RpBatch {
+        let results = match args.into_operation() {
+            BatchOperations::Delete(ops) => BatchedResults::Delete(
+                ops.into_iter()
+                    .map(|(path, _)| {
+                        self.record(DryRunRecord::new(op, &path));
+                        (path, Ok(RpDelete::default()))
+                    })
+                    .collect(),
+            ),
+            BatchOperations::Write(ops) => BatchedResults::Write(
+                ops.into_iter()
+                    .map(|(path, _, bs)| {
+                        let mut record = DryRunRecord::new(op, &path);
+                        record.size = Some(bs.len() as u64);
+                        self.record(record);
+                        (path, Ok(RpWrite::default()))
+                    })
+                    .collect(),
+            ),
+        };
+
+        RpBatch::new(results)
+    }
+
+    fn record_transfer(&self, op: Operation, from: &str, to: &str) {
+        let mut record = DryRunRecord::new(op, from);
+        record.target = Some(to.to_string());
+        self.record(record)
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for DryRunAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = DryRunWriter;
+    type BlockingWriter = DryRunWriter;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create(&self, path: &str, _: OpCreate) -> Result<RpCreate> {
+        self.record(DryRunRecord::new(Operation::Create, path));
+        Ok(RpCreate::default())
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        Ok((
+            RpWrite::default(),
+            DryRunWriter::new(Operation::Write, path, self.records.clone()),
+        ))
+    }
+
+    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        self.record(DryRunRecord::new(Operation::Delete, path));
+        Ok(RpDelete::default())
+    }
+
+    async fn copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
+        self.record_transfer(Operation::Copy, from, to);
+        Ok(RpCopy::default())
+    }
+
+    async fn rename(&self, from: &str, to: &str, _: OpRename) -> Result<RpRename> {
+        self.record_transfer(Operation::Rename, from, to);
+        Ok(RpRename::default())
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+        self.inner.scan(path, args).await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        Ok(self.record_batch(Operation::Batch, args))
+    }
+
+    fn blocking_create(&self, path: &str, _: OpCreate) -> Result<RpCreate> {
+        self.record(DryRunRecord::new(Operation::BlockingCreate, path));
+        Ok(RpCreate::default())
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        Ok((
+            RpWrite::default(),
+            DryRunWriter::new(Operation::BlockingWrite, path, self.records.clone()),
+        ))
+    }
+
+    fn blocking_delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        self.record(DryRunRecord::new(Operation::BlockingDelete, path));
+        Ok(RpDelete::default())
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
+        self.record_transfer(Operation::BlockingCopy, from, to);
+        Ok(RpCopy::default())
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, _: OpRename) -> Result<RpRename> {
+        self.record_transfer(Operation::BlockingRename, from, to);
+        Ok(RpRename::default())
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner.blocking_list(path, args)
+    }
+
+    fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        self.inner.blocking_scan(path, args)
+    }
+}
+
+/// DryRunWriter counts the bytes written and records the write on close.
+pub struct DryRunWriter {
+    record: DryRunRecord,
+    records: Arc<Mutex<Vec<DryRunRecord>>>,
+}
+
+impl DryRunWriter {
+    fn new(op: Operation, path: &str, records: Arc<Mutex<Vec<DryRunRecord>>>) -> Self {
+        let mut record = DryRunRecord::new(op, path);
+        record.size = Some(0);
+
+        Self { record, records }
+    }
+
+    fn close_and_record(&mut self) {
+        self.records.lock().push(self.record.clone())
+    }
+}
+
+#[async_trait]
+impl oio::Write for DryRunWriter {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.record.size = Some(bs.len() as u64);
+        Ok(())
+    }
+
+    async fn append(&mut self, bs: Bytes) -> Result<()> {
+        self.record.size = Some(self.record.size.unwrap_or_default() + bs.len() as u64);
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.close_and_record();
+        Ok(())
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl oio::BlockingWrite for DryRunWriter {
+    fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.record.size = Some(bs.len() as u64);
+        Ok(())
+    }
+
+    fn append(&mut self, bs: Bytes) -> Result<()> {
+        self.record.size = Some(self.record.size.unwrap_or_default() + bs.len() as u64);
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.close_and_record();
+        Ok(())
+    }
+
+    fn abort(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services;
+
+    #[tokio::test]
+    async fn test_dry_run() {
+        let layer = DryRunLayer::new();
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .finish();
+        op.write("existing", "Hello, World!")
+            .await
+            .expect("must write");
+        let dry = op.clone().layer(layer.clone());
+
+        dry.write("test", "Hello, World!")
+            .await
+            .expect("must write");
+        dry.delete("existing").await.expect("must delete");
+        dry.copy("existing", "copied").await.expect("must copy");
+        dry.blocking()
+            .write("blocking", vec![0; 1024])
+            .expect("must write");
+
+        // Reads are passed through, mutations never happen.
+        assert_eq!(
+            dry.read("existing").await.expect("must read"),
+            b"Hello, World!"
+        );
+        for path in ["test", "copied", "blocking"] {
+            let err = op.stat(path).await.expect_err("must fail");
+            assert_eq!(err.kind(), ErrorKind::NotFound, "{path}");
+            let err = dry.stat(path).await.expect_err("must fail");
+            assert_eq!(err.kind(), ErrorKind::NotFound, "{path}");
+        }
+        assert!(op.exists("existing").await.expect("must check"));
+
+        let records = layer.take_records();
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[0].operation(), Operation::Write);
+        assert_eq!(records[0].path(), "test");
+        assert_eq!(records[0].size(), Some(13));
+        assert_eq!(records[1].operation(), Operation::Delete);
+        assert_eq!(records[1].path(), "existing");
+        assert_eq!(records[2].operation(), Operation::Copy);
+        assert_eq!(records[2].target(), Some("copied"));
+        assert_eq!(records[3].operation(), Operation::BlockingWrite);
+        assert_eq!(records[3].size(), Some(1024));
+        assert!(layer.records().is_empty());
+    }
+}
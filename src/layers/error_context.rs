@@ -160,6 +160,30 @@ impl<A: Accessor> LayeredAccessor for ErrorContextAccessor<A> {
             .await
     }
 
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.inner
+            .copy(from, to, args)
+            .map_err(|err| {
+                err.with_operation(Operation::Copy)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("from", from)
+                    .with_context("to", to)
+            })
+            .await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.inner
+            .rename(from, to, args)
+            .map_err(|err| {
+                err.with_operation(Operation::Rename)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("from", from)
+                    .with_context("to", to)
+            })
+            .await
+    }
+
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
         self.inner
             .list(path, args)
@@ -214,21 +238,34 @@ impl<A: Accessor> LayeredAccessor for ErrorContextAccessor<A> {
         self.inner
             .batch(args)
             .map_ok(|v| {
-                let BatchedResults::Delete(res) = v.into_results();
-
-                let res = res
-                    .into_iter()
-                    .map(|(path, res)| {
-                        let res = res.map_err(|err| {
-                            err.with_operation(Operation::Delete)
-                                .with_context("service", self.meta.scheme())
-                                .with_context("path", &path)
-                        });
-                        (path, res)
-                    })
-                    .collect();
-
-                RpBatch::new(BatchedResults::Delete(res))
+                let with_context = |op: Operation, path: &str, err: Error| {
+                    err.with_operation(op)
+                        .with_context("service", self.meta.scheme())
+                        .with_context("path", path)
+                };
+
+                let res = match v.into_results() {
+                    BatchedResults::Delete(res) => BatchedResults::Delete(
+                        res.into_iter()
+                            .map(|(path, res)| {
+                                let res =
+                                    res.map_err(|err| with_context(Operation::Delete, &path, err));
+                                (path, res)
+                            })
+                            .collect(),
+                    ),
+                    BatchedResults::Write(res) => BatchedResults::Write(
+                        res.into_iter()
+                            .map(|(path, res)| {
+                                let res =
+                                    res.map_err(|err| with_context(Operation::Write, &path, err));
+                                (path, res)
+                            })
+                            .collect(),
+                    ),
+                };
+
+                RpBatch::new(res)
             })
             .map_err(|err| {
                 err.with_operation(Operation::Batch)
@@ -301,6 +338,24 @@ impl<A: Accessor> LayeredAccessor for ErrorContextAccessor<A> {
         })
     }
 
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.inner.blocking_copy(from, to, args).map_err(|err| {
+            err.with_operation(Operation::BlockingCopy)
+                .with_context("service", self.meta.scheme())
+                .with_context("from", from)
+                .with_context("to", to)
+        })
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.inner.blocking_rename(from, to, args).map_err(|err| {
+            err.with_operation(Operation::BlockingRename)
+                .with_context("service", self.meta.scheme())
+                .with_context("from", from)
+                .with_context("to", to)
+        })
+    }
+
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
         self.inner
             .blocking_list(path, args)
@@ -427,6 +482,21 @@ impl<T: oio::Write> oio::Write for ErrorContextWrapper<T> {
                 .with_context("path", &self.path)
         })
     }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await.map_err(|err| {
+            err.with_operation(WriteOperation::Abort)
+                .with_context("service", self.scheme)
+                .with_context("path", &self.path)
+        })
+    }
+
+    fn state(&self) -> Result<MultipartState> {
+        self.inner.state().map_err(|err| {
+            err.with_context("service", self.scheme)
+                .with_context("path", &self.path)
+        })
+    }
 }
 
 impl<T: oio::BlockingWrite> oio::BlockingWrite for ErrorContextWrapper<T> {
@@ -453,6 +523,14 @@ impl<T: oio::BlockingWrite> oio::BlockingWrite for ErrorContextWrapper<T> {
                 .with_context("path", &self.path)
         })
     }
+
+    fn abort(&mut self) -> Result<()> {
+        self.inner.abort().map_err(|err| {
+            err.with_operation(WriteOperation::BlockingAbort)
+                .with_context("service", self.scheme)
+                .with_context("path", &self.path)
+        })
+    }
 }
 
 #[async_trait::async_trait]
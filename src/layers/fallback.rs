@@ -0,0 +1,381 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use async_trait::async_trait;
+use log::warn;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::*;
+
+/// Fall back to a secondary operator while the underlying service fails.
+///
+/// Operations that fail with `NotFound` or a temporary error on the
+/// underlying service (the primary) will be sent to the secondary operator
+/// with the same path and args, for example a mirror in another region.
+///
+/// # Notes
+///
+/// - Only `read`, `stat`, `list` and `scan` will fall back by default.
+///   Use [`FallbackLayer::with_write`] to fall back `create`, `write` and
+///   `delete` as well.
+/// - Only errors returned while opening readers, writers and pagers will
+///   fall back, errors returned while streaming data will not.
+/// - If the secondary fails too, the error from the secondary will be
+///   returned.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::FallbackLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let mirror = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .finish();
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(FallbackLayer::new(mirror))
+///     .finish();
+/// ```
+#[derive(Debug, Clone)]
+pub struct FallbackLayer {
+    secondary: Operator,
+    write: bool,
+}
+
+impl FallbackLayer {
+    /// Create a new fallback layer that falls back to given operator.
+    pub fn new(secondary: Operator) -> Self {
+        Self {
+            secondary,
+            write: false,
+        }
+    }
+
+    /// Fall back `create`, `write` and `delete` too.
+    ///
+    /// Default: false
+    pub fn with_write(mut self, enabled: bool) -> Self {
+        self.write = enabled;
+        self
+    }
+}
+
+impl<A: Accessor> Layer<A> for FallbackLayer {
+    type LayeredAccessor = FallbackAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        FallbackAccessor {
+            inner,
+            secondary: self.secondary.inner().clone(),
+            write: self.write,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FallbackAccessor<A: Accessor> {
+    inner: A,
+    secondary: FusedAccessor,
+    write: bool,
+}
+
+impl<A: Accessor> FallbackAccessor<A> {
+    /// Check if given error from the primary should fall back.
+    fn should_fallback(&self, op: Operation, path: &str, err: &Error) -> bool {
+        let fallback = err.kind() == ErrorKind::NotFound || err.is_temporary();
+        if fallback {
+            warn!(
+                "service={} operation={} path={} -> primary failed, fall back to secondary: {err:?}",
+                self.inner.info().scheme(),
+                op,
+                path,
+            );
+        }
+        fallback
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for FallbackAccessor<A> {
+    type Inner = A;
+    type Reader = oio::Reader;
+    type BlockingReader = oio::BlockingReader;
+    type Writer = oio::Writer;
+    type BlockingWriter = oio::BlockingWriter;
+    type Pager = oio::Pager;
+    type BlockingPager = oio::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        match self.inner.create(path, args.clone()).await {
+            Err(err) if self.write && self.should_fallback(Operation::Create, path, &err) => {
+                self.secondary.create(path, args).await
+            }
+            res => res,
+        }
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        match self.inner.read(path, args.clone()).await {
+            Ok((rp, r)) => Ok((rp, Box::new(r) as oio::Reader)),
+            Err(err) if self.should_fallback(Operation::Read, path, &err) => {
+                self.secondary.read(path, args).await
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        match self.inner.write(path, args.clone()).await {
+            Ok((rp, w)) => Ok((rp, Box::new(w) as oio::Writer)),
+            Err(err) if self.write && self.should_fallback(Operation::Write, path, &err) => {
+                self.secondary.write(path, args).await
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        match self.inner.stat(path, args.clone()).await {
+            Err(err) if self.should_fallback(Operation::Stat, path, &err) => {
+                self.secondary.stat(path, args).await
+            }
+            res => res,
+        }
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        match self.inner.delete(path, args.clone()).await {
+            Err(err) if self.write && self.should_fallback(Operation::Delete, path, &err) => {
+                self.secondary.delete(path, args).await
+            }
+            res => res,
+        }
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        match self.inner.list(path, args.clone()).await {
+            Ok((rp, p)) => Ok((rp, Box::new(p) as oio::Pager)),
+            Err(err) if self.should_fallback(Operation::List, path, &err) => {
+                self.secondary.list(path, args).await
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+        match self.inner.scan(path, args.clone()).await {
+            Ok((rp, p)) => Ok((rp, Box::new(p) as oio::Pager)),
+            Err(err) if self.should_fallback(Operation::Scan, path, &err) => {
+                self.secondary.scan(path, args).await
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        match self.inner.blocking_create(path, args.clone()) {
+            Err(err)
+                if self.write && self.should_fallback(Operation::BlockingCreate, path, &err) =>
+            {
+                self.secondary.blocking_create(path, args)
+            }
+            res => res,
+        }
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        match self.inner.blocking_read(path, args.clone()) {
+            Ok((rp, r)) => Ok((rp, Box::new(r) as oio::BlockingReader)),
+            Err(err) if self.should_fallback(Operation::BlockingRead, path, &err) => {
+                self.secondary.blocking_read(path, args)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        match self.inner.blocking_write(path, args.clone()) {
+            Ok((rp, w)) => Ok((rp, Box::new(w) as oio::BlockingWriter)),
+            Err(err)
+                if self.write && self.should_fallback(Operation::BlockingWrite, path, &err) =>
+            {
+                self.secondary.blocking_write(path, args)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        match self.inner.blocking_stat(path, args.clone()) {
+            Err(err) if self.should_fallback(Operation::BlockingStat, path, &err) => {
+                self.secondary.blocking_stat(path, args)
+            }
+            res => res,
+        }
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        match self.inner.blocking_delete(path, args.clone()) {
+            Err(err)
+                if self.write && self.should_fallback(Operation::BlockingDelete, path, &err) =>
+            {
+                self.secondary.blocking_delete(path, args)
+            }
+            res => res,
+        }
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        match self.inner.blocking_list(path, args.clone()) {
+            Ok((rp, p)) => Ok((rp, Box::new(p) as oio::BlockingPager)),
+            Err(err) if self.should_fallback(Operation::BlockingList, path, &err) => {
+                self.secondary.blocking_list(path, args)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        match self.inner.blocking_scan(path, args.clone()) {
+            Ok((rp, p)) => Ok((rp, Box::new(p) as oio::BlockingPager)),
+            Err(err) if self.should_fallback(Operation::BlockingScan, path, &err) => {
+                self.secondary.blocking_scan(path, args)
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::layers::MockLayer;
+    use crate::services;
+
+    /// Fail paths that contain `broken` with a temporary error and paths
+    /// that contain `denied` with `PermissionDenied`.
+    fn mock_broken_layer() -> MockLayer {
+        MockLayer::default().with_error(|_, path| {
+            if path.contains("broken") {
+                Some(Error::new(ErrorKind::Unexpected, "mock broken").set_temporary())
+            } else if path.contains("denied") {
+                Some(Error::new(ErrorKind::PermissionDenied, "mock denied"))
+            } else {
+                None
+            }
+        })
+    }
+
+    fn new_operator(secondary: Operator, write: bool) -> Operator {
+        Operator::new(services::Memory::default())
+            .expect("must init")
+            .layer(mock_broken_layer())
+            .layer(FallbackLayer::new(secondary).with_write(write))
+            .finish()
+    }
+
+    async fn new_secondary() -> Operator {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .finish();
+        for path in ["mirrored", "broken", "denied", "dir_broken/file"] {
+            op.write(path, "Hello, World!").await.expect("must write");
+        }
+        op
+    }
+
+    #[tokio::test]
+    async fn test_fallback_read() {
+        let op = new_operator(new_secondary().await, false);
+        op.write("local", "Hello, Local!")
+            .await
+            .expect("must write");
+
+        // Primary returns NotFound or temporary errors, and secondary
+        // serves the object.
+        for path in ["mirrored", "broken"] {
+            assert_eq!(
+                op.read(path).await.expect("must read"),
+                b"Hello, World!",
+                "{path}"
+            );
+            assert_eq!(
+                op.stat(path).await.expect("must stat").content_length(),
+                13,
+                "{path}"
+            );
+        }
+        let entries: Vec<_> = op
+            .list("dir_broken/")
+            .await
+            .expect("must list")
+            .try_collect()
+            .await
+            .expect("must list");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path(), "dir_broken/file");
+
+        // Primary serves the object.
+        assert_eq!(op.read("local").await.expect("must read"), b"Hello, Local!");
+
+        // Permanent errors never fall back.
+        let err = op.read("denied").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        let err = op.read("not_exist").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        let op = op.blocking();
+        assert_eq!(op.read("mirrored").expect("must read"), b"Hello, World!");
+    }
+
+    #[tokio::test]
+    async fn test_fallback_write() {
+        let secondary = Operator::new(services::Memory::default())
+            .expect("must init")
+            .finish();
+
+        // Writes don't fall back by default.
+        let op = new_operator(secondary.clone(), false);
+        let err = op
+            .write("broken", "Hello, World!")
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(!secondary.exists("broken").await.expect("must stat"));
+
+        let op = new_operator(secondary.clone(), true);
+        op.write("broken", "Hello, World!")
+            .await
+            .expect("must write");
+        assert_eq!(
+            secondary.read("broken").await.expect("must read"),
+            b"Hello, World!"
+        );
+    }
+}
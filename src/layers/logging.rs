@@ -15,8 +15,11 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::borrow::Cow;
 use std::fmt::Debug;
+use std::fmt::Formatter;
 use std::io;
+use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
 
@@ -24,9 +27,6 @@ use async_trait::async_trait;
 use bytes::Bytes;
 use futures::FutureExt;
 use futures::TryFutureExt;
-use log::debug;
-use log::log;
-use log::trace;
 use log::Level;
 
 use crate::ops::*;
@@ -46,11 +46,16 @@ use crate::*;
 ///   - `errored`: the operation returns an expected error like `NotFound`.
 ///   - `failed`: the operation returns an unexpected error.
 ///
-/// # Todo
+/// # Fields
 ///
-/// We should migrate to log's kv api after it's ready.
+/// With `layers-logging-kv` enabled, every log entry carries the following
+/// fields via log's kv API besides the human-readable message:
 ///
-/// Tracking issue: <https://github.com/rust-lang/log/issues/328>
+/// - `service`: the scheme of the underlying service.
+/// - `operation`: the operation that is running.
+/// - `path`: the (redacted) path of the operation, if any.
+/// - `bytes`: the bytes read or written so far, for readers and writers.
+/// - `error_kind`: the [`ErrorKind`] of the returned error, if any.
 ///
 /// # Examples
 ///
@@ -82,10 +87,11 @@ use crate::*;
 /// ```shell
 /// RUST_LOG="info,opendal::services=debug" ./app
 /// ```
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub struct LoggingLayer {
     error_level: Option<Level>,
     failure_level: Option<Level>,
+    path_redactor: Option<PathRedactor>,
 }
 
 impl Default for LoggingLayer {
@@ -93,6 +99,7 @@ impl Default for LoggingLayer {
         Self {
             error_level: Some(Level::Warn),
             failure_level: Some(Level::Error),
+            path_redactor: None,
         }
     }
 }
@@ -117,6 +124,35 @@ impl LoggingLayer {
         self.failure_level = level;
         self
     }
+
+    /// Setting the redactor of path before it's logged.
+    ///
+    /// For example, users can hash or mask the tenant id in path.
+    ///
+    /// Paths will be logged AS-IS by default.
+    ///
+    /// ```
+    /// use opendal::layers::LoggingLayer;
+    ///
+    /// let _ = LoggingLayer::default().with_path_redactor(|_| "***".to_string());
+    /// ```
+    pub fn with_path_redactor(
+        mut self,
+        redactor: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.path_redactor = Some(PathRedactor(Arc::new(redactor)));
+        self
+    }
+}
+
+/// PathRedactor will be used to redact path before logging.
+#[derive(Clone)]
+struct PathRedactor(Arc<dyn Fn(&str) -> String + Send + Sync>);
+
+impl Debug for PathRedactor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PathRedactor").finish_non_exhaustive()
+    }
 }
 
 impl<A: Accessor> Layer<A> for LoggingLayer {
@@ -130,6 +166,7 @@ impl<A: Accessor> Layer<A> for LoggingLayer {
 
             error_level: self.error_level,
             failure_level: self.failure_level,
+            path_redactor: self.path_redactor.clone(),
         }
     }
 }
@@ -141,11 +178,42 @@ pub struct LoggingAccessor<A: Accessor> {
 
     error_level: Option<Level>,
     failure_level: Option<Level>,
+    path_redactor: Option<PathRedactor>,
 }
 
 static LOGGING_TARGET: &str = "opendal::services";
 
+/// Log with structured fields via log's kv API.
+///
+/// The kv API of log is still unstable, so fields will only be emitted if
+/// `layers-logging-kv` is enabled, otherwise only the message is logged.
+#[cfg(feature = "layers-logging-kv")]
+macro_rules! log_kv {
+    (target: $target:expr, $lvl:expr, $($key:ident = $value:expr),+; $($arg:tt)+) => {
+        log::log!(target: $target, $lvl, $($key = $value),+; $($arg)+)
+    };
+}
+
+/// Log with structured fields via log's kv API.
+///
+/// The kv API of log is still unstable, so fields will only be emitted if
+/// `layers-logging-kv` is enabled, otherwise only the message is logged.
+#[cfg(not(feature = "layers-logging-kv"))]
+macro_rules! log_kv {
+    (target: $target:expr, $lvl:expr, $($key:ident = $value:expr),+; $($arg:tt)+) => {
+        log::log!(target: $target, $lvl, $($arg)+)
+    };
+}
+
 impl<A: Accessor> LoggingAccessor<A> {
+    #[inline]
+    fn redact<'a>(&self, path: &'a str) -> Cow<'a, str> {
+        match &self.path_redactor {
+            Some(redactor) => Cow::Owned((redactor.0)(path)),
+            None => Cow::Borrowed(path),
+        }
+    }
+
     #[inline]
     fn err_status(&self, err: &Error) -> &'static str {
         if err.kind() == ErrorKind::Unexpected {
@@ -180,15 +248,21 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     fn metadata(&self) -> AccessorInfo {
-        debug!(
+        log_kv!(
             target: LOGGING_TARGET,
+            Level::Debug,
+            service = self.scheme.into_static(),
+            operation = Operation::Info.into_static();
             "service={} operation={} -> started",
             self.scheme,
             Operation::Info
         );
         let result = self.inner.info();
-        debug!(
+        log_kv!(
             target: LOGGING_TARGET,
+            Level::Debug,
+            service = self.scheme.into_static(),
+            operation = Operation::Info.into_static();
             "service={} operation={} -> finished: {:?}",
             self.scheme,
             Operation::Info,
@@ -199,36 +273,49 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     async fn create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
-        debug!(
+        let redacted = self.redact(path);
+        log_kv!(
             target: LOGGING_TARGET,
+            Level::Debug,
+            service = self.scheme.into_static(),
+            operation = Operation::Create.into_static(),
+            path = &*redacted;
             "service={} operation={} path={} -> started",
             self.scheme,
             Operation::Create,
-            path
+            redacted
         );
 
         self.inner
             .create(path, args)
             .await
             .map(|v| {
-                debug!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Debug,
+                    service = self.scheme.into_static(),
+                    operation = Operation::Create.into_static(),
+                    path = &*redacted;
                     "service={} operation={} path={} -> finished",
                     self.scheme,
                     Operation::Create,
-                    path
+                    redacted
                 );
                 v
             })
             .map_err(|err| {
                 if let Some(lvl) = self.err_level(&err) {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = Operation::Create.into_static(),
+                        path = &*redacted,
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} -> {}: {err:?}",
                         self.scheme,
                         Operation::Create,
-                        path,
+                        redacted,
                         self.err_status(&err)
                     )
                 };
@@ -237,12 +324,17 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        debug!(
+        let redacted = self.redact(path);
+        log_kv!(
             target: LOGGING_TARGET,
+            Level::Debug,
+            service = self.scheme.into_static(),
+            operation = Operation::Read.into_static(),
+            path = &*redacted;
             "service={} operation={} path={} range={} -> started",
             self.scheme,
             Operation::Read,
-            path,
+            redacted,
             args.range()
         );
 
@@ -252,28 +344,42 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
             .read(path, args)
             .await
             .map(|(rp, r)| {
-                debug!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Debug,
+                    service = self.scheme.into_static(),
+                    operation = Operation::Read.into_static(),
+                    path = &*redacted;
                     "service={} operation={} path={} range={} -> got reader",
                     self.scheme,
                     Operation::Read,
-                    path,
+                    redacted,
                     range
                 );
                 (
                     rp,
-                    LoggingReader::new(self.scheme, Operation::Read, path, r, self.failure_level),
+                    LoggingReader::new(
+                        self.scheme,
+                        Operation::Read,
+                        &redacted,
+                        r,
+                        self.failure_level,
+                    ),
                 )
             })
             .map_err(|err| {
                 if let Some(lvl) = self.err_level(&err) {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = Operation::Read.into_static(),
+                        path = &*redacted,
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} range={} -> {}: {err:?}",
                         self.scheme,
                         Operation::Read,
-                        path,
+                        redacted,
                         range,
                         self.err_status(&err)
                     )
@@ -283,38 +389,56 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        debug!(
+        let redacted = self.redact(path);
+        log_kv!(
             target: LOGGING_TARGET,
+            Level::Debug,
+            service = self.scheme.into_static(),
+            operation = Operation::Write.into_static(),
+            path = &*redacted;
             "service={} operation={} path={} -> started",
             self.scheme,
             Operation::Write,
-            path
+            redacted
         );
 
         self.inner
             .write(path, args)
             .await
             .map(|(rp, w)| {
-                debug!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Debug,
+                    service = self.scheme.into_static(),
+                    operation = Operation::Write.into_static(),
+                    path = &*redacted;
                     "service={} operation={} path={} -> start writing",
                     self.scheme,
                     Operation::Write,
-                    path,
+                    redacted,
+                );
+                let w = LoggingWriter::new(
+                    self.scheme,
+                    Operation::Write,
+                    &redacted,
+                    w,
+                    self.failure_level,
                 );
-                let w =
-                    LoggingWriter::new(self.scheme, Operation::Write, path, w, self.failure_level);
                 (rp, w)
             })
             .map_err(|err| {
                 if let Some(lvl) = self.err_level(&err) {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = Operation::Write.into_static(),
+                        path = &*redacted,
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} -> {}: {err:?}",
                         self.scheme,
                         Operation::Write,
-                        path,
+                        redacted,
                         self.err_status(&err)
                     )
                 };
@@ -323,36 +447,49 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        debug!(
+        let redacted = self.redact(path);
+        log_kv!(
             target: LOGGING_TARGET,
+            Level::Debug,
+            service = self.scheme.into_static(),
+            operation = Operation::Stat.into_static(),
+            path = &*redacted;
             "service={} operation={} path={} -> started",
             self.scheme,
             Operation::Stat,
-            path
+            redacted
         );
 
         self.inner
             .stat(path, args)
             .await
             .map(|v| {
-                debug!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Debug,
+                    service = self.scheme.into_static(),
+                    operation = Operation::Stat.into_static(),
+                    path = &*redacted;
                     "service={} operation={} path={} -> finished: {v:?}",
                     self.scheme,
                     Operation::Stat,
-                    path
+                    redacted
                 );
                 v
             })
             .map_err(|err| {
                 if let Some(lvl) = self.err_level(&err) {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = Operation::Stat.into_static(),
+                        path = &*redacted,
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} -> {}: {err:?}",
                         self.scheme,
                         Operation::Stat,
-                        path,
+                        redacted,
                         self.err_status(&err)
                     );
                 };
@@ -361,35 +498,48 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        debug!(
+        let redacted = self.redact(path);
+        log_kv!(
             target: LOGGING_TARGET,
+            Level::Debug,
+            service = self.scheme.into_static(),
+            operation = Operation::Delete.into_static(),
+            path = &*redacted;
             "service={} operation={} path={} -> started",
             self.scheme,
             Operation::Delete,
-            path
+            redacted
         );
 
         self.inner
             .delete(path, args.clone())
             .inspect(|v| match v {
                 Ok(_) => {
-                    debug!(
+                    log_kv!(
                         target: LOGGING_TARGET,
+                        Level::Debug,
+                        service = self.scheme.into_static(),
+                        operation = Operation::Delete.into_static(),
+                        path = &*redacted;
                         "service={} operation={} path={} -> finished",
                         self.scheme,
                         Operation::Delete,
-                        path
+                        redacted
                     );
                 }
                 Err(err) => {
                     if let Some(lvl) = self.err_level(err) {
-                        log!(
+                        log_kv!(
                             target: LOGGING_TARGET,
                             lvl,
+                            service = self.scheme.into_static(),
+                            operation = Operation::Delete.into_static(),
+                            path = &*redacted,
+                            error_kind = err.kind().into_static();
                             "service={} operation={} path={} -> {}: {err:?}",
                             self.scheme,
                             Operation::Delete,
-                            path,
+                            redacted,
                             self.err_status(err)
                         );
                     }
@@ -399,28 +549,37 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
-        debug!(
+        let redacted = self.redact(path);
+        log_kv!(
             target: LOGGING_TARGET,
+            Level::Debug,
+            service = self.scheme.into_static(),
+            operation = Operation::List.into_static(),
+            path = &*redacted;
             "service={} operation={} path={} -> started",
             self.scheme,
             Operation::List,
-            path
+            redacted
         );
 
         self.inner
             .list(path, args)
             .map(|v| match v {
                 Ok((rp, v)) => {
-                    debug!(
+                    log_kv!(
                         target: LOGGING_TARGET,
+                        Level::Debug,
+                        service = self.scheme.into_static(),
+                        operation = Operation::List.into_static(),
+                        path = &*redacted;
                         "service={} operation={} path={} -> start listing dir",
                         self.scheme,
                         Operation::List,
-                        path
+                        redacted
                     );
                     let streamer = LoggingPager::new(
                         self.scheme,
-                        path,
+                        &redacted,
                         Operation::List,
                         v,
                         self.error_level,
@@ -430,13 +589,17 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
                 }
                 Err(err) => {
                     if let Some(lvl) = self.err_level(&err) {
-                        log!(
+                        log_kv!(
                             target: LOGGING_TARGET,
                             lvl,
+                            service = self.scheme.into_static(),
+                            operation = Operation::List.into_static(),
+                            path = &*redacted,
+                            error_kind = err.kind().into_static();
                             "service={} operation={} path={} -> {}: {err:?}",
                             self.scheme,
                             Operation::List,
-                            path,
+                            redacted,
                             self.err_status(&err)
                         );
                     }
@@ -447,28 +610,37 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
-        debug!(
+        let redacted = self.redact(path);
+        log_kv!(
             target: LOGGING_TARGET,
+            Level::Debug,
+            service = self.scheme.into_static(),
+            operation = Operation::Scan.into_static(),
+            path = &*redacted;
             "service={} operation={} path={} -> started",
             self.scheme,
             Operation::Scan,
-            path
+            redacted
         );
 
         self.inner
             .scan(path, args)
             .map(|v| match v {
                 Ok((rp, v)) => {
-                    debug!(
+                    log_kv!(
                         target: LOGGING_TARGET,
+                        Level::Debug,
+                        service = self.scheme.into_static(),
+                        operation = Operation::Scan.into_static(),
+                        path = &*redacted;
                         "service={} operation={} path={} -> start scanning",
                         self.scheme,
                         Operation::Scan,
-                        path
+                        redacted
                     );
                     let streamer = LoggingPager::new(
                         self.scheme,
-                        path,
+                        &redacted,
                         Operation::Scan,
                         v,
                         self.error_level,
@@ -478,13 +650,17 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
                 }
                 Err(err) => {
                     if let Some(lvl) = self.err_level(&err) {
-                        log!(
+                        log_kv!(
                             target: LOGGING_TARGET,
                             lvl,
+                            service = self.scheme.into_static(),
+                            operation = Operation::Scan.into_static(),
+                            path = &*redacted,
+                            error_kind = err.kind().into_static();
                             "service={} operation={} path={} -> {}: {err:?}",
                             self.scheme,
                             Operation::Scan,
-                            path,
+                            redacted,
                             self.err_status(&err)
                         );
                     }
@@ -495,35 +671,48 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
-        debug!(
+        let redacted = self.redact(path);
+        log_kv!(
             target: LOGGING_TARGET,
+            Level::Debug,
+            service = self.scheme.into_static(),
+            operation = Operation::Presign.into_static(),
+            path = &*redacted;
             "service={} operation={} path={} -> started",
             self.scheme,
             Operation::Presign,
-            path
+            redacted
         );
 
         self.inner
             .presign(path, args)
             .map(|v| {
-                debug!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Debug,
+                    service = self.scheme.into_static(),
+                    operation = Operation::Presign.into_static(),
+                    path = &*redacted;
                     "service={} operation={} path={} -> finished: {v:?}",
                     self.scheme,
                     Operation::Presign,
-                    path
+                    redacted
                 );
                 v
             })
             .map_err(|err| {
                 if let Some(lvl) = self.err_level(&err) {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = Operation::Presign.into_static(),
+                        path = &*redacted,
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} -> {}: {err:?}",
                         self.scheme,
                         Operation::Presign,
-                        path,
+                        redacted,
                         self.err_status(&err)
                     );
                 }
@@ -534,8 +723,11 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
         let (op, count) = (args.operation().operation(), args.operation().len());
 
-        debug!(
+        log_kv!(
             target: LOGGING_TARGET,
+            Level::Debug,
+            service = self.scheme.into_static(),
+            operation = Operation::Batch.into_static();
             "service={} operation={}-{op} count={count} -> started",
             self.scheme,
             Operation::Batch,
@@ -544,8 +736,11 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
         self.inner
             .batch(args)
             .map_ok(|v| {
-                debug!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Debug,
+                    service = self.scheme.into_static(),
+                    operation = Operation::Batch.into_static();
                     "service={} operation={}-{op} count={count} -> finished: {}, succeed: {}, failed: {}",
                     self.scheme,
                     Operation::Batch,
@@ -557,9 +752,12 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
             })
             .map_err(|err| {
                 if let Some(lvl) = self.err_level(&err) {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = Operation::Batch.into_static(),
+                        error_kind = err.kind().into_static();
                         "service={} operation={}-{op} count={count} -> {}: {err:?}",
                         self.scheme,
                         Operation::Batch,
@@ -572,35 +770,48 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
-        debug!(
+        let redacted = self.redact(path);
+        log_kv!(
             target: LOGGING_TARGET,
+            Level::Debug,
+            service = self.scheme.into_static(),
+            operation = Operation::BlockingCreate.into_static(),
+            path = &*redacted;
             "service={} operation={} path={} -> started",
             self.scheme,
             Operation::BlockingCreate,
-            path
+            redacted
         );
 
         self.inner
             .blocking_create(path, args)
             .map(|v| {
-                debug!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Debug,
+                    service = self.scheme.into_static(),
+                    operation = Operation::BlockingCreate.into_static(),
+                    path = &*redacted;
                     "service={} operation={} path={} -> finished",
                     self.scheme,
                     Operation::BlockingCreate,
-                    path
+                    redacted
                 );
                 v
             })
             .map_err(|err| {
                 if let Some(lvl) = self.err_level(&err) {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = Operation::BlockingCreate.into_static(),
+                        path = &*redacted,
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} -> {}: {err:?}",
                         self.scheme,
                         Operation::BlockingCreate,
-                        path,
+                        redacted,
                         self.err_status(&err)
                     );
                 }
@@ -609,30 +820,39 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
-        debug!(
+        let redacted = self.redact(path);
+        log_kv!(
             target: LOGGING_TARGET,
+            Level::Debug,
+            service = self.scheme.into_static(),
+            operation = Operation::BlockingRead.into_static(),
+            path = &*redacted;
             "service={} operation={} path={} range={} -> started",
             self.scheme,
             Operation::BlockingRead,
-            path,
+            redacted,
             args.range(),
         );
 
         self.inner
             .blocking_read(path, args.clone())
             .map(|(rp, r)| {
-                debug!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Debug,
+                    service = self.scheme.into_static(),
+                    operation = Operation::BlockingRead.into_static(),
+                    path = &*redacted;
                     "service={} operation={} path={} range={} -> got reader",
                     self.scheme,
                     Operation::BlockingRead,
-                    path,
+                    redacted,
                     args.range(),
                 );
                 let r = LoggingReader::new(
                     self.scheme,
                     Operation::BlockingRead,
-                    path,
+                    &redacted,
                     r,
                     self.failure_level,
                 );
@@ -640,13 +860,17 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
             })
             .map_err(|err| {
                 if let Some(lvl) = self.err_level(&err) {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = Operation::BlockingRead.into_static(),
+                        path = &*redacted,
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} range={} -> {}: {err:?}",
                         self.scheme,
                         Operation::BlockingRead,
-                        path,
+                        redacted,
                         args.range(),
                         self.err_status(&err)
                     );
@@ -656,28 +880,37 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
-        debug!(
+        let redacted = self.redact(path);
+        log_kv!(
             target: LOGGING_TARGET,
+            Level::Debug,
+            service = self.scheme.into_static(),
+            operation = Operation::BlockingWrite.into_static(),
+            path = &*redacted;
             "service={} operation={} path={} -> started",
             self.scheme,
             Operation::BlockingWrite,
-            path,
+            redacted,
         );
 
         self.inner
             .blocking_write(path, args)
             .map(|(rp, w)| {
-                debug!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Debug,
+                    service = self.scheme.into_static(),
+                    operation = Operation::BlockingWrite.into_static(),
+                    path = &*redacted;
                     "service={} operation={} path={} -> written",
                     self.scheme,
                     Operation::BlockingWrite,
-                    path,
+                    redacted,
                 );
                 let w = LoggingWriter::new(
                     self.scheme,
                     Operation::BlockingWrite,
-                    path,
+                    &redacted,
                     w,
                     self.failure_level,
                 );
@@ -685,13 +918,17 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
             })
             .map_err(|err| {
                 if let Some(lvl) = self.err_level(&err) {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = Operation::BlockingWrite.into_static(),
+                        path = &*redacted,
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} -> {}: {err:?}",
                         self.scheme,
                         Operation::BlockingWrite,
-                        path,
+                        redacted,
                         self.err_status(&err)
                     );
                 }
@@ -700,35 +937,48 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        debug!(
+        let redacted = self.redact(path);
+        log_kv!(
             target: LOGGING_TARGET,
+            Level::Debug,
+            service = self.scheme.into_static(),
+            operation = Operation::BlockingStat.into_static(),
+            path = &*redacted;
             "service={} operation={} path={} -> started",
             self.scheme,
             Operation::BlockingStat,
-            path
+            redacted
         );
 
         self.inner
             .blocking_stat(path, args)
             .map(|v| {
-                debug!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Debug,
+                    service = self.scheme.into_static(),
+                    operation = Operation::BlockingStat.into_static(),
+                    path = &*redacted;
                     "service={} operation={} path={} -> finished: {v:?}",
                     self.scheme,
                     Operation::BlockingStat,
-                    path
+                    redacted
                 );
                 v
             })
             .map_err(|err| {
                 if let Some(lvl) = self.err_level(&err) {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = Operation::BlockingStat.into_static(),
+                        path = &*redacted,
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} -> {}: {err:?}",
                         self.scheme,
                         Operation::BlockingStat,
-                        path,
+                        redacted,
                         self.err_status(&err)
                     );
                 }
@@ -737,35 +987,48 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        debug!(
+        let redacted = self.redact(path);
+        log_kv!(
             target: LOGGING_TARGET,
+            Level::Debug,
+            service = self.scheme.into_static(),
+            operation = Operation::BlockingDelete.into_static(),
+            path = &*redacted;
             "service={} operation={} path={} -> started",
             self.scheme,
             Operation::BlockingDelete,
-            path
+            redacted
         );
 
         self.inner
             .blocking_delete(path, args)
             .map(|v| {
-                debug!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Debug,
+                    service = self.scheme.into_static(),
+                    operation = Operation::BlockingDelete.into_static(),
+                    path = &*redacted;
                     "service={} operation={} path={} -> finished",
                     self.scheme,
                     Operation::BlockingDelete,
-                    path
+                    redacted
                 );
                 v
             })
             .map_err(|err| {
                 if let Some(lvl) = self.err_level(&err) {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = Operation::BlockingDelete.into_static(),
+                        path = &*redacted,
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} -> {}: {err:?}",
                         self.scheme,
                         Operation::BlockingDelete,
-                        path,
+                        redacted,
                         self.err_status(&err)
                     );
                 }
@@ -774,27 +1037,36 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
-        debug!(
+        let redacted = self.redact(path);
+        log_kv!(
             target: LOGGING_TARGET,
+            Level::Debug,
+            service = self.scheme.into_static(),
+            operation = Operation::BlockingList.into_static(),
+            path = &*redacted;
             "service={} operation={} path={} -> started",
             self.scheme,
             Operation::BlockingList,
-            path
+            redacted
         );
 
         self.inner
             .blocking_list(path, args)
             .map(|(rp, v)| {
-                debug!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Debug,
+                    service = self.scheme.into_static(),
+                    operation = Operation::BlockingList.into_static(),
+                    path = &*redacted;
                     "service={} operation={} path={} -> got dir",
                     self.scheme,
                     Operation::BlockingList,
-                    path
+                    redacted
                 );
                 let li = LoggingPager::new(
                     self.scheme,
-                    path,
+                    &redacted,
                     Operation::BlockingList,
                     v,
                     self.error_level,
@@ -804,13 +1076,17 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
             })
             .map_err(|err| {
                 if let Some(lvl) = self.err_level(&err) {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = Operation::BlockingList.into_static(),
+                        path = &*redacted,
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} -> {}: {err:?}",
                         self.scheme,
                         Operation::BlockingList,
-                        path,
+                        redacted,
                         self.err_status(&err)
                     );
                 }
@@ -819,27 +1095,36 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
     }
 
     fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
-        debug!(
+        let redacted = self.redact(path);
+        log_kv!(
             target: LOGGING_TARGET,
+            Level::Debug,
+            service = self.scheme.into_static(),
+            operation = Operation::BlockingScan.into_static(),
+            path = &*redacted;
             "service={} operation={} path={} -> started",
             self.scheme,
             Operation::BlockingScan,
-            path
+            redacted
         );
 
         self.inner
             .blocking_scan(path, args)
             .map(|(rp, v)| {
-                debug!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Debug,
+                    service = self.scheme.into_static(),
+                    operation = Operation::BlockingScan.into_static(),
+                    path = &*redacted;
                     "service={} operation={} path={} -> start scanning",
                     self.scheme,
                     Operation::BlockingScan,
-                    path
+                    redacted
                 );
                 let li = LoggingPager::new(
                     self.scheme,
-                    path,
+                    &redacted,
                     Operation::BlockingScan,
                     v,
                     self.error_level,
@@ -849,13 +1134,17 @@ impl<A: Accessor> LayeredAccessor for LoggingAccessor<A> {
             })
             .map_err(|err| {
                 if let Some(lvl) = self.err_level(&err) {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = Operation::BlockingScan.into_static(),
+                        path = &*redacted,
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} -> {}: {err:?}",
                         self.scheme,
                         Operation::BlockingScan,
-                        path,
+                        redacted,
                         self.err_status(&err)
                     );
                 }
@@ -899,8 +1188,13 @@ impl<R> LoggingReader<R> {
 
 impl<R> Drop for LoggingReader<R> {
     fn drop(&mut self) {
-        debug!(
+        log_kv!(
             target: LOGGING_TARGET,
+            Level::Debug,
+            service = self.scheme.into_static(),
+            operation = self.op.into_static(),
+            path = self.path.as_str(),
+            bytes = self.read;
             "service={} operation={} path={} read={} -> data read finished",
             self.scheme,
             self.op,
@@ -916,8 +1210,13 @@ impl<R: oio::Read> oio::Read for LoggingReader<R> {
             Poll::Ready(res) => match res {
                 Ok(n) => {
                     self.read += n as u64;
-                    trace!(
+                    log_kv!(
                         target: LOGGING_TARGET,
+                        Level::Trace,
+                        service = self.scheme.into_static(),
+                        operation = ReadOperation::Read.into_static(),
+                        path = self.path.as_str(),
+                        bytes = self.read;
                         "service={} operation={} path={} read={} -> data read {}B ",
                         self.scheme,
                         ReadOperation::Read,
@@ -929,9 +1228,14 @@ impl<R: oio::Read> oio::Read for LoggingReader<R> {
                 }
                 Err(err) => {
                     if let Some(lvl) = self.failure_level {
-                        log!(
+                        log_kv!(
                             target: LOGGING_TARGET,
                             lvl,
+                            service = self.scheme.into_static(),
+                            operation = ReadOperation::Read.into_static(),
+                            path = self.path.as_str(),
+                            bytes = self.read,
+                            error_kind = err.kind().into_static();
                             "service={} operation={} path={} read={} -> data read failed: {err:?}",
                             self.scheme,
                             ReadOperation::Read,
@@ -943,8 +1247,13 @@ impl<R: oio::Read> oio::Read for LoggingReader<R> {
                 }
             },
             Poll::Pending => {
-                trace!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Trace,
+                    service = self.scheme.into_static(),
+                    operation = ReadOperation::Read.into_static(),
+                    path = self.path.as_str(),
+                    bytes = self.read;
                     "service={} operation={} path={} read={} -> data read pending",
                     self.scheme,
                     ReadOperation::Read,
@@ -960,8 +1269,13 @@ impl<R: oio::Read> oio::Read for LoggingReader<R> {
         match self.inner.poll_seek(cx, pos) {
             Poll::Ready(res) => match res {
                 Ok(n) => {
-                    trace!(
+                    log_kv!(
                         target: LOGGING_TARGET,
+                        Level::Trace,
+                        service = self.scheme.into_static(),
+                        operation = ReadOperation::Seek.into_static(),
+                        path = self.path.as_str(),
+                        bytes = self.read;
                         "service={} operation={} path={} read={} -> data seek to offset {n}",
                         self.scheme,
                         ReadOperation::Seek,
@@ -972,9 +1286,14 @@ impl<R: oio::Read> oio::Read for LoggingReader<R> {
                 }
                 Err(err) => {
                     if let Some(lvl) = self.failure_level {
-                        log!(
+                        log_kv!(
                             target: LOGGING_TARGET,
                             lvl,
+                            service = self.scheme.into_static(),
+                            operation = ReadOperation::Seek.into_static(),
+                            path = self.path.as_str(),
+                            bytes = self.read,
+                            error_kind = err.kind().into_static();
                             "service={} operation={} path={} read={} -> data read failed: {err:?}",
                             self.scheme,
                             ReadOperation::Seek,
@@ -986,8 +1305,13 @@ impl<R: oio::Read> oio::Read for LoggingReader<R> {
                 }
             },
             Poll::Pending => {
-                trace!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Trace,
+                    service = self.scheme.into_static(),
+                    operation = ReadOperation::Seek.into_static(),
+                    path = self.path.as_str(),
+                    bytes = self.read;
                     "service={} operation={} path={} read={} -> data seek pending",
                     self.scheme,
                     ReadOperation::Seek,
@@ -1004,8 +1328,13 @@ impl<R: oio::Read> oio::Read for LoggingReader<R> {
             Poll::Ready(res) => match res {
                 Some(Ok(bs)) => {
                     self.read += bs.len() as u64;
-                    trace!(
+                    log_kv!(
                         target: LOGGING_TARGET,
+                        Level::Trace,
+                        service = self.scheme.into_static(),
+                        operation = ReadOperation::Next.into_static(),
+                        path = self.path.as_str(),
+                        bytes = self.read;
                         "service={} operation={} path={} read={} -> data read {}B",
                         self.scheme,
                         ReadOperation::Next,
@@ -1017,9 +1346,14 @@ impl<R: oio::Read> oio::Read for LoggingReader<R> {
                 }
                 Some(Err(err)) => {
                     if let Some(lvl) = self.failure_level {
-                        log!(
+                        log_kv!(
                             target: LOGGING_TARGET,
                             lvl,
+                            service = self.scheme.into_static(),
+                            operation = ReadOperation::Next.into_static(),
+                            path = self.path.as_str(),
+                            bytes = self.read,
+                            error_kind = err.kind().into_static();
                             "service={} operation={} path={} read={} -> data read failed: {err:?}",
                             self.scheme,
                             ReadOperation::Next,
@@ -1032,8 +1366,13 @@ impl<R: oio::Read> oio::Read for LoggingReader<R> {
                 None => Poll::Ready(None),
             },
             Poll::Pending => {
-                trace!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Trace,
+                    service = self.scheme.into_static(),
+                    operation = ReadOperation::Next.into_static(),
+                    path = self.path.as_str(),
+                    bytes = self.read;
                     "service={} operation={} path={} read={} -> data read pending",
                     self.scheme,
                     ReadOperation::Next,
@@ -1051,8 +1390,13 @@ impl<R: oio::BlockingRead> oio::BlockingRead for LoggingReader<R> {
         match self.inner.read(buf) {
             Ok(n) => {
                 self.read += n as u64;
-                trace!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Trace,
+                    service = self.scheme.into_static(),
+                    operation = ReadOperation::BlockingRead.into_static(),
+                    path = self.path.as_str(),
+                    bytes = self.read;
                     "service={} operation={} path={} read={} -> data read {}B",
                     self.scheme,
                     ReadOperation::BlockingRead,
@@ -1064,9 +1408,14 @@ impl<R: oio::BlockingRead> oio::BlockingRead for LoggingReader<R> {
             }
             Err(err) => {
                 if let Some(lvl) = self.failure_level {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = ReadOperation::BlockingRead.into_static(),
+                        path = self.path.as_str(),
+                        bytes = self.read,
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} read={} -> data read failed: {err:?}",
                         self.scheme,
                         ReadOperation::BlockingRead,
@@ -1083,8 +1432,13 @@ impl<R: oio::BlockingRead> oio::BlockingRead for LoggingReader<R> {
     fn seek(&mut self, pos: io::SeekFrom) -> Result<u64> {
         match self.inner.seek(pos) {
             Ok(n) => {
-                trace!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Trace,
+                    service = self.scheme.into_static(),
+                    operation = ReadOperation::BlockingSeek.into_static(),
+                    path = self.path.as_str(),
+                    bytes = self.read;
                     "service={} operation={} path={} read={} -> data seek to offset {n}",
                     self.scheme,
                     ReadOperation::BlockingSeek,
@@ -1095,9 +1449,14 @@ impl<R: oio::BlockingRead> oio::BlockingRead for LoggingReader<R> {
             }
             Err(err) => {
                 if let Some(lvl) = self.failure_level {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = ReadOperation::BlockingSeek.into_static(),
+                        path = self.path.as_str(),
+                        bytes = self.read,
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} read={} -> data read failed: {err:?}",
                         self.scheme,
                         ReadOperation::BlockingSeek,
@@ -1114,8 +1473,13 @@ impl<R: oio::BlockingRead> oio::BlockingRead for LoggingReader<R> {
         match self.inner.next() {
             Some(Ok(bs)) => {
                 self.read += bs.len() as u64;
-                trace!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Trace,
+                    service = self.scheme.into_static(),
+                    operation = ReadOperation::BlockingNext.into_static(),
+                    path = self.path.as_str(),
+                    bytes = self.read;
                     "service={} operation={} path={} read={} -> data read {}B",
                     self.scheme,
                     ReadOperation::BlockingNext,
@@ -1127,9 +1491,14 @@ impl<R: oio::BlockingRead> oio::BlockingRead for LoggingReader<R> {
             }
             Some(Err(err)) => {
                 if let Some(lvl) = self.failure_level {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = ReadOperation::BlockingNext.into_static(),
+                        path = self.path.as_str(),
+                        bytes = self.read,
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} read={} -> data read failed: {err:?}",
                         self.scheme,
                         ReadOperation::BlockingNext,
@@ -1177,8 +1546,13 @@ impl<W> LoggingWriter<W> {
 
 impl<W> Drop for LoggingWriter<W> {
     fn drop(&mut self) {
-        debug!(
+        log_kv!(
             target: LOGGING_TARGET,
+            Level::Debug,
+            service = self.scheme.into_static(),
+            operation = self.op.into_static(),
+            path = self.path.as_str(),
+            bytes = self.written;
             "service={} operation={} path={} written={} -> data written finished",
             self.scheme,
             self.op,
@@ -1195,8 +1569,13 @@ impl<W: oio::Write> oio::Write for LoggingWriter<W> {
         match self.inner.write(bs).await {
             Ok(_) => {
                 self.written += size as u64;
-                trace!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Trace,
+                    service = self.scheme.into_static(),
+                    operation = WriteOperation::Write.into_static(),
+                    path = self.path.as_str(),
+                    bytes = self.written;
                     "service={} operation={} path={} written={} -> data write {}B",
                     self.scheme,
                     WriteOperation::Write,
@@ -1208,9 +1587,14 @@ impl<W: oio::Write> oio::Write for LoggingWriter<W> {
             }
             Err(err) => {
                 if let Some(lvl) = self.failure_level {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = WriteOperation::Write.into_static(),
+                        path = self.path.as_str(),
+                        bytes = self.written,
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} written={} -> data write failed: {err:?}",
                         self.scheme,
                         WriteOperation::Write,
@@ -1228,8 +1612,13 @@ impl<W: oio::Write> oio::Write for LoggingWriter<W> {
         match self.inner.append(bs).await {
             Ok(_) => {
                 self.written += size as u64;
-                trace!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Trace,
+                    service = self.scheme.into_static(),
+                    operation = WriteOperation::Append.into_static(),
+                    path = self.path.as_str(),
+                    bytes = self.written;
                     "service={} operation={} path={} written={} -> data write {}B",
                     self.scheme,
                     WriteOperation::Append,
@@ -1241,9 +1630,14 @@ impl<W: oio::Write> oio::Write for LoggingWriter<W> {
             }
             Err(err) => {
                 if let Some(lvl) = self.failure_level {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = WriteOperation::Append.into_static(),
+                        path = self.path.as_str(),
+                        bytes = self.written,
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} written={} -> data write failed: {err:?}",
                         self.scheme,
                         WriteOperation::Append,
@@ -1261,9 +1655,14 @@ impl<W: oio::Write> oio::Write for LoggingWriter<W> {
             Ok(_) => Ok(()),
             Err(err) => {
                 if let Some(lvl) = self.failure_level {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = WriteOperation::Close.into_static(),
+                        path = self.path.as_str(),
+                        bytes = self.written,
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} written={} -> data close failed: {err:?}",
                         self.scheme,
                         WriteOperation::Close,
@@ -1275,6 +1674,35 @@ impl<W: oio::Write> oio::Write for LoggingWriter<W> {
             }
         }
     }
+
+    async fn abort(&mut self) -> Result<()> {
+        match self.inner.abort().await {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                if let Some(lvl) = self.failure_level {
+                    log_kv!(
+                        target: LOGGING_TARGET,
+                        lvl,
+                        service = self.scheme.into_static(),
+                        operation = WriteOperation::Abort.into_static(),
+                        path = self.path.as_str(),
+                        bytes = self.written,
+                        error_kind = err.kind().into_static();
+                        "service={} operation={} path={} written={} -> data abort failed: {err:?}",
+                        self.scheme,
+                        WriteOperation::Abort,
+                        self.path,
+                        self.written,
+                    )
+                }
+                Err(err)
+            }
+        }
+    }
+
+    fn state(&self) -> Result<MultipartState> {
+        self.inner.state()
+    }
 }
 
 impl<W: oio::BlockingWrite> oio::BlockingWrite for LoggingWriter<W> {
@@ -1283,8 +1711,13 @@ impl<W: oio::BlockingWrite> oio::BlockingWrite for LoggingWriter<W> {
         match self.inner.write(bs) {
             Ok(_) => {
                 self.written += size as u64;
-                trace!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Trace,
+                    service = self.scheme.into_static(),
+                    operation = WriteOperation::BlockingWrite.into_static(),
+                    path = self.path.as_str(),
+                    bytes = self.written;
                     "service={} operation={} path={} written={} -> data write {}B",
                     self.scheme,
                     WriteOperation::BlockingWrite,
@@ -1296,9 +1729,14 @@ impl<W: oio::BlockingWrite> oio::BlockingWrite for LoggingWriter<W> {
             }
             Err(err) => {
                 if let Some(lvl) = self.failure_level {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = WriteOperation::BlockingWrite.into_static(),
+                        path = self.path.as_str(),
+                        bytes = self.written,
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} written={} -> data write failed: {err:?}",
                         self.scheme,
                         WriteOperation::BlockingWrite,
@@ -1316,8 +1754,13 @@ impl<W: oio::BlockingWrite> oio::BlockingWrite for LoggingWriter<W> {
         match self.inner.append(bs) {
             Ok(_) => {
                 self.written += size as u64;
-                trace!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Trace,
+                    service = self.scheme.into_static(),
+                    operation = WriteOperation::BlockingAppend.into_static(),
+                    path = self.path.as_str(),
+                    bytes = self.written;
                     "service={} operation={} path={} written={} -> data write {}B",
                     self.scheme,
                     WriteOperation::BlockingAppend,
@@ -1329,9 +1772,14 @@ impl<W: oio::BlockingWrite> oio::BlockingWrite for LoggingWriter<W> {
             }
             Err(err) => {
                 if let Some(lvl) = self.failure_level {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = WriteOperation::BlockingAppend.into_static(),
+                        path = self.path.as_str(),
+                        bytes = self.written,
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} written={} -> data write failed: {err:?}",
                         self.scheme,
                         WriteOperation::BlockingAppend,
@@ -1349,9 +1797,14 @@ impl<W: oio::BlockingWrite> oio::BlockingWrite for LoggingWriter<W> {
             Ok(_) => Ok(()),
             Err(err) => {
                 if let Some(lvl) = self.failure_level {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = WriteOperation::BlockingClose.into_static(),
+                        path = self.path.as_str(),
+                        bytes = self.written,
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} written={} -> data close failed: {err:?}",
                         self.scheme,
                         WriteOperation::BlockingClose,
@@ -1363,6 +1816,31 @@ impl<W: oio::BlockingWrite> oio::BlockingWrite for LoggingWriter<W> {
             }
         }
     }
+
+    fn abort(&mut self) -> Result<()> {
+        match self.inner.abort() {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                if let Some(lvl) = self.failure_level {
+                    log_kv!(
+                        target: LOGGING_TARGET,
+                        lvl,
+                        service = self.scheme.into_static(),
+                        operation = WriteOperation::BlockingAbort.into_static(),
+                        path = self.path.as_str(),
+                        bytes = self.written,
+                        error_kind = err.kind().into_static();
+                        "service={} operation={} path={} written={} -> data abort failed: {err:?}",
+                        self.scheme,
+                        WriteOperation::BlockingAbort,
+                        self.path,
+                        self.written,
+                    )
+                }
+                Err(err)
+            }
+        }
+    }
 }
 
 pub struct LoggingPager<P> {
@@ -1400,16 +1878,24 @@ impl<P> LoggingPager<P> {
 impl<P> Drop for LoggingPager<P> {
     fn drop(&mut self) {
         if self.finished {
-            debug!(
+            log_kv!(
                 target: LOGGING_TARGET,
+                Level::Debug,
+                service = self.scheme.into_static(),
+                operation = self.op.into_static(),
+                path = self.path.as_str();
                 "service={} operation={} path={} -> all entries read finished",
                 self.scheme,
                 self.op,
                 self.path
             );
         } else {
-            debug!(
+            log_kv!(
                 target: LOGGING_TARGET,
+                Level::Debug,
+                service = self.scheme.into_static(),
+                operation = self.op.into_static(),
+                path = self.path.as_str();
                 "service={} operation={} path={} -> partial entries read finished",
                 self.scheme,
                 self.op,
@@ -1446,8 +1932,12 @@ impl<P: oio::Page> oio::Page for LoggingPager<P> {
 
         match &res {
             Ok(Some(des)) => {
-                debug!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Debug,
+                    service = self.scheme.into_static(),
+                    operation = self.op.into_static(),
+                    path = self.path.as_str();
                     "service={} operation={} path={} -> listed {} entries",
                     self.scheme,
                     self.op,
@@ -1456,17 +1946,25 @@ impl<P: oio::Page> oio::Page for LoggingPager<P> {
                 );
             }
             Ok(None) => {
-                debug!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Debug,
+                    service = self.scheme.into_static(),
+                    operation = self.op.into_static(),
+                    path = self.path.as_str();
                     "service={} operation={} path={} -> finished", self.scheme, self.op, self.path
                 );
                 self.finished = true;
             }
             Err(err) => {
                 if let Some(lvl) = self.err_level(err) {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = self.op.into_static(),
+                        path = self.path.as_str(),
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} -> {}: {err:?}",
                         self.scheme,
                         self.op,
@@ -1487,8 +1985,12 @@ impl<P: oio::BlockingPage> oio::BlockingPage for LoggingPager<P> {
 
         match &res {
             Ok(Some(des)) => {
-                debug!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Debug,
+                    service = self.scheme.into_static(),
+                    operation = self.op.into_static(),
+                    path = self.path.as_str();
                     "service={} operation={} path={} -> got {} entries",
                     self.scheme,
                     self.op,
@@ -1497,17 +1999,25 @@ impl<P: oio::BlockingPage> oio::BlockingPage for LoggingPager<P> {
                 );
             }
             Ok(None) => {
-                debug!(
+                log_kv!(
                     target: LOGGING_TARGET,
+                    Level::Debug,
+                    service = self.scheme.into_static(),
+                    operation = self.op.into_static(),
+                    path = self.path.as_str();
                     "service={} operation={} path={} -> finished", self.scheme, self.op, self.path
                 );
                 self.finished = true;
             }
             Err(err) => {
                 if let Some(lvl) = self.err_level(err) {
-                    log!(
+                    log_kv!(
                         target: LOGGING_TARGET,
                         lvl,
+                        service = self.scheme.into_static(),
+                        operation = self.op.into_static(),
+                        path = self.path.as_str(),
+                        error_kind = err.kind().into_static();
                         "service={} operation={} path={} -> {}: {err:?}",
                         self.scheme,
                         self.op,
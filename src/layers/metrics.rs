@@ -54,6 +54,12 @@ static METRIC_REQUESTS_DURATION_SECONDS: &str = "opendal_requests_duration_secon
 static METRICS_ERRORS_TOTAL: &str = "opendal_errors_total";
 /// bytes_total records all bytes processed by operator.
 static METRIC_BYTES_TOTAL: &str = "opendal_bytes_total";
+/// bytes_read_total records bytes read from underlying storage as soon as
+/// they are streamed through reader.
+static METRIC_BYTES_READ_TOTAL: &str = "opendal_bytes_read_total";
+/// bytes_written_total records bytes written into underlying storage as
+/// soon as they are accepted by writer.
+static METRIC_BYTES_WRITTEN_TOTAL: &str = "opendal_bytes_written_total";
 
 /// The scheme of the service.
 static LABEL_SERVICE: &str = "service";
@@ -70,6 +76,8 @@ static LABEL_ERROR: &str = "error";
 /// - `opendal_requests_duration_seconds`: Request duration seconds.
 /// - `opendal_errors_total`: Total error numbers.
 /// - `opendal_bytes_total`: bytes read/write from/to underlying storage.
+/// - `opendal_bytes_read_total`: bytes read from underlying storage.
+/// - `opendal_bytes_written_total`: bytes written into underlying storage.
 ///
 /// # Labels
 ///
@@ -85,6 +93,34 @@ static LABEL_ERROR: &str = "error";
 /// Otherwise, the histogram data collected by `requests_duration_seconds`
 /// could result in OOM.
 ///
+/// `requests_duration_seconds` will be recorded for both successful and
+/// failed requests. For `read` and `write`, the duration covers the whole
+/// lifetime of the returned reader/writer.
+///
+/// `bytes_total` will only be recorded after the reader/writer has been
+/// dropped, while `bytes_read_total` and `bytes_written_total` will be
+/// updated as data flows. Readers that are dropped in the middle will
+/// only count bytes that have been actually transferred.
+///
+/// # Buckets
+///
+/// The `metrics` crate leaves histogram buckets to exporters, so buckets
+/// can't be applied by this layer directly. Buckets are configured via
+/// [`MetricsLayer::with_buckets`] (default to
+/// [`MetricsLayer::DEFAULT_DURATION_SECONDS_BUCKETS`], from 1ms to 10s)
+/// and should be passed to the exporter via [`MetricsLayer::buckets`]:
+///
+/// ```ignore
+/// use metrics_exporter_prometheus::Matcher;
+/// use metrics_exporter_prometheus::PrometheusBuilder;
+///
+/// let layer = MetricsLayer::default().with_buckets(vec![0.01, 0.1, 1.0]);
+/// let builder = PrometheusBuilder::new().set_buckets_for_metric(
+///     Matcher::Full("opendal_requests_duration_seconds".to_string()),
+///     layer.buckets(),
+/// )?;
+/// ```
+///
 /// # Examples
 ///
 /// ```
@@ -95,7 +131,7 @@ static LABEL_ERROR: &str = "error";
 ///
 /// let _ = Operator::new(services::Memory::default())
 ///     .expect("must init")
-///     .layer(MetricsLayer)
+///     .layer(MetricsLayer::default())
 ///     .finish();
 /// ```
 ///
@@ -114,8 +150,42 @@ static LABEL_ERROR: &str = "error";
 /// let (recorder, exporter) = builder.build().expect("failed to build recorder/exporter");
 /// let recorder = builder.build_recorder().expect("failed to build recorder");
 /// ```
-#[derive(Debug, Copy, Clone)]
-pub struct MetricsLayer;
+#[derive(Debug, Clone)]
+pub struct MetricsLayer {
+    buckets: Vec<f64>,
+}
+
+impl Default for MetricsLayer {
+    fn default() -> Self {
+        Self {
+            buckets: Self::DEFAULT_DURATION_SECONDS_BUCKETS.to_vec(),
+        }
+    }
+}
+
+impl MetricsLayer {
+    /// Default buckets of `opendal_requests_duration_seconds` in seconds.
+    pub const DEFAULT_DURATION_SECONDS_BUCKETS: &'static [f64] = &[
+        0.001, 0.002, 0.005, 0.01, 0.02, 0.05, 0.1, 0.2, 0.5, 1.0, 2.0, 5.0, 10.0,
+    ];
+
+    /// Set buckets of `opendal_requests_duration_seconds` in seconds.
+    ///
+    /// Buckets will be sorted, and NaN or duplicated values will be removed.
+    pub fn with_buckets(mut self, buckets: Vec<f64>) -> Self {
+        let mut buckets: Vec<f64> = buckets.into_iter().filter(|v| !v.is_nan()).collect();
+        buckets.sort_by(|a, b| a.partial_cmp(b).expect("NaN has been filtered"));
+        buckets.dedup();
+
+        self.buckets = buckets;
+        self
+    }
+
+    /// Get buckets of `opendal_requests_duration_seconds` in seconds.
+    pub fn buckets(&self) -> &[f64] {
+        &self.buckets
+    }
+}
 
 impl<A: Accessor> Layer<A> for MetricsLayer {
     type LayeredAccessor = MetricsAccessor<A>;
@@ -146,10 +216,12 @@ struct MetricsHandler {
     requests_total_read: Counter,
     requests_duration_seconds_read: Histogram,
     bytes_total_read: Counter,
+    bytes_read_total_read: Counter,
 
     requests_total_write: Counter,
     requests_duration_seconds_write: Histogram,
     bytes_total_write: Counter,
+    bytes_written_total_write: Counter,
 
     requests_total_stat: Counter,
     requests_duration_seconds_stat: Histogram,
@@ -175,11 +247,12 @@ struct MetricsHandler {
     requests_total_blocking_read: Counter,
     requests_duration_seconds_blocking_read: Histogram,
     bytes_total_blocking_read: Counter,
+    bytes_read_total_blocking_read: Counter,
 
     requests_total_blocking_write: Counter,
     requests_duration_seconds_blocking_write: Histogram,
-    #[allow(dead_code)]
     bytes_total_blocking_write: Counter,
+    bytes_written_total_blocking_write: Counter,
 
     requests_total_blocking_stat: Counter,
     requests_duration_seconds_blocking_stat: Histogram,
@@ -236,6 +309,11 @@ impl MetricsHandler {
                 LABEL_SERVICE => service,
                 LABEL_OPERATION => Operation::Read.into_static(),
             ),
+            bytes_read_total_read: register_counter!(
+                METRIC_BYTES_READ_TOTAL,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => Operation::Read.into_static(),
+            ),
 
             requests_total_write: register_counter!(
                 METRIC_REQUESTS_TOTAL,
@@ -252,6 +330,11 @@ impl MetricsHandler {
                 LABEL_SERVICE => service,
                 LABEL_OPERATION => Operation::Write.into_static(),
             ),
+            bytes_written_total_write: register_counter!(
+                METRIC_BYTES_WRITTEN_TOTAL,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => Operation::Write.into_static(),
+            ),
 
             requests_total_stat: register_counter!(
                 METRIC_REQUESTS_TOTAL,
@@ -345,6 +428,11 @@ impl MetricsHandler {
                 LABEL_SERVICE => service,
                 LABEL_OPERATION => Operation::BlockingRead.into_static(),
             ),
+            bytes_read_total_blocking_read: register_counter!(
+                METRIC_BYTES_READ_TOTAL,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => Operation::BlockingRead.into_static(),
+            ),
 
             requests_total_blocking_write: register_counter!(
                 METRIC_REQUESTS_TOTAL,
@@ -361,6 +449,11 @@ impl MetricsHandler {
                 LABEL_SERVICE => service,
                 LABEL_OPERATION => Operation::BlockingWrite.into_static(),
             ),
+            bytes_written_total_blocking_write: register_counter!(
+                METRIC_BYTES_WRITTEN_TOTAL,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => Operation::BlockingWrite.into_static(),
+            ),
 
             requests_total_blocking_stat: register_counter!(
                 METRIC_REQUESTS_TOTAL,
@@ -497,12 +590,16 @@ impl<A: Accessor> LayeredAccessor for MetricsAccessor<A> {
                             Operation::Read,
                             self.handle.clone(),
                             self.handle.bytes_total_read.clone(),
+                            self.handle.bytes_read_total_read.clone(),
                             self.handle.requests_duration_seconds_read.clone(),
                             Some(start),
                         ),
                     )
                 })
                 .map_err(|err| {
+                    let dur = start.elapsed().as_secs_f64();
+
+                    self.handle.requests_duration_seconds_read.record(dur);
                     self.handle
                         .increment_errors_total(Operation::Read, err.kind());
                     err
@@ -526,12 +623,16 @@ impl<A: Accessor> LayeredAccessor for MetricsAccessor<A> {
                         Operation::Write,
                         self.handle.clone(),
                         self.handle.bytes_total_write.clone(),
+                        self.handle.bytes_written_total_write.clone(),
                         self.handle.requests_duration_seconds_write.clone(),
                         Some(start),
                     ),
                 )
             })
             .inspect_err(|e| {
+                let dur = start.elapsed().as_secs_f64();
+
+                self.handle.requests_duration_seconds_write.record(dur);
                 self.handle
                     .increment_errors_total(Operation::Write, e.kind());
             })
@@ -545,14 +646,16 @@ impl<A: Accessor> LayeredAccessor for MetricsAccessor<A> {
 
         self.inner
             .stat(path, args)
-            .inspect_ok(|_| {
+            .map(|v| {
                 let dur = start.elapsed().as_secs_f64();
 
                 self.handle.requests_duration_seconds_stat.record(dur);
-            })
-            .inspect_err(|e| {
-                self.handle
-                    .increment_errors_total(Operation::Stat, e.kind());
+
+                v.map_err(|e| {
+                    self.handle
+                        .increment_errors_total(Operation::Stat, e.kind());
+                    e
+                })
             })
             .await
     }
@@ -564,14 +667,16 @@ impl<A: Accessor> LayeredAccessor for MetricsAccessor<A> {
 
         self.inner
             .delete(path, args)
-            .inspect_ok(|_| {
+            .map(|v| {
                 let dur = start.elapsed().as_secs_f64();
 
                 self.handle.requests_duration_seconds_delete.record(dur);
-            })
-            .inspect_err(|e| {
-                self.handle
-                    .increment_errors_total(Operation::Delete, e.kind());
+
+                v.map_err(|e| {
+                    self.handle
+                        .increment_errors_total(Operation::Delete, e.kind());
+                    e
+                })
             })
             .await
     }
@@ -583,14 +688,16 @@ impl<A: Accessor> LayeredAccessor for MetricsAccessor<A> {
 
         self.inner
             .list(path, args)
-            .inspect_ok(|_| {
+            .map(|v| {
                 let dur = start.elapsed().as_secs_f64();
 
                 self.handle.requests_duration_seconds_list.record(dur);
-            })
-            .inspect_err(|e| {
-                self.handle
-                    .increment_errors_total(Operation::List, e.kind());
+
+                v.map_err(|e| {
+                    self.handle
+                        .increment_errors_total(Operation::List, e.kind());
+                    e
+                })
             })
             .await
     }
@@ -602,14 +709,16 @@ impl<A: Accessor> LayeredAccessor for MetricsAccessor<A> {
 
         self.inner
             .scan(path, args)
-            .inspect_ok(|_| {
+            .map(|v| {
                 let dur = start.elapsed().as_secs_f64();
 
                 self.handle.requests_duration_seconds_scan.record(dur);
-            })
-            .inspect_err(|e| {
-                self.handle
-                    .increment_errors_total(Operation::Scan, e.kind());
+
+                v.map_err(|e| {
+                    self.handle
+                        .increment_errors_total(Operation::Scan, e.kind());
+                    e
+                })
             })
             .await
     }
@@ -676,6 +785,7 @@ impl<A: Accessor> LayeredAccessor for MetricsAccessor<A> {
                     Operation::BlockingRead,
                     self.handle.clone(),
                     self.handle.bytes_total_blocking_read.clone(),
+                    self.handle.bytes_read_total_blocking_read.clone(),
                     self.handle.requests_duration_seconds_blocking_read.clone(),
                     Some(start),
                 ),
@@ -683,6 +793,11 @@ impl<A: Accessor> LayeredAccessor for MetricsAccessor<A> {
         });
 
         result.map_err(|e| {
+            let dur = start.elapsed().as_secs_f64();
+
+            self.handle
+                .requests_duration_seconds_blocking_read
+                .record(dur);
             self.handle
                 .increment_errors_total(Operation::BlockingRead, e.kind());
             e
@@ -694,11 +809,6 @@ impl<A: Accessor> LayeredAccessor for MetricsAccessor<A> {
 
         let start = Instant::now();
         let result = self.inner.blocking_write(path, args);
-        let dur = start.elapsed().as_secs_f64();
-
-        self.handle
-            .requests_duration_seconds_blocking_write
-            .record(dur);
 
         result
             .map(|(rp, w)| {
@@ -708,13 +818,19 @@ impl<A: Accessor> LayeredAccessor for MetricsAccessor<A> {
                         w,
                         Operation::BlockingWrite,
                         self.handle.clone(),
-                        self.handle.bytes_total_write.clone(),
-                        self.handle.requests_duration_seconds_write.clone(),
+                        self.handle.bytes_total_blocking_write.clone(),
+                        self.handle.bytes_written_total_blocking_write.clone(),
+                        self.handle.requests_duration_seconds_blocking_write.clone(),
                         Some(start),
                     ),
                 )
             })
             .map_err(|e| {
+                let dur = start.elapsed().as_secs_f64();
+
+                self.handle
+                    .requests_duration_seconds_blocking_write
+                    .record(dur);
                 self.handle
                     .increment_errors_total(Operation::BlockingWrite, e.kind());
                 e
@@ -799,6 +915,7 @@ pub struct MetricWrapper<R> {
 
     op: Operation,
     bytes_counter: Counter,
+    transferred_counter: Counter,
     requests_duration_seconds: Histogram,
     handle: Arc<MetricsHandler>,
 
@@ -812,6 +929,7 @@ impl<R> MetricWrapper<R> {
         op: Operation,
         handle: Arc<MetricsHandler>,
         bytes_counter: Counter,
+        transferred_counter: Counter,
         requests_duration_seconds: Histogram,
         start: Option<Instant>,
     ) -> Self {
@@ -820,11 +938,18 @@ impl<R> MetricWrapper<R> {
             op,
             handle,
             bytes_counter,
+            transferred_counter,
             requests_duration_seconds,
             start,
             bytes: 0,
         }
     }
+
+    /// Record bytes that have been transferred.
+    fn transferred(&mut self, n: u64) {
+        self.bytes += n;
+        self.transferred_counter.increment(n);
+    }
 }
 
 impl<R> Drop for MetricWrapper<R> {
@@ -841,7 +966,7 @@ impl<R: oio::Read> oio::Read for MetricWrapper<R> {
     fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
         self.inner.poll_read(cx, buf).map(|res| match res {
             Ok(bytes) => {
-                self.bytes += bytes as u64;
+                self.transferred(bytes as u64);
                 Ok(bytes)
             }
             Err(e) => {
@@ -864,7 +989,7 @@ impl<R: oio::Read> oio::Read for MetricWrapper<R> {
     fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
         self.inner.poll_next(cx).map(|res| match res {
             Some(Ok(bytes)) => {
-                self.bytes += bytes.len() as u64;
+                self.transferred(bytes.len() as u64);
                 Some(Ok(bytes))
             }
             Some(Err(e)) => {
@@ -881,7 +1006,7 @@ impl<R: oio::BlockingRead> oio::BlockingRead for MetricWrapper<R> {
         self.inner
             .read(buf)
             .map(|n| {
-                self.bytes += n as u64;
+                self.transferred(n as u64);
                 n
             })
             .map_err(|e| {
@@ -900,7 +1025,7 @@ impl<R: oio::BlockingRead> oio::BlockingRead for MetricWrapper<R> {
     fn next(&mut self) -> Option<Result<Bytes>> {
         self.inner.next().map(|res| match res {
             Ok(bytes) => {
-                self.bytes += bytes.len() as u64;
+                self.transferred(bytes.len() as u64);
                 Ok(bytes)
             }
             Err(e) => {
@@ -918,7 +1043,7 @@ impl<R: oio::Write> oio::Write for MetricWrapper<R> {
         self.inner
             .write(bs)
             .await
-            .map(|_| self.bytes += size as u64)
+            .map(|_| self.transferred(size as u64))
             .map_err(|err| {
                 self.handle.increment_errors_total(self.op, err.kind());
                 err
@@ -930,7 +1055,7 @@ impl<R: oio::Write> oio::Write for MetricWrapper<R> {
         self.inner
             .append(bs)
             .await
-            .map(|_| self.bytes += size as u64)
+            .map(|_| self.transferred(size as u64))
             .map_err(|err| {
                 self.handle.increment_errors_total(self.op, err.kind());
                 err
@@ -943,6 +1068,17 @@ impl<R: oio::Write> oio::Write for MetricWrapper<R> {
             err
         })
     }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await.map_err(|err| {
+            self.handle.increment_errors_total(self.op, err.kind());
+            err
+        })
+    }
+
+    fn state(&self) -> Result<MultipartState> {
+        self.inner.state()
+    }
 }
 
 impl<R: oio::BlockingWrite> oio::BlockingWrite for MetricWrapper<R> {
@@ -950,7 +1086,7 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for MetricWrapper<R> {
         let size = bs.len();
         self.inner
             .write(bs)
-            .map(|_| self.bytes += size as u64)
+            .map(|_| self.transferred(size as u64))
             .map_err(|err| {
                 self.handle.increment_errors_total(self.op, err.kind());
                 err
@@ -961,7 +1097,7 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for MetricWrapper<R> {
         let size = bs.len();
         self.inner
             .append(bs)
-            .map(|_| self.bytes += size as u64)
+            .map(|_| self.transferred(size as u64))
             .map_err(|err| {
                 self.handle.increment_errors_total(self.op, err.kind());
                 err
@@ -974,4 +1110,211 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for MetricWrapper<R> {
             err
         })
     }
+
+    fn abort(&mut self) -> Result<()> {
+        self.inner.abort().map_err(|err| {
+            self.handle.increment_errors_total(self.op, err.kind());
+            err
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use futures::AsyncReadExt;
+    use metrics::CounterFn;
+    use metrics::GaugeFn;
+    use metrics::HistogramFn;
+    use metrics::Key;
+    use metrics::KeyName;
+    use metrics::Recorder;
+    use metrics::SharedString;
+    use metrics::Unit;
+    use once_cell::sync::Lazy;
+
+    use super::*;
+    use crate::services;
+
+    /// Observations of `requests_duration_seconds` keyed by operation.
+    static OBSERVATIONS: Lazy<Mutex<Vec<String>>> = Lazy::new(|| Mutex::new(Vec::new()));
+    /// Values of counters keyed by metric name and operation.
+    static COUNTERS: Lazy<Mutex<HashMap<(String, String), u64>>> =
+        Lazy::new(|| Mutex::new(HashMap::new()));
+
+    /// Whether the mock recorder has been installed by us.
+    static INSTALLED: Lazy<bool> =
+        Lazy::new(|| metrics::set_boxed_recorder(Box::new(MockRecorder)).is_ok());
+    static TEST_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+    /// Install the mock recorder and take the test lock.
+    ///
+    /// Recorder is global, so tests are run one by one with all recorded
+    /// values cleared in between.
+    ///
+    /// Returns `None` if another recorder has been installed in this
+    /// process, in which case nothing can be observed.
+    async fn setup() -> Option<tokio::sync::MutexGuard<'static, ()>> {
+        if !*INSTALLED {
+            log::warn!("global metrics recorder has been installed by others, skip test");
+            return None;
+        }
+
+        let guard = TEST_LOCK.lock().await;
+        OBSERVATIONS.lock().unwrap().clear();
+        COUNTERS.lock().unwrap().clear();
+        Some(guard)
+    }
+
+    fn counter(name: &str, op: Operation) -> u64 {
+        COUNTERS
+            .lock()
+            .unwrap()
+            .get(&(name.to_string(), op.into_static().to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    struct MockRecorder;
+
+    struct MockCounter(String, String);
+
+    impl CounterFn for MockCounter {
+        fn increment(&self, value: u64) {
+            *COUNTERS
+                .lock()
+                .unwrap()
+                .entry((self.0.clone(), self.1.clone()))
+                .or_default() += value;
+        }
+
+        fn absolute(&self, value: u64) {
+            COUNTERS
+                .lock()
+                .unwrap()
+                .insert((self.0.clone(), self.1.clone()), value);
+        }
+    }
+
+    struct MockHistogram(String);
+
+    impl HistogramFn for MockHistogram {
+        fn record(&self, _: f64) {
+            OBSERVATIONS.lock().unwrap().push(self.0.clone());
+        }
+    }
+
+    struct NoopGauge;
+
+    impl GaugeFn for NoopGauge {
+        fn increment(&self, _: f64) {}
+        fn decrement(&self, _: f64) {}
+        fn set(&self, _: f64) {}
+    }
+
+    impl Recorder for MockRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key) -> Counter {
+            let op = key
+                .labels()
+                .find(|l| l.key() == LABEL_OPERATION)
+                .map(|l| l.value().to_string())
+                .unwrap_or_default();
+            Counter::from_arc(Arc::new(MockCounter(key.name().to_string(), op)))
+        }
+
+        fn register_gauge(&self, _: &Key) -> metrics::Gauge {
+            metrics::Gauge::from_arc(Arc::new(NoopGauge))
+        }
+
+        fn register_histogram(&self, key: &Key) -> Histogram {
+            let op = key
+                .labels()
+                .find(|l| l.key() == LABEL_OPERATION)
+                .map(|l| l.value().to_string())
+                .unwrap_or_default();
+            Histogram::from_arc(Arc::new(MockHistogram(op)))
+        }
+    }
+
+    #[test]
+    fn test_with_buckets() {
+        let layer = MetricsLayer::default();
+        assert_eq!(
+            layer.buckets(),
+            MetricsLayer::DEFAULT_DURATION_SECONDS_BUCKETS
+        );
+
+        let layer = layer.with_buckets(vec![1.0, 0.1, f64::NAN, 0.1, 0.5]);
+        assert_eq!(layer.buckets(), &[0.1, 0.5, 1.0]);
+    }
+
+    #[tokio::test]
+    async fn test_requests_duration_seconds() {
+        let _guard = match setup().await {
+            Some(guard) => guard,
+            None => return,
+        };
+
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(MetricsLayer::default())
+            .finish();
+
+        op.write("test", "Hello, World!")
+            .await
+            .expect("write must succeed");
+        op.read("test").await.expect("read must succeed");
+        op.stat("test").await.expect("stat must succeed");
+        op.stat("not_exist").await.expect_err("stat must fail");
+        op.list("/").await.expect("list must succeed");
+        op.delete("test").await.expect("delete must succeed");
+
+        let observations = OBSERVATIONS.lock().unwrap().clone();
+        let count = |op: Operation| {
+            observations
+                .iter()
+                .filter(|v| v.as_str() == op.into_static())
+                .count()
+        };
+        assert_eq!(count(Operation::Write), 1);
+        assert_eq!(count(Operation::Read), 1);
+        // Both successful and failed stat should be recorded.
+        assert_eq!(count(Operation::Stat), 2);
+        assert_eq!(count(Operation::List), 1);
+        assert_eq!(count(Operation::Delete), 1);
+    }
+
+    #[tokio::test]
+    async fn test_bytes_transferred() {
+        let _guard = match setup().await {
+            Some(guard) => guard,
+            None => return,
+        };
+
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(MetricsLayer::default())
+            .finish();
+
+        let content = vec![1; 4096];
+        op.write("test", content).await.expect("write must succeed");
+        assert_eq!(counter(METRIC_BYTES_WRITTEN_TOTAL, Operation::Write), 4096);
+
+        // Read half of the object and drop the reader.
+        let mut r = op.reader("test").await.expect("reader must be created");
+        let mut buf = vec![0; 2048];
+        r.read_exact(&mut buf).await.expect("read must succeed");
+        // Bytes must be counted while streaming.
+        assert_eq!(counter(METRIC_BYTES_READ_TOTAL, Operation::Read), 2048);
+        drop(r);
+
+        assert_eq!(counter(METRIC_BYTES_READ_TOTAL, Operation::Read), 2048);
+        assert_eq!(counter(METRIC_BYTES_TOTAL, Operation::Read), 2048);
+    }
 }
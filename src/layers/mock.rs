@@ -0,0 +1,260 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::*;
+
+type ErrorFn = Arc<dyn Fn(Operation, &str) -> Option<Error> + Send + Sync>;
+type MetadataFn = Arc<dyn Fn(Operation, &str, Metadata) -> Metadata + Send + Sync>;
+
+/// MockLayer is a test only layer that injects errors into and rewrites
+/// metadata returned by the underlying service, so that we can test the
+/// behavior of operator and layers against `Memory`.
+#[derive(Clone, Default)]
+pub(crate) struct MockLayer {
+    error: Option<ErrorFn>,
+    metadata: Option<MetadataFn>,
+    batch: bool,
+    batch_count: Arc<AtomicUsize>,
+}
+
+impl MockLayer {
+    /// Return the error built by `f` before forwarding the operation.
+    ///
+    /// Operations of batch will be checked one by one as `Batch`, so that
+    /// tests can fail some keys of a batch only.
+    pub fn with_error(
+        mut self,
+        f: impl Fn(Operation, &str) -> Option<Error> + Send + Sync + 'static,
+    ) -> Self {
+        self.error = Some(Arc::new(f));
+        self
+    }
+
+    /// Replace the metadata returned by `stat` and `read` with `f`.
+    pub fn with_metadata(
+        mut self,
+        f: impl Fn(Operation, &str, Metadata) -> Metadata + Send + Sync + 'static,
+    ) -> Self {
+        self.metadata = Some(Arc::new(f));
+        self
+    }
+
+    /// Advertise batch delete and write and serve them one by one.
+    pub fn with_batch(mut self) -> Self {
+        self.batch = true;
+        self
+    }
+
+    /// The number of batch requests served so far.
+    pub fn batch_count(&self) -> usize {
+        self.batch_count.load(Ordering::SeqCst)
+    }
+}
+
+impl<A: Accessor> Layer<A> for MockLayer {
+    type LayeredAccessor = MockAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        MockAccessor {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+pub(crate) struct MockAccessor<A: Accessor> {
+    inner: A,
+    layer: MockLayer,
+}
+
+impl<A: Accessor> Debug for MockAccessor<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockAccessor")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: Accessor> MockAccessor<A> {
+    fn check(&self, op: Operation, path: &str) -> Result<()> {
+        match self.layer.error.as_ref().and_then(|f| f(op, path)) {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    fn map_metadata(&self, op: Operation, path: &str, meta: Metadata) -> Metadata {
+        match &self.layer.metadata {
+            Some(f) => f(op, path, meta),
+            None => meta,
+        }
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for MockAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn metadata(&self) -> AccessorInfo {
+        let mut meta = self.inner.info();
+        if self.layer.batch {
+            meta.set_capabilities(
+                meta.capabilities() | AccessorCapability::Batch | AccessorCapability::BatchWrite,
+            );
+        }
+        meta
+    }
+
+    async fn create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        self.check(Operation::Create, path)?;
+        self.inner.create(path, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.check(Operation::Read, path)?;
+        let (rp, r) = self.inner.read(path, args).await?;
+        let meta = self.map_metadata(Operation::Read, path, rp.into_metadata());
+        Ok((RpRead::with_metadata(meta), r))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.check(Operation::Write, path)?;
+        self.inner.write(path, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.check(Operation::Stat, path)?;
+        let meta = self.inner.stat(path, args).await?.into_metadata();
+        Ok(RpStat::new(self.map_metadata(Operation::Stat, path, meta)))
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.check(Operation::Delete, path)?;
+        self.inner.delete(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.check(Operation::List, path)?;
+        self.inner.list(path, args).await
+    }
+
+    async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+        self.check(Operation::Scan, path)?;
+        self.inner.scan(path, args).await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        if !self.layer.batch {
+            return self.inner.batch(args).await;
+        }
+        self.layer.batch_count.fetch_add(1, Ordering::SeqCst);
+
+        match args.into_operation() {
+            BatchOperations::Delete(ops) => {
+                let mut results = Vec::with_capacity(ops.len());
+                for (path, op) in ops {
+                    let res = match self.check(Operation::Batch, &path) {
+                        Ok(()) => self.inner.delete(&path, op).await,
+                        Err(err) => Err(err),
+                    };
+                    results.push((path, res));
+                }
+
+                Ok(RpBatch::new(BatchedResults::Delete(results)))
+            }
+            BatchOperations::Write(ops) => {
+                let mut results = Vec::with_capacity(ops.len());
+                for (path, op, bs) in ops {
+                    let res = match self.check(Operation::Batch, &path) {
+                        Ok(()) => {
+                            let (rp, mut w) = self.inner.write(&path, op).await?;
+                            oio::Write::write(&mut w, bs).await?;
+                            oio::Write::close(&mut w).await?;
+                            Ok(rp)
+                        }
+                        Err(err) => Err(err),
+                    };
+                    results.push((path, res));
+                }
+
+                Ok(RpBatch::new(BatchedResults::Write(results)))
+            }
+        }
+    }
+
+    fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        self.check(Operation::BlockingCreate, path)?;
+        self.inner.blocking_create(path, args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.check(Operation::BlockingRead, path)?;
+        let (rp, r) = self.inner.blocking_read(path, args)?;
+        let meta = self.map_metadata(Operation::BlockingRead, path, rp.into_metadata());
+        Ok((RpRead::with_metadata(meta), r))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.check(Operation::BlockingWrite, path)?;
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.check(Operation::BlockingStat, path)?;
+        let meta = self.inner.blocking_stat(path, args)?.into_metadata();
+        Ok(RpStat::new(self.map_metadata(
+            Operation::BlockingStat,
+            path,
+            meta,
+        )))
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.check(Operation::BlockingDelete, path)?;
+        self.inner.blocking_delete(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.check(Operation::BlockingList, path)?;
+        self.inner.blocking_list(path, args)
+    }
+
+    fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        self.check(Operation::BlockingScan, path)?;
+        self.inner.blocking_scan(path, args)
+    }
+}
@@ -17,38 +17,89 @@
 
 //! `Layer` is the mechanism to intercept operations.
 
+mod cache;
+pub use self::cache::CacheLayer;
+
+#[cfg(feature = "layers-chaos")]
+mod chaos;
+#[cfg(feature = "layers-chaos")]
+pub use self::chaos::ChaosLayer;
+
+mod checksum;
+pub use self::checksum::ReadChecksumLayer;
+
 mod concurrent_limit;
-pub use concurrent_limit::ConcurrentLimitLayer;
+pub use self::concurrent_limit::ConcurrentLimitLayer;
+
+mod content_type_guess;
+pub use self::content_type_guess::ContentTypeGuessLayer;
+
+mod copy;
+pub use self::copy::CopyLayer;
+
+mod dry_run;
+pub use self::dry_run::DryRunLayer;
+pub use self::dry_run::DryRunRecord;
+
+mod fallback;
+pub use self::fallback::FallbackLayer;
 
 mod immutable_index;
-pub use immutable_index::ImmutableIndexLayer;
+pub use self::immutable_index::ImmutableIndexLayer;
 
 mod logging;
-pub use logging::LoggingLayer;
-
-#[cfg(feature = "layers-chaos")]
-mod chaos;
-#[cfg(feature = "layers-chaos")]
-pub use chaos::ChaosLayer;
+pub use self::logging::LoggingLayer;
 
 #[cfg(feature = "layers-metrics")]
 mod metrics;
 #[cfg(feature = "layers-metrics")]
 pub use self::metrics::MetricsLayer;
 
+mod prefix;
+pub use self::prefix::PrefixLayer;
+
+mod read_retry;
+pub use self::read_retry::ReadRetryLayer;
+
+mod request_id;
+pub use self::request_id::RequestIdLayer;
+
 mod retry;
 pub use self::retry::RetryLayer;
 
+mod singleflight;
+pub use self::singleflight::SingleflightLayer;
+
+mod throttle;
+pub use self::throttle::ThrottleLayer;
+
+mod timeout;
+pub use self::timeout::TimeoutLayer;
+
 #[cfg(feature = "layers-tracing")]
 mod tracing;
 #[cfg(feature = "layers-tracing")]
 pub use self::tracing::TracingLayer;
 
+mod validate;
+pub use self::validate::ValidateLayer;
+
+mod write_buffer;
+pub use self::write_buffer::WriteBufferLayer;
+
+mod write_once;
+pub use self::write_once::WriteOnceLayer;
+
 mod type_eraser;
-pub(crate) use type_eraser::TypeEraseLayer;
+pub(crate) use self::type_eraser::TypeEraseLayer;
 
 mod error_context;
-pub(crate) use error_context::ErrorContextLayer;
+pub(crate) use self::error_context::ErrorContextLayer;
 
 mod complete;
-pub(crate) use complete::CompleteLayer;
+pub(crate) use self::complete::CompleteLayer;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+pub(crate) use self::mock::MockLayer;
@@ -0,0 +1,458 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use async_trait::async_trait;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::*;
+
+/// Scope the underlying storage services under a virtual root.
+///
+/// # Notes
+///
+/// PrefixLayer prepends the prefix to every path passed in and strips it
+/// from the entries returned by `list` and `scan`, so that users can pin
+/// an operator to a prefix (like a tenant) without prepending it everywhere.
+///
+/// - Double slashes in both prefix and paths will be normalized.
+/// - Paths that contain `..` will be rejected with `PermissionDenied` so
+///   that they can't escape the prefix.
+///
+/// # Panics
+///
+/// `PrefixLayer::new` will panic if the prefix contains `..`.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::PrefixLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(PrefixLayer::new("tenant-a/"))
+///     .finish();
+/// ```
+#[derive(Debug, Clone)]
+pub struct PrefixLayer {
+    prefix: String,
+}
+
+impl PrefixLayer {
+    /// Create a new PrefixLayer with given prefix.
+    ///
+    /// The prefix will be normalized to style like `abc/def/`, and an
+    /// empty prefix (or `/`) makes this layer a no-op.
+    pub fn new(prefix: &str) -> Self {
+        let mut prefix = normalize_path(prefix);
+        assert!(
+            !prefix.split('/').any(|v| v == ".."),
+            "prefix must not contain `..`"
+        );
+
+        if prefix == "/" {
+            prefix.clear();
+        } else if !prefix.ends_with('/') {
+            prefix.push('/');
+        }
+
+        Self { prefix }
+    }
+}
+
+impl<A: Accessor> Layer<A> for PrefixLayer {
+    type LayeredAccessor = PrefixAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        PrefixAccessor {
+            inner,
+            prefix: self.prefix.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PrefixAccessor<A: Accessor> {
+    inner: A,
+    prefix: String,
+}
+
+impl<A: Accessor> PrefixAccessor<A> {
+    /// Build the real path in underlying services.
+    fn prepend(&self, path: &str) -> Result<String> {
+        let path = normalize_path(path);
+        if path.split('/').any(|v| v == "..") {
+            return Err(Error::new(
+                ErrorKind::PermissionDenied,
+                "path must not escape the prefix",
+            )
+            .with_context("prefix", &self.prefix)
+            .with_context("path", &path));
+        }
+
+        if path == "/" {
+            if self.prefix.is_empty() {
+                return Ok(path);
+            }
+            return Ok(self.prefix.clone());
+        }
+
+        Ok(format!("{}{}", self.prefix, path))
+    }
+
+    /// Prepend prefix to `start_after` of given list args, so that the
+    /// continuation token returned by lister can be passed in as is.
+    fn prepend_list_args(&self, args: OpList) -> Result<OpList> {
+        match args.start_after() {
+            Some(v) => {
+                let key = self.prepend(v)?;
+                Ok(args.with_start_after(&key))
+            }
+            None => Ok(args),
+        }
+    }
+}
+
+/// Strip the prefix from path returned by underlying services.
+fn strip_prefix(prefix: &str, path: &str) -> String {
+    match path.strip_prefix(prefix) {
+        Some("") => "/".to_string(),
+        Some(v) => v.to_string(),
+        None => path.to_string(),
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for PrefixAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Pager = PrefixPager<A::Pager>;
+    type BlockingPager = PrefixPager<A::BlockingPager>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn metadata(&self) -> AccessorInfo {
+        let mut meta = self.inner.info();
+        let root = format!("{}{}", meta.root(), self.prefix);
+        meta.set_root(&root);
+
+        meta
+    }
+
+    async fn create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        self.inner.create(&self.prepend(path)?, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(&self.prepend(path)?, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(&self.prepend(path)?, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.inner.stat(&self.prepend(path)?, args).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.inner.delete(&self.prepend(path)?, args).await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.inner
+            .copy(&self.prepend(from)?, &self.prepend(to)?, args)
+            .await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.inner
+            .rename(&self.prepend(from)?, &self.prepend(to)?, args)
+            .await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner
+            .list(&self.prepend(path)?, self.prepend_list_args(args)?)
+            .await
+            .map(|(rp, p)| (rp, PrefixPager::new(p, &self.prefix)))
+    }
+
+    async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+        self.inner
+            .scan(&self.prepend(path)?, args)
+            .await
+            .map(|(rp, p)| (rp, PrefixPager::new(p, &self.prefix)))
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let ops = match args.into_operation() {
+            BatchOperations::Delete(ops) => BatchOperations::Delete(
+                ops.into_iter()
+                    .map(|(path, op)| Ok((self.prepend(&path)?, op)))
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+            BatchOperations::Write(ops) => BatchOperations::Write(
+                ops.into_iter()
+                    .map(|(path, op, bs)| Ok((self.prepend(&path)?, op, bs)))
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+        };
+
+        let rp = self.inner.batch(OpBatch::new(ops)).await?;
+
+        let results = match rp.into_results() {
+            BatchedResults::Delete(results) => BatchedResults::Delete(
+                results
+                    .into_iter()
+                    .map(|(path, res)| (strip_prefix(&self.prefix, &path), res))
+                    .collect(),
+            ),
+            BatchedResults::Write(results) => BatchedResults::Write(
+                results
+                    .into_iter()
+                    .map(|(path, res)| (strip_prefix(&self.prefix, &path), res))
+                    .collect(),
+            ),
+        };
+
+        Ok(RpBatch::new(results))
+    }
+
+    fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.inner.presign(&self.prepend(path)?, args)
+    }
+
+    fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        self.inner.blocking_create(&self.prepend(path)?, args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(&self.prepend(path)?, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(&self.prepend(path)?, args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.inner.blocking_stat(&self.prepend(path)?, args)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.inner.blocking_delete(&self.prepend(path)?, args)
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.inner
+            .blocking_copy(&self.prepend(from)?, &self.prepend(to)?, args)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.inner
+            .blocking_rename(&self.prepend(from)?, &self.prepend(to)?, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner
+            .blocking_list(&self.prepend(path)?, self.prepend_list_args(args)?)
+            .map(|(rp, p)| (rp, PrefixPager::new(p, &self.prefix)))
+    }
+
+    fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        self.inner
+            .blocking_scan(&self.prepend(path)?, args)
+            .map(|(rp, p)| (rp, PrefixPager::new(p, &self.prefix)))
+    }
+}
+
+pub struct PrefixPager<P> {
+    inner: P,
+    prefix: String,
+}
+
+impl<P> PrefixPager<P> {
+    fn new(inner: P, prefix: &str) -> Self {
+        Self {
+            inner,
+            prefix: prefix.to_string(),
+        }
+    }
+
+    fn strip(&self, mut entries: Vec<oio::Entry>) -> Vec<oio::Entry> {
+        for entry in entries.iter_mut() {
+            let path = strip_prefix(&self.prefix, entry.path());
+            entry.set_path(&path);
+        }
+        entries
+    }
+}
+
+#[async_trait]
+impl<P: oio::Page> oio::Page for PrefixPager<P> {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        let entries = self.inner.next().await?;
+        Ok(entries.map(|v| self.strip(v)))
+    }
+}
+
+impl<P: oio::BlockingPage> oio::BlockingPage for PrefixPager<P> {
+    fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        let entries = self.inner.next()?;
+        Ok(entries.map(|v| self.strip(v)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::services;
+
+    #[test]
+    fn test_prefix_normalize() {
+        assert_eq!(PrefixLayer::new("tenant-a").prefix, "tenant-a/");
+        assert_eq!(PrefixLayer::new("/tenant-a//b/").prefix, "tenant-a/b/");
+        assert_eq!(PrefixLayer::new("/").prefix, "");
+    }
+
+    #[test]
+    #[should_panic(expected = "prefix must not contain `..`")]
+    fn test_prefix_with_parent_dir() {
+        let _ = PrefixLayer::new("tenant-a/../tenant-b");
+    }
+
+    #[tokio::test]
+    async fn test_prefix_layer() {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .finish();
+        let prefixed = op.clone().layer(PrefixLayer::new("tenant-a/"));
+
+        prefixed
+            .write("foo.txt", "Hello, World!")
+            .await
+            .expect("must write");
+        prefixed
+            .write("dir//bar.txt", "Hello, World!")
+            .await
+            .expect("must write");
+
+        assert_eq!(
+            op.read("tenant-a/foo.txt").await.expect("must read"),
+            b"Hello, World!"
+        );
+        assert!(op.exists("tenant-a/dir/bar.txt").await.expect("must check"));
+        assert!(!op.exists("foo.txt").await.expect("must check"));
+
+        let mut paths = prefixed
+            .list("/")
+            .await
+            .expect("must list")
+            .map_ok(|v| v.path().to_string())
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("must collect");
+        paths.sort();
+        assert_eq!(paths, vec!["dir/", "foo.txt"]);
+
+        let paths = prefixed
+            .scan("dir/")
+            .await
+            .expect("must scan")
+            .map_ok(|v| v.path().to_string())
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("must collect");
+        assert_eq!(paths, vec!["dir/bar.txt"]);
+    }
+
+    #[tokio::test]
+    async fn test_prefix_layer_list_with_start_after() {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .finish();
+        let prefixed = op.clone().layer(PrefixLayer::new("tenant-a/"));
+        for path in ["dir/a", "dir/b", "dir/c"] {
+            prefixed.write(path, "test").await.expect("must write");
+        }
+        op.write("tenant-b/dir/d", "test")
+            .await
+            .expect("must write");
+
+        let mut lister = prefixed.list("dir/").await.expect("must list");
+        let first = lister
+            .try_next()
+            .await
+            .expect("must list")
+            .expect("must have entry");
+        // Token must not contain the prefix.
+        let token = lister
+            .continuation_token()
+            .expect("must have token")
+            .to_string();
+        assert_eq!(token, first.path());
+        assert!(token.starts_with("dir/"));
+
+        let mut paths = prefixed
+            .list_with("dir/", OpList::new().with_start_after(&token))
+            .await
+            .expect("must list")
+            .map_ok(|v| v.path().to_string())
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("must collect");
+        paths.push(token);
+        paths.sort();
+        assert_eq!(paths, vec!["dir/a", "dir/b", "dir/c"]);
+
+        let paths = prefixed
+            .blocking()
+            .list_with("dir/", OpList::new().with_start_after("dir/b"))
+            .expect("must list")
+            .map(|v| v.map(|v| v.path().to_string()))
+            .collect::<Result<Vec<_>>>()
+            .expect("must collect");
+        assert_eq!(paths, vec!["dir/c"]);
+    }
+
+    #[tokio::test]
+    async fn test_prefix_layer_escape() {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .finish()
+            .layer(PrefixLayer::new("tenant-a/"));
+
+        let err = op
+            .write("../tenant-b/foo.txt", "Hello, World!")
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        let err = op.read("dir/../../foo.txt").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+    }
+}
@@ -0,0 +1,900 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::io::SeekFrom;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use log::warn;
+
+use crate::ops::*;
+use crate::raw::oio::ReadOperation;
+use crate::raw::*;
+use crate::*;
+
+/// Resume reading from the last received byte while the read stream
+/// breaks in the middle.
+///
+/// Unlike [`RetryLayer`](super::RetryLayer) which retries whole
+/// operations, this layer will send a new ranged read starts from the
+/// offset that consumer has received while the stream returns a temporary
+/// error (like connection reset), and continue the stream transparently.
+///
+/// # Notes
+///
+/// - The object must not be changed between reads. The etag returned by
+///   the first read will be compared with the resumed one (or the one
+///   returned by `stat` if the resumed read doesn't carry etag), and
+///   `ErrorKind::ConditionNotMatch` will be returned if they are not the
+///   same.
+/// - Reads that don't return etag can't be verified and will not be
+///   resumed.
+/// - The count of resume will be reset once the stream makes progress.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::ReadRetryLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(ReadRetryLayer::new().with_max_times(5))
+///     .finish();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReadRetryLayer {
+    max_times: usize,
+}
+
+impl Default for ReadRetryLayer {
+    fn default() -> Self {
+        Self { max_times: 3 }
+    }
+}
+
+impl ReadRetryLayer {
+    /// Create a new ReadRetryLayer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the max times of resuming without progress.
+    ///
+    /// Default to `3`.
+    pub fn with_max_times(mut self, max_times: usize) -> Self {
+        self.max_times = max_times;
+        self
+    }
+}
+
+impl<A: Accessor> Layer<A> for ReadRetryLayer {
+    type LayeredAccessor = ReadRetryAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        ReadRetryAccessor {
+            inner: Arc::new(inner),
+            max_times: self.max_times,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ReadRetryAccessor<A: Accessor> {
+    inner: Arc<A>,
+    max_times: usize,
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for ReadRetryAccessor<A> {
+    type Inner = A;
+    type Reader = ReadRetryReader<A, A::Reader>;
+    type BlockingReader = ReadRetryReader<A, A::BlockingReader>;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let (rp, r) = self.inner.read(path, args.clone()).await?;
+        let etag = parse_etag_from_rp(&rp);
+
+        let r = ReadRetryReader::new(self.inner.clone(), path, args, etag, self.max_times, r);
+        Ok((rp, r))
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let (rp, r) = self.inner.blocking_read(path, args.clone())?;
+        let etag = parse_etag_from_rp(&rp);
+
+        let r = ReadRetryReader::new(self.inner.clone(), path, args, etag, self.max_times, r);
+        Ok((rp, r))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+        self.inner.scan(path, args).await
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner.blocking_list(path, args)
+    }
+
+    fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        self.inner.blocking_scan(path, args)
+    }
+}
+
+fn parse_etag_from_rp(rp: &RpRead) -> Option<String> {
+    let meta = rp.metadata();
+    if meta.bit().contains(Metakey::Etag) {
+        meta.etag().map(|v| v.to_string())
+    } else {
+        None
+    }
+}
+
+/// Make sure the resumed object is the same as the one we started with.
+fn check_etag(path: &str, expected: &str, actual: Option<&str>) -> Result<()> {
+    if actual == Some(expected) {
+        return Ok(());
+    }
+
+    Err(Error::new(
+        ErrorKind::ConditionNotMatch,
+        "object has been modified while reading, can't resume",
+    )
+    .with_operation(ReadOperation::Read)
+    .with_context("path", path)
+    .with_context("expected_etag", expected)
+    .with_context("actual_etag", actual.unwrap_or("<none>")))
+}
+
+enum State<R> {
+    Reading(R),
+    /// The future is wrapped by `Mutex` to make `State` Sync, it will only
+    /// be accessed via `get_mut` without locking.
+    Resuming(Mutex<BoxFuture<'static, Result<R>>>),
+    /// Resuming has been failed, the reader can't be used anymore.
+    Failed,
+}
+
+pub struct ReadRetryReader<A: Accessor, R> {
+    inner: Arc<A>,
+    path: String,
+    args: OpRead,
+    etag: Option<String>,
+    max_times: usize,
+
+    /// The count of bytes that have been consumed.
+    cur: u64,
+    /// The position that current reader starts from, all positions of
+    /// current reader are relative to it after resumed.
+    base: u64,
+    /// The times of resuming since last progress.
+    times: usize,
+    state: State<R>,
+}
+
+impl<A: Accessor, R> ReadRetryReader<A, R> {
+    fn new(
+        inner: Arc<A>,
+        path: &str,
+        args: OpRead,
+        etag: Option<String>,
+        max_times: usize,
+        r: R,
+    ) -> Self {
+        Self {
+            inner,
+            path: path.to_string(),
+            args,
+            etag,
+            max_times,
+            cur: 0,
+            base: 0,
+            times: 0,
+            state: State::Reading(r),
+        }
+    }
+
+    /// Check if given error could be resumed.
+    fn should_resume(&mut self, err: &Error) -> bool {
+        if !err.is_temporary() || self.etag.is_none() || self.times >= self.max_times {
+            return false;
+        }
+
+        self.times += 1;
+        warn!(
+            target: "opendal::service",
+            "operation={} path={} -> resume read from {}: error={:?}",
+            ReadOperation::Read, self.path, self.cur, err);
+        true
+    }
+
+    /// Build the args to read the remaining range.
+    fn resume_args(&self) -> OpRead {
+        let range = self.args.range();
+        let range = match (range.offset(), range.size()) {
+            (Some(offset), size) => BytesRange::new(
+                Some(offset + self.cur),
+                size.map(|v| v.saturating_sub(self.cur)),
+            ),
+            (None, Some(size)) => BytesRange::new(None, Some(size.saturating_sub(self.cur))),
+            (None, None) => BytesRange::new(Some(self.cur), None),
+        };
+
+        self.args.clone().with_range(range)
+    }
+
+    fn progress(&mut self, n: u64) {
+        self.cur += n;
+        if n > 0 {
+            self.times = 0;
+        }
+    }
+
+    /// Translate the seek position into the one of current reader.
+    ///
+    /// Returns `Err(target)` if the target is before `base`, which can't be
+    /// reached by current reader and must be read again from `target`.
+    fn translate_seek(&self, pos: SeekFrom) -> Result<std::result::Result<SeekFrom, u64>> {
+        let target = match pos {
+            SeekFrom::Start(n) => n,
+            SeekFrom::Current(n) => match (self.cur as i64).checked_add(n) {
+                Some(n) if n >= 0 => n as u64,
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::Unexpected,
+                        "invalid seek to a negative or overflowing position",
+                    )
+                    .with_operation(ReadOperation::Seek)
+                    .with_context("path", &self.path))
+                }
+            },
+            // Current reader shares the same end with the whole range.
+            SeekFrom::End(_) => return Ok(Ok(pos)),
+        };
+
+        if target >= self.base {
+            Ok(Ok(SeekFrom::Start(target - self.base)))
+        } else {
+            Ok(Err(target))
+        }
+    }
+}
+
+impl<A: Accessor> ReadRetryReader<A, A::Reader> {
+    /// Start resuming from `cur`.
+    fn start_resume(&mut self) {
+        self.base = self.cur;
+        self.state = State::Resuming(Mutex::new(self.resume_future()));
+    }
+
+    fn resume_future(&self) -> BoxFuture<'static, Result<A::Reader>> {
+        let acc = self.inner.clone();
+        let path = self.path.clone();
+        let etag = self
+            .etag
+            .clone()
+            .expect("etag must be valid while resuming");
+        let args = self.resume_args();
+        let stat_args = args.to_stat();
+
+        async move {
+            let (rp, r) = acc.read(&path, args).await?;
+            match parse_etag_from_rp(&rp) {
+                Some(v) => check_etag(&path, &etag, Some(&v))?,
+                None => {
+                    let meta = acc.stat(&path, stat_args).await?.into_metadata();
+                    check_etag(&path, &etag, meta.etag())?
+                }
+            }
+            Ok(r)
+        }
+        .boxed()
+    }
+
+    /// Poll the resuming future if exists.
+    fn poll_resume(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        loop {
+            let fut = match &mut self.state {
+                State::Reading(_) => return Poll::Ready(Ok(())),
+                State::Resuming(fut) => fut.get_mut().expect("lock must succeed"),
+                State::Failed => return Poll::Ready(Err(new_failed_error(&self.path))),
+            };
+
+            match ready!(fut.poll_unpin(cx)) {
+                Ok(r) => self.state = State::Reading(r),
+                Err(err) if self.should_resume(&err) => self.start_resume(),
+                Err(err) => {
+                    self.state = State::Failed;
+                    return Poll::Ready(Err(err));
+                }
+            }
+        }
+    }
+}
+
+fn new_failed_error(path: &str) -> Error {
+    Error::new(
+        ErrorKind::Unexpected,
+        "reader has been failed to resume and can't be used anymore",
+    )
+    .with_operation(ReadOperation::Read)
+    .with_context("path", path)
+}
+
+impl<A: Accessor> oio::Read for ReadRetryReader<A, A::Reader> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        loop {
+            ready!(self.poll_resume(cx))?;
+
+            let r = match &mut self.state {
+                State::Reading(r) => r,
+                _ => unreachable!("reader must be ready after resuming"),
+            };
+            match ready!(r.poll_read(cx, buf)) {
+                Ok(n) => {
+                    self.progress(n as u64);
+                    return Poll::Ready(Ok(n));
+                }
+                Err(err) if self.should_resume(&err) => self.start_resume(),
+                Err(err) => return Poll::Ready(Err(err)),
+            }
+        }
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        loop {
+            ready!(self.poll_resume(cx))?;
+
+            let pos = match self.translate_seek(pos)? {
+                Ok(pos) => pos,
+                // Read again from the target.
+                Err(target) => {
+                    self.cur = target;
+                    self.start_resume();
+                    continue;
+                }
+            };
+            let r = match &mut self.state {
+                State::Reading(r) => r,
+                _ => unreachable!("reader must be ready after resuming"),
+            };
+            let n = ready!(r.poll_seek(cx, pos))? + self.base;
+            self.cur = n;
+            return Poll::Ready(Ok(n));
+        }
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        loop {
+            if let Err(err) = ready!(self.poll_resume(cx)) {
+                return Poll::Ready(Some(Err(err)));
+            }
+
+            let r = match &mut self.state {
+                State::Reading(r) => r,
+                _ => unreachable!("reader must be ready after resuming"),
+            };
+            match ready!(r.poll_next(cx)) {
+                Some(Ok(bs)) => {
+                    self.progress(bs.len() as u64);
+                    return Poll::Ready(Some(Ok(bs)));
+                }
+                Some(Err(err)) if self.should_resume(&err) => self.start_resume(),
+                res => return Poll::Ready(res),
+            }
+        }
+    }
+}
+
+impl<A: Accessor> ReadRetryReader<A, A::BlockingReader> {
+    fn blocking_resume(&mut self) -> Result<()> {
+        loop {
+            let args = self.resume_args();
+            let etag = self
+                .etag
+                .as_deref()
+                .expect("etag must be valid while resuming");
+
+            let res = self
+                .inner
+                .blocking_read(&self.path, args)
+                .and_then(|(rp, r)| {
+                    match parse_etag_from_rp(&rp) {
+                        Some(v) => check_etag(&self.path, etag, Some(&v))?,
+                        None => {
+                            let meta = self
+                                .inner
+                                .blocking_stat(&self.path, self.args.to_stat())?
+                                .into_metadata();
+                            check_etag(&self.path, etag, meta.etag())?
+                        }
+                    }
+                    Ok(r)
+                });
+
+            match res {
+                Ok(r) => {
+                    self.base = self.cur;
+                    self.state = State::Reading(r);
+                    return Ok(());
+                }
+                Err(err) if self.should_resume(&err) => continue,
+                Err(err) => {
+                    self.state = State::Failed;
+                    return Err(err);
+                }
+            }
+        }
+    }
+
+    fn blocking_reader(&mut self) -> Result<&mut A::BlockingReader> {
+        match &mut self.state {
+            State::Reading(r) => Ok(r),
+            _ => Err(new_failed_error(&self.path)),
+        }
+    }
+}
+
+impl<A: Accessor> oio::BlockingRead for ReadRetryReader<A, A::BlockingReader> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        loop {
+            match self.blocking_reader()?.read(buf) {
+                Ok(n) => {
+                    self.progress(n as u64);
+                    return Ok(n);
+                }
+                Err(err) if self.should_resume(&err) => self.blocking_resume()?,
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let pos = match self.translate_seek(pos)? {
+            Ok(pos) => pos,
+            // Read again from the target.
+            Err(target) => {
+                self.cur = target;
+                self.blocking_resume()?;
+                SeekFrom::Start(0)
+            }
+        };
+        let n = self.blocking_reader()?.seek(pos)? + self.base;
+        self.cur = n;
+        Ok(n)
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        loop {
+            let r = match self.blocking_reader() {
+                Ok(r) => r,
+                Err(err) => return Some(Err(err)),
+            };
+            match r.next() {
+                Some(Ok(bs)) => {
+                    self.progress(bs.len() as u64);
+                    return Some(Ok(bs));
+                }
+                Some(Err(err)) if self.should_resume(&err) => {
+                    if let Err(err) = self.blocking_resume() {
+                        return Some(Err(err));
+                    }
+                }
+                res => return res,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use futures::TryStreamExt;
+
+    use super::*;
+
+    /// Every read will be dropped after returning `DROP_AFTER` bytes.
+    const DROP_AFTER: usize = 100;
+
+    fn content() -> Vec<u8> {
+        (0..1024).map(|v| v as u8).collect()
+    }
+
+    #[derive(Debug, Default)]
+    struct MockState {
+        etag: Option<String>,
+        /// The times of reads that will be dropped.
+        drops: usize,
+        /// Change the etag after the first read.
+        modify_after_read: bool,
+        /// The ranges of all reads.
+        ranges: Vec<BytesRange>,
+    }
+
+    #[derive(Debug, Clone, Default)]
+    struct MockBuilder {
+        state: Arc<Mutex<MockState>>,
+    }
+
+    impl MockBuilder {
+        fn new(etag: Option<&str>, drops: usize) -> Self {
+            let state = MockState {
+                etag: etag.map(|v| v.to_string()),
+                drops,
+                ..Default::default()
+            };
+            Self {
+                state: Arc::new(Mutex::new(state)),
+            }
+        }
+
+        fn operator(&self) -> Operator {
+            Operator::new(self.clone())
+                .unwrap()
+                .layer(ReadRetryLayer::new())
+                .finish()
+        }
+
+        fn ranges(&self) -> Vec<BytesRange> {
+            self.state.lock().unwrap().ranges.clone()
+        }
+    }
+
+    impl Builder for MockBuilder {
+        const SCHEME: Scheme = Scheme::Custom("mock");
+        type Accessor = MockService;
+
+        fn from_map(_: HashMap<String, String>) -> Self {
+            Self::default()
+        }
+
+        fn build(&mut self) -> Result<Self::Accessor> {
+            Ok(MockService {
+                state: self.state.clone(),
+            })
+        }
+    }
+
+    #[derive(Debug)]
+    struct MockService {
+        state: Arc<Mutex<MockState>>,
+    }
+
+    impl MockService {
+        fn metadata(&self, state: &MockState, size: usize) -> Metadata {
+            let mut meta = Metadata::new(EntryMode::FILE).with_content_length(size as u64);
+            if let Some(etag) = &state.etag {
+                meta.set_etag(etag);
+            }
+            meta
+        }
+    }
+
+    #[async_trait]
+    impl Accessor for MockService {
+        type Reader = MockReader;
+        type BlockingReader = MockReader;
+        type Writer = ();
+        type BlockingWriter = ();
+        type Pager = ();
+        type BlockingPager = ();
+
+        fn info(&self) -> AccessorInfo {
+            let mut am = AccessorInfo::default();
+            am.set_capabilities(AccessorCapability::Read | AccessorCapability::Blocking)
+                .set_hints(AccessorHint::ReadSeekable | AccessorHint::ReadStreamable);
+            am
+        }
+
+        async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+            self.blocking_stat(path, args)
+        }
+
+        async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            self.blocking_read(path, args)
+        }
+
+        fn blocking_stat(&self, _: &str, _: OpStat) -> Result<RpStat> {
+            let state = self.state.lock().unwrap();
+            Ok(RpStat::new(self.metadata(&state, content().len())))
+        }
+
+        fn blocking_read(&self, _: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+            let mut state = self.state.lock().unwrap();
+            if state.modify_after_read && !state.ranges.is_empty() {
+                state.etag = Some("\"modified\"".to_string());
+            }
+            state.ranges.push(args.range());
+
+            let data = content();
+            let range = args.range();
+            let (start, end) = match (range.offset(), range.size()) {
+                (Some(offset), Some(size)) => (offset as usize, (offset + size) as usize),
+                (Some(offset), None) => (offset as usize, data.len()),
+                (None, Some(size)) => (data.len() - size as usize, data.len()),
+                (None, None) => (0, data.len()),
+            };
+            let drop_after = if state.drops > 0 {
+                state.drops -= 1;
+                Some(DROP_AFTER)
+            } else {
+                None
+            };
+
+            let meta = self.metadata(&state, end - start);
+            Ok((
+                RpRead::with_metadata(meta),
+                MockReader {
+                    data: Bytes::from(data[start..end].to_vec()),
+                    pos: 0,
+                    drop_after,
+                },
+            ))
+        }
+    }
+
+    struct MockReader {
+        data: Bytes,
+        pos: usize,
+        drop_after: Option<usize>,
+    }
+
+    impl MockReader {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            let remaining = match self.drop_after {
+                Some(n) if self.pos >= n => {
+                    return Err(
+                        Error::new(ErrorKind::Unexpected, "connection reset").set_temporary()
+                    )
+                }
+                Some(n) => n - self.pos,
+                None => usize::MAX,
+            };
+
+            let size = buf.len().min(remaining).min(self.data.len() - self.pos);
+            buf[..size].copy_from_slice(&self.data[self.pos..self.pos + size]);
+            self.pos += size;
+            Ok(size)
+        }
+
+        fn next(&mut self) -> Option<Result<Bytes>> {
+            let mut buf = vec![0; 64];
+            match self.read(&mut buf) {
+                Ok(0) => None,
+                Ok(n) => Some(Ok(Bytes::from(buf[..n].to_vec()))),
+                Err(err) => Some(Err(err)),
+            }
+        }
+    }
+
+    impl oio::Read for MockReader {
+        fn poll_read(&mut self, _: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+            Poll::Ready(self.read(buf))
+        }
+
+        fn poll_seek(&mut self, _: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+            Poll::Ready(oio::BlockingRead::seek(self, pos))
+        }
+
+        fn poll_next(&mut self, _: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+            Poll::Ready(self.next())
+        }
+    }
+
+    impl oio::BlockingRead for MockReader {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            MockReader::read(self, buf)
+        }
+
+        fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+            let (base, amt) = match pos {
+                SeekFrom::Start(n) => (0, n as i64),
+                SeekFrom::End(n) => (self.data.len() as i64, n),
+                SeekFrom::Current(n) => (self.pos as i64, n),
+            };
+            match base.checked_add(amt) {
+                Some(n) if n >= 0 => self.pos = n as usize,
+                _ => return Err(Error::new(ErrorKind::Unexpected, "invalid seek")),
+            }
+            Ok(self.pos as u64)
+        }
+
+        fn next(&mut self) -> Option<Result<Bytes>> {
+            MockReader::next(self)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resume_read() {
+        let builder = MockBuilder::new(Some("\"etag\""), 3);
+        let op = builder.operator();
+
+        let bs = op.read("test").await.expect("read must succeed");
+        assert_eq!(bs, content());
+
+        // Every resumed read starts from the last received byte.
+        let offsets: Vec<_> = builder.ranges().iter().map(|v| v.offset()).collect();
+        assert_eq!(offsets[1..], [Some(100), Some(200), Some(300)]);
+    }
+
+    #[tokio::test]
+    async fn test_resume_range_read() {
+        let builder = MockBuilder::new(Some("\"etag\""), 2);
+        let op = builder.operator();
+
+        let bs = op
+            .range_read("test", 10..510)
+            .await
+            .expect("read must succeed");
+        assert_eq!(bs, content()[10..510]);
+        assert_eq!(
+            builder.ranges()[1..],
+            [
+                BytesRange::new(Some(110), Some(400)),
+                BytesRange::new(Some(210), Some(300))
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resume_stream() {
+        let builder = MockBuilder::new(Some("\"etag\""), 5);
+        let op = builder.operator();
+
+        let bs: Vec<Bytes> = op
+            .reader("test")
+            .await
+            .expect("reader must be created")
+            .try_collect()
+            .await
+            .expect("stream must succeed");
+        assert_eq!(bs.concat(), content());
+        assert_eq!(builder.ranges().len(), 6);
+    }
+
+    #[test]
+    fn test_resume_blocking_read() {
+        let builder = MockBuilder::new(Some("\"etag\""), 3);
+        let op = builder.operator().blocking();
+
+        let bs = op.read("test").expect("read must succeed");
+        assert_eq!(bs, content());
+        assert_eq!(builder.ranges().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_seek_after_resume() {
+        use futures::AsyncReadExt;
+        use futures::AsyncSeekExt;
+
+        let builder = MockBuilder::new(Some("\"etag\""), 1);
+        let op = builder.operator();
+
+        let mut r = op.reader("test").await.expect("reader must be created");
+        let mut bs = vec![0; 150];
+        r.read_exact(&mut bs).await.expect("read must succeed");
+        assert_eq!(bs, content()[..150]);
+
+        // Positions after resumed are translated against the resume base.
+        let mut bs = vec![0; 10];
+        assert_eq!(r.seek(SeekFrom::Start(120)).await.unwrap(), 120);
+        r.read_exact(&mut bs).await.expect("read must succeed");
+        assert_eq!(bs, content()[120..130]);
+        assert_eq!(r.seek(SeekFrom::Current(20)).await.unwrap(), 150);
+        r.read_exact(&mut bs).await.expect("read must succeed");
+        assert_eq!(bs, content()[150..160]);
+        assert_eq!(r.seek(SeekFrom::End(-10)).await.unwrap(), 1014);
+        r.read_exact(&mut bs).await.expect("read must succeed");
+        assert_eq!(bs, content()[1014..]);
+
+        // Positions before the resume base will be read again.
+        assert_eq!(r.seek(SeekFrom::Start(50)).await.unwrap(), 50);
+        r.read_exact(&mut bs).await.expect("read must succeed");
+        assert_eq!(bs, content()[50..60]);
+        assert_eq!(
+            builder.ranges()[1..],
+            [
+                BytesRange::new(Some(100), None),
+                BytesRange::new(Some(50), None)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_blocking_seek_after_resume() {
+        use std::io::Read;
+        use std::io::Seek;
+
+        let builder = MockBuilder::new(Some("\"etag\""), 1);
+        let op = builder.operator().blocking();
+
+        let mut r = op.reader("test").expect("reader must be created");
+        let mut bs = vec![0; 150];
+        r.read_exact(&mut bs).expect("read must succeed");
+
+        let mut bs = vec![0; 10];
+        assert_eq!(r.seek(SeekFrom::Start(120)).unwrap(), 120);
+        r.read_exact(&mut bs).expect("read must succeed");
+        assert_eq!(bs, content()[120..130]);
+        assert_eq!(r.seek(SeekFrom::Start(50)).unwrap(), 50);
+        r.read_exact(&mut bs).expect("read must succeed");
+        assert_eq!(bs, content()[50..60]);
+    }
+
+    /// Read the stream until an error returned.
+    async fn read_until_error(op: &Operator) -> Error {
+        let mut r = op.reader("test").await.expect("reader must be created");
+        loop {
+            match oio::ReadExt::next(&mut r).await {
+                Some(Ok(_)) => continue,
+                Some(Err(err)) => return err,
+                None => panic!("read must fail"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resume_with_modified_object() {
+        let builder = MockBuilder::new(Some("\"etag\""), 1);
+        builder.state.lock().unwrap().modify_after_read = true;
+        let op = builder.operator();
+
+        let err = read_until_error(&op).await;
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+        assert_eq!(builder.ranges().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_no_resume_without_etag() {
+        let builder = MockBuilder::new(None, 1);
+        let op = builder.operator();
+
+        let err = read_until_error(&op).await;
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.is_temporary());
+        assert_eq!(builder.ranges().len(), 1);
+    }
+}
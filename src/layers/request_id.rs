@@ -0,0 +1,580 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::FutureExt;
+use http::HeaderName;
+use http::HeaderValue;
+use log::debug;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::*;
+
+/// Attach a request id to every operation.
+///
+/// # Notes
+///
+/// For every operation, this layer will:
+///
+/// - generate a new request id (an uuid v4 by default).
+/// - send the request id in header `x-request-id` (configurable via
+///   [`RequestIdLayer::with_header_name`]) of all http requests issued by
+///   this operation, including the ones issued by its reader, writer or pager.
+/// - attach the request id to returned errors, which can be fetched by
+///   [`Error::request_id`].
+///
+/// Request ids will be logged at `debug` level with target `opendal::layers::request_id`.
+///
+/// Services that don't send requests via http will ignore the header, but
+/// their errors still carry the request id.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::RequestIdLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(RequestIdLayer::new().with_header_name("x-amz-client-request-id"))
+///     .finish();
+/// ```
+#[derive(Clone)]
+pub struct RequestIdLayer {
+    header_name: HeaderName,
+    generator: Arc<dyn Fn() -> String + Send + Sync>,
+}
+
+impl Default for RequestIdLayer {
+    fn default() -> Self {
+        Self {
+            header_name: HeaderName::from_static("x-request-id"),
+            generator: Arc::new(|| uuid::Uuid::new_v4().to_string()),
+        }
+    }
+}
+
+impl RequestIdLayer {
+    /// Create a new request id layer with default settings.
+    ///
+    /// - header name: `x-request-id`
+    /// - generator: uuid v4
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the header name that carries the request id.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `name` is not a valid header name.
+    pub fn with_header_name(mut self, name: &str) -> Self {
+        self.header_name = HeaderName::from_str(name).expect("header name must be valid");
+        self
+    }
+
+    /// Set the generator of request ids.
+    ///
+    /// Generated ids must be valid header values, otherwise the operation
+    /// will fail with [`ErrorKind::Unexpected`].
+    pub fn with_generator(
+        mut self,
+        generator: impl Fn() -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.generator = Arc::new(generator);
+        self
+    }
+}
+
+impl<A: Accessor> Layer<A> for RequestIdLayer {
+    type LayeredAccessor = RequestIdAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        RequestIdAccessor {
+            inner,
+
+            header_name: self.header_name.clone(),
+            generator: self.generator.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RequestIdAccessor<A: Accessor> {
+    inner: A,
+
+    header_name: HeaderName,
+    generator: Arc<dyn Fn() -> String + Send + Sync>,
+}
+
+impl<A: Accessor> Debug for RequestIdAccessor<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestIdAccessor")
+            .field("inner", &self.inner)
+            .field("header_name", &self.header_name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: Accessor> RequestIdAccessor<A> {
+    /// Generate a new request id for given operation.
+    fn request_id(&self, op: Operation, path: &str) -> Result<RequestId> {
+        let id = (self.generator)();
+        let value = HeaderValue::from_str(&id).map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "generated request id is invalid")
+                .with_operation(op)
+                .with_context("path", path)
+                .with_context("request_id", &id)
+                .set_source(err)
+        })?;
+
+        debug!(
+            target: "opendal::layers::request_id",
+            "service={} operation={} path={} request_id={}",
+            self.inner.info().scheme(),
+            op,
+            path,
+            id
+        );
+
+        Ok(RequestId {
+            name: self.header_name.clone(),
+            value,
+            id: Arc::from(id),
+        })
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for RequestIdAccessor<A> {
+    type Inner = A;
+    type Reader = RequestIdWrapper<A::Reader>;
+    type BlockingReader = RequestIdWrapper<A::BlockingReader>;
+    type Writer = RequestIdWrapper<A::Writer>;
+    type BlockingWriter = RequestIdWrapper<A::BlockingWriter>;
+    type Pager = RequestIdWrapper<A::Pager>;
+    type BlockingPager = RequestIdWrapper<A::BlockingPager>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        let rid = self.request_id(Operation::Create, path)?;
+        rid.clone().scope(self.inner.create(path, args)).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let rid = self.request_id(Operation::Read, path)?;
+        rid.clone()
+            .scope(self.inner.read(path, args))
+            .map(|v| v.map(|(rp, r)| (rp, RequestIdWrapper::new(r, rid))))
+            .await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let rid = self.request_id(Operation::Write, path)?;
+        rid.clone()
+            .scope(self.inner.write(path, args))
+            .map(|v| v.map(|(rp, w)| (rp, RequestIdWrapper::new(w, rid))))
+            .await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let rid = self.request_id(Operation::Copy, from)?;
+        rid.scope(self.inner.copy(from, to, args)).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let rid = self.request_id(Operation::Rename, from)?;
+        rid.scope(self.inner.rename(from, to, args)).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let rid = self.request_id(Operation::Stat, path)?;
+        rid.scope(self.inner.stat(path, args)).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let rid = self.request_id(Operation::Delete, path)?;
+        rid.scope(self.inner.delete(path, args)).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        let rid = self.request_id(Operation::List, path)?;
+        rid.clone()
+            .scope(self.inner.list(path, args))
+            .map(|v| v.map(|(rp, p)| (rp, RequestIdWrapper::new(p, rid))))
+            .await
+    }
+
+    async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+        let rid = self.request_id(Operation::Scan, path)?;
+        rid.clone()
+            .scope(self.inner.scan(path, args))
+            .map(|v| v.map(|(rp, p)| (rp, RequestIdWrapper::new(p, rid))))
+            .await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let rid = self.request_id(Operation::Batch, "")?;
+        rid.scope(self.inner.batch(args)).await
+    }
+
+    fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        let rid = self.request_id(Operation::BlockingCreate, path)?;
+        rid.call(|| self.inner.blocking_create(path, args))
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let rid = self.request_id(Operation::BlockingRead, path)?;
+        rid.call(|| self.inner.blocking_read(path, args))
+            .map(|(rp, r)| (rp, RequestIdWrapper::new(r, rid)))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let rid = self.request_id(Operation::BlockingWrite, path)?;
+        rid.call(|| self.inner.blocking_write(path, args))
+            .map(|(rp, w)| (rp, RequestIdWrapper::new(w, rid)))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let rid = self.request_id(Operation::BlockingCopy, from)?;
+        rid.call(|| self.inner.blocking_copy(from, to, args))
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let rid = self.request_id(Operation::BlockingRename, from)?;
+        rid.call(|| self.inner.blocking_rename(from, to, args))
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let rid = self.request_id(Operation::BlockingStat, path)?;
+        rid.call(|| self.inner.blocking_stat(path, args))
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let rid = self.request_id(Operation::BlockingDelete, path)?;
+        rid.call(|| self.inner.blocking_delete(path, args))
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        let rid = self.request_id(Operation::BlockingList, path)?;
+        rid.call(|| self.inner.blocking_list(path, args))
+            .map(|(rp, p)| (rp, RequestIdWrapper::new(p, rid)))
+    }
+
+    fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        let rid = self.request_id(Operation::BlockingScan, path)?;
+        rid.call(|| self.inner.blocking_scan(path, args))
+            .map(|(rp, p)| (rp, RequestIdWrapper::new(p, rid)))
+    }
+}
+
+/// RequestId carries the request id of an operation.
+#[derive(Clone)]
+struct RequestId {
+    name: HeaderName,
+    value: HeaderValue,
+    id: Arc<str>,
+}
+
+impl RequestId {
+    /// Call `f` with the request id set, and attach it to the returned error.
+    fn call<T>(&self, f: impl FnOnce() -> Result<T>) -> Result<T> {
+        with_request_id_header(&self.name, &self.value, f).map_err(|err| self.attach(err))
+    }
+
+    /// Wrap `fut` so that it will be polled with the request id set.
+    fn scope<F, T>(self, fut: F) -> RequestIdFuture<F>
+    where
+        F: Future<Output = Result<T>> + Unpin,
+    {
+        RequestIdFuture { rid: self, fut }
+    }
+
+    fn attach(&self, err: Error) -> Error {
+        err.with_request_id(&self.id)
+    }
+}
+
+struct RequestIdFuture<F> {
+    rid: RequestId,
+    fut: F,
+}
+
+impl<F, T> Future for RequestIdFuture<F>
+where
+    F: Future<Output = Result<T>> + Unpin,
+{
+    type Output = Result<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let rid = &this.rid;
+        let fut = &mut this.fut;
+
+        with_request_id_header(&rid.name, &rid.value, || fut.poll_unpin(cx))
+            .map_err(|err| rid.attach(err))
+    }
+}
+
+pub struct RequestIdWrapper<R> {
+    inner: R,
+    rid: RequestId,
+}
+
+impl<R> RequestIdWrapper<R> {
+    fn new(inner: R, rid: RequestId) -> Self {
+        Self { inner, rid }
+    }
+}
+
+impl<R: oio::Read> oio::Read for RequestIdWrapper<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        let (rid, inner) = (&self.rid, &mut self.inner);
+        with_request_id_header(&rid.name, &rid.value, || inner.poll_read(cx, buf))
+            .map_err(|err| rid.attach(err))
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: io::SeekFrom) -> Poll<Result<u64>> {
+        let (rid, inner) = (&self.rid, &mut self.inner);
+        with_request_id_header(&rid.name, &rid.value, || inner.poll_seek(cx, pos))
+            .map_err(|err| rid.attach(err))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        let (rid, inner) = (&self.rid, &mut self.inner);
+        with_request_id_header(&rid.name, &rid.value, || inner.poll_next(cx))
+            .map(|v| v.map(|v| v.map_err(|err| rid.attach(err))))
+    }
+}
+
+impl<R: oio::BlockingRead> oio::BlockingRead for RequestIdWrapper<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let inner = &mut self.inner;
+        self.rid.call(|| inner.read(buf))
+    }
+
+    fn seek(&mut self, pos: io::SeekFrom) -> Result<u64> {
+        let inner = &mut self.inner;
+        self.rid.call(|| inner.seek(pos))
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        let (rid, inner) = (&self.rid, &mut self.inner);
+        with_request_id_header(&rid.name, &rid.value, || inner.next())
+            .map(|v| v.map_err(|err| rid.attach(err)))
+    }
+}
+
+#[async_trait]
+impl<R: oio::Write> oio::Write for RequestIdWrapper<R> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.rid.clone().scope(self.inner.write(bs)).await
+    }
+
+    async fn append(&mut self, bs: Bytes) -> Result<()> {
+        self.rid.clone().scope(self.inner.append(bs)).await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.rid.clone().scope(self.inner.close()).await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.rid.clone().scope(self.inner.abort()).await
+    }
+
+    fn state(&self) -> Result<MultipartState> {
+        self.inner.state()
+    }
+}
+
+impl<R: oio::BlockingWrite> oio::BlockingWrite for RequestIdWrapper<R> {
+    fn write(&mut self, bs: Bytes) -> Result<()> {
+        let inner = &mut self.inner;
+        self.rid.call(|| inner.write(bs))
+    }
+
+    fn append(&mut self, bs: Bytes) -> Result<()> {
+        let inner = &mut self.inner;
+        self.rid.call(|| inner.append(bs))
+    }
+
+    fn close(&mut self) -> Result<()> {
+        let inner = &mut self.inner;
+        self.rid.call(|| inner.close())
+    }
+
+    fn abort(&mut self) -> Result<()> {
+        let inner = &mut self.inner;
+        self.rid.call(|| inner.abort())
+    }
+}
+
+#[async_trait]
+impl<P: oio::Page> oio::Page for RequestIdWrapper<P> {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        self.rid.clone().scope(self.inner.next()).await
+    }
+}
+
+impl<P: oio::BlockingPage> oio::BlockingPage for RequestIdWrapper<P> {
+    fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        let inner = &mut self.inner;
+        self.rid.call(|| inner.next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::matchers::header;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+
+    use super::*;
+    use crate::services::Http;
+
+    async fn new_operator(layer: RequestIdLayer) -> (MockServer, Operator) {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mock_server = MockServer::start().await;
+
+        let mut builder = Http::default();
+        builder.endpoint(&mock_server.uri());
+        builder.root("/");
+        let op = Operator::new(builder)
+            .expect("must init")
+            .layer(layer)
+            .finish();
+
+        (mock_server, op)
+    }
+
+    #[tokio::test]
+    async fn test_request_id_header() -> Result<()> {
+        let (mock_server, op) =
+            new_operator(RequestIdLayer::new().with_generator(|| "test-id".to_string())).await;
+        Mock::given(method("GET"))
+            .and(path("/hello"))
+            .and(header("x-request-id", "test-id"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "13")
+                    .set_body_string("Hello, World!"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let bs = op.read("hello").await?;
+        assert_eq!(bs, b"Hello, World!");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_request_id_custom_header_name() -> Result<()> {
+        let (mock_server, op) = new_operator(
+            RequestIdLayer::new()
+                .with_header_name("x-amz-client-request-id")
+                .with_generator(|| "test-id".to_string()),
+        )
+        .await;
+        Mock::given(method("HEAD"))
+            .and(path("/hello"))
+            .and(header("x-amz-client-request-id", "test-id"))
+            .respond_with(ResponseTemplate::new(200).insert_header("content-length", "13"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let meta = op.stat("hello").await?;
+        assert_eq!(meta.content_length(), 13);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_request_id_on_error() -> Result<()> {
+        let (mock_server, op) =
+            new_operator(RequestIdLayer::new().with_generator(|| "test-id".to_string())).await;
+        Mock::given(method("HEAD"))
+            .and(path("/hello"))
+            .and(header("x-request-id", "test-id"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let err = op.stat("hello").await.expect_err("must fail");
+        assert_eq!(err.request_id(), Some("test-id"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_request_id_generated() -> Result<()> {
+        let (mock_server, op) = new_operator(RequestIdLayer::new()).await;
+        Mock::given(method("HEAD"))
+            .and(path("/hello"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+
+        let err = op.stat("hello").await.expect_err("must fail");
+        let id = err.request_id().expect("request id must be set");
+        assert!(uuid::Uuid::parse_str(id).is_ok());
+
+        let requests = mock_server
+            .received_requests()
+            .await
+            .expect("must recorded");
+        assert_eq!(requests.len(), 1);
+        assert_eq!(
+            requests[0]
+                .headers
+                .get(&"x-request-id".into())
+                .map(|v| v.as_str()),
+            Some(id)
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_request_id() {
+        let (_mock_server, op) =
+            new_operator(RequestIdLayer::new().with_generator(|| "bad\nid".to_string())).await;
+
+        let err = op.stat("hello").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+}
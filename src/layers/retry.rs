@@ -19,9 +19,12 @@ use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::io;
 use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
 use std::task::Context;
 use std::task::Poll;
 use std::time::Duration;
+use std::time::Instant;
 
 use async_trait::async_trait;
 use backon::BackoffBuilder;
@@ -33,6 +36,7 @@ use bytes::Bytes;
 use futures::ready;
 use futures::FutureExt;
 use log::warn;
+use rand::Rng;
 
 use crate::ops::*;
 use crate::raw::oio::PageOperation;
@@ -49,6 +53,13 @@ use crate::*;
 /// returns true. If operation still failed, this layer will set error to
 /// `Persistent` which means error has been retried.
 ///
+/// Users can use [`RetryLayer::with_retry_predicate`] to skip retrying some
+/// temporary errors, like `PermissionDenied`.
+///
+/// If the error carries a [`Error::retry_after`] hint (usually parsed from
+/// the `Retry-After` header), this layer will wait for the hinted delay
+/// instead of the computed backoff.
+///
 /// `write` and `blocking_write` don't support retry so far, visit [this issue](https://github.com/datafuselabs/opendal/issues/1223) for more details.
 ///
 /// # Examples
@@ -66,7 +77,7 @@ use crate::*;
 ///     .finish();
 /// ```
 #[derive(Default, Clone)]
-pub struct RetryLayer(ExponentialBuilder);
+pub struct RetryLayer(RetryBackoffBuilder);
 
 impl RetryLayer {
     /// Create a new retry layer.
@@ -87,12 +98,56 @@ impl RetryLayer {
         Self::default()
     }
 
-    /// Set jitter of current backoff.
+    /// Set full jitter of current backoff.
+    ///
+    /// If jitter is enabled, every delay will be randomized in `(0, delay]`.
+    ///
+    /// This is the same as `with_jitter_factor(1.0)`.
+    pub fn with_jitter(self) -> Self {
+        self.with_jitter_factor(1.0)
+    }
+
+    /// Set jitter factor of current backoff.
+    ///
+    /// If jitter factor is set, every delay will be randomized in
+    /// `(delay * (1 - jitter_factor), delay]`. The random number is
+    /// generated by [`rand::thread_rng`].
+    ///
+    /// Jitter is disabled by default, so the delays are deterministic.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if input jitter factor is not in `[0.0, 1.0]`.
+    pub fn with_jitter_factor(mut self, jitter_factor: f32) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&jitter_factor),
+            "jitter factor must be in [0.0, 1.0]"
+        );
+
+        self.0.jitter_factor = Some(jitter_factor);
+        self
+    }
+
+    /// Set retry predicate of current layer.
     ///
-    /// If jitter is enabled, ExponentialBackoff will add a random jitter in `[0, min_delay)
-    /// to current delay.
-    pub fn with_jitter(mut self) -> Self {
-        self.0 = self.0.with_jitter();
+    /// The predicate will be consulted in addition to
+    /// [`Error::is_temporary`]: an error will be retried only if it's
+    /// temporary AND the predicate returns `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opendal::layers::RetryLayer;
+    /// use opendal::ErrorKind;
+    ///
+    /// let _ = RetryLayer::new()
+    ///     .with_retry_predicate(|err| err.kind() != ErrorKind::PermissionDenied);
+    /// ```
+    pub fn with_retry_predicate(
+        mut self,
+        predicate: impl Fn(&Error) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.0.predicate = Some(Arc::new(predicate));
         self
     }
 
@@ -102,13 +157,13 @@ impl RetryLayer {
     ///
     /// This function will panic if input factor smaller than `1.0`.
     pub fn with_factor(mut self, factor: f32) -> Self {
-        self.0 = self.0.with_factor(factor);
+        self.0.inner = self.0.inner.with_factor(factor);
         self
     }
 
     /// Set min_delay of current backoff.
     pub fn with_min_delay(mut self, min_delay: Duration) -> Self {
-        self.0 = self.0.with_min_delay(min_delay);
+        self.0.inner = self.0.inner.with_min_delay(min_delay);
         self
     }
 
@@ -116,7 +171,7 @@ impl RetryLayer {
     ///
     /// Delay will not increasing if current delay is larger than max_delay.
     pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
-        self.0 = self.0.with_max_delay(max_delay);
+        self.0.inner = self.0.inner.with_max_delay(max_delay);
         self
     }
 
@@ -124,9 +179,155 @@ impl RetryLayer {
     ///
     /// Backoff will return `None` if max times is reaching.
     pub fn with_max_times(mut self, max_times: usize) -> Self {
-        self.0 = self.0.with_max_times(max_times);
+        self.0.inner = self.0.inner.with_max_times(max_times);
         self
     }
+
+    /// Set max_elapsed_time of current backoff.
+    ///
+    /// Backoff will return `None` if the elapsed time since the first
+    /// attempt plus the next delay exceeds max_elapsed_time, and the last
+    /// error will be returned.
+    ///
+    /// This works together with max_times, retry stops at whichever
+    /// limit is reached first.
+    pub fn with_max_elapsed_time(mut self, max_elapsed_time: Duration) -> Self {
+        self.0.max_elapsed_time = Some(max_elapsed_time);
+        self
+    }
+}
+
+/// RetryPredicate decides whether a temporary error should be retried.
+type RetryPredicate = Arc<dyn Fn(&Error) -> bool + Send + Sync>;
+
+/// RetryHint records the [`Error::retry_after`] of the last failed attempt.
+type RetryHint = Arc<Mutex<Option<Duration>>>;
+
+/// RetryBackoffBuilder wraps [`ExponentialBuilder`] with an optional
+/// jitter factor and retry predicate.
+#[derive(Default, Clone)]
+struct RetryBackoffBuilder {
+    inner: ExponentialBuilder,
+    jitter_factor: Option<f32>,
+    max_elapsed_time: Option<Duration>,
+    predicate: Option<RetryPredicate>,
+    hint: RetryHint,
+}
+
+impl RetryBackoffBuilder {
+    /// Create a builder with the same settings but its own retry hint.
+    ///
+    /// Every retried call should use a scoped builder, so that hints from
+    /// concurrent calls will not affect each other.
+    fn scoped(&self) -> Self {
+        Self {
+            hint: RetryHint::default(),
+            ..self.clone()
+        }
+    }
+
+    /// Check if given error should be retried.
+    fn should_retry(&self, err: &Error) -> bool {
+        err.is_temporary() && self.predicate.as_ref().map_or(true, |f| f(err))
+    }
+
+    /// Check the result of operation.
+    ///
+    /// Temporary errors that rejected by predicate will be marked as
+    /// persistent, so that they will not be retried.
+    ///
+    /// The retry hint of error will be recorded and used by the next delay.
+    fn check<T>(&self, r: Result<T>) -> Result<T> {
+        r.map_err(|err| {
+            *self.hint.lock().expect("lock must be acquired") = err.retry_after();
+
+            if err.is_temporary() && !self.should_retry(&err) {
+                err.set_persistent()
+            } else {
+                err
+            }
+        })
+    }
+}
+
+impl Debug for RetryBackoffBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryBackoffBuilder")
+            .field("inner", &self.inner)
+            .field("jitter_factor", &self.jitter_factor)
+            .field("max_elapsed_time", &self.max_elapsed_time)
+            .finish_non_exhaustive()
+    }
+}
+
+impl BackoffBuilder for RetryBackoffBuilder {
+    type Backoff = RetryBackoff;
+
+    fn build(&self) -> Self::Backoff {
+        RetryBackoff {
+            inner: self.inner.build(),
+            jitter_factor: self.jitter_factor,
+            max_elapsed_time: self.max_elapsed_time,
+            start: Instant::now(),
+            hint: self.hint.clone(),
+        }
+    }
+}
+
+/// RetryBackoff is the backoff built by [`RetryBackoffBuilder`].
+#[derive(Debug)]
+struct RetryBackoff {
+    inner: ExponentialBackoff,
+    jitter_factor: Option<f32>,
+    max_elapsed_time: Option<Duration>,
+    start: Instant,
+    hint: RetryHint,
+}
+
+impl RetryBackoff {
+    /// Return the next delay for given error.
+    ///
+    /// The [`Error::retry_after`] will be preferred if present, but it still
+    /// counts toward max times and max elapsed time.
+    fn next_after(&mut self, err: &Error) -> Option<Duration> {
+        let delay = self.next_delay()?;
+        self.check_elapsed(err.retry_after().unwrap_or(delay))
+    }
+
+    /// Return the next delay without checking max elapsed time.
+    fn next_delay(&mut self) -> Option<Duration> {
+        let delay = self.inner.next()?;
+
+        // Services know better than us about when to retry.
+        if let Some(hint) = self.hint.lock().expect("lock must be acquired").take() {
+            return Some(hint);
+        }
+
+        match self.jitter_factor {
+            None => Some(delay),
+            Some(factor) => {
+                let jitter = factor * rand::thread_rng().gen::<f32>();
+                Some(delay.mul_f32(1.0 - jitter))
+            }
+        }
+    }
+
+    /// Return `None` if sleeping for given delay would exceed max elapsed time.
+    fn check_elapsed(&self, delay: Duration) -> Option<Duration> {
+        match self.max_elapsed_time {
+            Some(max) if self.start.elapsed() + delay > max => None,
+            _ => Some(delay),
+        }
+    }
+}
+
+impl Iterator for RetryBackoff {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let delay = self.next_delay()?;
+        self.check_elapsed(delay)
+    }
 }
 
 impl<A: Accessor> Layer<A> for RetryLayer {
@@ -143,7 +344,7 @@ impl<A: Accessor> Layer<A> for RetryLayer {
 #[derive(Clone)]
 pub struct RetryAccessor<A: Accessor> {
     inner: A,
-    builder: ExponentialBuilder,
+    builder: RetryBackoffBuilder,
 }
 
 impl<A: Accessor> Debug for RetryAccessor<A> {
@@ -169,127 +370,177 @@ impl<A: Accessor> LayeredAccessor for RetryAccessor<A> {
     }
 
     async fn create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
-        { || self.inner.create(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
+        let builder = self.builder.scoped();
+        {
+            || {
+                self.inner
+                    .create(path, args.clone())
+                    .map(|v| builder.check(v))
+            }
+        }
+        .retry(&builder)
+        .when(|e| e.is_temporary())
+        .notify(|err, dur| {
+            warn!(
                     target: "opendal::service",
                     "operation={} -> retry after {}s: error={:?}",
                     Operation::Create, dur.as_secs_f64(), err)
-            })
-            .map(|v| v.map_err(|e| e.set_persistent()))
-            .await
+        })
+        .map(|v| v.map_err(|e| e.set_persistent()))
+        .await
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        { || self.inner.read(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
+        let builder = self.builder.scoped();
+        {
+            || {
+                self.inner
+                    .read(path, args.clone())
+                    .map(|v| builder.check(v))
+            }
+        }
+        .retry(&builder)
+        .when(|e| e.is_temporary())
+        .notify(|err, dur| {
+            warn!(
                     target: "opendal::service",
                     "operation={} -> retry after {}s: error={:?}",
                     Operation::Read, dur.as_secs_f64(), err)
-            })
-            .map(|v| {
-                v.map(|(rp, r)| (rp, RetryWrapper::new(r, path, self.builder.clone())))
-                    .map_err(|e| e.set_persistent())
-            })
-            .await
+        })
+        .map(|v| {
+            v.map(|(rp, r)| (rp, RetryWrapper::new(r, path, self.builder.clone())))
+                .map_err(|e| e.set_persistent())
+        })
+        .await
     }
 
     /// Return `Interrupted` Error even after retry.
     ///
     /// Allowing users to retry the write request from upper logic.
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        { || self.inner.write(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
+        let builder = self.builder.scoped();
+        {
+            || {
+                self.inner
+                    .write(path, args.clone())
+                    .map(|v| builder.check(v))
+            }
+        }
+        .retry(&builder)
+        .when(|e| e.is_temporary())
+        .notify(|err, dur| {
+            warn!(
                     target: "opendal::service",
                     "operation={} -> retry after {}s: error={:?}",
                     Operation::Write, dur.as_secs_f64(), err)
-            })
-            .map(|v| {
-                v.map(|(rp, r)| (rp, RetryWrapper::new(r, path, self.builder.clone())))
-                    .map_err(|e| e.set_persistent())
-            })
-            .await
+        })
+        .map(|v| {
+            v.map(|(rp, r)| (rp, RetryWrapper::new(r, path, self.builder.clone())))
+                .map_err(|e| e.set_persistent())
+        })
+        .await
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        { || self.inner.stat(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
+        let builder = self.builder.scoped();
+        {
+            || {
+                self.inner
+                    .stat(path, args.clone())
+                    .map(|v| builder.check(v))
+            }
+        }
+        .retry(&builder)
+        .when(|e| e.is_temporary())
+        .notify(|err, dur| {
+            warn!(
                     target: "opendal::service",
                     "operation={} -> retry after {}s: error={:?}",
                     Operation::Stat, dur.as_secs_f64(), err)
-            })
-            .map(|v| v.map_err(|e| e.set_persistent()))
-            .await
+        })
+        .map(|v| v.map_err(|e| e.set_persistent()))
+        .await
     }
 
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        { || self.inner.delete(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
+        let builder = self.builder.scoped();
+        {
+            || {
+                self.inner
+                    .delete(path, args.clone())
+                    .map(|v| builder.check(v))
+            }
+        }
+        .retry(&builder)
+        .when(|e| e.is_temporary())
+        .notify(|err, dur| {
+            warn!(
                     target: "opendal::service",
                     "operation={} -> retry after {}s: error={:?}",
                     Operation::Delete, dur.as_secs_f64(), err)
-            })
-            .map(|v| v.map_err(|e| e.set_persistent()))
-            .await
+        })
+        .map(|v| v.map_err(|e| e.set_persistent()))
+        .await
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
-        { || self.inner.list(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
+        let builder = self.builder.scoped();
+        {
+            || {
+                self.inner
+                    .list(path, args.clone())
+                    .map(|v| builder.check(v))
+            }
+        }
+        .retry(&builder)
+        .when(|e| e.is_temporary())
+        .notify(|err, dur| {
+            warn!(
                     target: "opendal::service",
                     "operation={} -> retry after {}s: error={:?}",
                     Operation::List, dur.as_secs_f64(), err)
+        })
+        .map(|v| {
+            v.map(|(l, p)| {
+                let pager = RetryWrapper::new(p, path, self.builder.clone());
+                (l, pager)
             })
-            .map(|v| {
-                v.map(|(l, p)| {
-                    let pager = RetryWrapper::new(p, path, self.builder.clone());
-                    (l, pager)
-                })
-                .map_err(|e| e.set_persistent())
-            })
-            .await
+            .map_err(|e| e.set_persistent())
+        })
+        .await
     }
 
     async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
-        { || self.inner.scan(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
-            .notify(|err, dur| {
-                warn!(
+        let builder = self.builder.scoped();
+        {
+            || {
+                self.inner
+                    .scan(path, args.clone())
+                    .map(|v| builder.check(v))
+            }
+        }
+        .retry(&builder)
+        .when(|e| e.is_temporary())
+        .notify(|err, dur| {
+            warn!(
                     target: "opendal::service",
                     "operation={} -> retry after {}s: error={:?}",
                     Operation::Scan, dur.as_secs_f64(), err)
+        })
+        .map(|v| {
+            v.map(|(l, p)| {
+                let pager = RetryWrapper::new(p, path, self.builder.clone());
+                (l, pager)
             })
-            .map(|v| {
-                v.map(|(l, p)| {
-                    let pager = RetryWrapper::new(p, path, self.builder.clone());
-                    (l, pager)
-                })
-                .map_err(|e| e.set_persistent())
-            })
-            .await
+            .map_err(|e| e.set_persistent())
+        })
+        .await
     }
 
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
-        { || self.inner.batch(args.clone()) }
-            .retry(&self.builder)
+        let builder = self.builder.scoped();
+        { || self.inner.batch(args.clone()).map(|v| builder.check(v)) }
+            .retry(&builder)
             .when(|e| e.is_temporary())
             .notify(|err, dur| {
                 warn!(
@@ -302,8 +553,9 @@ impl<A: Accessor> LayeredAccessor for RetryAccessor<A> {
     }
 
     fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
-        { || self.inner.blocking_create(path, args.clone()) }
-            .retry(&self.builder)
+        let builder = self.builder.scoped();
+        { || builder.check(self.inner.blocking_create(path, args.clone())) }
+            .retry(&builder)
             .when(|e| e.is_temporary())
             .notify(|err, dur| {
                 warn!(
@@ -316,8 +568,9 @@ impl<A: Accessor> LayeredAccessor for RetryAccessor<A> {
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
-        { || self.inner.blocking_read(path, args.clone()) }
-            .retry(&self.builder)
+        let builder = self.builder.scoped();
+        { || builder.check(self.inner.blocking_read(path, args.clone())) }
+            .retry(&builder)
             .when(|e| e.is_temporary())
             .notify(|err, dur| {
                 warn!(
@@ -331,8 +584,9 @@ impl<A: Accessor> LayeredAccessor for RetryAccessor<A> {
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
-        { || self.inner.blocking_write(path, args.clone()) }
-            .retry(&self.builder)
+        let builder = self.builder.scoped();
+        { || builder.check(self.inner.blocking_write(path, args.clone())) }
+            .retry(&builder)
             .when(|e| e.is_temporary())
             .notify(|err, dur| {
                 warn!(
@@ -346,8 +600,9 @@ impl<A: Accessor> LayeredAccessor for RetryAccessor<A> {
     }
 
     fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        { || self.inner.blocking_stat(path, args.clone()) }
-            .retry(&self.builder)
+        let builder = self.builder.scoped();
+        { || builder.check(self.inner.blocking_stat(path, args.clone())) }
+            .retry(&builder)
             .when(|e| e.is_temporary())
             .notify(|err, dur| {
                 warn!(
@@ -360,8 +615,9 @@ impl<A: Accessor> LayeredAccessor for RetryAccessor<A> {
     }
 
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        { || self.inner.blocking_delete(path, args.clone()) }
-            .retry(&self.builder)
+        let builder = self.builder.scoped();
+        { || builder.check(self.inner.blocking_delete(path, args.clone())) }
+            .retry(&builder)
             .when(|e| e.is_temporary())
             .notify(|err, dur| {
                 warn!(
@@ -374,8 +630,9 @@ impl<A: Accessor> LayeredAccessor for RetryAccessor<A> {
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
-        { || self.inner.blocking_list(path, args.clone()) }
-            .retry(&self.builder)
+        let builder = self.builder.scoped();
+        { || builder.check(self.inner.blocking_list(path, args.clone())) }
+            .retry(&builder)
             .when(|e| e.is_temporary())
             .notify(|err, dur| {
                 warn!(
@@ -392,8 +649,9 @@ impl<A: Accessor> LayeredAccessor for RetryAccessor<A> {
     }
 
     fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
-        { || self.inner.blocking_scan(path, args.clone()) }
-            .retry(&self.builder)
+        let builder = self.builder.scoped();
+        { || builder.check(self.inner.blocking_scan(path, args.clone())) }
+            .retry(&builder)
             .when(|e| e.is_temporary())
             .notify(|err, dur| {
                 warn!(
@@ -413,13 +671,13 @@ impl<A: Accessor> LayeredAccessor for RetryAccessor<A> {
 pub struct RetryWrapper<R> {
     inner: R,
     path: String,
-    builder: ExponentialBuilder,
-    current_backoff: Option<ExponentialBackoff>,
+    builder: RetryBackoffBuilder,
+    current_backoff: Option<RetryBackoff>,
     sleep: Option<Pin<Box<tokio::time::Sleep>>>,
 }
 
 impl<R> RetryWrapper<R> {
-    fn new(inner: R, path: &str, backoff: ExponentialBuilder) -> Self {
+    fn new(inner: R, path: &str, backoff: RetryBackoffBuilder) -> Self {
         Self {
             inner,
             path: path.to_string(),
@@ -442,7 +700,7 @@ impl<R: oio::Read> oio::Read for RetryWrapper<R> {
                 self.current_backoff = None;
                 Poll::Ready(Ok(v))
             }
-            Err(err) if !err.is_temporary() => {
+            Err(err) if !self.builder.should_retry(&err) => {
                 self.current_backoff = None;
                 Poll::Ready(Err(err))
             }
@@ -455,7 +713,7 @@ impl<R: oio::Read> oio::Read for RetryWrapper<R> {
                     }
                 };
 
-                match backoff.next() {
+                match backoff.next_after(&err) {
                     None => {
                         self.current_backoff = None;
                         Poll::Ready(Err(err))
@@ -484,7 +742,7 @@ impl<R: oio::Read> oio::Read for RetryWrapper<R> {
                 self.current_backoff = None;
                 Poll::Ready(Ok(v))
             }
-            Err(err) if !err.is_temporary() => {
+            Err(err) if !self.builder.should_retry(&err) => {
                 self.current_backoff = None;
                 Poll::Ready(Err(err))
             }
@@ -497,7 +755,7 @@ impl<R: oio::Read> oio::Read for RetryWrapper<R> {
                     }
                 };
 
-                match backoff.next() {
+                match backoff.next_after(&err) {
                     None => {
                         self.current_backoff = None;
                         Poll::Ready(Err(err))
@@ -543,7 +801,7 @@ impl<R: oio::Read> oio::Read for RetryWrapper<R> {
                     }
                 };
 
-                match backoff.next() {
+                match backoff.next_after(&err) {
                     None => {
                         self.current_backoff = None;
                         Poll::Ready(Some(Err(err)))
@@ -564,8 +822,9 @@ impl<R: oio::Read> oio::Read for RetryWrapper<R> {
 
 impl<R: oio::BlockingRead> oio::BlockingRead for RetryWrapper<R> {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
-        { || self.inner.read(buf) }
-            .retry(&self.builder)
+        let builder = self.builder.scoped();
+        { || builder.check(self.inner.read(buf)) }
+            .retry(&builder)
             .when(|e| e.is_temporary())
             .notify(move |err, dur| {
                 warn!(
@@ -578,8 +837,9 @@ impl<R: oio::BlockingRead> oio::BlockingRead for RetryWrapper<R> {
     }
 
     fn seek(&mut self, pos: io::SeekFrom) -> Result<u64> {
-        { || self.inner.seek(pos) }
-            .retry(&self.builder)
+        let builder = self.builder.scoped();
+        { || builder.check(self.inner.seek(pos)) }
+            .retry(&builder)
             .when(|e| e.is_temporary())
             .notify(move |err, dur| {
                 warn!(
@@ -592,8 +852,9 @@ impl<R: oio::BlockingRead> oio::BlockingRead for RetryWrapper<R> {
     }
 
     fn next(&mut self) -> Option<Result<Bytes>> {
-        { || self.inner.next().transpose() }
-            .retry(&self.builder)
+        let builder = self.builder.scoped();
+        { || builder.check(self.inner.next().transpose()) }
+            .retry(&builder)
             .when(|e| e.is_temporary())
             .notify(move |err, dur| {
                 warn!(
@@ -615,8 +876,8 @@ impl<R: oio::Write> oio::Write for RetryWrapper<R> {
         loop {
             match self.inner.write(bs.clone()).await {
                 Ok(v) => return Ok(v),
-                Err(e) if !e.is_temporary() => return Err(e),
-                Err(e) => match backoff.next() {
+                Err(e) if !self.builder.should_retry(&e) => return Err(e),
+                Err(e) => match backoff.next_after(&e) {
                     None => return Err(e),
                     Some(dur) => {
                         warn!(target: "opendal::service",
@@ -636,8 +897,8 @@ impl<R: oio::Write> oio::Write for RetryWrapper<R> {
         loop {
             match self.inner.append(bs.clone()).await {
                 Ok(v) => return Ok(v),
-                Err(e) if !e.is_temporary() => return Err(e),
-                Err(e) => match backoff.next() {
+                Err(e) if !self.builder.should_retry(&e) => return Err(e),
+                Err(e) => match backoff.next_after(&e) {
                     None => return Err(e),
                     Some(dur) => {
                         warn!(target: "opendal::service",
@@ -657,8 +918,8 @@ impl<R: oio::Write> oio::Write for RetryWrapper<R> {
         loop {
             match self.inner.close().await {
                 Ok(v) => return Ok(v),
-                Err(e) if !e.is_temporary() => return Err(e),
-                Err(e) => match backoff.next() {
+                Err(e) if !self.builder.should_retry(&e) => return Err(e),
+                Err(e) => match backoff.next_after(&e) {
                     None => return Err(e),
                     Some(dur) => {
                         warn!(target: "opendal::service",
@@ -671,12 +932,38 @@ impl<R: oio::Write> oio::Write for RetryWrapper<R> {
             }
         }
     }
+
+    async fn abort(&mut self) -> Result<()> {
+        let mut backoff = self.builder.build();
+
+        loop {
+            match self.inner.abort().await {
+                Ok(v) => return Ok(v),
+                Err(e) if !self.builder.should_retry(&e) => return Err(e),
+                Err(e) => match backoff.next_after(&e) {
+                    None => return Err(e),
+                    Some(dur) => {
+                        warn!(target: "opendal::service",
+                              "operation={} path={} -> pager retry after {}s: error={:?}",
+                              WriteOperation::Abort, self.path, dur.as_secs_f64(), e);
+                        tokio::time::sleep(dur).await;
+                        continue;
+                    }
+                },
+            }
+        }
+    }
+
+    fn state(&self) -> Result<MultipartState> {
+        self.inner.state()
+    }
 }
 
 impl<R: oio::BlockingWrite> oio::BlockingWrite for RetryWrapper<R> {
     fn write(&mut self, bs: Bytes) -> Result<()> {
-        { || self.inner.write(bs.clone()) }
-            .retry(&self.builder)
+        let builder = self.builder.scoped();
+        { || builder.check(self.inner.write(bs.clone())) }
+            .retry(&builder)
             .when(|e| e.is_temporary())
             .notify(move |err, dur| {
                 warn!(
@@ -689,8 +976,9 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for RetryWrapper<R> {
     }
 
     fn append(&mut self, bs: Bytes) -> Result<()> {
-        { || self.inner.append(bs.clone()) }
-            .retry(&self.builder)
+        let builder = self.builder.scoped();
+        { || builder.check(self.inner.append(bs.clone())) }
+            .retry(&builder)
             .when(|e| e.is_temporary())
             .notify(move |err, dur| {
                 warn!(
@@ -703,8 +991,9 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for RetryWrapper<R> {
     }
 
     fn close(&mut self) -> Result<()> {
-        { || self.inner.close() }
-            .retry(&self.builder)
+        let builder = self.builder.scoped();
+        { || builder.check(self.inner.close()) }
+            .retry(&builder)
             .when(|e| e.is_temporary())
             .notify(move |err, dur| {
                 warn!(
@@ -715,6 +1004,21 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for RetryWrapper<R> {
             .call()
             .map_err(|e| e.set_persistent())
     }
+
+    fn abort(&mut self) -> Result<()> {
+        let builder = self.builder.scoped();
+        { || builder.check(self.inner.abort()) }
+            .retry(&builder)
+            .when(|e| e.is_temporary())
+            .notify(move |err, dur| {
+                warn!(
+                target: "opendal::service",
+                "operation={} -> pager retry after {}s: error={:?}",
+               WriteOperation::BlockingAbort, dur.as_secs_f64(), err)
+            })
+            .call()
+            .map_err(|e| e.set_persistent())
+    }
 }
 
 #[async_trait]
@@ -725,8 +1029,8 @@ impl<P: oio::Page> oio::Page for RetryWrapper<P> {
         loop {
             match self.inner.next().await {
                 Ok(v) => return Ok(v),
-                Err(e) if !e.is_temporary() => return Err(e),
-                Err(e) => match backoff.next() {
+                Err(e) if !self.builder.should_retry(&e) => return Err(e),
+                Err(e) => match backoff.next_after(&e) {
                     None => return Err(e),
                     Some(dur) => {
                         warn!(target: "opendal::service",
@@ -743,8 +1047,9 @@ impl<P: oio::Page> oio::Page for RetryWrapper<P> {
 
 impl<P: oio::BlockingPage> oio::BlockingPage for RetryWrapper<P> {
     fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
-        { || self.inner.next() }
-            .retry(&self.builder)
+        let builder = self.builder.scoped();
+        { || builder.check(self.inner.next()) }
+            .retry(&builder)
             .when(|e| e.is_temporary())
             .notify(move |err, dur| {
                 warn!(
@@ -825,6 +1130,22 @@ mod tests {
             ))
         }
 
+        async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+            let mut attempt = self.attempt.lock().unwrap();
+            *attempt += 1;
+
+            if path == "rate_limited" {
+                return Err(Error::new(ErrorKind::RateLimited, "slow down")
+                    .set_temporary()
+                    .with_retry_after(Duration::from_millis(1)));
+            }
+
+            Err(
+                Error::new(ErrorKind::PermissionDenied, "temporary permission denied")
+                    .set_temporary(),
+            )
+        }
+
         async fn list(&self, _: &str, _: OpList) -> Result<(RpList, Self::Pager)> {
             let pager = MockPager::default();
             Ok((RpList::default(), pager))
@@ -926,6 +1247,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_retry_backoff_without_jitter() {
+        let builder = RetryLayer::new()
+            .with_min_delay(Duration::from_millis(10))
+            .with_max_times(5)
+            .0;
+
+        let expected: Vec<_> = ExponentialBuilder::default()
+            .with_min_delay(Duration::from_millis(10))
+            .with_max_times(5)
+            .build()
+            .collect();
+        let actual: Vec<_> = builder.build().collect();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_retry_backoff_with_jitter() {
+        let cases = vec![
+            ("full jitter", 1.0),
+            ("half jitter", 0.5),
+            ("no jitter", 0.0),
+        ];
+
+        for (name, factor) in cases {
+            let builder = RetryLayer::new()
+                .with_min_delay(Duration::from_millis(10))
+                .with_max_times(5)
+                .with_jitter_factor(factor)
+                .0;
+            let expected: Vec<_> = ExponentialBuilder::default()
+                .with_min_delay(Duration::from_millis(10))
+                .with_max_times(5)
+                .build()
+                .collect();
+
+            for _ in 0..1000 {
+                let actual: Vec<_> = builder.build().collect();
+                assert_eq!(actual.len(), expected.len(), "{name}");
+
+                for (actual, expected) in actual.into_iter().zip(expected.iter()) {
+                    assert!(actual <= *expected, "{name}: {actual:?} > {expected:?}");
+                    assert!(
+                        actual >= expected.mul_f32(1.0 - factor),
+                        "{name}: {actual:?} < {:?}",
+                        expected.mul_f32(1.0 - factor)
+                    );
+                }
+            }
+        }
+    }
+
     #[tokio::test]
     async fn test_retry_read() {
         let _ = env_logger::try_init();
@@ -971,4 +1345,130 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[tokio::test]
+    async fn test_retry_predicate() {
+        let _ = env_logger::try_init();
+
+        let builder = MockBuilder::default();
+        let op = Operator::new(builder.clone())
+            .unwrap()
+            .layer(
+                RetryLayer::new()
+                    .with_min_delay(Duration::from_millis(1))
+                    .with_retry_predicate(|e| e.kind() != ErrorKind::PermissionDenied),
+            )
+            .finish();
+
+        let err = op
+            .stat("permission_denied")
+            .await
+            .expect_err("stat must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        // The error is rejected by predicate, we should request it only once.
+        assert_eq!(*builder.attempt.lock().unwrap(), 1);
+
+        let builder = MockBuilder::default();
+        let op = Operator::new(builder.clone())
+            .unwrap()
+            .layer(RetryLayer::new().with_min_delay(Duration::from_millis(1)))
+            .finish();
+
+        let err = op
+            .stat("permission_denied")
+            .await
+            .expect_err("stat must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        // Without predicate, the error is temporary, we should request it 1 + 3 times.
+        assert_eq!(*builder.attempt.lock().unwrap(), 4);
+    }
+
+    #[test]
+    fn test_retry_backoff_prefer_retry_after() {
+        let builder = RetryLayer::new()
+            .with_min_delay(Duration::from_secs(10))
+            .with_max_times(2)
+            .0
+            .scoped();
+        let mut backoff = builder.build();
+
+        let err = Error::new(ErrorKind::RateLimited, "slow down")
+            .set_temporary()
+            .with_retry_after(Duration::from_millis(5));
+        let _ = builder.check::<()>(Err(err));
+        assert_eq!(backoff.next(), Some(Duration::from_millis(5)));
+
+        // The hint has been consumed, fallback to computed backoff.
+        assert_eq!(backoff.next(), Some(Duration::from_secs(20)));
+        assert_eq!(backoff.next(), None);
+    }
+
+    #[test]
+    fn test_retry_backoff_with_max_elapsed_time() {
+        let builder = RetryLayer::new()
+            .with_min_delay(Duration::from_secs(1))
+            .with_max_times(10)
+            .with_max_elapsed_time(Duration::from_secs(4))
+            .0;
+
+        // 1s + 2s fits in the budget, but the next 4s delay doesn't.
+        let actual: Vec<_> = builder.build().collect();
+        assert_eq!(actual, vec![Duration::from_secs(1), Duration::from_secs(2)]);
+
+        // max_times still works if it's reached first.
+        let builder = RetryLayer::new()
+            .with_min_delay(Duration::from_secs(1))
+            .with_max_times(1)
+            .with_max_elapsed_time(Duration::from_secs(60))
+            .0;
+        let actual: Vec<_> = builder.build().collect();
+        assert_eq!(actual, vec![Duration::from_secs(1)]);
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_max_elapsed_time() {
+        let _ = env_logger::try_init();
+
+        let builder = MockBuilder::default();
+        let op = Operator::new(builder.clone())
+            .unwrap()
+            .layer(
+                RetryLayer::new()
+                    .with_min_delay(Duration::from_millis(20))
+                    .with_max_times(10)
+                    .with_max_elapsed_time(Duration::from_millis(50)),
+            )
+            .finish();
+
+        let err = op
+            .stat("permission_denied")
+            .await
+            .expect_err("stat must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        // The first 20ms delay fits in the budget, but the next 40ms doesn't,
+        // so we should request it only 1 + 1 times.
+        assert_eq!(*builder.attempt.lock().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_retry_after() {
+        let _ = env_logger::try_init();
+
+        let builder = MockBuilder::default();
+        let op = Operator::new(builder.clone())
+            .unwrap()
+            .layer(
+                RetryLayer::new()
+                    .with_min_delay(Duration::from_secs(60))
+                    .with_max_times(2),
+            )
+            .finish();
+
+        let err = tokio::time::timeout(Duration::from_secs(10), op.stat("rate_limited"))
+            .await
+            .expect("retry after must be preferred over backoff")
+            .expect_err("stat must fail");
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+        assert_eq!(*builder.attempt.lock().unwrap(), 3);
+    }
 }
@@ -0,0 +1,426 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::io::SeekFrom;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::future::Shared;
+use futures::FutureExt;
+
+use crate::ops::*;
+use crate::raw::oio::ReadExt;
+use crate::raw::*;
+use crate::*;
+
+/// Deduplicate concurrent reads of the same object.
+///
+/// # Notes
+///
+/// Reads with the same path and range that arrive while another one is
+/// still in flight will wait for and share its result instead of sending
+/// a new request:
+///
+/// - Only reads without other options will be shared. Reads with options
+///   like version, encryption, custom headers or decompress will be
+///   forwarded and streamed as is since they could return different
+///   content.
+/// - If the shared read fails, all waiters will see the same error.
+/// - Results are never kept after the read completes, please use
+///   [`CacheLayer`][crate::layers::CacheLayer] for caching.
+/// - Content of shared reads will be loaded into memory as a whole, please
+///   use this layer for small objects only.
+/// - Blocking reads will be forwarded as is.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::SingleflightLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(SingleflightLayer::new())
+///     .finish();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SingleflightLayer;
+
+impl SingleflightLayer {
+    /// Create a new singleflight layer.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<A: Accessor> Layer<A> for SingleflightLayer {
+    type LayeredAccessor = SingleflightAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        SingleflightAccessor {
+            inner: Arc::new(inner),
+            inflight: Arc::default(),
+        }
+    }
+}
+
+type SharedRead = Shared<BoxFuture<'static, std::result::Result<(Metadata, Bytes), Arc<Error>>>>;
+
+pub struct SingleflightAccessor<A: Accessor> {
+    inner: Arc<A>,
+    inflight: Arc<Mutex<HashMap<(String, BytesRange), SharedRead>>>,
+}
+
+impl<A: Accessor> Debug for SingleflightAccessor<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SingleflightAccessor")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: Accessor> SingleflightAccessor<A> {
+    /// Read the whole content, metadata returned by service will be kept
+    /// so that etag and checksums are still available to upper layers.
+    async fn read_all(inner: &A, path: &str, args: OpRead) -> Result<(Metadata, Bytes)> {
+        let (rp, mut r) = inner.read(path, args).await?;
+
+        let mut bs =
+            Vec::with_capacity(rp.metadata().content_length_raw().unwrap_or_default() as usize);
+        let mut buf = vec![0; 64 * 1024];
+        loop {
+            match r.read(&mut buf).await? {
+                0 => break,
+                n => bs.extend_from_slice(&buf[..n]),
+            }
+        }
+
+        let mut meta = rp.into_metadata();
+        if meta.content_length_raw().is_none() {
+            meta.set_content_length(bs.len() as u64);
+        }
+        Ok((meta, Bytes::from(bs)))
+    }
+
+    /// Check if given read could share result with others.
+    ///
+    /// Only range is used as the key, so reads with any other option set
+    /// will not be shared.
+    fn is_shareable(args: &OpRead) -> bool {
+        let default = OpRead::default();
+
+        args.max_size().is_none()
+            && args.concurrent() == default.concurrent()
+            && args.chunk().is_none()
+            && args.encryption().is_none()
+            && args.seek_buffer() == default.seek_buffer()
+            && args.headers().is_empty()
+            && args.version().is_none()
+            && !args.decompress()
+    }
+
+    /// Build an owned error for every waiter of the shared read.
+    fn shared_error(err: &Error) -> Error {
+        let mut e =
+            Error::new(err.kind(), "shared read failed").set_source(anyhow::anyhow!("{err}"));
+        if err.is_temporary() {
+            e = e.set_temporary();
+        }
+        if let Some(dur) = err.retry_after() {
+            e = e.with_retry_after(dur);
+        }
+        e
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for SingleflightAccessor<A> {
+    type Inner = A;
+    type Reader = SingleflightReader<A::Reader>;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        if !Self::is_shareable(&args) {
+            let (rp, r) = self.inner.read(path, args).await?;
+            return Ok((rp, SingleflightReader::Inner(r)));
+        }
+
+        let key = (path.to_string(), args.range());
+
+        let fut = {
+            let mut inflight = self.inflight.lock().expect("lock must succeed");
+            inflight
+                .entry(key.clone())
+                .or_insert_with(|| {
+                    let inner = self.inner.clone();
+                    let inflight = self.inflight.clone();
+                    async move {
+                        let res = Self::read_all(&inner, &key.0, args).await;
+                        // Remove the entry once finished so that results
+                        // never outlive the read.
+                        inflight.lock().expect("lock must succeed").remove(&key);
+                        res.map_err(Arc::new)
+                    }
+                    .boxed()
+                    .shared()
+                })
+                .clone()
+        };
+
+        let (meta, bs) = fut.await.map_err(|err| Self::shared_error(&err))?;
+        Ok((
+            RpRead::with_metadata(meta),
+            SingleflightReader::Shared(oio::Cursor::from(bs)),
+        ))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+        self.inner.scan(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner.blocking_list(path, args)
+    }
+
+    fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        self.inner.blocking_scan(path, args)
+    }
+}
+
+/// SingleflightReader returns the shared content, or streams from the
+/// inner reader for reads that can't be shared.
+pub enum SingleflightReader<R> {
+    /// Content shared with other reads.
+    Shared(oio::Cursor),
+    /// Reader of the inner accessor.
+    Inner(R),
+}
+
+impl<R: oio::Read> oio::Read for SingleflightReader<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        match self {
+            SingleflightReader::Shared(r) => r.poll_read(cx, buf),
+            SingleflightReader::Inner(r) => r.poll_read(cx, buf),
+        }
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        match self {
+            SingleflightReader::Shared(r) => r.poll_seek(cx, pos),
+            SingleflightReader::Inner(r) => r.poll_seek(cx, pos),
+        }
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        match self {
+            SingleflightReader::Shared(r) => r.poll_next(cx),
+            SingleflightReader::Inner(r) => r.poll_next(cx),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::future::join_all;
+
+    use super::*;
+
+    #[derive(Default, Clone)]
+    struct MockBuilder {
+        attempt: Arc<Mutex<usize>>,
+    }
+
+    impl Builder for MockBuilder {
+        const SCHEME: Scheme = Scheme::Custom("mock");
+        type Accessor = MockService;
+
+        fn from_map(_: HashMap<String, String>) -> Self {
+            Self::default()
+        }
+
+        fn build(&mut self) -> Result<Self::Accessor> {
+            Ok(MockService {
+                attempt: self.attempt.clone(),
+            })
+        }
+    }
+
+    #[derive(Debug, Clone, Default)]
+    struct MockService {
+        attempt: Arc<Mutex<usize>>,
+    }
+
+    #[async_trait]
+    impl Accessor for MockService {
+        type Reader = oio::Cursor;
+        type BlockingReader = ();
+        type Writer = ();
+        type BlockingWriter = ();
+        type Pager = ();
+        type BlockingPager = ();
+
+        fn info(&self) -> AccessorInfo {
+            let mut am = AccessorInfo::default();
+            am.set_capabilities(AccessorCapability::Read | AccessorCapability::ReadWithVersion);
+
+            am
+        }
+
+        async fn read(&self, path: &str, _: OpRead) -> Result<(RpRead, Self::Reader)> {
+            *self.attempt.lock().unwrap() += 1;
+            // Make sure all reads are in flight at the same time.
+            tokio::time::sleep(Duration::from_millis(50)).await;
+
+            match path {
+                "error" => Err(Error::new(ErrorKind::PermissionDenied, "mock error")),
+                _ => Ok((
+                    RpRead::with_metadata(
+                        Metadata::new(EntryMode::FILE)
+                            .with_content_length(13)
+                            .with_etag("\"etag\"".to_string()),
+                    ),
+                    oio::Cursor::from(b"Hello, World!".to_vec()),
+                )),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_singleflight_read() {
+        let builder = MockBuilder::default();
+        let op = Operator::new(builder.clone())
+            .unwrap()
+            .layer(SingleflightLayer::new())
+            .finish();
+
+        let results = join_all((0..8).map(|_| op.read("inflight"))).await;
+        for res in results {
+            assert_eq!(res.expect("must read"), b"Hello, World!");
+        }
+        assert_eq!(*builder.attempt.lock().unwrap(), 1);
+
+        // Completed reads must not be shared.
+        op.read("inflight").await.expect("must read");
+        assert_eq!(*builder.attempt.lock().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_singleflight_read_error() {
+        let builder = MockBuilder::default();
+        let op = Operator::new(builder.clone())
+            .unwrap()
+            .layer(SingleflightLayer::new())
+            .finish();
+
+        let results = join_all((0..8).map(|_| op.read("error"))).await;
+        for res in results {
+            let err = res.expect_err("must fail");
+            assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        }
+        assert_eq!(*builder.attempt.lock().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_singleflight_read_different_range() {
+        let builder = MockBuilder::default();
+        let op = Operator::new(builder.clone())
+            .unwrap()
+            .layer(SingleflightLayer::new())
+            .finish();
+
+        let (a, b) = futures::join!(
+            op.range_read("inflight", 0..4),
+            op.range_read("inflight", 4..8)
+        );
+        a.expect("must read");
+        b.expect("must read");
+        assert_eq!(*builder.attempt.lock().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_singleflight_read_with_options() {
+        let builder = MockBuilder::default();
+        let op = Operator::new(builder.clone())
+            .unwrap()
+            .layer(SingleflightLayer::new())
+            .finish();
+
+        // Reads of different versions must not share result.
+        let (a, b) = futures::join!(
+            op.read("inflight"),
+            op.read_with("inflight", OpRead::new().with_version("v1"))
+        );
+        a.expect("must read");
+        b.expect("must read");
+        assert_eq!(*builder.attempt.lock().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_singleflight_read_metadata() {
+        let acc = SingleflightLayer::new().layer(MockService::default());
+
+        // Metadata returned by service must be kept for both shared and
+        // forwarded reads.
+        let (rp, r) = LayeredAccessor::read(&acc, "inflight", OpRead::new())
+            .await
+            .unwrap();
+        assert_eq!(rp.metadata().etag(), Some("\"etag\""));
+        assert!(matches!(r, SingleflightReader::Shared(_)));
+
+        let (rp, r) = LayeredAccessor::read(&acc, "inflight", OpRead::new().with_version("v1"))
+            .await
+            .unwrap();
+        assert_eq!(rp.metadata().etag(), Some("\"etag\""));
+        assert!(matches!(r, SingleflightReader::Inner(_)));
+    }
+}
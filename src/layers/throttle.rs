@@ -0,0 +1,469 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io::SeekFrom;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::ready;
+use futures::FutureExt;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::*;
+
+/// Add a bandwidth rate limiter to the underlying services.
+///
+/// # Notes
+///
+/// ThrottleLayer uses a token bucket shared by all readers and writers
+/// of the same operator. Every byte read or written consumes one token,
+/// and polls will be delayed (instead of returning errors) while the
+/// bucket is empty.
+///
+/// - `bandwidth`: the max bytes per second that can be transferred.
+/// - `burst`: the capacity of the bucket, a.k.a., the max bytes that can
+///   be transferred at once. Default to `bandwidth`.
+///
+/// If no bandwidth is set, this layer is a no-op.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::ThrottleLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+/// use opendal::Scheme;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(
+///         ThrottleLayer::new()
+///             .with_bandwidth(10 * 1024 * 1024)
+///             .with_burst(1024 * 1024),
+///     )
+///     .finish();
+/// ```
+#[derive(Default, Clone)]
+pub struct ThrottleLayer {
+    bandwidth: Option<u64>,
+    burst: Option<u64>,
+}
+
+impl ThrottleLayer {
+    /// Create a new throttle layer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the bandwidth in bytes per second.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if input bandwidth is `0`.
+    pub fn with_bandwidth(mut self, bandwidth: u64) -> Self {
+        assert!(bandwidth > 0, "bandwidth must be larger than 0");
+
+        self.bandwidth = Some(bandwidth);
+        self
+    }
+
+    /// Set the burst size in bytes.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if input burst is `0`.
+    pub fn with_burst(mut self, burst: u64) -> Self {
+        assert!(burst > 0, "burst must be larger than 0");
+
+        self.burst = Some(burst);
+        self
+    }
+}
+
+impl<A: Accessor> Layer<A> for ThrottleLayer {
+    type LayeredAccessor = ThrottleAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        let limiter = self.bandwidth.map(|bandwidth| {
+            let burst = self.burst.unwrap_or(bandwidth);
+            Arc::new(Mutex::new(TokenBucket::new(bandwidth, burst)))
+        });
+
+        ThrottleAccessor { inner, limiter }
+    }
+}
+
+/// TokenBucket is a simple token bucket that refills at `rate` tokens per
+/// second up to `capacity` tokens.
+///
+/// Tokens could be negative after consuming, which means following
+/// operations need to wait until the debt has been refilled.
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    last: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u64, capacity: u64) -> Self {
+        Self {
+            rate: rate as f64,
+            capacity: capacity as f64,
+            tokens: capacity as f64,
+            last: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.last = now;
+    }
+
+    /// Returns the duration to wait before next operation.
+    fn wait_time(&mut self) -> Option<Duration> {
+        self.refill();
+
+        if self.tokens >= 0.0 {
+            None
+        } else {
+            Some(Duration::from_secs_f64(-self.tokens / self.rate))
+        }
+    }
+
+    fn consume(&mut self, n: usize) {
+        self.refill();
+        self.tokens -= n as f64;
+    }
+
+    /// The max bytes that can be transferred at once.
+    fn burst(&self) -> usize {
+        self.capacity as usize
+    }
+}
+
+type Limiter = Arc<Mutex<TokenBucket>>;
+
+#[derive(Debug, Clone)]
+pub struct ThrottleAccessor<A: Accessor> {
+    inner: A,
+    limiter: Option<Limiter>,
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for ThrottleAccessor<A> {
+    type Inner = A;
+    type Reader = ThrottleWrapper<A::Reader>;
+    type BlockingReader = ThrottleWrapper<A::BlockingReader>;
+    type Writer = ThrottleWrapper<A::Writer>;
+    type BlockingWriter = ThrottleWrapper<A::BlockingWriter>;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner
+            .read(path, args)
+            .await
+            .map(|(rp, r)| (rp, ThrottleWrapper::new(r, self.limiter.clone())))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner
+            .write(path, args)
+            .await
+            .map(|(rp, w)| (rp, ThrottleWrapper::new(w, self.limiter.clone())))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+        self.inner.scan(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner
+            .blocking_read(path, args)
+            .map(|(rp, r)| (rp, ThrottleWrapper::new(r, self.limiter.clone())))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner
+            .blocking_write(path, args)
+            .map(|(rp, w)| (rp, ThrottleWrapper::new(w, self.limiter.clone())))
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner.blocking_list(path, args)
+    }
+
+    fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        self.inner.blocking_scan(path, args)
+    }
+}
+
+pub struct ThrottleWrapper<R> {
+    inner: R,
+
+    limiter: Option<Limiter>,
+    sleep: Option<Pin<Box<tokio::time::Sleep>>>,
+}
+
+impl<R> ThrottleWrapper<R> {
+    fn new(inner: R, limiter: Option<Limiter>) -> Self {
+        Self {
+            inner,
+            limiter,
+            sleep: None,
+        }
+    }
+
+    fn wait_time(&self) -> Option<Duration> {
+        self.limiter
+            .as_ref()
+            .and_then(|l| l.lock().expect("lock must be valid").wait_time())
+    }
+
+    fn consume(&self, n: usize) {
+        if let Some(l) = self.limiter.as_ref() {
+            l.lock().expect("lock must be valid").consume(n)
+        }
+    }
+
+    fn burst(&self) -> Option<usize> {
+        self.limiter
+            .as_ref()
+            .map(|l| l.lock().expect("lock must be valid").burst())
+    }
+
+    /// Poll until the bucket has tokens to consume.
+    fn poll_wait(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        loop {
+            if let Some(sleep) = self.sleep.as_mut() {
+                ready!(sleep.poll_unpin(cx));
+                self.sleep = None;
+            }
+
+            match self.wait_time() {
+                None => return Poll::Ready(()),
+                Some(dur) => self.sleep = Some(Box::pin(tokio::time::sleep(dur))),
+            }
+        }
+    }
+
+    async fn wait(&self) {
+        while let Some(dur) = self.wait_time() {
+            tokio::time::sleep(dur).await
+        }
+    }
+
+    fn blocking_wait(&self) {
+        while let Some(dur) = self.wait_time() {
+            std::thread::sleep(dur)
+        }
+    }
+}
+
+impl<R: oio::Read> oio::Read for ThrottleWrapper<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        ready!(self.poll_wait(cx));
+
+        let size = self.burst().map_or(buf.len(), |v| v.min(buf.len()));
+        let n = ready!(self.inner.poll_read(cx, &mut buf[..size]))?;
+        self.consume(n);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        self.inner.poll_seek(cx, pos)
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        ready!(self.poll_wait(cx));
+
+        let res = ready!(self.inner.poll_next(cx));
+        if let Some(Ok(bs)) = &res {
+            self.consume(bs.len());
+        }
+        Poll::Ready(res)
+    }
+}
+
+impl<R: oio::BlockingRead> oio::BlockingRead for ThrottleWrapper<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.blocking_wait();
+
+        let size = self.burst().map_or(buf.len(), |v| v.min(buf.len()));
+        let n = self.inner.read(&mut buf[..size])?;
+        self.consume(n);
+        Ok(n)
+    }
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner.seek(pos)
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        self.blocking_wait();
+
+        let res = self.inner.next();
+        if let Some(Ok(bs)) = &res {
+            self.consume(bs.len());
+        }
+        res
+    }
+}
+
+#[async_trait]
+impl<R: oio::Write> oio::Write for ThrottleWrapper<R> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.wait().await;
+
+        let size = bs.len();
+        self.inner.write(bs).await?;
+        self.consume(size);
+        Ok(())
+    }
+
+    async fn append(&mut self, bs: Bytes) -> Result<()> {
+        self.wait().await;
+
+        let size = bs.len();
+        self.inner.append(bs).await?;
+        self.consume(size);
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.inner.close().await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+
+    fn state(&self) -> Result<MultipartState> {
+        self.inner.state()
+    }
+}
+
+impl<R: oio::BlockingWrite> oio::BlockingWrite for ThrottleWrapper<R> {
+    fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.blocking_wait();
+
+        let size = bs.len();
+        self.inner.write(bs)?;
+        self.consume(size);
+        Ok(())
+    }
+
+    fn append(&mut self, bs: Bytes) -> Result<()> {
+        self.blocking_wait();
+
+        let size = bs.len();
+        self.inner.append(bs)?;
+        self.consume(size);
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.inner.close()
+    }
+
+    fn abort(&mut self) -> Result<()> {
+        self.inner.abort()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services;
+
+    #[tokio::test]
+    async fn test_throttle_read() {
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(
+                ThrottleLayer::new()
+                    .with_bandwidth(100_000)
+                    .with_burst(1000),
+            )
+            .finish();
+
+        op.write("test", vec![0; 21_000])
+            .await
+            .expect("write must succeed");
+
+        let now = Instant::now();
+        let bs = op.read("test").await.expect("read must succeed");
+        let elapsed = now.elapsed();
+
+        assert_eq!(bs.len(), 21_000);
+        // The bucket is full at the beginning, the remaining 20_000 bytes
+        // need at least 200ms.
+        assert!(
+            elapsed >= Duration::from_millis(190),
+            "read must be throttled, elapsed: {elapsed:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_throttle_larger_than_chunk() {
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(ThrottleLayer::new().with_bandwidth(1024 * 1024))
+            .finish();
+
+        op.write("test", vec![0; 4096])
+            .await
+            .expect("write must succeed");
+
+        let now = Instant::now();
+        let bs = op.read("test").await.expect("read must succeed");
+        let elapsed = now.elapsed();
+
+        assert_eq!(bs.len(), 4096);
+        assert!(
+            elapsed < Duration::from_millis(100),
+            "read must not be throttled, elapsed: {elapsed:?}"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "bandwidth must be larger than 0")]
+    fn test_throttle_zero_bandwidth() {
+        let _ = ThrottleLayer::new().with_bandwidth(0);
+    }
+}
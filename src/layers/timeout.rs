@@ -0,0 +1,529 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::future::Future;
+use std::io::SeekFrom;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::FutureExt;
+
+use crate::ops::*;
+use crate::raw::oio::PageOperation;
+use crate::raw::oio::ReadOperation;
+use crate::raw::oio::WriteOperation;
+use crate::raw::*;
+use crate::*;
+
+/// Add timeout for every operations.
+///
+/// # Notes
+///
+/// This layer supports two kinds of timeout:
+///
+/// - `timeout`: the max duration of an operation like `stat`, `delete` or
+///   the request that opens a reader/writer/pager.
+/// - `io_timeout`: the max idle duration between successive polls of a
+///   reader/writer/pager. It will be reset every time the underlying io
+///   returns ready, so a slow-but-steady stream will not be interrupted.
+///
+/// Errors returned by this layer are `Unexpected` and marked as temporary,
+/// so users can combine it with [`RetryLayer`](super::RetryLayer).
+///
+/// Blocking operations are not affected by this layer.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use anyhow::Result;
+/// use opendal::layers::TimeoutLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+/// use opendal::Scheme;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(
+///         TimeoutLayer::new()
+///             .with_timeout(Duration::from_secs(10))
+///             .with_io_timeout(Duration::from_secs(3)),
+///     )
+///     .finish();
+/// ```
+#[derive(Clone)]
+pub struct TimeoutLayer {
+    timeout: Duration,
+    io_timeout: Duration,
+}
+
+impl Default for TimeoutLayer {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(60),
+            io_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl TimeoutLayer {
+    /// Create a new timeout layer with default settings.
+    ///
+    /// - timeout: 60 seconds
+    /// - io_timeout: 10 seconds
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set timeout of the whole operation.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set io timeout of reader/writer/pager.
+    ///
+    /// This is the max duration between successive successful polls.
+    pub fn with_io_timeout(mut self, io_timeout: Duration) -> Self {
+        self.io_timeout = io_timeout;
+        self
+    }
+}
+
+impl<A: Accessor> Layer<A> for TimeoutLayer {
+    type LayeredAccessor = TimeoutAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        TimeoutAccessor {
+            inner,
+
+            timeout: self.timeout,
+            io_timeout: self.io_timeout,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TimeoutAccessor<A: Accessor> {
+    inner: A,
+
+    timeout: Duration,
+    io_timeout: Duration,
+}
+
+impl<A: Accessor> TimeoutAccessor<A> {
+    async fn timeout<F: Future<Output = Result<T>>, T>(&self, op: Operation, fut: F) -> Result<T> {
+        tokio::time::timeout(self.timeout, fut).await.map_err(|_| {
+            Error::new(ErrorKind::Unexpected, "operation timeout reached")
+                .with_operation(op)
+                .with_context("timeout", format!("{:?}", self.timeout))
+                .set_temporary()
+        })?
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for TimeoutAccessor<A> {
+    type Inner = A;
+    type Reader = TimeoutWrapper<A::Reader>;
+    type BlockingReader = A::BlockingReader;
+    type Writer = TimeoutWrapper<A::Writer>;
+    type BlockingWriter = A::BlockingWriter;
+    type Pager = TimeoutWrapper<A::Pager>;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        self.timeout(Operation::Create, self.inner.create(path, args))
+            .await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.timeout(Operation::Read, self.inner.read(path, args))
+            .map(|v| v.map(|(rp, r)| (rp, TimeoutWrapper::new(r, self.io_timeout))))
+            .await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.timeout(Operation::Write, self.inner.write(path, args))
+            .map(|v| v.map(|(rp, r)| (rp, TimeoutWrapper::new(r, self.io_timeout))))
+            .await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.timeout(Operation::Stat, self.inner.stat(path, args))
+            .await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.timeout(Operation::Delete, self.inner.delete(path, args))
+            .await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.timeout(Operation::List, self.inner.list(path, args))
+            .map(|v| v.map(|(rp, p)| (rp, TimeoutWrapper::new(p, self.io_timeout))))
+            .await
+    }
+
+    async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+        self.timeout(Operation::Scan, self.inner.scan(path, args))
+            .map(|v| v.map(|(rp, p)| (rp, TimeoutWrapper::new(p, self.io_timeout))))
+            .await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        self.timeout(Operation::Batch, self.inner.batch(args)).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner.blocking_list(path, args)
+    }
+
+    fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        self.inner.blocking_scan(path, args)
+    }
+}
+
+pub struct TimeoutWrapper<R> {
+    inner: R,
+
+    io_timeout: Duration,
+    sleep: Option<Pin<Box<tokio::time::Sleep>>>,
+}
+
+impl<R> TimeoutWrapper<R> {
+    fn new(inner: R, io_timeout: Duration) -> Self {
+        Self {
+            inner,
+            io_timeout,
+            sleep: None,
+        }
+    }
+
+    /// Poll the io timer while inner io is pending.
+    ///
+    /// The timer will be started at the first pending poll, and returns
+    /// error if it's fired before inner io is ready.
+    fn poll_io_timeout(&mut self, cx: &mut Context<'_>, op: &'static str) -> Result<()> {
+        let io_timeout = self.io_timeout;
+        let sleep = self
+            .sleep
+            .get_or_insert_with(|| Box::pin(tokio::time::sleep(io_timeout)));
+
+        match sleep.poll_unpin(cx) {
+            Poll::Pending => Ok(()),
+            Poll::Ready(_) => {
+                self.sleep = None;
+                Err(new_io_timeout_error(op, io_timeout))
+            }
+        }
+    }
+
+    async fn io_timeout<F: Future<Output = Result<T>>, T>(
+        io_timeout: Duration,
+        op: &'static str,
+        fut: F,
+    ) -> Result<T> {
+        tokio::time::timeout(io_timeout, fut)
+            .await
+            .map_err(|_| new_io_timeout_error(op, io_timeout))?
+    }
+}
+
+fn new_io_timeout_error(op: &'static str, io_timeout: Duration) -> Error {
+    Error::new(ErrorKind::Unexpected, "io timeout reached")
+        .with_operation(op)
+        .with_context("io_timeout", format!("{io_timeout:?}"))
+        .set_temporary()
+}
+
+impl<R: oio::Read> oio::Read for TimeoutWrapper<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        match self.inner.poll_read(cx, buf) {
+            Poll::Pending => {
+                self.poll_io_timeout(cx, ReadOperation::Read.into_static())?;
+                Poll::Pending
+            }
+            Poll::Ready(v) => {
+                self.sleep = None;
+                Poll::Ready(v)
+            }
+        }
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        match self.inner.poll_seek(cx, pos) {
+            Poll::Pending => {
+                self.poll_io_timeout(cx, ReadOperation::Seek.into_static())?;
+                Poll::Pending
+            }
+            Poll::Ready(v) => {
+                self.sleep = None;
+                Poll::Ready(v)
+            }
+        }
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        match self.inner.poll_next(cx) {
+            Poll::Pending => {
+                if let Err(err) = self.poll_io_timeout(cx, ReadOperation::Next.into_static()) {
+                    return Poll::Ready(Some(Err(err)));
+                }
+                Poll::Pending
+            }
+            Poll::Ready(v) => {
+                self.sleep = None;
+                Poll::Ready(v)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<R: oio::Write> oio::Write for TimeoutWrapper<R> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        Self::io_timeout(
+            self.io_timeout,
+            WriteOperation::Write.into_static(),
+            self.inner.write(bs),
+        )
+        .await
+    }
+
+    async fn append(&mut self, bs: Bytes) -> Result<()> {
+        Self::io_timeout(
+            self.io_timeout,
+            WriteOperation::Append.into_static(),
+            self.inner.append(bs),
+        )
+        .await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        Self::io_timeout(
+            self.io_timeout,
+            WriteOperation::Close.into_static(),
+            self.inner.close(),
+        )
+        .await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        Self::io_timeout(
+            self.io_timeout,
+            WriteOperation::Abort.into_static(),
+            self.inner.abort(),
+        )
+        .await
+    }
+
+    fn state(&self) -> Result<MultipartState> {
+        self.inner.state()
+    }
+}
+
+#[async_trait]
+impl<P: oio::Page> oio::Page for TimeoutWrapper<P> {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        Self::io_timeout(
+            self.io_timeout,
+            PageOperation::Next.into_static(),
+            self.inner.next(),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use futures::AsyncReadExt;
+
+    use super::*;
+
+    #[derive(Default, Clone)]
+    struct MockBuilder {
+        interval: Option<Duration>,
+        hang: bool,
+    }
+
+    impl Builder for MockBuilder {
+        const SCHEME: Scheme = Scheme::Custom("mock");
+        type Accessor = MockService;
+
+        fn from_map(_: HashMap<String, String>) -> Self {
+            Self::default()
+        }
+
+        fn build(&mut self) -> Result<Self::Accessor> {
+            Ok(MockService {
+                interval: self.interval,
+                hang: self.hang,
+            })
+        }
+    }
+
+    #[derive(Debug, Clone, Default)]
+    struct MockService {
+        interval: Option<Duration>,
+        hang: bool,
+    }
+
+    #[async_trait]
+    impl Accessor for MockService {
+        type Reader = MockReader;
+        type BlockingReader = ();
+        type Writer = ();
+        type BlockingWriter = ();
+        type Pager = ();
+        type BlockingPager = ();
+
+        fn info(&self) -> AccessorInfo {
+            let mut am = AccessorInfo::default();
+            am.set_capabilities(AccessorCapability::Read);
+            am.set_hints(AccessorHint::ReadStreamable);
+
+            am
+        }
+
+        async fn read(&self, _: &str, _: OpRead) -> Result<(RpRead, Self::Reader)> {
+            Ok((
+                RpRead::new(5),
+                MockReader {
+                    interval: self.interval,
+                    hang: self.hang,
+                    pos: 0,
+                    sleep: None,
+                },
+            ))
+        }
+    }
+
+    /// MockReader will emit one byte every `interval`, and hangs after the
+    /// first byte if `hang` is true.
+    #[derive(Debug)]
+    struct MockReader {
+        interval: Option<Duration>,
+        hang: bool,
+        pos: usize,
+        sleep: Option<Pin<Box<tokio::time::Sleep>>>,
+    }
+
+    impl oio::Read for MockReader {
+        fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+            if self.pos >= 5 {
+                return Poll::Ready(Ok(0));
+            }
+            if self.hang && self.pos >= 1 {
+                return Poll::Pending;
+            }
+
+            if let Some(interval) = self.interval {
+                let sleep = self
+                    .sleep
+                    .get_or_insert_with(|| Box::pin(tokio::time::sleep(interval)));
+                if sleep.poll_unpin(cx).is_pending() {
+                    return Poll::Pending;
+                }
+                self.sleep = None;
+            }
+
+            buf[0] = b'a';
+            self.pos += 1;
+            Poll::Ready(Ok(1))
+        }
+
+        fn poll_seek(&mut self, _: &mut Context<'_>, _: SeekFrom) -> Poll<Result<u64>> {
+            Poll::Ready(Err(Error::new(
+                ErrorKind::Unsupported,
+                "output reader doesn't support seeking",
+            )))
+        }
+
+        fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+            let mut bs = vec![0; 1];
+            match futures::ready!(self.poll_read(cx, &mut bs)) {
+                Ok(0) => Poll::Ready(None),
+                Ok(v) => Poll::Ready(Some(Ok(Bytes::from(bs[..v].to_vec())))),
+                Err(err) => Poll::Ready(Some(Err(err))),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_io_timeout_on_hang() {
+        let builder = MockBuilder {
+            interval: None,
+            hang: true,
+        };
+        let op = Operator::new(builder)
+            .unwrap()
+            .layer(TimeoutLayer::new().with_io_timeout(Duration::from_millis(100)))
+            .finish();
+
+        let mut r = op.reader("test").await.expect("reader must be created");
+        let mut content = Vec::new();
+        let err = r
+            .read_to_end(&mut content)
+            .await
+            .expect_err("read must timeout");
+
+        assert_eq!(content, b"a");
+        assert!(err.to_string().contains("io timeout"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_io_timeout_on_slow_but_steady() {
+        let builder = MockBuilder {
+            interval: Some(Duration::from_millis(50)),
+            hang: false,
+        };
+        let op = Operator::new(builder)
+            .unwrap()
+            .layer(TimeoutLayer::new().with_io_timeout(Duration::from_millis(200)))
+            .finish();
+
+        let mut r = op.reader("test").await.expect("reader must be created");
+        let mut content = Vec::new();
+        let size = r
+            .read_to_end(&mut content)
+            .await
+            .expect("read must succeed");
+
+        assert_eq!(size, 5);
+        assert_eq!(content, b"aaaaa");
+    }
+}
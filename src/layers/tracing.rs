@@ -116,6 +116,24 @@ use crate::*;
 /// ```
 ///
 /// For real-world usage, please take a look at [`tracing-opentelemetry`](https://crates.io/crates/tracing-opentelemetry).
+///
+/// # Attributes
+///
+/// Every operation span carries the following attributes:
+///
+/// - `otel.kind`: always `client`.
+/// - `opendal.operation`: the operation name like `read`.
+/// - `opendal.path`: the path of this operation (absent for `batch`).
+///
+/// `read` and `write` spans will also record:
+///
+/// - `opendal.range`, `opendal.range.start`, `opendal.range.end`: the
+///   inclusive byte range of read, only known bounds are recorded.
+/// - `opendal.bytes`: the bytes actually transferred, recorded after the
+///   reader or writer has been dropped.
+///
+/// All attributes are evaluated lazily, so there is nearly no cost if the
+/// span is disabled.
 pub struct TracingLayer;
 
 impl<A: Accessor> Layer<A> for TracingLayer {
@@ -150,20 +168,52 @@ impl<A: Accessor> LayeredAccessor for TracingAccessor<A> {
         self.inner.info()
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        level = "debug",
+        skip(self, path),
+        fields(
+            otel.kind = "client",
+            opendal.operation = Operation::Create.into_static(),
+            opendal.path = path,
+        )
+    )]
     async fn create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
         self.inner.create(path, args).await
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        level = "debug",
+        skip(self, path),
+        fields(
+            otel.kind = "client",
+            opendal.operation = Operation::Read.into_static(),
+            opendal.path = path,
+            opendal.range = %args.range(),
+            opendal.range.start = tracing::field::Empty,
+            opendal.range.end = tracing::field::Empty,
+            opendal.bytes = tracing::field::Empty,
+        )
+    )]
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let span = Span::current();
+        record_range(&span, args.range());
+
         self.inner
             .read(path, args)
-            .map(|v| v.map(|(rp, r)| (rp, TracingWrapper::new(Span::current(), r))))
+            .map(|v| v.map(|(rp, r)| (rp, TracingWrapper::new(span, r))))
             .await
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        level = "debug",
+        skip(self, path),
+        fields(
+            otel.kind = "client",
+            opendal.operation = Operation::Write.into_static(),
+            opendal.path = path,
+            opendal.bytes = tracing::field::Empty,
+        )
+    )]
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
         self.inner
             .write(path, args)
@@ -171,17 +221,41 @@ impl<A: Accessor> LayeredAccessor for TracingAccessor<A> {
             .map(|(rp, r)| (rp, TracingWrapper::new(Span::current(), r)))
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        level = "debug",
+        skip(self, path),
+        fields(
+            otel.kind = "client",
+            opendal.operation = Operation::Stat.into_static(),
+            opendal.path = path,
+        )
+    )]
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         self.inner.stat(path, args).await
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        level = "debug",
+        skip(self, path),
+        fields(
+            otel.kind = "client",
+            opendal.operation = Operation::Delete.into_static(),
+            opendal.path = path,
+        )
+    )]
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.inner.delete(path, args).await
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        level = "debug",
+        skip(self, path),
+        fields(
+            otel.kind = "client",
+            opendal.operation = Operation::List.into_static(),
+            opendal.path = path,
+        )
+    )]
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
         self.inner
             .list(path, args)
@@ -189,7 +263,15 @@ impl<A: Accessor> LayeredAccessor for TracingAccessor<A> {
             .await
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        level = "debug",
+        skip(self, path),
+        fields(
+            otel.kind = "client",
+            opendal.operation = Operation::Scan.into_static(),
+            opendal.path = path,
+        )
+    )]
     async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
         self.inner
             .scan(path, args)
@@ -197,53 +279,132 @@ impl<A: Accessor> LayeredAccessor for TracingAccessor<A> {
             .await
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        level = "debug",
+        skip(self, path),
+        fields(
+            otel.kind = "client",
+            opendal.operation = Operation::Presign.into_static(),
+            opendal.path = path,
+        )
+    )]
     fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
         self.inner.presign(path, args)
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        level = "debug",
+        skip(self),
+        fields(
+            otel.kind = "client",
+            opendal.operation = Operation::Batch.into_static(),
+        )
+    )]
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
         self.inner.batch(args).await
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        level = "debug",
+        skip(self, path),
+        fields(
+            otel.kind = "client",
+            opendal.operation = Operation::BlockingCreate.into_static(),
+            opendal.path = path,
+        )
+    )]
     fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
         self.inner.blocking_create(path, args)
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        level = "debug",
+        skip(self, path),
+        fields(
+            otel.kind = "client",
+            opendal.operation = Operation::BlockingRead.into_static(),
+            opendal.path = path,
+            opendal.range = %args.range(),
+            opendal.range.start = tracing::field::Empty,
+            opendal.range.end = tracing::field::Empty,
+            opendal.bytes = tracing::field::Empty,
+        )
+    )]
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let span = Span::current();
+        record_range(&span, args.range());
+
         self.inner
             .blocking_read(path, args)
-            .map(|(rp, r)| (rp, TracingWrapper::new(Span::current(), r)))
+            .map(|(rp, r)| (rp, TracingWrapper::new(span, r)))
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        level = "debug",
+        skip(self, path),
+        fields(
+            otel.kind = "client",
+            opendal.operation = Operation::BlockingWrite.into_static(),
+            opendal.path = path,
+            opendal.bytes = tracing::field::Empty,
+        )
+    )]
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
         self.inner
             .blocking_write(path, args)
             .map(|(rp, r)| (rp, TracingWrapper::new(Span::current(), r)))
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        level = "debug",
+        skip(self, path),
+        fields(
+            otel.kind = "client",
+            opendal.operation = Operation::BlockingStat.into_static(),
+            opendal.path = path,
+        )
+    )]
     fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         self.inner.blocking_stat(path, args)
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        level = "debug",
+        skip(self, path),
+        fields(
+            otel.kind = "client",
+            opendal.operation = Operation::BlockingDelete.into_static(),
+            opendal.path = path,
+        )
+    )]
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.inner.blocking_delete(path, args)
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        level = "debug",
+        skip(self, path),
+        fields(
+            otel.kind = "client",
+            opendal.operation = Operation::BlockingList.into_static(),
+            opendal.path = path,
+        )
+    )]
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
         self.inner
             .blocking_list(path, args)
             .map(|(rp, it)| (rp, TracingWrapper::new(Span::current(), it)))
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
+    #[tracing::instrument(
+        level = "debug",
+        skip(self, path),
+        fields(
+            otel.kind = "client",
+            opendal.operation = Operation::BlockingScan.into_static(),
+            opendal.path = path,
+        )
+    )]
     fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
         self.inner
             .blocking_scan(path, args)
@@ -251,14 +412,46 @@ impl<A: Accessor> LayeredAccessor for TracingAccessor<A> {
     }
 }
 
+/// Record the start and end (inclusive) of range into given span.
+///
+/// Only the known bounds will be recorded, for example, suffix range like
+/// `-1024` has no start.
+fn record_range(span: &Span, range: BytesRange) {
+    if span.is_disabled() {
+        return;
+    }
+
+    if let Some(start) = range.offset() {
+        span.record("opendal.range.start", start);
+
+        if let Some(size) = range.size().filter(|v| *v > 0) {
+            span.record("opendal.range.end", start + size - 1);
+        }
+    }
+}
+
+/// TracingWrapper will record the transferred bytes into `opendal.bytes`
+/// of span after dropped.
 pub struct TracingWrapper<R> {
     span: Span,
     inner: R,
+    bytes: u64,
 }
 
 impl<R> TracingWrapper<R> {
     fn new(span: Span, inner: R) -> Self {
-        Self { span, inner }
+        Self {
+            span,
+            inner,
+            bytes: 0,
+        }
+    }
+}
+
+impl<R> Drop for TracingWrapper<R> {
+    fn drop(&mut self) {
+        // Spans without `opendal.bytes` will ignore this record.
+        self.span.record("opendal.bytes", self.bytes);
     }
 }
 
@@ -268,7 +461,11 @@ impl<R: oio::Read> oio::Read for TracingWrapper<R> {
         level = "trace",
         skip_all)]
     fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
-        self.inner.poll_read(cx, buf)
+        let res = self.inner.poll_read(cx, buf);
+        if let Poll::Ready(Ok(n)) = &res {
+            self.bytes += *n as u64;
+        }
+        res
     }
 
     #[tracing::instrument(
@@ -284,7 +481,11 @@ impl<R: oio::Read> oio::Read for TracingWrapper<R> {
         level = "trace",
         skip_all)]
     fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
-        self.inner.poll_next(cx)
+        let res = self.inner.poll_next(cx);
+        if let Poll::Ready(Some(Ok(bs))) = &res {
+            self.bytes += bs.len() as u64;
+        }
+        res
     }
 }
 
@@ -294,7 +495,9 @@ impl<R: oio::BlockingRead> oio::BlockingRead for TracingWrapper<R> {
         level = "trace",
         skip_all)]
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
-        self.inner.read(buf)
+        let n = self.inner.read(buf)?;
+        self.bytes += n as u64;
+        Ok(n)
     }
 
     #[tracing::instrument(
@@ -310,7 +513,11 @@ impl<R: oio::BlockingRead> oio::BlockingRead for TracingWrapper<R> {
         level = "trace",
         skip_all)]
     fn next(&mut self) -> Option<Result<Bytes>> {
-        self.inner.next()
+        let res = self.inner.next();
+        if let Some(Ok(bs)) = &res {
+            self.bytes += bs.len() as u64;
+        }
+        res
     }
 }
 
@@ -321,7 +528,10 @@ impl<R: oio::Write> oio::Write for TracingWrapper<R> {
         level = "trace",
         skip_all)]
     async fn write(&mut self, bs: Bytes) -> Result<()> {
-        self.inner.write(bs).await
+        let size = bs.len() as u64;
+        self.inner.write(bs).await?;
+        self.bytes += size;
+        Ok(())
     }
 
     #[tracing::instrument(
@@ -329,7 +539,10 @@ impl<R: oio::Write> oio::Write for TracingWrapper<R> {
         level = "trace",
         skip_all)]
     async fn append(&mut self, bs: Bytes) -> Result<()> {
-        self.inner.append(bs).await
+        let size = bs.len() as u64;
+        self.inner.append(bs).await?;
+        self.bytes += size;
+        Ok(())
     }
 
     #[tracing::instrument(
@@ -339,6 +552,18 @@ impl<R: oio::Write> oio::Write for TracingWrapper<R> {
     async fn close(&mut self) -> Result<()> {
         self.inner.close().await
     }
+
+    #[tracing::instrument(
+        parent = &self.span,
+        level = "trace",
+        skip_all)]
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+
+    fn state(&self) -> Result<MultipartState> {
+        self.inner.state()
+    }
 }
 
 impl<R: oio::BlockingWrite> oio::BlockingWrite for TracingWrapper<R> {
@@ -347,7 +572,10 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for TracingWrapper<R> {
         level = "trace",
         skip_all)]
     fn write(&mut self, bs: Bytes) -> Result<()> {
-        self.inner.write(bs)
+        let size = bs.len() as u64;
+        self.inner.write(bs)?;
+        self.bytes += size;
+        Ok(())
     }
 
     #[tracing::instrument(
@@ -355,7 +583,10 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for TracingWrapper<R> {
         level = "trace",
         skip_all)]
     fn append(&mut self, bs: Bytes) -> Result<()> {
-        self.inner.append(bs)
+        let size = bs.len() as u64;
+        self.inner.append(bs)?;
+        self.bytes += size;
+        Ok(())
     }
 
     #[tracing::instrument(
@@ -365,6 +596,14 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for TracingWrapper<R> {
     fn close(&mut self) -> Result<()> {
         self.inner.close()
     }
+
+    #[tracing::instrument(
+        parent = &self.span,
+        level = "trace",
+        skip_all)]
+    fn abort(&mut self) -> Result<()> {
+        self.inner.abort()
+    }
 }
 
 #[async_trait]
@@ -381,3 +620,95 @@ impl<R: oio::BlockingPage> oio::BlockingPage for TracingWrapper<R> {
         self.inner.next()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::fmt::Debug;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    use tracing::field::Field;
+    use tracing::field::Visit;
+    use tracing::span;
+    use tracing::Subscriber;
+    use tracing_subscriber::layer::Context;
+    use tracing_subscriber::prelude::*;
+    use tracing_subscriber::registry::LookupSpan;
+
+    use super::*;
+    use crate::services;
+
+    type Fields = HashMap<String, String>;
+
+    /// Record the fields of all spans by span name.
+    #[derive(Default, Clone)]
+    struct MockLayer {
+        spans: Arc<Mutex<HashMap<String, Fields>>>,
+    }
+
+    struct MockVisitor<'a>(&'a mut Fields);
+
+    impl Visit for MockVisitor<'_> {
+        fn record_u64(&mut self, field: &Field, value: u64) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0
+                .insert(field.name().to_string(), format!("{value:?}"));
+        }
+    }
+
+    impl<S: Subscriber + for<'a> LookupSpan<'a>> tracing_subscriber::Layer<S> for MockLayer {
+        fn on_new_span(&self, attrs: &span::Attributes<'_>, _: &span::Id, _: Context<'_, S>) {
+            let mut spans = self.spans.lock().unwrap();
+            let fields = spans
+                .entry(attrs.metadata().name().to_string())
+                .or_default();
+            attrs.record(&mut MockVisitor(fields));
+        }
+
+        fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+            let name = ctx.span(id).expect("span must exist").name();
+            let mut spans = self.spans.lock().unwrap();
+            let fields = spans.entry(name.to_string()).or_default();
+            values.record(&mut MockVisitor(fields));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tracing_read() {
+        let layer = MockLayer::default();
+        let _guard = tracing_subscriber::registry()
+            .with(layer.clone())
+            .set_default();
+
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .layer(TracingLayer)
+            .finish();
+        op.write("test", "Hello, World!").await.expect("must write");
+
+        let bs = op.range_read("test", 2..7).await.expect("must read");
+        assert_eq!(bs, b"llo, ");
+
+        let spans = layer.spans.lock().unwrap();
+        let read = spans.get("read").expect("read span must exist");
+        assert_eq!(read["otel.kind"], "client");
+        assert_eq!(read["opendal.operation"], "read");
+        assert_eq!(read["opendal.path"], "test");
+        assert_eq!(read["opendal.range"], "2-6");
+        assert_eq!(read["opendal.range.start"], "2");
+        assert_eq!(read["opendal.range.end"], "6");
+        assert_eq!(read["opendal.bytes"], "5");
+
+        let write = spans.get("write").expect("write span must exist");
+        assert_eq!(write["opendal.path"], "test");
+        assert_eq!(write["opendal.bytes"], "13");
+    }
+}
@@ -0,0 +1,351 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use async_trait::async_trait;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::*;
+
+/// Reject unsafe paths before sending requests to underlying services.
+///
+/// # Notes
+///
+/// User supplied paths could escape the root of services like `fs` or
+/// confuse the underlying storage. ValidateLayer checks every path passed
+/// in and returns an error with `ErrorKind::Unexpected` before any I/O.
+///
+/// Leading `/` will be trimmed by [`Operator`] before reaching layers, so
+/// absolute paths are always resolved under the root.
+///
+/// # Policy
+///
+/// - [`ValidateLayer::lenient`] only rejects paths that are unsafe for
+///   all services:
+///   - `..` segments that could traverse out of the root.
+///   - NUL bytes that will truncate paths on `fs`.
+/// - [`ValidateLayer::strict`] (the default) rejects the following paths
+///   as well:
+///   - `.` segments.
+///   - Control characters like `\n` and `\x1b`.
+///   - Components longer than 255 bytes, which is configurable via
+///     [`ValidateLayer::with_max_component_length`].
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::ValidateLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(ValidateLayer::default())
+///     .finish();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ValidateLayer {
+    strict: bool,
+    max_component_length: usize,
+}
+
+impl Default for ValidateLayer {
+    fn default() -> Self {
+        Self::strict()
+    }
+}
+
+impl ValidateLayer {
+    /// Create a ValidateLayer with strict policy.
+    pub fn strict() -> Self {
+        Self {
+            strict: true,
+            max_component_length: 255,
+        }
+    }
+
+    /// Create a ValidateLayer with lenient policy.
+    pub fn lenient() -> Self {
+        Self {
+            strict: false,
+            max_component_length: 255,
+        }
+    }
+
+    /// Set the max length in bytes of every component of path.
+    ///
+    /// Only works with strict policy.
+    ///
+    /// Default: 255
+    pub fn with_max_component_length(mut self, len: usize) -> Self {
+        self.max_component_length = len;
+        self
+    }
+}
+
+impl<A: Accessor> Layer<A> for ValidateLayer {
+    type LayeredAccessor = ValidateAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        ValidateAccessor {
+            inner,
+            strict: self.strict,
+            max_component_length: self.max_component_length,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ValidateAccessor<A: Accessor> {
+    inner: A,
+    strict: bool,
+    max_component_length: usize,
+}
+
+impl<A: Accessor> ValidateAccessor<A> {
+    /// Check given path against the policy.
+    fn validate(&self, op: Operation, path: &str) -> Result<()> {
+        let reason = if path.contains('\0') {
+            Some("path must not contain NUL")
+        } else if path.split('/').any(|v| v == "..") {
+            Some("path must not contain `..`")
+        } else if !self.strict {
+            None
+        } else if path.split('/').any(|v| v == ".") {
+            Some("path must not contain `.`")
+        } else if path.chars().any(|c| c.is_control()) {
+            Some("path must not contain control characters")
+        } else if path.split('/').any(|v| v.len() > self.max_component_length) {
+            Some("path contains overlong component")
+        } else {
+            None
+        };
+
+        match reason {
+            None => Ok(()),
+            Some(reason) => Err(Error::new(ErrorKind::Unexpected, reason)
+                .with_operation(op)
+                .with_context("service", self.inner.info().scheme().into_static())
+                .with_context("path", path.escape_debug().to_string())),
+        }
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for ValidateAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        self.validate(Operation::Create, path)?;
+        self.inner.create(path, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.validate(Operation::Read, path)?;
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.validate(Operation::Write, path)?;
+        self.inner.write(path, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.validate(Operation::Stat, path)?;
+        self.inner.stat(path, args).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.validate(Operation::Delete, path)?;
+        self.inner.delete(path, args).await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.validate(Operation::Copy, from)?;
+        self.validate(Operation::Copy, to)?;
+        self.inner.copy(from, to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.validate(Operation::Rename, from)?;
+        self.validate(Operation::Rename, to)?;
+        self.inner.rename(from, to, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.validate(Operation::List, path)?;
+        self.inner.list(path, args).await
+    }
+
+    async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+        self.validate(Operation::Scan, path)?;
+        self.inner.scan(path, args).await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        match args.operation() {
+            BatchOperations::Delete(ops) => {
+                for (path, _) in ops {
+                    self.validate(Operation::Batch, path)?;
+                }
+            }
+            BatchOperations::Write(ops) => {
+                for (path, _, _) in ops {
+                    self.validate(Operation::Batch, path)?;
+                }
+            }
+        }
+
+        self.inner.batch(args).await
+    }
+
+    fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.validate(Operation::Presign, path)?;
+        self.inner.presign(path, args)
+    }
+
+    fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        self.validate(Operation::BlockingCreate, path)?;
+        self.inner.blocking_create(path, args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.validate(Operation::BlockingRead, path)?;
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.validate(Operation::BlockingWrite, path)?;
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.validate(Operation::BlockingStat, path)?;
+        self.inner.blocking_stat(path, args)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.validate(Operation::BlockingDelete, path)?;
+        self.inner.blocking_delete(path, args)
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.validate(Operation::BlockingCopy, from)?;
+        self.validate(Operation::BlockingCopy, to)?;
+        self.inner.blocking_copy(from, to, args)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.validate(Operation::BlockingRename, from)?;
+        self.validate(Operation::BlockingRename, to)?;
+        self.inner.blocking_rename(from, to, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.validate(Operation::BlockingList, path)?;
+        self.inner.blocking_list(path, args)
+    }
+
+    fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        self.validate(Operation::BlockingScan, path)?;
+        self.inner.blocking_scan(path, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services;
+
+    fn new_operator(layer: ValidateLayer) -> Operator {
+        Operator::new(services::Memory::default())
+            .expect("must init")
+            .layer(layer)
+            .finish()
+    }
+
+    #[tokio::test]
+    async fn test_validate_traversal() {
+        let op = new_operator(ValidateLayer::lenient());
+
+        let err = op
+            .write("../etc/passwd", "Hello, World!")
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        let err = op.read("dir/../../foo").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        let err = op.copy("foo", "../foo").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+
+    #[tokio::test]
+    async fn test_validate_nul() {
+        let op = new_operator(ValidateLayer::lenient());
+
+        let err = op
+            .write("foo\0.txt", "Hello, World!")
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(!op.exists("foo").await.expect("must check"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_policy() {
+        let strict = new_operator(ValidateLayer::strict().with_max_component_length(8));
+        let lenient = new_operator(ValidateLayer::lenient().with_max_component_length(8));
+
+        for path in ["foo\nbar", "./foo", "overlong-name"] {
+            let err = strict
+                .write(path, "Hello, World!")
+                .await
+                .expect_err("must fail");
+            assert_eq!(err.kind(), ErrorKind::Unexpected, "{path:?}");
+
+            lenient
+                .write(path, "Hello, World!")
+                .await
+                .expect("must write");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validate_nested_path() {
+        let op = new_operator(ValidateLayer::default());
+
+        op.write("dir/sub.dir/foo.txt", "Hello, World!")
+            .await
+            .expect("must write");
+        assert_eq!(
+            op.read("/dir//sub.dir/foo.txt").await.expect("must read"),
+            b"Hello, World!"
+        );
+        op.delete("dir/sub.dir/foo.txt").await.expect("must delete");
+    }
+}
@@ -0,0 +1,414 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use bytes::BytesMut;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::*;
+
+/// Add write buffer to coalesce small appends.
+///
+/// # Notes
+///
+/// Every [`Writer::append`] will be sent to underlying services directly,
+/// which could be slow for services that have high per-request overhead
+/// (or even fail for services like s3 that require a minimum part size).
+///
+/// WriteBufferLayer will accumulate appended bytes in memory until
+/// `buffer_size` is reached, and then flush them to the underlying writer
+/// as a whole. The remaining bytes will be flushed while closing.
+///
+/// - Order of appended bytes is preserved.
+/// - Flush error will be returned by the `append` or `close` that triggers
+///   it, the buffered bytes are kept so that callers can retry.
+/// - Buffered bytes will be dropped on `abort`.
+/// - `write` will bypass the buffer since it already carries the whole
+///   content.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::WriteBufferLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+/// use opendal::Scheme;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(WriteBufferLayer::new(8 * 1024 * 1024))
+///     .finish();
+/// ```
+///
+/// [`Writer::append`]: crate::Writer::append
+#[derive(Debug, Clone)]
+pub struct WriteBufferLayer {
+    buffer_size: usize,
+}
+
+impl WriteBufferLayer {
+    /// Create a new WriteBufferLayer with given buffer size in bytes.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if input buffer size is `0`.
+    pub fn new(buffer_size: usize) -> Self {
+        assert!(buffer_size > 0, "buffer size must be larger than 0");
+
+        Self { buffer_size }
+    }
+}
+
+impl<A: Accessor> Layer<A> for WriteBufferLayer {
+    type LayeredAccessor = WriteBufferAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        WriteBufferAccessor {
+            inner,
+            buffer_size: self.buffer_size,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WriteBufferAccessor<A: Accessor> {
+    inner: A,
+    buffer_size: usize,
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for WriteBufferAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = WriteBufferWriter<A::Writer>;
+    type BlockingWriter = WriteBufferWriter<A::BlockingWriter>;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner
+            .write(path, args)
+            .await
+            .map(|(rp, w)| (rp, WriteBufferWriter::new(w, self.buffer_size)))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+        self.inner.scan(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner
+            .blocking_write(path, args)
+            .map(|(rp, w)| (rp, WriteBufferWriter::new(w, self.buffer_size)))
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner.blocking_list(path, args)
+    }
+
+    fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        self.inner.blocking_scan(path, args)
+    }
+}
+
+pub struct WriteBufferWriter<W> {
+    inner: W,
+
+    buffer_size: usize,
+    buf: BytesMut,
+}
+
+impl<W> WriteBufferWriter<W> {
+    fn new(inner: W, buffer_size: usize) -> Self {
+        Self {
+            inner,
+            buffer_size,
+            buf: BytesMut::new(),
+        }
+    }
+
+    /// Buffer input bytes, returns the bytes that need to be flushed if
+    /// buffer is full.
+    fn buffer(&mut self, bs: Bytes) -> Option<Bytes> {
+        self.buf.extend_from_slice(&bs);
+
+        if self.buf.len() >= self.buffer_size {
+            Some(self.buf.split().freeze())
+        } else {
+            None
+        }
+    }
+
+    /// Put the bytes back into buffer if flush failed.
+    ///
+    /// The buffer must be empty here since we always flush all of it.
+    fn restore(&mut self, bs: Bytes) {
+        debug_assert!(self.buf.is_empty(), "buffer must be empty while restore");
+
+        self.buf.extend_from_slice(&bs);
+    }
+}
+
+#[async_trait]
+impl<W: oio::Write> oio::Write for WriteBufferWriter<W> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.inner.write(bs).await
+    }
+
+    async fn append(&mut self, bs: Bytes) -> Result<()> {
+        if let Some(bs) = self.buffer(bs) {
+            if let Err(err) = self.inner.append(bs.clone()).await {
+                self.restore(bs);
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        if !self.buf.is_empty() {
+            let bs = self.buf.split().freeze();
+            if let Err(err) = self.inner.append(bs.clone()).await {
+                self.restore(bs);
+                return Err(err);
+            }
+        }
+
+        self.inner.close().await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.buf.clear();
+        self.inner.abort().await
+    }
+
+    fn state(&self) -> Result<MultipartState> {
+        // Only flushed parts are recorded in state, buffered bytes will
+        // be lost if users resume from it.
+        self.inner.state()
+    }
+}
+
+impl<W: oio::BlockingWrite> oio::BlockingWrite for WriteBufferWriter<W> {
+    fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.inner.write(bs)
+    }
+
+    fn append(&mut self, bs: Bytes) -> Result<()> {
+        if let Some(bs) = self.buffer(bs) {
+            if let Err(err) = self.inner.append(bs.clone()) {
+                self.restore(bs);
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<()> {
+        if !self.buf.is_empty() {
+            let bs = self.buf.split().freeze();
+            if let Err(err) = self.inner.append(bs.clone()) {
+                self.restore(bs);
+                return Err(err);
+            }
+        }
+
+        self.inner.close()
+    }
+
+    fn abort(&mut self) -> Result<()> {
+        self.buf.clear();
+        self.inner.abort()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[derive(Default, Clone)]
+    struct MockBuilder {
+        appended: Arc<Mutex<Vec<Bytes>>>,
+        fail_after: Option<usize>,
+    }
+
+    impl Builder for MockBuilder {
+        const SCHEME: Scheme = Scheme::Custom("mock");
+        type Accessor = MockService;
+
+        fn from_map(_: HashMap<String, String>) -> Self {
+            Self::default()
+        }
+
+        fn build(&mut self) -> Result<Self::Accessor> {
+            Ok(MockService {
+                appended: self.appended.clone(),
+                fail_after: self.fail_after,
+            })
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    struct MockService {
+        appended: Arc<Mutex<Vec<Bytes>>>,
+        fail_after: Option<usize>,
+    }
+
+    #[async_trait]
+    impl Accessor for MockService {
+        type Reader = ();
+        type BlockingReader = ();
+        type Writer = MockWriter;
+        type BlockingWriter = ();
+        type Pager = ();
+        type BlockingPager = ();
+
+        fn info(&self) -> AccessorInfo {
+            let mut am = AccessorInfo::default();
+            am.set_capabilities(AccessorCapability::Write);
+
+            am
+        }
+
+        async fn write(&self, _: &str, _: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+            Ok((
+                RpWrite::default(),
+                MockWriter {
+                    appended: self.appended.clone(),
+                    fail_after: self.fail_after,
+                },
+            ))
+        }
+    }
+
+    struct MockWriter {
+        appended: Arc<Mutex<Vec<Bytes>>>,
+        fail_after: Option<usize>,
+    }
+
+    #[async_trait]
+    impl oio::Write for MockWriter {
+        async fn write(&mut self, bs: Bytes) -> Result<()> {
+            self.appended.lock().unwrap().push(bs);
+            Ok(())
+        }
+
+        async fn append(&mut self, bs: Bytes) -> Result<()> {
+            let mut appended = self.appended.lock().unwrap();
+            if Some(appended.len()) == self.fail_after {
+                return Err(Error::new(ErrorKind::Unexpected, "append failed"));
+            }
+
+            appended.push(bs);
+            Ok(())
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn abort(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_buffer_coalesce_appends() {
+        let builder = MockBuilder::default();
+        let op = Operator::new(builder.clone())
+            .unwrap()
+            .layer(WriteBufferLayer::new(64 * 1024))
+            .finish();
+
+        let mut expected = Vec::new();
+        let mut w = op.writer("test").await.unwrap();
+        for i in 0..1000 {
+            let chunk = vec![(i % 256) as u8; 100 + i % 100];
+            expected.extend_from_slice(&chunk);
+            w.append(chunk).await.expect("append must succeed");
+        }
+        w.close().await.expect("close must succeed");
+
+        let appended = builder.appended.lock().unwrap();
+        // 1000 chunks with about 150KiB in total, so only 3 appends.
+        assert_eq!(appended.len(), 3);
+        assert!(appended[..2].iter().all(|bs| bs.len() >= 64 * 1024));
+        assert_eq!(appended.concat(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_write_buffer_flush_error() {
+        let builder = MockBuilder {
+            fail_after: Some(1),
+            ..Default::default()
+        };
+        let op = Operator::new(builder.clone())
+            .unwrap()
+            .layer(WriteBufferLayer::new(10))
+            .finish();
+
+        let mut w = op.writer("test").await.unwrap();
+        w.append(vec![0; 10])
+            .await
+            .expect("first flush must succeed");
+        w.append(vec![1; 5]).await.expect("must be buffered");
+        let err = w
+            .append(vec![2; 5])
+            .await
+            .expect_err("second flush must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        // Buffered bytes will be flushed again while closing.
+        let err = w.close().await.expect_err("close must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        let appended = builder.appended.lock().unwrap();
+        assert_eq!(appended.len(), 1);
+        assert_eq!(appended[0], vec![0; 10]);
+    }
+
+    #[test]
+    #[should_panic(expected = "buffer size must be larger than 0")]
+    fn test_write_buffer_zero_size() {
+        let _ = WriteBufferLayer::new(0);
+    }
+}
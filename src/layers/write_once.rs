@@ -0,0 +1,424 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::*;
+
+/// Make sure objects are never overwritten once they have been written.
+///
+/// Writes to an existing path will fail with [`ErrorKind::AlreadyExists`].
+/// This is useful for write-once-read-many (WORM) storage.
+///
+/// # Notes
+///
+/// This layer will:
+///
+/// - send writes with `If-None-Match: *` if the service supports it (check
+///   [`OperatorInfo::can_write_with_if_none_match`]). The service will reject
+///   the write if the path exists, so it's race-safe.
+/// - `stat` the path before writing otherwise.
+///
+/// The fallback is NOT race-safe: another writer could create the path
+/// between the `stat` and the `write` (TOCTOU), and the latter write will
+/// overwrite it. Please use services with create-only semantics if you need
+/// strict guarantees.
+///
+/// Append, `create` of files, and the target of `copy` and `rename` are
+/// checked via `stat`. Batch write will be disabled so that every object is
+/// checked.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::WriteOnceLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(WriteOnceLayer)
+///     .finish();
+/// ```
+#[derive(Debug, Copy, Clone, Default)]
+pub struct WriteOnceLayer;
+
+impl<A: Accessor> Layer<A> for WriteOnceLayer {
+    type LayeredAccessor = WriteOnceAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        let create_only = inner
+            .info()
+            .capabilities()
+            .contains(AccessorCapability::WriteWithIfNoneMatch);
+
+        WriteOnceAccessor { inner, create_only }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WriteOnceAccessor<A: Accessor> {
+    inner: A,
+
+    /// Whether the service supports create-only write via `If-None-Match: *`.
+    create_only: bool,
+}
+
+fn new_already_exists_error(op: Operation, path: &str) -> Error {
+    Error::new(
+        ErrorKind::AlreadyExists,
+        "path already exists and can't be overwritten",
+    )
+    .with_operation(op)
+    .with_context("path", path)
+}
+
+impl<A: Accessor> WriteOnceAccessor<A> {
+    /// Check if we can write with `If-None-Match: *`.
+    fn use_create_only(&self, args: &OpWrite) -> bool {
+        self.create_only && !args.append_existing() && args.if_match().is_none()
+    }
+
+    /// Make sure given path doesn't exist.
+    async fn ensure_not_exist(&self, op: Operation, path: &str) -> Result<()> {
+        match self.inner.stat(path, OpStat::new()).await {
+            Ok(_) => Err(new_already_exists_error(op, path)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn blocking_ensure_not_exist(&self, op: Operation, path: &str) -> Result<()> {
+        match self.inner.blocking_stat(path, OpStat::new()) {
+            Ok(_) => Err(new_already_exists_error(op, path)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[async_trait]
+impl<A: Accessor> LayeredAccessor for WriteOnceAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = WriteOnceWriter<A::Writer>;
+    type BlockingWriter = WriteOnceWriter<A::BlockingWriter>;
+    type Pager = A::Pager;
+    type BlockingPager = A::BlockingPager;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn metadata(&self) -> AccessorInfo {
+        let mut meta = self.inner.info();
+        meta.set_capabilities(meta.capabilities() - AccessorCapability::BatchWrite);
+        meta
+    }
+
+    async fn create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        if args.mode() == EntryMode::FILE {
+            self.ensure_not_exist(Operation::Create, path).await?;
+        }
+
+        self.inner.create(path, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let create_only = self.use_create_only(&args);
+        let args = if create_only {
+            args.with_if_none_match("*")
+        } else {
+            self.ensure_not_exist(Operation::Write, path).await?;
+            args
+        };
+
+        self.inner
+            .write(path, args)
+            .await
+            .map(|(rp, w)| (rp, WriteOnceWriter::new(w, path, create_only)))
+            .map_err(|err| map_condition_error(err, path, create_only))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.ensure_not_exist(Operation::Copy, to).await?;
+        self.inner.copy(from, to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.ensure_not_exist(Operation::Rename, to).await?;
+        self.inner.rename(from, to, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+        self.inner.scan(path, args).await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        if let BatchOperations::Write(_) = args.operation() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "batch write is not supported by write once layer",
+            )
+            .with_operation(Operation::Batch));
+        }
+
+        self.inner.batch(args).await
+    }
+
+    fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        if args.mode() == EntryMode::FILE {
+            self.blocking_ensure_not_exist(Operation::BlockingCreate, path)?;
+        }
+
+        self.inner.blocking_create(path, args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let create_only = self.use_create_only(&args);
+        let args = if create_only {
+            args.with_if_none_match("*")
+        } else {
+            self.blocking_ensure_not_exist(Operation::BlockingWrite, path)?;
+            args
+        };
+
+        self.inner
+            .blocking_write(path, args)
+            .map(|(rp, w)| (rp, WriteOnceWriter::new(w, path, create_only)))
+            .map_err(|err| map_condition_error(err, path, create_only))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.blocking_ensure_not_exist(Operation::BlockingCopy, to)?;
+        self.inner.blocking_copy(from, to, args)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.blocking_ensure_not_exist(Operation::BlockingRename, to)?;
+        self.inner.blocking_rename(from, to, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.inner.blocking_list(path, args)
+    }
+
+    fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        self.inner.blocking_scan(path, args)
+    }
+}
+
+/// Convert `ConditionNotMatch` returned by create-only write into `AlreadyExists`.
+fn map_condition_error(err: Error, path: &str, create_only: bool) -> Error {
+    if create_only && err.kind() == ErrorKind::ConditionNotMatch {
+        new_already_exists_error(Operation::Write, path).set_source(err)
+    } else {
+        err
+    }
+}
+
+pub struct WriteOnceWriter<W> {
+    inner: W,
+    path: String,
+    create_only: bool,
+}
+
+impl<W> WriteOnceWriter<W> {
+    fn new(inner: W, path: &str, create_only: bool) -> Self {
+        Self {
+            inner,
+            path: path.to_string(),
+            create_only,
+        }
+    }
+
+    fn map_err<T>(&self, res: Result<T>) -> Result<T> {
+        res.map_err(|err| map_condition_error(err, &self.path, self.create_only))
+    }
+}
+
+#[async_trait]
+impl<W: oio::Write> oio::Write for WriteOnceWriter<W> {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        let res = self.inner.write(bs).await;
+        self.map_err(res)
+    }
+
+    async fn append(&mut self, bs: Bytes) -> Result<()> {
+        let res = self.inner.append(bs).await;
+        self.map_err(res)
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        let res = self.inner.close().await;
+        self.map_err(res)
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+
+    fn state(&self) -> Result<MultipartState> {
+        self.inner.state()
+    }
+}
+
+impl<W: oio::BlockingWrite> oio::BlockingWrite for WriteOnceWriter<W> {
+    fn write(&mut self, bs: Bytes) -> Result<()> {
+        let res = self.inner.write(bs);
+        self.map_err(res)
+    }
+
+    fn append(&mut self, bs: Bytes) -> Result<()> {
+        let res = self.inner.append(bs);
+        self.map_err(res)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        let res = self.inner.close();
+        self.map_err(res)
+    }
+
+    fn abort(&mut self) -> Result<()> {
+        self.inner.abort()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+    use wiremock::matchers::header;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+
+    use super::*;
+    use crate::services;
+
+    fn new_memory_operator() -> Operator {
+        Operator::new(services::Memory::default())
+            .expect("must init")
+            .layer(WriteOnceLayer)
+            .finish()
+    }
+
+    #[tokio::test]
+    async fn test_write_once() {
+        let op = new_memory_operator();
+
+        op.write("test", "Hello")
+            .await
+            .expect("first write must succeed");
+        let err = op.write("test", "World").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+        assert_eq!(op.read("test").await.unwrap(), b"Hello");
+
+        let err = op.blocking().write("test", "World").expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+        assert_eq!(op.read("test").await.unwrap(), b"Hello");
+
+        // Other paths are not affected.
+        op.write("other", "World").await.expect("must succeed");
+        assert_eq!(op.read("other").await.unwrap(), b"World");
+    }
+
+    #[tokio::test]
+    async fn test_write_once_create_and_write_many() {
+        let op = new_memory_operator();
+        op.write("test", "Hello").await.unwrap();
+
+        op.create_dir("dir/")
+            .await
+            .expect("create dir must succeed");
+        op.create_dir("dir/")
+            .await
+            .expect("create dir is idempotent");
+
+        let results = op
+            .write_many(stream::iter(vec![
+                ("test".to_string(), Bytes::from("World")),
+                ("new".to_string(), Bytes::from("World")),
+            ]))
+            .await
+            .expect("must succeed");
+        for (path, res) in results {
+            match path.as_str() {
+                "test" => assert_eq!(res.unwrap_err().kind(), ErrorKind::AlreadyExists),
+                _ => assert!(res.is_ok()),
+            }
+        }
+        assert_eq!(op.read("test").await.unwrap(), b"Hello");
+    }
+
+    #[tokio::test]
+    async fn test_write_once_with_create_only() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/test/exists"))
+            .and(header("if-none-match", "*"))
+            .respond_with(ResponseTemplate::new(412))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/test/new"))
+            .and(header("if-none-match", "*"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut builder = services::S3::default();
+        builder
+            .bucket("test")
+            .endpoint(&server.uri())
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key")
+            .disable_config_load();
+        let op = Operator::new(builder)
+            .expect("must init")
+            .layer(WriteOnceLayer)
+            .finish();
+
+        op.write("new", "Hello").await.expect("must succeed");
+        let err = op.write("exists", "Hello").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+
+        // Create-only write must not stat first.
+        let requests = server.received_requests().await.expect("must recorded");
+        assert!(requests.iter().all(|r| r.method.to_string() == "PUT"));
+    }
+}
@@ -166,6 +166,41 @@ pub trait Accessor: Send + Sync + Debug + Unpin + 'static {
         ))
     }
 
+    /// Invoke the `copy` operation from the specified path to another.
+    ///
+    /// Require [`AccessorCapability::Copy`]
+    ///
+    /// # Behavior
+    ///
+    /// - `from` and `to` MUST be file paths, DON'T NEED to check mode.
+    /// - Copy SHOULD overwrite `to` if it already exists.
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let (_, _, _) = (from, to, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `rename` operation from the specified path to another.
+    ///
+    /// Require [`AccessorCapability::Rename`]
+    ///
+    /// # Behavior
+    ///
+    /// - `from` and `to` MUST be file paths, DON'T NEED to check mode.
+    /// - Rename SHOULD be atomic and overwrite `to` if it already exists.
+    /// - Services without native rename MUST NOT emulate it via copy and delete.
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let (_, _, _) = (from, to, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
     /// Invoke the `list` operation on the specified path.
     ///
     /// Require [`AccessorCapability::List`]
@@ -291,6 +326,34 @@ pub trait Accessor: Send + Sync + Debug + Unpin + 'static {
         ))
     }
 
+    /// Invoke the `blocking_copy` operation from the specified path to another.
+    ///
+    /// This operation is the blocking version of [`Accessor::copy`]
+    ///
+    /// Require [`AccessorCapability::Copy`] and [`AccessorCapability::Blocking`]
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let (_, _, _) = (from, to, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `blocking_rename` operation from the specified path to another.
+    ///
+    /// This operation is the blocking version of [`Accessor::rename`]
+    ///
+    /// Require [`AccessorCapability::Rename`] and [`AccessorCapability::Blocking`]
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let (_, _, _) = (from, to, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
     /// Invoke the `blocking_list` operation on the specified path.
     ///
     /// This operation is the blocking version of [`Accessor::list`]
@@ -339,6 +402,7 @@ impl Accessor for () {
             name: "dummy".to_string(),
             capabilities: None.into(),
             hints: None.into(),
+            list_metakeys: None.into(),
         }
     }
 }
@@ -374,6 +438,12 @@ impl<T: Accessor + ?Sized> Accessor for Arc<T> {
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.as_ref().delete(path, args).await
     }
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.as_ref().copy(from, to, args).await
+    }
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.as_ref().rename(from, to, args).await
+    }
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
         self.as_ref().list(path, args).await
     }
@@ -404,6 +474,12 @@ impl<T: Accessor + ?Sized> Accessor for Arc<T> {
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.as_ref().blocking_delete(path, args)
     }
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.as_ref().blocking_copy(from, to, args)
+    }
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.as_ref().blocking_rename(from, to, args)
+    }
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
         self.as_ref().blocking_list(path, args)
     }
@@ -433,6 +509,7 @@ pub struct AccessorInfo {
     name: String,
     capabilities: FlagSet<AccessorCapability>,
     hints: FlagSet<AccessorHint>,
+    list_metakeys: FlagSet<Metakey>,
 }
 
 impl AccessorInfo {
@@ -500,6 +577,19 @@ impl AccessorInfo {
         self.hints = hints.into();
         self
     }
+
+    /// Get the metakeys that backend's list response carries for file entries.
+    ///
+    /// `Metakey::Mode` is always included.
+    pub fn list_metakeys(&self) -> FlagSet<Metakey> {
+        self.list_metakeys | Metakey::Mode
+    }
+
+    /// Set the metakeys that backend's list response carries for file entries.
+    pub fn set_list_metakeys(&mut self, metakeys: impl Into<FlagSet<Metakey>>) -> &mut Self {
+        self.list_metakeys = metakeys.into();
+        self
+    }
 }
 
 flags! {
@@ -509,8 +599,14 @@ flags! {
         Read,
         /// Add this capability if service supports `write` and `delete`
         Write,
+        /// Add this capability if service supports creating dirs via `create`
+        CreateDir,
         /// Add this capability if service supports `list`
         List,
+        /// Add this capability if service supports `copy`
+        Copy,
+        /// Add this capability if service supports `rename`
+        Rename,
         /// Add this capability if service supports `scan`
         Scan,
         /// Add this capability if service supports `presign`
@@ -519,6 +615,56 @@ flags! {
         Blocking,
         /// Add this capability if service supports `batch`
         Batch,
+        /// Add this capability if service supports `batch` with
+        /// `BatchOperations::Write`
+        BatchWrite,
+        /// Add this capability if service supports `stat` with conditions
+        /// like `if_none_match` and `if_modified_since`
+        ConditionalStat,
+        /// Add this capability if service supports `write` with `if_match`
+        WriteWithIfMatch,
+        /// Add this capability if service supports `write` with `if_none_match`
+        WriteWithIfNoneMatch,
+        /// Add this capability if service supports resuming multipart upload
+        /// via `OpWrite::with_resume`
+        ResumableWrite,
+        /// Add this capability if service supports `write` with
+        /// `ChecksumAlgorithm::Md5` via `OpWrite::with_checksum`
+        WriteWithContentMd5,
+        /// Add this capability if service supports appending content to
+        /// an existing object via `Operator::append`
+        WriteCanAppend,
+        /// Add this capability if service supports `list` with
+        /// `OpList::with_start_after`
+        ListWithStartAfter,
+        /// Add this capability if service supports `write` and `read` with
+        /// `OpWrite::with_encryption`
+        WriteWithEncryption,
+        /// Add this capability if service supports `read` with
+        /// `OpRead::with_header`
+        ReadWithHeader,
+        /// Add this capability if service supports `write` with
+        /// `OpWrite::with_header`
+        WriteWithHeader,
+        /// Add this capability if service supports `read` and `stat` with
+        /// `OpRead::with_version` and `OpStat::with_version`
+        ReadWithVersion,
+        /// Add this capability if service supports `copy` with
+        /// `OpCopy::with_if_none_match`
+        CopyWithIfNoneMatch,
+        /// Add this capability if service supports `write` with
+        /// `OpWrite::with_concurrent`
+        WriteWithConcurrent,
+        /// Add this capability if service supports `write` with
+        /// `OpWrite::with_if_unmodified_since`
+        WriteWithIfUnmodifiedSince,
+        /// Add this capability if service supports `delete` with
+        /// `OpDelete::with_if_unmodified_since`
+        DeleteWithIfUnmodifiedSince,
+        /// Add this capability if service can tell whether the path existed
+        /// from the `delete` response, and returns `NotFound` for `delete`
+        /// with `OpDelete::with_strict` if not
+        DeleteWithStrict,
     }
 }
 
@@ -537,5 +683,11 @@ flags! {
         ///
         /// It's better to use stream to reading data.
         ReadStreamable,
+        /// List ordered pages means pages returned by list are in
+        /// lexicographic order.
+        ///
+        /// Entries within one page may be not sorted, but they are all
+        /// less than the entries of next page.
+        ListOrderedPages,
     }
 }
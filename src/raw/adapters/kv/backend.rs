@@ -62,7 +62,15 @@ impl<S: Adapter> Accessor for Backend<S> {
 
     fn info(&self) -> AccessorInfo {
         let mut am: AccessorInfo = self.kv.metadata().into();
+        // List is built on scan by `CompleteLayer` which supports start
+        // after for us.
+        //
+        // Dirs are stored as empty values with trailing `/`.
+        let cap = am.capabilities()
+            | AccessorCapability::CreateDir
+            | AccessorCapability::ListWithStartAfter;
         am.set_root(&self.root)
+            .set_capabilities(cap)
             .set_hints(AccessorHint::ReadStreamable | AccessorHint::ReadSeekable);
 
         am
@@ -86,6 +94,12 @@ impl<S: Adapter> Accessor for Backend<S> {
 
         let bs = match self.kv.get(&p).await? {
             Some(bs) => bs,
+            None if self.kv.get(&format!("{p}/")).await?.is_some() => {
+                return Err(Error::new(
+                    ErrorKind::IsADirectory,
+                    "given path is a directory",
+                ))
+            }
             None => return Err(Error::new(ErrorKind::NotFound, "kv doesn't have this path")),
         };
 
@@ -100,6 +114,12 @@ impl<S: Adapter> Accessor for Backend<S> {
 
         let bs = match self.kv.blocking_get(&p)? {
             Some(bs) => bs,
+            None if self.kv.blocking_get(&format!("{p}/"))?.is_some() => {
+                return Err(Error::new(
+                    ErrorKind::IsADirectory,
+                    "given path is a directory",
+                ))
+            }
             None => return Err(Error::new(ErrorKind::NotFound, "kv doesn't have this path")),
         };
 
@@ -168,6 +188,14 @@ impl<S: Adapter> Accessor for Backend<S> {
     async fn scan(&self, path: &str, _: OpScan) -> Result<(RpScan, Self::Pager)> {
         let p = build_abs_path(&self.root, path);
         let res = self.kv.scan(&p).await?;
+        // Scanning a file path returns nothing, check it only in this case.
+        if res.is_empty() && !p.is_empty() && self.kv.get(p.trim_end_matches('/')).await?.is_some()
+        {
+            return Err(Error::new(
+                ErrorKind::NotADirectory,
+                "given path is not a directory",
+            ));
+        }
         let pager = KvPager::new(&self.root, res);
 
         Ok((RpScan::default(), pager))
@@ -176,6 +204,16 @@ impl<S: Adapter> Accessor for Backend<S> {
     fn blocking_scan(&self, path: &str, _: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
         let p = build_abs_path(&self.root, path);
         let res = self.kv.blocking_scan(&p)?;
+        // Scanning a file path returns nothing, check it only in this case.
+        if res.is_empty()
+            && !p.is_empty()
+            && self.kv.blocking_get(p.trim_end_matches('/'))?.is_some()
+        {
+            return Err(Error::new(
+                ErrorKind::NotADirectory,
+                "given path is not a directory",
+            ));
+        }
         let pager = KvPager::new(&self.root, res);
 
         Ok((RpScan::default(), pager))
@@ -196,7 +234,7 @@ where
                 bs
             }
             (Some(offset), None) => bs.split_off(offset as usize),
-            (None, Some(size)) => bs.split_off(bs.len() - size as usize),
+            (None, Some(size)) => bs.split_off(bs.len().saturating_sub(size as usize)),
             (None, None) => bs,
         }
     }
@@ -209,11 +247,8 @@ pub struct KvPager {
 
 impl KvPager {
     fn new(root: &str, inner: Vec<String>) -> Self {
-        let root = if root == "/" {
-            "".to_string()
-        } else {
-            root.to_string()
-        };
+        // Keys are stored without the leading `/` of root.
+        let root = build_abs_path(root, "/");
 
         Self {
             root,
@@ -295,6 +330,12 @@ impl<S: Adapter> oio::Write for KvWriter<S> {
 
         Ok(())
     }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.buf.clear();
+
+        Ok(())
+    }
 }
 
 impl<S: Adapter> oio::BlockingWrite for KvWriter<S> {
@@ -315,4 +356,10 @@ impl<S: Adapter> oio::BlockingWrite for KvWriter<S> {
 
         Ok(())
     }
+
+    fn abort(&mut self) -> Result<()> {
+        self.buf.clear();
+
+        Ok(())
+    }
 }
@@ -29,6 +29,12 @@ use bytes::Bytes;
 use futures::ready;
 use futures::Stream;
 use futures::StreamExt;
+use http::header::CONTENT_LENGTH;
+use http::header::CONTENT_RANGE;
+use http::HeaderValue;
+use http::Response;
+use http::StatusCode;
+use log::warn;
 
 use crate::raw::*;
 use crate::Error;
@@ -133,6 +139,11 @@ pub struct IncomingAsyncBody {
     size: Option<u64>,
     consumed: u64,
     chunk: Option<Bytes>,
+
+    /// Bytes to skip from the start of the stream.
+    skip: u64,
+    /// Bytes left to return, `None` means unlimited.
+    remaining: Option<u64>,
 }
 
 impl IncomingAsyncBody {
@@ -143,9 +154,22 @@ impl IncomingAsyncBody {
             size,
             consumed: 0,
             chunk: None,
+
+            skip: 0,
+            remaining: None,
         }
     }
 
+    /// Only return `size` bytes starting from `offset` of the stream.
+    ///
+    /// The content length check is still performed on the whole stream
+    /// until `size` bytes have been returned.
+    fn slice(mut self, offset: u64, size: Option<u64>) -> Self {
+        self.skip = offset;
+        self.remaining = size;
+        self
+    }
+
     /// Consume the entire body.
     pub async fn consume(mut self) -> Result<()> {
         use oio::ReadExt;
@@ -197,10 +221,13 @@ impl IncomingAsyncBody {
     fn check(expect: u64, actual: u64) -> Result<()> {
         match actual.cmp(&expect) {
             Ordering::Equal => Ok(()),
+            // Connection could be closed before all data returned, it's
+            // fine to retry in this case.
             Ordering::Less => Err(Error::new(
                 ErrorKind::Unexpected,
                 &format!("reader got too less data, expect: {expect}, actual: {actual}"),
-            )),
+            )
+            .set_temporary()),
             Ordering::Greater => Err(Error::new(
                 ErrorKind::Unexpected,
                 &format!("reader got too much data, expect: {expect}, actual: {actual}"),
@@ -249,21 +276,234 @@ impl oio::Read for IncomingAsyncBody {
             return Poll::Ready(Some(Ok(bs)));
         }
 
-        let res = match ready!(self.inner.poll_next_unpin(cx)) {
-            Some(Ok(bs)) => {
-                self.consumed += bs.len() as u64;
-                Some(Ok(bs))
-            }
-            Some(Err(err)) => Some(Err(err)),
-            None => {
-                if let Some(size) = self.size {
-                    Self::check(size, self.consumed)?;
+        let res = loop {
+            match ready!(self.inner.poll_next_unpin(cx)) {
+                Some(Ok(mut bs)) => {
+                    self.consumed += bs.len() as u64;
+
+                    if self.skip > 0 {
+                        let amt = min(self.skip, bs.len() as u64);
+                        bs.advance(amt as usize);
+                        self.skip -= amt;
+                        if bs.is_empty() {
+                            continue;
+                        }
+                    }
+                    if let Some(remaining) = self.remaining {
+                        // All bytes of the sliced range have been returned,
+                        // drain the rest so that content length can still
+                        // be checked.
+                        if remaining == 0 {
+                            continue;
+                        }
+                        bs.truncate(min(remaining, bs.len() as u64) as usize);
+                        self.remaining = Some(remaining - bs.len() as u64);
+                    }
+
+                    break Some(Ok(bs));
                 }
+                Some(Err(err)) => break Some(Err(err)),
+                None => {
+                    if let Some(size) = self.size {
+                        Self::check(size, self.consumed)?;
+                    }
 
-                None
+                    break None;
+                }
             }
         };
 
         Poll::Ready(res)
     }
 }
+
+/// Slice the response at client side if the server ignored the requested range.
+///
+/// Some servers ignore the `Range` header and return the whole content with
+/// `200 OK` but without `Content-Range`. Processing that body as the
+/// requested range will return wrong data silently, so we will:
+///
+/// - log a warning about this behavior.
+/// - skip and truncate the body to the requested range.
+/// - rewrite the response into `206 Partial Content` with correct
+///   `Content-Length` and `Content-Range`, so that services can parse
+///   metadata as usual.
+///
+/// Responses for full range, non `200 OK` responses and responses that
+/// carry `Content-Range` will be returned as is.
+///
+/// # Errors
+///
+/// Returns `Unexpected` if a suffix range is requested but the server
+/// doesn't return `Content-Length`, since we can't locate the suffix
+/// without the total size.
+pub fn slice_ignored_range(
+    resp: Response<IncomingAsyncBody>,
+    range: BytesRange,
+) -> Result<Response<IncomingAsyncBody>> {
+    if range.is_full()
+        || resp.status() != StatusCode::OK
+        || resp.headers().contains_key(CONTENT_RANGE)
+    {
+        return Ok(resp);
+    }
+
+    let total = parse_content_length(resp.headers())?;
+    let (offset, size) = match (range.offset(), range.size(), total) {
+        (Some(offset), size, _) => (offset, size),
+        (None, Some(size), Some(total)) => (total.saturating_sub(size), Some(size)),
+        _ => {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "server ignored suffix range without content length",
+            )
+            .with_operation("http_util::slice_ignored_range")
+            .with_context("range", range.to_string()))
+        }
+    };
+
+    warn!(
+        "server ignored range {} and returned the whole content, slicing at client side",
+        range
+    );
+
+    let (mut parts, body) = resp.into_parts();
+    parts.status = StatusCode::PARTIAL_CONTENT;
+    if let Some(total) = total {
+        let start = min(offset, total);
+        let end = size.map_or(total, |size| min(start.saturating_add(size), total));
+        parts
+            .headers
+            .insert(CONTENT_LENGTH, HeaderValue::from(end - start));
+        if start < end {
+            let content_range = BytesContentRange::default()
+                .with_range(start, end - 1)
+                .with_size(total);
+            parts.headers.insert(
+                CONTENT_RANGE,
+                HeaderValue::from_str(&content_range.to_header())
+                    .expect("content range must be valid header value"),
+            );
+        }
+    } else {
+        parts.headers.remove(CONTENT_LENGTH);
+    }
+
+    Ok(Response::from_parts(parts, body.slice(offset, size)))
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+
+    fn new_response(content: &'static [u8], with_length: bool) -> Response<IncomingAsyncBody> {
+        let stream = stream::iter(content.chunks(3).map(|v| Ok(Bytes::from_static(v))));
+        let size = Some(content.len() as u64).filter(|_| with_length);
+
+        let mut builder = Response::builder().status(StatusCode::OK);
+        if let Some(size) = size {
+            builder = builder.header(CONTENT_LENGTH, size);
+        }
+        builder
+            .body(IncomingAsyncBody::new(Box::new(stream), size))
+            .expect("must build")
+    }
+
+    #[tokio::test]
+    async fn test_slice_ignored_range() -> Result<()> {
+        let cases = vec![
+            ("full", BytesRange::from(..), true, "Hello, World!", None),
+            (
+                "offset",
+                BytesRange::from(7..),
+                true,
+                "World!",
+                Some("bytes 7-12/13"),
+            ),
+            (
+                "offset and size",
+                BytesRange::from(2..9),
+                true,
+                "llo, Wo",
+                Some("bytes 2-8/13"),
+            ),
+            (
+                "suffix",
+                BytesRange::new(None, Some(6)),
+                true,
+                "World!",
+                Some("bytes 7-12/13"),
+            ),
+            (
+                "size overflow",
+                BytesRange::from(7..1024),
+                true,
+                "World!",
+                Some("bytes 7-12/13"),
+            ),
+            ("offset overflow", BytesRange::from(1024..), true, "", None),
+            (
+                "no content length",
+                BytesRange::from(2..9),
+                false,
+                "llo, Wo",
+                None,
+            ),
+        ];
+
+        for (name, range, with_length, expected, content_range) in cases {
+            let resp = slice_ignored_range(new_response(b"Hello, World!", with_length), range)?;
+
+            if !range.is_full() {
+                assert_eq!(resp.status(), StatusCode::PARTIAL_CONTENT, "{name}");
+            }
+            assert_eq!(
+                resp.headers()
+                    .get(CONTENT_RANGE)
+                    .map(|v| v.to_str().unwrap()),
+                content_range,
+                "{name}"
+            );
+            if with_length {
+                assert_eq!(
+                    parse_content_length(resp.headers())?,
+                    Some(expected.len() as u64),
+                    "{name}"
+                );
+            }
+
+            let bs = resp.into_body().bytes().await?;
+            assert_eq!(bs, expected.as_bytes(), "{name}");
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_slice_check_content_length() {
+        let stream = stream::iter(vec![Ok(Bytes::from_static(b"Hello, World!"))]);
+        let body = IncomingAsyncBody::new(Box::new(stream), Some(20)).slice(2, Some(3));
+
+        let res = body.bytes().await;
+        assert_eq!(res.err().map(|err| err.kind()), Some(ErrorKind::Unexpected));
+    }
+
+    #[test]
+    fn test_slice_ignored_suffix_range_without_length() {
+        let range = BytesRange::new(None, Some(6));
+        let res = slice_ignored_range(new_response(b"Hello, World!", false), range);
+        assert_eq!(res.err().map(|err| err.kind()), Some(ErrorKind::Unexpected));
+    }
+
+    #[test]
+    fn test_slice_ignored_range_honored() -> Result<()> {
+        let mut resp = new_response(b"Hello", true);
+        *resp.status_mut() = StatusCode::PARTIAL_CONTENT;
+
+        let resp = slice_ignored_range(resp, BytesRange::from(7..12))?;
+        assert_eq!(resp.headers().get(CONTENT_LENGTH).unwrap(), "5");
+        Ok(())
+    }
+}
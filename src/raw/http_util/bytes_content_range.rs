@@ -89,6 +89,15 @@ impl BytesContentRange {
         self.2
     }
 
+    /// Check if this BytesContentRange is unsatisfiable.
+    ///
+    /// Servers will return `Content-Range: bytes */<size>` along with
+    /// `416 Range Not Satisfiable`. Only the total size is known in this case,
+    /// and users can still read it via [`BytesContentRange::size`].
+    pub fn is_unsatisfiable(&self) -> bool {
+        self.0.is_none() && self.1.is_none() && self.2.is_some()
+    }
+
     /// Get the range inclusive of this BytesContentRange, return `None` if range is not known.
     pub fn range(&self) -> Option<Range<u64>> {
         if let (Some(start), Some(end)) = (self.0, self.1) {
@@ -132,6 +141,72 @@ impl BytesContentRange {
             _ => unreachable!("invalid bytes range: {:?}", self),
         }
     }
+
+    /// Check if this content range fully covers the given range.
+    ///
+    /// Open-ended range like `bytes=500-` and suffix range like `bytes=-500`
+    /// can only be resolved while the size is known, `false` will be returned
+    /// otherwise.
+    pub fn contains(&self, other: &BytesRange) -> bool {
+        match (self.range_inclusive(), self.resolve(other)) {
+            (Some(r), Some((start, end))) => *r.start() <= start && end <= *r.end(),
+            _ => false,
+        }
+    }
+
+    /// Get the overlapped part between this content range and the given range.
+    ///
+    /// Returns `None` if they are disjoint or can't be resolved.
+    pub fn intersection(&self, other: &BytesRange) -> Option<BytesContentRange> {
+        let r = self.range_inclusive()?;
+        let (start, end) = self.resolve(other)?;
+
+        let (start, end) = (start.max(*r.start()), end.min(*r.end()));
+        if start <= end {
+            Some(Self(Some(start), Some(end), self.2))
+        } else {
+            None
+        }
+    }
+
+    /// Clamp the given range into `[0, size)` of this content range.
+    ///
+    /// The returned range always carries both offset and size. Returns
+    /// `None` if the size is unknown or the range is out of bounds.
+    pub fn clamp_to_total(&self, range: &BytesRange) -> Option<BytesRange> {
+        self.2?;
+
+        self.resolve(range)
+            .map(|(start, end)| BytesRange::from(start..=end))
+    }
+
+    /// Resolve given range into inclusive `(start, end)` with the known size.
+    ///
+    /// Returns `None` if the range is empty after resolving.
+    fn resolve(&self, range: &BytesRange) -> Option<(u64, u64)> {
+        let (start, end) = match (range.offset(), range.size(), self.2) {
+            (_, Some(0), _) => return None,
+            (Some(offset), Some(size), None) => (offset, offset.checked_add(size)? - 1),
+            // Range that overflows must end beyond the total size.
+            (Some(offset), Some(size), Some(total)) => (
+                offset,
+                offset
+                    .checked_add(size)
+                    .map_or(total, |v| v.min(total))
+                    .checked_sub(1)?,
+            ),
+            (Some(offset), None, Some(total)) => (offset, total.checked_sub(1)?),
+            (None, Some(size), Some(total)) => (total.saturating_sub(size), total.checked_sub(1)?),
+            (None, None, Some(total)) => (0, total.checked_sub(1)?),
+            (_, None, None) | (None, Some(_), None) => return None,
+        };
+
+        if start <= end {
+            Some((start, end))
+        } else {
+            None
+        }
+    }
 }
 
 impl Display for BytesContentRange {
@@ -162,7 +237,17 @@ impl FromStr for BytesContentRange {
                 .set_source(e)
         };
 
+        // Content-Range: bytes */<size> is returned with 416 Range Not Satisfiable.
         if let Some(size) = s.strip_prefix("*/") {
+            if size == "*" {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "header content range must contain at least one of range or size",
+                )
+                .with_operation("BytesContentRange::from_str")
+                .with_context("value", value));
+            }
+
             return Ok(
                 BytesContentRange::default().with_size(size.parse().map_err(parse_int_error)?)
             );
@@ -187,6 +272,14 @@ impl FromStr for BytesContentRange {
         }
         let start: u64 = v[0].parse().map_err(parse_int_error)?;
         let end: u64 = v[1].parse().map_err(parse_int_error)?;
+        if start > end {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "header content range start is larger than end",
+            )
+            .with_operation("BytesContentRange::from_str")
+            .with_context("value", value));
+        }
         let mut bcr = BytesContentRange::default().with_range(start, end);
 
         // Handle size part first.
@@ -233,6 +326,39 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_bytes_content_range_from_str_invalid() {
+        let cases = vec![
+            ("wildcard only", "bytes */*"),
+            ("start larger than end", "bytes 500-499/1234"),
+            ("missing unit", "0-499/1234"),
+            ("missing size", "bytes 0-499"),
+        ];
+
+        for (name, input) in cases {
+            let err = input
+                .parse::<BytesContentRange>()
+                .expect_err("parse must fail");
+            assert_eq!(err.kind(), ErrorKind::Unexpected, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_bytes_content_range_is_unsatisfiable() -> Result<()> {
+        let cases = vec![
+            ("bytes */500", true),
+            ("bytes 0-499/1234", false),
+            ("bytes 0-499/*", false),
+        ];
+
+        for (input, expected) in cases {
+            let bcr: BytesContentRange = input.parse()?;
+            assert_eq!(bcr.is_unsatisfiable(), expected, "{input}");
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_from_bytes_range() {
         let cases = vec![
@@ -277,6 +403,162 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bytes_content_range_contains() {
+        let cached = BytesContentRange::default()
+            .with_range(1024, 2047)
+            .with_size(4096);
+
+        let cases = vec![
+            (
+                "fully contained",
+                BytesRange::new(Some(1024), Some(512)),
+                true,
+            ),
+            (
+                "exactly same",
+                BytesRange::new(Some(1024), Some(1024)),
+                true,
+            ),
+            (
+                "partial overlap",
+                BytesRange::new(Some(2000), Some(100)),
+                false,
+            ),
+            ("disjoint", BytesRange::new(Some(3000), Some(100)), false),
+            ("open ended", BytesRange::new(Some(1536), None), false),
+            ("suffix", BytesRange::new(None, Some(512)), false),
+        ];
+        for (name, input, expected) in cases {
+            assert_eq!(cached.contains(&input), expected, "{name}");
+        }
+
+        let cached = BytesContentRange::default()
+            .with_range(2048, 4095)
+            .with_size(4096);
+        assert!(cached.contains(&BytesRange::new(None, Some(512))));
+        assert!(cached.contains(&BytesRange::new(Some(3000), None)));
+        assert!(!cached.contains(&BytesRange::new(None, Some(3000))));
+        // Range ends after size will be clamped.
+        assert!(cached.contains(&BytesRange::new(Some(3000), Some(8192))));
+
+        // Open ended range can't be resolved without size.
+        let cached = BytesContentRange::default().with_range(0, 4095);
+        assert!(!cached.contains(&BytesRange::new(Some(0), None)));
+        assert!(cached.contains(&BytesRange::new(Some(0), Some(4096))));
+        // Range overflows can't be resolved without size.
+        assert!(!cached.contains(&BytesRange::new(Some(1), Some(u64::MAX))));
+    }
+
+    #[test]
+    fn test_bytes_content_range_intersection() {
+        let cached = BytesContentRange::default()
+            .with_range(1024, 2047)
+            .with_size(4096);
+
+        let cases = vec![
+            (
+                "fully contained",
+                BytesRange::new(Some(1100), Some(100)),
+                Some(
+                    BytesContentRange::default()
+                        .with_range(1100, 1199)
+                        .with_size(4096),
+                ),
+            ),
+            (
+                "partial overlap",
+                BytesRange::new(Some(2000), Some(100)),
+                Some(
+                    BytesContentRange::default()
+                        .with_range(2000, 2047)
+                        .with_size(4096),
+                ),
+            ),
+            ("disjoint", BytesRange::new(Some(3000), Some(100)), None),
+            (
+                "open ended",
+                BytesRange::new(Some(1536), None),
+                Some(
+                    BytesContentRange::default()
+                        .with_range(1536, 2047)
+                        .with_size(4096),
+                ),
+            ),
+            ("suffix disjoint", BytesRange::new(None, Some(512)), None),
+            (
+                "suffix overlap",
+                BytesRange::new(None, Some(3000)),
+                Some(
+                    BytesContentRange::default()
+                        .with_range(1096, 2047)
+                        .with_size(4096),
+                ),
+            ),
+        ];
+        for (name, input, expected) in cases {
+            assert_eq!(cached.intersection(&input), expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_bytes_content_range_clamp_to_total() {
+        let total = BytesContentRange::default().with_size(1024);
+
+        let cases = vec![
+            (
+                "full",
+                BytesRange::new(None, None),
+                Some(BytesRange::from(0..1024)),
+            ),
+            (
+                "in bounds",
+                BytesRange::new(Some(0), Some(100)),
+                Some(BytesRange::from(0..100)),
+            ),
+            (
+                "end out of bounds",
+                BytesRange::new(Some(1000), Some(100)),
+                Some(BytesRange::from(1000..1024)),
+            ),
+            (
+                "start out of bounds",
+                BytesRange::new(Some(1024), Some(100)),
+                None,
+            ),
+            (
+                "open ended",
+                BytesRange::new(Some(500), None),
+                Some(BytesRange::from(500..1024)),
+            ),
+            (
+                "suffix",
+                BytesRange::new(None, Some(500)),
+                Some(BytesRange::from(524..1024)),
+            ),
+            (
+                "suffix larger than total",
+                BytesRange::new(None, Some(2048)),
+                Some(BytesRange::from(0..1024)),
+            ),
+            ("empty", BytesRange::new(Some(0), Some(0)), None),
+            (
+                "size overflow",
+                BytesRange::new(Some(1000), Some(u64::MAX)),
+                Some(BytesRange::from(1000..1024)),
+            ),
+        ];
+        for (name, input, expected) in cases {
+            assert_eq!(total.clamp_to_total(&input), expected, "{name}");
+        }
+
+        let unknown = BytesContentRange::default().with_range(0, 1023);
+        assert_eq!(
+            unknown.clamp_to_total(&BytesRange::new(Some(0), Some(100))),
+            None
+        );
+    }
+
     #[test]
     fn test_bytes_content_range_to_string() {
         let h = BytesContentRange::default().with_size(1024);
@@ -49,7 +49,7 @@ use crate::Result;
 ///
 /// - `0..1024` will be converted to header `range: bytes=0-1024`
 /// - `..1024` will be converted to header `range: bytes=-1024`
-#[derive(Default, Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub struct BytesRange(
     /// Offset of the range.
     Option<u64>,
@@ -72,6 +72,28 @@ impl BytesRange {
         BytesRange(offset, size)
     }
 
+    /// Create a range that reads the last `size` bytes of content.
+    ///
+    /// ```
+    /// use opendal::BytesRange;
+    ///
+    /// assert_eq!(BytesRange::suffix(500).to_header(), "bytes=-500");
+    /// ```
+    pub fn suffix(size: u64) -> Self {
+        BytesRange(None, Some(size))
+    }
+
+    /// Create a range that reads from `offset` to the end of content.
+    ///
+    /// ```
+    /// use opendal::BytesRange;
+    ///
+    /// assert_eq!(BytesRange::from_offset(1000).to_header(), "bytes=1000-");
+    /// ```
+    pub fn from_offset(offset: u64) -> Self {
+        BytesRange(Some(offset), None)
+    }
+
     /// Get offset of BytesRange.
     pub fn offset(&self) -> Option<u64> {
         self.0
@@ -99,6 +121,25 @@ impl BytesRange {
         format!("bytes={self}")
     }
 
+    /// Resolve a suffix range into an absolute range with given total size.
+    ///
+    /// Some services (like azblob) don't support `bytes=-<suffix-length>`,
+    /// we can stat the object first and convert the range into
+    /// `bytes=<range-start>-<range-end>` instead.
+    ///
+    /// Ranges that already have an offset will be returned as is.
+    pub fn resolve_suffix(&self, total_size: u64) -> Self {
+        match (self.0, self.1) {
+            (None, Some(size)) => BytesRange(Some(total_size.saturating_sub(size)), None),
+            _ => *self,
+        }
+    }
+
+    /// Check if this range is a suffix range like `bytes=-<suffix-length>`.
+    pub fn is_suffix(&self) -> bool {
+        self.0.is_none() && self.1.is_some()
+    }
+
     /// Convert bytes range into rust range.
     pub fn to_range(&self) -> impl RangeBounds<u64> {
         (
@@ -251,6 +292,28 @@ mod tests {
         assert_eq!(h.to_header(), "bytes=1024-2047");
     }
 
+    #[test]
+    fn test_bytes_range_resolve_suffix() {
+        let h = BytesRange::new(None, Some(100));
+        assert!(h.is_suffix());
+        assert_eq!(h.resolve_suffix(1000), BytesRange::new(Some(900), None));
+        assert_eq!(h.resolve_suffix(1000).to_header(), "bytes=900-");
+        assert_eq!(h.resolve_suffix(10), BytesRange::new(Some(0), None));
+
+        let h = BytesRange::new(Some(10), Some(100));
+        assert!(!h.is_suffix());
+        assert_eq!(h.resolve_suffix(1000), h);
+
+        // Both paths should read the same content.
+        let bs = Bytes::from((0..1000).map(|v| v as u8).collect::<Vec<u8>>());
+        let h = BytesRange::new(None, Some(100));
+        let native = h.apply_on_bytes(bs.clone());
+        let resolved = h.resolve_suffix(bs.len() as u64).apply_on_bytes(bs.clone());
+        assert_eq!(native.len(), 100);
+        assert_eq!(native, resolved);
+        assert_eq!(native, bs.slice(900..));
+    }
+
     #[test]
     fn test_bytes_range_from_range_bounds() {
         assert_eq!(BytesRange::new(None, None), BytesRange::from(..));
@@ -267,6 +330,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bytes_range_builder() {
+        let cases = [
+            ("range", BytesRange::from(0..100), "bytes=0-99"),
+            ("inclusive range", BytesRange::from(0..=100), "bytes=0-100"),
+            ("suffix", BytesRange::suffix(500), "bytes=-500"),
+            ("offset", BytesRange::from_offset(1000), "bytes=1000-"),
+            ("range from", BytesRange::from(1000..), "bytes=1000-"),
+        ];
+
+        for (name, range, expected) in cases {
+            assert_eq!(range.to_header(), expected, "{name}");
+        }
+    }
+
     #[test]
     fn test_bytes_range_from_str() -> Result<()> {
         let cases = vec![
@@ -31,6 +31,7 @@ use reqwest::Url;
 
 use super::body::IncomingAsyncBody;
 use super::dns::*;
+use super::insert_request_id_header;
 use super::parse_content_length;
 use super::AsyncBody;
 use super::Body;
@@ -152,7 +153,8 @@ impl HttpClient {
 
     /// Send a request in blocking way.
     pub fn send(&self, req: Request<Body>) -> Result<Response<Body>> {
-        let (parts, body) = req.into_parts();
+        let (mut parts, body) = req.into_parts();
+        insert_request_id_header(&mut parts.headers);
 
         let mut ur = self
             .sync_client
@@ -173,7 +175,16 @@ impl HttpClient {
                             | ureq::ErrorKind::Io
                     );
 
-                    let mut err = Error::new(ErrorKind::Unexpected, "send blocking request")
+                    let message = if matches!(
+                        transport.kind(),
+                        ureq::ErrorKind::Dns | ureq::ErrorKind::ConnectionFailed
+                    ) {
+                        "failed to connect to service"
+                    } else {
+                        "send blocking request"
+                    };
+
+                    let mut err = Error::new(ErrorKind::Unexpected, message)
                         .with_operation("http_util::Client::send")
                         .set_source(transport);
                     if is_temporary {
@@ -202,7 +213,8 @@ impl HttpClient {
     /// Send a request in async way.
     pub async fn send_async(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
         let is_head = req.method() == http::Method::HEAD;
-        let (parts, body) = req.into_parts();
+        let (mut parts, body) = req.into_parts();
+        insert_request_id_header(&mut parts.headers);
 
         let mut req_builder = self
             .async_client
@@ -237,7 +249,17 @@ impl HttpClient {
                 err.is_status()
             );
 
-            let mut oerr = Error::new(ErrorKind::Unexpected, "send async request")
+            // Tell connection failures apart so that users can find out
+            // unreachable endpoints.
+            let message = if err.is_connect() {
+                "failed to connect to service"
+            } else if err.is_timeout() {
+                "send async request timed out"
+            } else {
+                "send async request"
+            };
+
+            let mut oerr = Error::new(ErrorKind::Unexpected, message)
                 .with_operation("http_util::Client::send_async")
                 .set_source(err);
             if is_temporary {
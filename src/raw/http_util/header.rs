@@ -15,20 +15,34 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
+use std::time::Duration;
+
 use base64::engine::general_purpose;
 use base64::Engine;
 use http::header::HeaderName;
+use http::header::CACHE_CONTROL;
 use http::header::CONTENT_DISPOSITION;
+use http::header::CONTENT_ENCODING;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_RANGE;
 use http::header::CONTENT_TYPE;
 use http::header::ETAG;
+use http::header::EXPIRES;
 use http::header::LAST_MODIFIED;
 use http::header::LOCATION;
+use http::header::RETRY_AFTER;
 use http::HeaderMap;
+use http::HeaderValue;
+use http::Request;
+use log::warn;
 use md5::Digest;
+use percent_encoding::percent_decode_str;
+use time::format_description;
 use time::format_description::well_known::Rfc2822;
+use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
+use time::UtcOffset;
 
 use crate::raw::*;
 use crate::EntryMode;
@@ -36,6 +50,65 @@ use crate::Error;
 use crate::ErrorKind;
 use crate::Metadata;
 use crate::Result;
+use crate::StorageClass;
+
+/// Parse header value by given name from header map.
+///
+/// This function is used by services to parse their own headers which are
+/// not handled by [`parse_into_metadata`], like `x-amz-version-id`.
+pub fn parse_header<'a>(headers: &'a HeaderMap, name: &str) -> Result<Option<&'a str>> {
+    match headers.get(name) {
+        None => Ok(None),
+        Some(v) => Ok(Some(v.to_str().map_err(|e| {
+            Error::new(
+                ErrorKind::Unexpected,
+                "header value has to be valid utf-8 string",
+            )
+            .with_operation("http_util::parse_header")
+            .with_context("header", name)
+            .set_source(e)
+        })?)),
+    }
+}
+
+/// Parse user defined metadata from header map by given prefix.
+///
+/// Prefix is service-specific, like `x-amz-meta-` for s3 and `x-goog-meta-`
+/// for gcs. The returned keys are lowercased with the prefix stripped.
+///
+/// Values that are not valid utf-8 strings will be skipped.
+pub fn parse_user_metadata(headers: &HeaderMap, prefix: &str) -> HashMap<String, String> {
+    let prefix = prefix.to_ascii_lowercase();
+
+    headers
+        .iter()
+        .filter_map(|(k, v)| {
+            let key = k.as_str().to_ascii_lowercase();
+            let key = key.strip_prefix(&prefix)?;
+            let value = v.to_str().ok()?;
+            Some((key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Parse storage class from given value.
+///
+/// Services should parse the value from their own header, like
+/// `x-amz-storage-class` for s3 and `x-ms-access-tier` for azblob. The value
+/// is matched case-insensitively, unknown values will be returned as
+/// [`StorageClass::Other`].
+pub fn parse_storage_class(value: &str) -> StorageClass {
+    match value.to_ascii_uppercase().as_str() {
+        "STANDARD" | "HOT" => StorageClass::Standard,
+        "STANDARD_IA" | "ONEZONE_IA" => StorageClass::InfrequentAccess,
+        "GLACIER" | "GLACIER_IR" => StorageClass::Glacier,
+        "DEEP_ARCHIVE" | "ARCHIVE" => StorageClass::Archive,
+        "COOL" | "COLD" => StorageClass::Cold,
+        "NEARLINE" => StorageClass::Nearline,
+        "COLDLINE" => StorageClass::Coldline,
+        _ => StorageClass::Other(value.to_string()),
+    }
+}
 
 /// Parse redirect location from header map
 ///
@@ -79,6 +152,23 @@ pub fn parse_content_length(headers: &HeaderMap) -> Result<Option<u64>> {
     }
 }
 
+/// Parse the total content length of the object from header map.
+///
+/// For `206 Partial Content` responses, `Content-Length` is only the length
+/// of returned range. So we prefer the total size in `Content-Range` like
+/// `bytes 0-99/1000` or `bytes */1000`, and fall back to `Content-Length`
+/// if `Content-Range` is absent.
+///
+/// Returns `None` if `Content-Range` is present but the total size is
+/// unknown (`bytes 0-99/*`), since `Content-Length` is not the total size
+/// in this case.
+pub fn parse_content_length_from_range(headers: &HeaderMap) -> Result<Option<u64>> {
+    match parse_content_range(headers)? {
+        Some(range) => Ok(range.size()),
+        None => parse_content_length(headers),
+    }
+}
+
 /// Parse content md5 from header map.
 pub fn parse_content_md5(headers: &HeaderMap) -> Result<Option<&str>> {
     match headers.get(HeaderName::from_static("content-md5")) {
@@ -94,6 +184,44 @@ pub fn parse_content_md5(headers: &HeaderMap) -> Result<Option<&str>> {
     }
 }
 
+/// Parse content md5 from header map and validate its shape.
+///
+/// Unlike [`parse_content_md5`], the value must be a base64 encoded 16 bytes
+/// md5 digest like `WOctCY1SS662e7ziElh4cw==`.
+///
+/// # Errors
+///
+/// Return an unexpected error if the value is not valid base64 or the
+/// decoded digest is not 16 bytes, for example a hex digest returned by
+/// misbehaving gateways.
+pub fn parse_content_md5_checked(headers: &HeaderMap) -> Result<Option<&str>> {
+    let v = match parse_content_md5(headers)? {
+        None => return Ok(None),
+        Some(v) => v,
+    };
+
+    let digest = general_purpose::STANDARD.decode(v).map_err(|e| {
+        Error::new(
+            ErrorKind::Unexpected,
+            "header value is not valid base64 string",
+        )
+        .with_operation("http_util::parse_content_md5_checked")
+        .with_context("value", v)
+        .set_source(e)
+    })?;
+    if digest.len() != 16 {
+        return Err(Error::new(
+            ErrorKind::Unexpected,
+            "header value is not valid md5 digest",
+        )
+        .with_operation("http_util::parse_content_md5_checked")
+        .with_context("value", v)
+        .with_context("length", digest.len().to_string()));
+    }
+
+    Ok(Some(v))
+}
+
 /// Parse content type from header map.
 pub fn parse_content_type(headers: &HeaderMap) -> Result<Option<&str>> {
     match headers.get(CONTENT_TYPE) {
@@ -110,6 +238,10 @@ pub fn parse_content_type(headers: &HeaderMap) -> Result<Option<&str>> {
 }
 
 /// Parse content range from header map.
+///
+/// `Content-Range: bytes */<size>` returned with `416 Range Not Satisfiable`
+/// will be parsed successfully, use [`BytesContentRange::is_unsatisfiable`]
+/// to detect it and [`BytesContentRange::size`] to get the total size.
 pub fn parse_content_range(headers: &HeaderMap) -> Result<Option<BytesContentRange>> {
     match headers.get(CONTENT_RANGE) {
         None => Ok(None),
@@ -129,6 +261,9 @@ pub fn parse_content_range(headers: &HeaderMap) -> Result<Option<BytesContentRan
 }
 
 /// Parse last modified from header map.
+///
+/// `Last-Modified` should be in rfc2822 format, but some services return
+/// rfc3339 instead. We will try rfc2822 first and fallback to rfc3339.
 pub fn parse_last_modified(headers: &HeaderMap) -> Result<Option<OffsetDateTime>> {
     match headers.get(LAST_MODIFIED) {
         None => Ok(None),
@@ -141,21 +276,68 @@ pub fn parse_last_modified(headers: &HeaderMap) -> Result<Option<OffsetDateTime>
                 .with_operation("http_util::parse_last_modified")
                 .set_source(e)
             })?;
-            let t = OffsetDateTime::parse(v, &Rfc2822).map_err(|e| {
+            let t = OffsetDateTime::parse(v, &Rfc2822)
+                .or_else(|_| OffsetDateTime::parse(v, &Rfc3339))
+                .map_err(|e| {
+                    Error::new(
+                        ErrorKind::Unexpected,
+                        "header value is not valid rfc2822 or rfc3339 time",
+                    )
+                    .with_operation("http_util::parse_last_modified")
+                    .with_context("value", v)
+                    .set_source(e)
+                })?;
+
+            Ok(Some(t))
+        }
+    }
+}
+
+/// Parse expires from header map.
+///
+/// `Expires` should be in HTTP-date format like `Thu, 01 Dec 1994 16:00:00 GMT`.
+pub fn parse_expires(headers: &HeaderMap) -> Result<Option<OffsetDateTime>> {
+    match headers.get(EXPIRES) {
+        None => Ok(None),
+        Some(v) => {
+            let v = v.to_str().map_err(|e| {
                 Error::new(
                     ErrorKind::Unexpected,
-                    "header value is not valid rfc2822 time",
+                    "header value is not valid utf-8 string",
                 )
-                .with_operation("http_util::parse_last_modified")
+                .with_operation("http_util::parse_expires")
                 .set_source(e)
             })?;
+            let t = OffsetDateTime::parse(v, &Rfc2822).map_err(|e| {
+                Error::new(ErrorKind::Unexpected, "header value is not valid http date")
+                    .with_operation("http_util::parse_expires")
+                    .with_context("value", v)
+                    .set_source(e)
+            })?;
 
             Ok(Some(t))
         }
     }
 }
 
+/// Parse expires from header map like [`parse_expires`], but treat invalid
+/// values like `0` as already expired.
+///
+/// Refer to [RFC 7234 Section 5.3](https://httpwg.org/specs/rfc7234.html#header.expires):
+/// A cache recipient MUST interpret invalid date formats, especially the
+/// value "0", as representing a time in the past (i.e., "already expired").
+fn parse_expires_or_expired(headers: &HeaderMap) -> Option<OffsetDateTime> {
+    match parse_expires(headers) {
+        Ok(v) => v,
+        Err(_) => Some(OffsetDateTime::UNIX_EPOCH),
+    }
+}
+
 /// Parse etag from header map.
+///
+/// The returned value is byte-faithful to the header, for example `"abc"` or
+/// `W/"abc"`. Use [`parse_etag_normalized`] if the weak prefix and quotes
+/// should be stripped before comparing.
 pub fn parse_etag(headers: &HeaderMap) -> Result<Option<&str>> {
     match headers.get(ETAG) {
         None => Ok(None),
@@ -170,6 +352,53 @@ pub fn parse_etag(headers: &HeaderMap) -> Result<Option<&str>> {
     }
 }
 
+/// Parse etag from header map and normalize it.
+///
+/// The weak validator prefix `W/` and surrounding quotes will be stripped,
+/// so `W/"abc"` and `"abc"` will both be returned as `abc`. Already unquoted
+/// values will be returned untouched.
+pub fn parse_etag_normalized(headers: &HeaderMap) -> Result<Option<&str>> {
+    match parse_etag(headers)? {
+        None => Ok(None),
+        Some(v) => Ok(Some(normalize_etag(v).map_err(|err| {
+            err.with_operation("http_util::parse_etag_normalized")
+        })?)),
+    }
+}
+
+/// Check if given etag is a weak validator like `W/"abc"`.
+pub fn is_weak_etag(etag: &str) -> bool {
+    etag.starts_with("W/")
+}
+
+/// Normalize etag by stripping the weak validator prefix `W/` and
+/// surrounding quotes.
+///
+/// # Errors
+///
+/// Return an unexpected error if the etag is only `W/` without quoted body
+/// or the quotes are unbalanced.
+pub fn normalize_etag(etag: &str) -> Result<&str> {
+    let v = etag.strip_prefix("W/").unwrap_or(etag);
+    if is_weak_etag(etag) && v.is_empty() {
+        return Err(
+            Error::new(ErrorKind::Unexpected, "weak etag doesn't have quoted body")
+                .with_operation("http_util::normalize_etag")
+                .with_context("etag", etag),
+        );
+    }
+
+    match (v.starts_with('"'), v.len() >= 2 && v.ends_with('"')) {
+        (true, true) => Ok(&v[1..v.len() - 1]),
+        (false, false) => Ok(v),
+        _ => Err(
+            Error::new(ErrorKind::Unexpected, "etag has unbalanced quotes")
+                .with_operation("http_util::normalize_etag")
+                .with_context("etag", etag),
+        ),
+    }
+}
+
 /// Parse Content-Disposition for header map
 pub fn parse_content_disposition(headers: &HeaderMap) -> Result<Option<&str>> {
     match headers.get(CONTENT_DISPOSITION) {
@@ -185,6 +414,175 @@ pub fn parse_content_disposition(headers: &HeaderMap) -> Result<Option<&str>> {
     }
 }
 
+/// Parse Content-Encoding for header map
+pub fn parse_content_encoding(headers: &HeaderMap) -> Result<Option<&str>> {
+    match headers.get(CONTENT_ENCODING) {
+        None => Ok(None),
+        Some(v) => Ok(Some(v.to_str().map_err(|e| {
+            Error::new(
+                ErrorKind::Unexpected,
+                "header value has to be valid utf-8 string",
+            )
+            .with_operation("http_util::parse_content_encoding")
+            .set_source(e)
+        })?)),
+    }
+}
+
+/// Parse filename from the value of Content-Disposition.
+///
+/// Both `filename="x"` and RFC 5987 encoded `filename*=UTF-8''x` are
+/// supported. `filename*` will be preferred if both of them exist as
+/// suggested by [RFC 6266](https://www.rfc-editor.org/rfc/rfc6266#section-4.3).
+///
+/// Returns `None` if there is no filename, like `inline`.
+pub fn parse_content_disposition_filename(value: &str) -> Option<String> {
+    let mut filename = None;
+
+    // The first part is the disposition type, skip it.
+    for param in split_header_params(value).into_iter().skip(1) {
+        let (name, value) = match param.split_once('=') {
+            Some((name, value)) => (name, value.trim()),
+            None => continue,
+        };
+
+        match name.trim().to_ascii_lowercase().as_str() {
+            "filename*" => {
+                if let Some(v) = decode_ext_value(value) {
+                    return Some(v);
+                }
+            }
+            "filename" if filename.is_none() => filename = Some(unquote_header_value(value)),
+            _ => {}
+        }
+    }
+
+    filename
+}
+
+/// Split header value by `;` while keeping quoted strings untouched.
+fn split_header_params(value: &str) -> Vec<&str> {
+    let mut params = Vec::new();
+    let (mut start, mut quoted, mut escaped) = (0, false, false);
+
+    for (idx, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            ';' if !quoted => {
+                params.push(&value[start..idx]);
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    params.push(&value[start..]);
+
+    params
+}
+
+/// Unquote a quoted-string, unescaping `\"` and `\\`.
+///
+/// Tokens that are not quoted will be returned as is.
+fn unquote_header_value(value: &str) -> String {
+    let inner = match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(v) => v,
+        None => return value.to_string(),
+    };
+
+    let mut s = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => s.extend(chars.next()),
+            c => s.push(c),
+        }
+    }
+    s
+}
+
+/// Decode ext-value defined by [RFC 5987](https://www.rfc-editor.org/rfc/rfc5987#section-3.2),
+/// like `UTF-8''%e2%82%ac%20rates`.
+fn decode_ext_value(value: &str) -> Option<String> {
+    let mut parts = value.splitn(3, '\'');
+    let (charset, _language, encoded) = (parts.next()?, parts.next()?, parts.next()?);
+
+    let bs: Vec<u8> = percent_decode_str(encoded).collect();
+    match charset.to_ascii_lowercase().as_str() {
+        "utf-8" => String::from_utf8(bs).ok(),
+        "iso-8859-1" => Some(bs.into_iter().map(char::from).collect()),
+        _ => None,
+    }
+}
+
+/// Parse Cache-Control from header map.
+///
+/// The value will be returned AS-IS, like `max-age=3600, public`. Users
+/// should parse the directives by themselves.
+pub fn parse_cache_control(headers: &HeaderMap) -> Result<Option<&str>> {
+    match headers.get(CACHE_CONTROL) {
+        None => Ok(None),
+        Some(v) => Ok(Some(v.to_str().map_err(|e| {
+            Error::new(
+                ErrorKind::Unexpected,
+                "header value has to be valid utf-8 string",
+            )
+            .with_operation("http_util::parse_cache_control")
+            .set_source(e)
+        })?)),
+    }
+}
+
+/// Parse delete marker from header map by given header name.
+///
+/// Delete marker is service-specific, like `x-amz-delete-marker` of s3.
+/// Absent header will be treated as `false`, and the value is compared
+/// case-insensitively with `true`.
+pub fn parse_delete_marker(headers: &HeaderMap, name: &str) -> Result<bool> {
+    match parse_header(headers, name)? {
+        None => Ok(false),
+        Some(v) => Ok(v.trim().eq_ignore_ascii_case("true")),
+    }
+}
+
+/// Parse Retry-After from header map.
+///
+/// `Retry-After` could be either delay-seconds like `120` or an http date
+/// like `Fri, 31 Mar 2023 03:02:03 GMT`. The returned duration is relative
+/// to now, and dates in the past will be returned as zero.
+pub fn parse_retry_after(headers: &HeaderMap) -> Result<Option<Duration>> {
+    let v = match headers.get(RETRY_AFTER) {
+        None => return Ok(None),
+        Some(v) => v.to_str().map_err(|e| {
+            Error::new(
+                ErrorKind::Unexpected,
+                "header value is not valid utf-8 string",
+            )
+            .with_operation("http_util::parse_retry_after")
+            .set_source(e)
+        })?,
+    };
+    let v = v.trim();
+
+    if let Ok(secs) = v.parse::<u64>() {
+        return Ok(Some(Duration::from_secs(secs)));
+    }
+
+    let t = OffsetDateTime::parse(v, &Rfc2822).map_err(|e| {
+        Error::new(
+            ErrorKind::Unexpected,
+            "header value is not valid delay-seconds or http date",
+        )
+        .with_operation("http_util::parse_retry_after")
+        .with_context("value", v)
+        .set_source(e)
+    })?;
+
+    let delay = t - OffsetDateTime::now_utc();
+    Ok(Some(delay.try_into().unwrap_or(Duration::ZERO)))
+}
+
 /// parse_into_metadata will parse standards http headers into Metadata.
 ///
 /// # Notes
@@ -192,6 +590,21 @@ pub fn parse_content_disposition(headers: &HeaderMap) -> Result<Option<&str>> {
 /// parse_into_metadata only handles the standard behavior of http
 /// headers. If services have their own logic, they should update the parsed
 /// metadata on demand.
+///
+/// `Content-Type` will never be guessed from path. Services that want to
+/// fill it for objects stored without the header should call
+/// [`guess_content_type_if_absent`] after this function.
+///
+/// Invalid `Expires` like `0` will be parsed as `UNIX_EPOCH` which means
+/// already expired, instead of returning an error.
+///
+/// For example, `version` will always be left `None` since the header name
+/// is service-specific (`x-amz-version-id` for s3, `x-goog-generation` for
+/// gcs). Services should set it via [`parse_header`] after calling this
+/// function.
+///
+/// The same applies to [`Metadata::is_deleted`], which will always be left
+/// `false`. Services should set it via [`parse_delete_marker`].
 pub fn parse_into_metadata(path: &str, headers: &HeaderMap) -> Result<Metadata> {
     let mode = if path.ends_with('/') {
         EntryMode::DIR
@@ -224,13 +637,111 @@ pub fn parse_into_metadata(path: &str, headers: &HeaderMap) -> Result<Metadata>
         m.set_last_modified(v);
     }
 
+    if let Some(v) = parse_expires_or_expired(headers) {
+        m.set_expires(v);
+    }
+
     if let Some(v) = parse_content_disposition(headers)? {
         m.set_content_disposition(v);
     }
 
+    if let Some(v) = parse_content_encoding(headers)? {
+        m.set_content_encoding(v);
+    }
+
+    if let Some(v) = parse_cache_control(headers)? {
+        m.set_cache_control(v);
+    }
+
     Ok(m)
 }
 
+/// parse_into_metadata_lossy will parse standards http headers into Metadata
+/// like [`parse_into_metadata`], but skips headers that failed to parse
+/// instead of returning an error.
+///
+/// Unparseable fields will be left unset, and a warning will be logged.
+///
+/// This is useful while handling a large list of objects where one bad
+/// header shouldn't fail the whole list.
+pub fn parse_into_metadata_lossy(path: &str, headers: &HeaderMap) -> Metadata {
+    let mode = if path.ends_with('/') {
+        EntryMode::DIR
+    } else {
+        EntryMode::FILE
+    };
+    let mut m = Metadata::new(mode);
+
+    if let Some(v) = ok_or_warn(path, parse_content_length(headers)) {
+        m.set_content_length(v);
+    }
+
+    if let Some(v) = ok_or_warn(path, parse_content_type(headers)) {
+        m.set_content_type(v);
+    }
+
+    if let Some(v) = ok_or_warn(path, parse_content_range(headers)) {
+        m.set_content_range(v);
+    }
+
+    if let Some(v) = ok_or_warn(path, parse_etag(headers)) {
+        m.set_etag(v);
+    }
+
+    if let Some(v) = ok_or_warn(path, parse_content_md5(headers)) {
+        m.set_content_md5(v);
+    }
+
+    if let Some(v) = ok_or_warn(path, parse_last_modified(headers)) {
+        m.set_last_modified(v);
+    }
+
+    if let Some(v) = parse_expires_or_expired(headers) {
+        m.set_expires(v);
+    }
+
+    if let Some(v) = ok_or_warn(path, parse_content_disposition(headers)) {
+        m.set_content_disposition(v);
+    }
+
+    if let Some(v) = ok_or_warn(path, parse_content_encoding(headers)) {
+        m.set_content_encoding(v);
+    }
+
+    if let Some(v) = ok_or_warn(path, parse_cache_control(headers)) {
+        m.set_cache_control(v);
+    }
+
+    m
+}
+
+/// guess_content_type_if_absent will set content type of given metadata
+/// from the extension of path via [`guess_content_type`].
+///
+/// This is an opt-in fallback for objects stored without `Content-Type`.
+/// The guess only happens if the header is absent: a present header will
+/// never be overridden, even if it's invalid. Dirs are always skipped.
+pub fn guess_content_type_if_absent(path: &str, headers: &HeaderMap, m: &mut Metadata) {
+    if !m.mode().is_file() || headers.contains_key(CONTENT_TYPE) {
+        return;
+    }
+
+    if let Some(v) = guess_content_type(path) {
+        m.set_content_type(v);
+    }
+}
+
+/// Convert parse result into option, logging the error if failed.
+fn ok_or_warn<T>(path: &str, r: Result<Option<T>>) -> Option<T> {
+    match r {
+        Ok(v) => v,
+        Err(err) => {
+            warn!("parse metadata of {path} failed, skip this field: {err:?}");
+            None
+        }
+    }
+}
+
 /// format content md5 header by given input.
 pub fn format_content_md5(bs: &[u8]) -> String {
     let mut hasher = md5::Md5::new();
@@ -239,11 +750,81 @@ pub fn format_content_md5(bs: &[u8]) -> String {
     general_purpose::STANDARD.encode(hasher.finalize())
 }
 
+/// Check if given value is a valid `Content-MD5` header value, a.k.a.
+/// base64 encoded 16 bytes digest.
+pub fn is_valid_content_md5(v: &str) -> bool {
+    matches!(general_purpose::STANDARD.decode(v), Ok(bs) if bs.len() == 16)
+}
+
+/// format http date like `If-Modified-Since` header by given time.
+///
+/// The time will be converted into UTC, like:
+///
+/// - `Fri, 31 Mar 2023 03:02:03 GMT`
+pub fn format_http_date(t: OffsetDateTime) -> Result<String> {
+    let format = format_description::parse(
+        "[weekday repr:short], [day] [month repr:short] [year] [hour]:[minute]:[second] GMT",
+    )
+    .expect("http date format must be valid");
+
+    t.to_offset(UtcOffset::UTC).format(&format).map_err(|e| {
+        Error::new(
+            ErrorKind::Unexpected,
+            "time can't be formatted as http date",
+        )
+        .with_operation("http_util::format_http_date")
+        .with_context("value", t.to_string())
+        .set_source(e)
+    })
+}
+
+/// format content range header by given range and total size.
+///
+/// The range is inclusive: `[start..=end]`, and unknown total size will be
+/// represented as `*`, like:
+///
+/// - `bytes 0-1023/4096`
+/// - `bytes 0-1023/*`
+///
+/// # Errors
+///
+/// If input start is larger than end or end is out of the total size,
+/// function will return an unexpected error.
+pub fn format_content_range(start: u64, end: u64, total: Option<u64>) -> Result<String> {
+    if start > end {
+        return Err(Error::new(
+            ErrorKind::Unexpected,
+            "can't build content range header with start larger than end",
+        )
+        .with_context("start", start.to_string())
+        .with_context("end", end.to_string()));
+    }
+
+    let mut bcr = BytesContentRange::default().with_range(start, end);
+    if let Some(total) = total {
+        if end >= total {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "can't build content range header with end out of total size",
+            )
+            .with_context("end", end.to_string())
+            .with_context("total", total.to_string()));
+        }
+
+        bcr = bcr.with_size(total);
+    }
+
+    Ok(bcr.to_header())
+}
+
 /// format authorization header by basic auth.
 ///
 /// # Errors
 ///
 /// If input username is empty, function will return an unexpected error.
+///
+/// If input username or password contains control characters (like `\n`),
+/// function will return an unexpected error.
 pub fn format_authorization_by_basic(username: &str, password: &str) -> Result<String> {
     if username.is_empty() {
         return Err(Error::new(
@@ -251,6 +832,18 @@ pub fn format_authorization_by_basic(username: &str, password: &str) -> Result<S
             "can't build authorization header with empty username",
         ));
     }
+    if username.chars().any(|c| c.is_ascii_control()) {
+        return Err(Error::new(
+            ErrorKind::Unexpected,
+            "can't build authorization header with control characters in username",
+        ));
+    }
+    if password.chars().any(|c| c.is_ascii_control()) {
+        return Err(Error::new(
+            ErrorKind::Unexpected,
+            "can't build authorization header with control characters in password",
+        ));
+    }
 
     let value = general_purpose::STANDARD.encode(format!("{username}:{password}"));
 
@@ -273,14 +866,607 @@ pub fn format_authorization_by_bearer(token: &str) -> Result<String> {
     Ok(format!("Bearer {token}"))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// format `x-amz-security-token` header by aws session token.
+///
+/// Temporary credentials carry a session token that must be sent along
+/// with the SigV4 signed request. The token will be trimmed, and an empty
+/// token will be treated as not set.
+///
+/// # Errors
+///
+/// If input token contains control characters (like `\n`), function will
+/// return an unexpected error.
+pub fn format_authorization_by_aws_session_token(token: Option<&str>) -> Result<Option<String>> {
+    let token = match token.map(|v| v.trim()) {
+        None | Some("") => return Ok(None),
+        Some(v) => v,
+    };
 
-    /// Test cases is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_DeleteObjects.html
-    #[test]
-    fn test_format_content_md5() {
-        let cases = vec![(
+    if token.chars().any(|c| c.is_ascii_control()) {
+        return Err(Error::new(
+            ErrorKind::Unexpected,
+            "can't build security token header with control characters in token",
+        ));
+    }
+
+    Ok(Some(token.to_string()))
+}
+
+/// format authorization header by aws signature version 4.
+///
+/// The signature must be calculated by caller first, this function only
+/// assembles the header value like:
+///
+/// ```text
+/// AWS4-HMAC-SHA256 Credential=<access_key_id>/<date>/<region>/<service>/aws4_request, SignedHeaders=<signed_headers>, Signature=<signature>
+/// ```
+///
+/// - `date` is the date of credential scope in `YYYYMMDD` format.
+/// - `signed_headers` will be converted to lowercase and sorted.
+///
+/// Refer to [Signature Version 4](https://docs.aws.amazon.com/general/latest/gr/sigv4-add-signature-to-request.html)
+/// for more information.
+///
+/// # Errors
+///
+/// If input access_key_id, region or signature is empty, function will
+/// return an unexpected error.
+pub fn format_authorization_by_aws_sigv4(
+    access_key_id: &str,
+    date: &str,
+    region: &str,
+    service: &str,
+    signed_headers: &[&str],
+    signature: &str,
+) -> Result<String> {
+    if access_key_id.is_empty() {
+        return Err(Error::new(
+            ErrorKind::Unexpected,
+            "can't build authorization header with empty access key id",
+        ));
+    }
+    if region.is_empty() {
+        return Err(Error::new(
+            ErrorKind::Unexpected,
+            "can't build authorization header with empty region",
+        ));
+    }
+    if signature.is_empty() {
+        return Err(Error::new(
+            ErrorKind::Unexpected,
+            "can't build authorization header with empty signature",
+        ));
+    }
+
+    let mut signed_headers: Vec<_> = signed_headers
+        .iter()
+        .map(|v| v.trim().to_lowercase())
+        .collect();
+    signed_headers.sort();
+
+    Ok(format!(
+        "AWS4-HMAC-SHA256 Credential={access_key_id}/{date}/{region}/{service}/aws4_request, SignedHeaders={}, Signature={signature}",
+        signed_headers.join(";")
+    ))
+}
+
+/// Headers that managed by OpenDAL or signers which can't be set by users.
+const RESERVED_HEADERS: &[&str] = &[
+    "authorization",
+    "host",
+    "content-length",
+    "transfer-encoding",
+    "connection",
+    "date",
+    "x-amz-date",
+    "x-amz-content-sha256",
+    "x-amz-security-token",
+];
+
+/// Parse and validate user supplied custom headers.
+///
+/// Returns error if the header name or value is not legal, or the
+/// header is reserved by OpenDAL like `Authorization`.
+pub fn parse_custom_headers(
+    headers: &[(String, String)],
+) -> Result<Vec<(HeaderName, HeaderValue)>> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+                Error::new(ErrorKind::ConfigInvalid, "custom header name is invalid")
+                    .with_context("header", name)
+                    .set_source(e)
+            })?;
+            let value = HeaderValue::from_str(value).map_err(|e| {
+                Error::new(ErrorKind::ConfigInvalid, "custom header value is invalid")
+                    .with_context("header", name.as_str())
+                    .set_source(e)
+            })?;
+
+            if RESERVED_HEADERS.contains(&name.as_str()) {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "custom header is reserved by OpenDAL and can't be overridden",
+                )
+                .with_context("header", name.as_str()));
+            }
+
+            Ok((name, value))
+        })
+        .collect()
+}
+
+/// Insert user supplied custom headers into request.
+///
+/// Returns error if the header collides with the ones that already set by
+/// OpenDAL so that users can't clobber them silently.
+pub fn insert_custom_headers<T>(req: &mut Request<T>, headers: &[(String, String)]) -> Result<()> {
+    let headers = parse_custom_headers(headers)?;
+
+    if let Some((name, _)) = headers.iter().find(|(k, _)| req.headers().contains_key(k)) {
+        return Err(Error::new(
+            ErrorKind::ConfigInvalid,
+            "custom header is set by OpenDAL and can't be overridden",
+        )
+        .with_context("header", name.as_str()));
+    }
+
+    for (name, value) in headers {
+        req.headers_mut().append(name, value);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Metakey;
+
+    #[test]
+    fn test_is_valid_content_md5() {
+        assert!(is_valid_content_md5(&format_content_md5(b"Hello, World!")));
+        assert!(is_valid_content_md5("ZajifYh5KDgxtmS9i38K1A=="));
+        // Not base64.
+        assert!(!is_valid_content_md5("not a digest"));
+        // Hex encoded md5.
+        assert!(!is_valid_content_md5("65a8e27d8879283831b664bd8b7f0ad4"));
+        // Base64 encoded but not 16 bytes.
+        assert!(!is_valid_content_md5("SGVsbG8="));
+    }
+
+    #[test]
+    fn test_parse_content_disposition_filename() {
+        let cases = vec![
+            ("inline only", "inline", None),
+            ("attachment only", "attachment", None),
+            (
+                "ascii",
+                "attachment; filename=\"filename.jpg\"",
+                Some("filename.jpg"),
+            ),
+            ("unquoted", "attachment; filename=a.txt", Some("a.txt")),
+            (
+                "escaped quotes",
+                r#"attachment; filename="say \"hi\"; now.txt""#,
+                Some(r#"say "hi"; now.txt"#),
+            ),
+            (
+                "percent-encoded utf-8",
+                "attachment; filename*=UTF-8''%E4%BD%A0%E5%A5%BD%20world.txt",
+                Some("你好 world.txt"),
+            ),
+            (
+                "prefer filename*",
+                "attachment; filename=\"EURO rates\"; filename*=utf-8''%e2%82%ac%20rates",
+                Some("€ rates"),
+            ),
+            (
+                "iso-8859-1",
+                "attachment; filename*=iso-8859-1'en'%A3%20rates",
+                Some("£ rates"),
+            ),
+            (
+                "invalid filename* fallback",
+                "attachment; filename*=unknown''abc; filename=abc.txt",
+                Some("abc.txt"),
+            ),
+        ];
+
+        for (name, input, expected) in cases {
+            assert_eq!(
+                parse_content_disposition_filename(input).as_deref(),
+                expected,
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_guess_content_type_if_absent() -> Result<()> {
+        let headers = HeaderMap::new();
+        let mut meta = parse_into_metadata("a.json", &headers)?;
+        assert!(
+            !meta.bit().contains(Metakey::ContentType),
+            "guess must be opt-in"
+        );
+        guess_content_type_if_absent("a.json", &headers, &mut meta);
+        assert_eq!(meta.content_type(), Some("application/json"));
+
+        let mut meta = parse_into_metadata("dir.json/", &headers)?;
+        guess_content_type_if_absent("dir.json/", &headers, &mut meta);
+        assert!(!meta.bit().contains(Metakey::ContentType));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+        let mut meta = parse_into_metadata("a.json", &headers)?;
+        guess_content_type_if_absent("a.json", &headers, &mut meta);
+        assert_eq!(meta.content_type(), Some("text/plain"));
+
+        // Present but invalid header must not be replaced by a guess.
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_bytes(b"\xff").expect("must be valid header value"),
+        );
+        let mut meta = parse_into_metadata_lossy("a.json", &headers);
+        guess_content_type_if_absent("a.json", &headers, &mut meta);
+        assert!(!meta.bit().contains(Metakey::ContentType));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_into_metadata_lossy() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_LENGTH, HeaderValue::from_static("1024"));
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+        headers.insert(ETAG, HeaderValue::from_static("\"abc\""));
+        headers.insert(LAST_MODIFIED, HeaderValue::from_static("not a time"));
+        headers.insert(CONTENT_RANGE, HeaderValue::from_static("bytes 1-0/1024"));
+
+        assert!(parse_into_metadata("test", &headers).is_err());
+
+        let meta = parse_into_metadata_lossy("test", &headers);
+        assert_eq!(meta.content_length(), 1024);
+        assert_eq!(meta.content_type(), Some("text/plain"));
+        assert_eq!(meta.etag(), Some("\"abc\""));
+        assert!(!meta.bit().contains(Metakey::LastModified));
+        assert!(!meta.bit().contains(Metakey::ContentRange));
+    }
+
+    #[test]
+    fn test_parse_user_metadata() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("x-amz-meta-author"),
+            HeaderValue::from_static("xuanwo"),
+        );
+        headers.insert(
+            HeaderName::from_static("x-amz-meta-project"),
+            HeaderValue::from_static("opendal"),
+        );
+        headers.insert(
+            HeaderName::from_static("x-amz-meta-binary"),
+            HeaderValue::from_bytes(b"\xff\xfe").expect("must be valid header value"),
+        );
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+
+        let meta = parse_user_metadata(&headers, "X-Amz-Meta-");
+        assert_eq!(meta.len(), 2);
+        assert_eq!(meta.get("author").map(|v| v.as_str()), Some("xuanwo"));
+        assert_eq!(meta.get("project").map(|v| v.as_str()), Some("opendal"));
+        assert!(!meta.contains_key("binary"));
+
+        assert!(parse_user_metadata(&headers, "x-goog-meta-").is_empty());
+    }
+
+    #[test]
+    fn test_parse_storage_class() {
+        let cases = vec![
+            ("s3 standard", "STANDARD", StorageClass::Standard),
+            ("s3 ia", "STANDARD_IA", StorageClass::InfrequentAccess),
+            ("s3 glacier", "GLACIER", StorageClass::Glacier),
+            ("s3 deep archive", "DEEP_ARCHIVE", StorageClass::Archive),
+            ("gcs nearline", "NEARLINE", StorageClass::Nearline),
+            ("gcs coldline", "COLDLINE", StorageClass::Coldline),
+            ("azblob hot", "Hot", StorageClass::Standard),
+            ("azblob cool", "Cool", StorageClass::Cold),
+            (
+                "unknown",
+                "INTELLIGENT_TIERING",
+                StorageClass::Other("INTELLIGENT_TIERING".to_string()),
+            ),
+        ];
+
+        for (name, input, expected) in cases {
+            assert_eq!(parse_storage_class(input), expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_parse_content_length_from_range() -> Result<()> {
+        let cases = vec![
+            (
+                "partial content",
+                vec![(CONTENT_LENGTH, "100"), (CONTENT_RANGE, "bytes 0-99/1000")],
+                Some(1000),
+            ),
+            (
+                "unsatisfiable",
+                vec![(CONTENT_LENGTH, "0"), (CONTENT_RANGE, "bytes */1000")],
+                Some(1000),
+            ),
+            (
+                "unknown total size",
+                vec![(CONTENT_LENGTH, "100"), (CONTENT_RANGE, "bytes 0-99/*")],
+                None,
+            ),
+            ("ok", vec![(CONTENT_LENGTH, "1000")], Some(1000)),
+            ("empty", vec![], None),
+        ];
+
+        for (name, input, expected) in cases {
+            let mut headers = HeaderMap::new();
+            for (k, v) in input {
+                headers.insert(k, HeaderValue::from_static(v));
+            }
+
+            assert_eq!(
+                parse_content_length_from_range(&headers)?,
+                expected,
+                "{name}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_header() -> Result<()> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("x-amz-version-id"),
+            HeaderValue::from_static("3/L4kqtJlcpXroDTDmJ+rmSpXd3dIbrHY"),
+        );
+
+        assert_eq!(
+            parse_header(&headers, "x-amz-version-id")?,
+            Some("3/L4kqtJlcpXroDTDmJ+rmSpXd3dIbrHY")
+        );
+        assert_eq!(parse_header(&headers, "x-goog-generation")?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_delete_marker() -> Result<()> {
+        let cases = vec![
+            ("true", Some("true"), true),
+            ("upper case", Some("TRUE"), true),
+            ("false", Some("false"), false),
+            ("absent", None, false),
+        ];
+
+        for (name, input, expected) in cases {
+            let mut headers = HeaderMap::new();
+            if let Some(v) = input {
+                headers.insert(
+                    HeaderName::from_static("x-amz-delete-marker"),
+                    HeaderValue::from_static(v),
+                );
+            }
+
+            let mut meta = parse_into_metadata("test", &headers)?;
+            assert!(!meta.is_deleted(), "{name}");
+
+            if parse_delete_marker(&headers, "x-amz-delete-marker")? {
+                meta.set_is_deleted(true);
+            }
+            assert_eq!(meta.is_deleted(), expected, "{name}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_cache_control() -> Result<()> {
+        let cases = vec![
+            ("max-age=3600, public", Some("max-age=3600, public")),
+            ("no-cache", Some("no-cache")),
+        ];
+
+        for (input, expected) in cases {
+            let mut headers = HeaderMap::new();
+            headers.insert(CACHE_CONTROL, HeaderValue::from_static(input));
+
+            assert_eq!(parse_cache_control(&headers)?, expected);
+
+            let meta = parse_into_metadata("test", &headers)?;
+            assert_eq!(meta.cache_control(), expected);
+        }
+
+        assert_eq!(parse_cache_control(&HeaderMap::new())?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_content_range() -> Result<()> {
+        let cases = vec![
+            (
+                "bytes */500",
+                BytesContentRange::default().with_size(500),
+                true,
+            ),
+            (
+                "bytes 0-499/1234",
+                BytesContentRange::default()
+                    .with_range(0, 499)
+                    .with_size(1234),
+                false,
+            ),
+            (
+                "bytes 0-499/*",
+                BytesContentRange::default().with_range(0, 499),
+                false,
+            ),
+        ];
+
+        for (input, expected, unsatisfiable) in cases {
+            let mut headers = HeaderMap::new();
+            headers.insert(CONTENT_RANGE, HeaderValue::from_static(input));
+
+            let actual = parse_content_range(&headers)?.expect("must have content range");
+            assert_eq!(actual, expected, "{input}");
+            assert_eq!(actual.is_unsatisfiable(), unsatisfiable, "{input}");
+        }
+
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_RANGE, HeaderValue::from_static("bytes */*"));
+        assert!(parse_content_range(&headers).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_content_md5_checked() -> Result<()> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("content-md5"),
+            HeaderValue::from_static("WOctCY1SS662e7ziElh4cw=="),
+        );
+        assert_eq!(
+            parse_content_md5_checked(&headers)?,
+            Some("WOctCY1SS662e7ziElh4cw==")
+        );
+
+        assert_eq!(parse_content_md5_checked(&HeaderMap::new())?, None);
+
+        let cases = vec![
+            ("hex digest", "58e72d098d524baeb67bbce212587873"),
+            ("truncated base64", "WOctCY1SS662e7zi"),
+            ("empty", ""),
+        ];
+        for (name, input) in cases {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                HeaderName::from_static("content-md5"),
+                HeaderValue::from_static(input),
+            );
+
+            let err = parse_content_md5_checked(&headers).expect_err(name);
+            assert_eq!(err.kind(), ErrorKind::Unexpected, "{name}");
+            assert!(err.to_string().contains(input), "{name}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_last_modified() -> Result<()> {
+        let expected = OffsetDateTime::from_unix_timestamp(1680231723).expect("must be valid");
+
+        let cases = vec![
+            ("rfc2822", "Fri, 31 Mar 2023 03:02:03 GMT", expected),
+            ("rfc3339 with z", "2023-03-31T03:02:03Z", expected),
+            (
+                "rfc3339 with fractional seconds and offset",
+                "2023-03-31T03:02:03.250+00:00",
+                expected + time::Duration::milliseconds(250),
+            ),
+        ];
+
+        for (name, input, expected) in cases {
+            let mut headers = HeaderMap::new();
+            headers.insert(LAST_MODIFIED, HeaderValue::from_static(input));
+
+            assert_eq!(parse_last_modified(&headers)?, Some(expected), "{name}");
+        }
+
+        let mut headers = HeaderMap::new();
+        headers.insert(LAST_MODIFIED, HeaderValue::from_static("yesterday"));
+        assert!(parse_last_modified(&headers).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_expires() -> Result<()> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            EXPIRES,
+            HeaderValue::from_static("Fri, 31 Mar 2023 03:02:03 GMT"),
+        );
+        assert_eq!(
+            parse_expires(&headers)?,
+            Some(OffsetDateTime::from_unix_timestamp(1680231723).expect("must be valid"))
+        );
+
+        let meta = parse_into_metadata("foo", &headers)?;
+        assert_eq!(
+            meta.expires(),
+            Some(OffsetDateTime::from_unix_timestamp(1680231723).expect("must be valid"))
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(EXPIRES, HeaderValue::from_static("0"));
+        assert!(parse_expires(&headers).is_err());
+        // Invalid expires means already expired.
+        let meta = parse_into_metadata("foo", &headers)?;
+        assert_eq!(meta.expires(), Some(OffsetDateTime::UNIX_EPOCH));
+        let meta = parse_into_metadata_lossy("foo", &headers);
+        assert_eq!(meta.expires(), Some(OffsetDateTime::UNIX_EPOCH));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_etag_normalized() -> Result<()> {
+        let cases = vec![
+            ("strong", r#""abc""#, "abc", false),
+            ("weak", r#"W/"abc""#, "abc", true),
+            ("unquoted", "abc", "abc", false),
+            ("weak unquoted", "W/abc", "abc", true),
+            ("empty quoted", r#""""#, "", false),
+        ];
+
+        for (name, input, expected, weak) in cases {
+            let mut headers = HeaderMap::new();
+            headers.insert(ETAG, HeaderValue::from_static(input));
+
+            assert_eq!(parse_etag(&headers)?, Some(input), "{name}");
+            assert_eq!(parse_etag_normalized(&headers)?, Some(expected), "{name}");
+            assert_eq!(is_weak_etag(input), weak, "{name}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_etag_normalized_malformed() {
+        let cases = vec![
+            ("only weak prefix", "W/"),
+            ("missing closing quote", r#""abc"#),
+            ("missing opening quote", r#"abc""#),
+            ("single quote", r#"""#),
+            ("weak with unbalanced quote", r#"W/"abc"#),
+        ];
+
+        for (name, input) in cases {
+            let mut headers = HeaderMap::new();
+            headers.insert(ETAG, HeaderValue::from_static(input));
+
+            let err = parse_etag_normalized(&headers).expect_err(name);
+            assert_eq!(err.kind(), ErrorKind::Unexpected, "{name}");
+        }
+    }
+
+    /// Test cases is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_DeleteObjects.html
+    #[test]
+    fn test_format_content_md5() {
+        let cases = vec![(
             r#"<Delete>
 <Object>
  <Key>sample1.txt</Key>
@@ -299,6 +1485,94 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_retry_after_seconds() -> Result<()> {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+
+        assert_eq!(parse_retry_after(&headers)?, Some(Duration::from_secs(120)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_retry_after_http_date() -> Result<()> {
+        let mut headers = HeaderMap::new();
+        let future = format_http_date(OffsetDateTime::now_utc() + Duration::from_secs(60))?;
+        headers.insert(RETRY_AFTER, HeaderValue::from_str(&future).unwrap());
+
+        let delay = parse_retry_after(&headers)?.expect("must have retry after");
+        assert!(delay > Duration::from_secs(55), "{delay:?}");
+        assert!(delay <= Duration::from_secs(60), "{delay:?}");
+
+        // Dates in the past means retry now.
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Fri, 31 Mar 2023 03:02:03 GMT"),
+        );
+        assert_eq!(parse_retry_after(&headers)?, Some(Duration::ZERO));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_retry_after_absent() -> Result<()> {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers)?, None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("soon"));
+        assert!(parse_retry_after(&headers).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_http_date() -> Result<()> {
+        let t = OffsetDateTime::from_unix_timestamp(1680231723).expect("must be valid");
+
+        let cases = vec![
+            ("utc", t),
+            (
+                "with offset",
+                t.to_offset(UtcOffset::from_hms(8, 0, 0).expect("must be valid")),
+            ),
+        ];
+
+        for (name, input) in cases {
+            assert_eq!(
+                format_http_date(input)?,
+                "Fri, 31 Mar 2023 03:02:03 GMT",
+                "{name}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_content_range() -> Result<()> {
+        let cases = vec![
+            ("known total", (0, 1023, Some(4096)), "bytes 0-1023/4096"),
+            ("unknown total", (1024, 2047, None), "bytes 1024-2047/*"),
+            ("single byte", (0, 0, Some(1)), "bytes 0-0/1"),
+        ];
+
+        for (name, (start, end, total), expected) in cases {
+            let actual = format_content_range(start, end, total)?;
+
+            assert_eq!(actual, expected, "{name}")
+        }
+
+        let cases = vec![
+            ("start larger than end", (1, 0, None)),
+            ("end out of total", (0, 1, Some(1))),
+        ];
+        for (name, (start, end, total)) in cases {
+            let err = format_content_range(start, end, total).expect_err(name);
+
+            assert_eq!(err.kind(), ErrorKind::Unexpected, "{name}")
+        }
+
+        Ok(())
+    }
+
     /// Test cases is borrowed from
     ///
     /// - RFC2617: https://datatracker.ietf.org/doc/html/rfc2617#section-2
@@ -324,9 +1598,105 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_format_authorization_by_basic_invalid() {
+        let cases = vec![
+            ("empty username", "", "opensesame"),
+            ("newline in username", "alad\ndin", "opensesame"),
+            ("newline in password", "aladdin", "open\nsesame"),
+            ("del in password", "aladdin", "open\x7fsesame"),
+        ];
+
+        for (name, username, password) in cases {
+            let err =
+                format_authorization_by_basic(username, password).expect_err("format must fail");
+
+            assert_eq!(err.kind(), ErrorKind::Unexpected, "{name}");
+        }
+    }
+
+    /// Test cases is borrowed from
+    ///
+    /// - AWS SigV4 test suite: https://docs.aws.amazon.com/general/latest/gr/signature-v4-test-suite.html
+    #[test]
+    fn test_format_authorization_by_aws_sigv4() {
+        let cases = vec![
+            (
+                "get-vanilla",
+                ("AKIDEXAMPLE", "20150830", "us-east-1", "service"),
+                vec!["host", "x-amz-date"],
+                "5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31",
+                "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, SignedHeaders=host;x-amz-date, Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31",
+            ),
+            (
+                "iam with unsorted headers",
+                ("AKIDEXAMPLE", "20150830", "us-east-1", "iam"),
+                vec!["X-Amz-Date", "Host", "Content-Type"],
+                "5d672d79c15b13162d9279b0855cfba6789a8edb4c82c400e06b5924a6f2b5d7",
+                "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/iam/aws4_request, SignedHeaders=content-type;host;x-amz-date, Signature=5d672d79c15b13162d9279b0855cfba6789a8edb4c82c400e06b5924a6f2b5d7",
+            ),
+        ];
+
+        for (name, (ak, date, region, service), headers, signature, expected) in cases {
+            let actual =
+                format_authorization_by_aws_sigv4(ak, date, region, service, &headers, signature)
+                    .expect("format must success");
+
+            assert_eq!(actual, expected, "{name}")
+        }
+    }
+
+    #[test]
+    fn test_format_authorization_by_aws_sigv4_invalid() {
+        let cases = vec![
+            ("empty access key", ("", "us-east-1", "signature")),
+            ("empty region", ("AKIDEXAMPLE", "", "signature")),
+            ("empty signature", ("AKIDEXAMPLE", "us-east-1", "")),
+        ];
+
+        for (name, (ak, region, signature)) in cases {
+            let err = format_authorization_by_aws_sigv4(
+                ak,
+                "20150830",
+                region,
+                "s3",
+                &["host"],
+                signature,
+            )
+            .expect_err(name);
+
+            assert_eq!(err.kind(), ErrorKind::Unexpected, "{name}")
+        }
+    }
+
     /// Test cases is borrowed from
     ///
     /// - RFC6750: https://datatracker.ietf.org/doc/html/rfc6750
+    #[test]
+    fn test_format_authorization_by_aws_session_token() {
+        let cases = vec![
+            ("not set", None, None),
+            ("empty", Some(""), None),
+            ("blank", Some("  "), None),
+            (
+                "valid",
+                Some("FwoGZXIvYXdzEBYaDH"),
+                Some("FwoGZXIvYXdzEBYaDH"),
+            ),
+            ("trimmed", Some(" FwoGZXIvYXdz\n"), Some("FwoGZXIvYXdz")),
+        ];
+
+        for (name, token, expected) in cases {
+            let actual =
+                format_authorization_by_aws_session_token(token).expect("format must success");
+            assert_eq!(actual.as_deref(), expected, "{name}");
+        }
+
+        let err = format_authorization_by_aws_session_token(Some("FwoGZXIv\nYXdz"))
+            .expect_err("token with newline must be rejected");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+
     #[test]
     fn test_format_authorization_by_bearer() {
         let cases = vec![("mF_9.B5f-4.1JqM", "Bearer mF_9.B5f-4.1JqM")];
@@ -337,4 +1707,28 @@ mod tests {
             assert_eq!(actual, expected)
         }
     }
+
+    #[test]
+    fn test_insert_custom_headers() {
+        let mut req = Request::get("http://example.com")
+            .header(CONTENT_TYPE, "text/plain")
+            .body(())
+            .unwrap();
+
+        insert_custom_headers(&mut req, &[("x-tenant-id".to_string(), "a".to_string())])
+            .expect("must succeed");
+        assert_eq!(req.headers()["x-tenant-id"], "a");
+
+        for (name, value) in [
+            ("Authorization", "Bearer token"),
+            ("content-type", "text/html"),
+            ("invalid header", "a"),
+            ("x-feature", "invalid\nvalue"),
+        ] {
+            let err = insert_custom_headers(&mut req, &[(name.to_string(), value.to_string())])
+                .expect_err("must fail");
+            assert_eq!(err.kind(), ErrorKind::ConfigInvalid, "{name}");
+        }
+        assert_eq!(req.headers()[CONTENT_TYPE], "text/plain");
+    }
 }
@@ -26,27 +26,55 @@ mod client;
 pub use client::HttpClient;
 
 mod body;
+pub use body::slice_ignored_range;
 pub use body::AsyncBody;
 pub use body::Body;
 pub use body::IncomingAsyncBody;
 
 mod header;
+pub use header::format_authorization_by_aws_session_token;
+pub use header::format_authorization_by_aws_sigv4;
 pub use header::format_authorization_by_basic;
 pub use header::format_authorization_by_bearer;
 pub use header::format_content_md5;
+pub use header::format_content_range;
+pub use header::format_http_date;
+pub use header::guess_content_type_if_absent;
+pub use header::insert_custom_headers;
+pub use header::is_valid_content_md5;
+pub use header::is_weak_etag;
+pub use header::normalize_etag;
+pub use header::parse_cache_control;
 pub use header::parse_content_disposition;
+pub use header::parse_content_disposition_filename;
+pub use header::parse_content_encoding;
 pub use header::parse_content_length;
+pub use header::parse_content_length_from_range;
 pub use header::parse_content_md5;
+pub use header::parse_content_md5_checked;
 pub use header::parse_content_range;
 pub use header::parse_content_type;
+pub use header::parse_custom_headers;
+pub use header::parse_delete_marker;
 pub use header::parse_etag;
+pub use header::parse_etag_normalized;
+pub use header::parse_expires;
+pub use header::parse_header;
 pub use header::parse_into_metadata;
+pub use header::parse_into_metadata_lossy;
 pub use header::parse_last_modified;
 pub use header::parse_location;
+pub use header::parse_retry_after;
+pub use header::parse_storage_class;
+pub use header::parse_user_metadata;
 
 mod uri;
 pub use uri::percent_encode_path;
 
+mod request_id;
+pub(crate) use request_id::insert_request_id_header;
+pub use request_id::with_request_id_header;
+
 mod error;
 pub use error::new_request_build_error;
 pub use error::new_request_sign_error;
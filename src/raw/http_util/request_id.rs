@@ -0,0 +1,96 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::cell::RefCell;
+
+use http::HeaderMap;
+use http::HeaderName;
+use http::HeaderValue;
+
+thread_local! {
+    static REQUEST_ID: RefCell<Option<(HeaderName, HeaderValue)>> = const { RefCell::new(None) };
+}
+
+/// Call `f` with given request id.
+///
+/// All requests sent by [`HttpClient`][super::HttpClient] inside `f` will
+/// carry the request id in header `name` unless the header has been set.
+///
+/// Async operations should call this function in every poll, like
+/// [`RequestIdLayer`][crate::layers::RequestIdLayer] does.
+pub fn with_request_id_header<T>(name: &HeaderName, id: &HeaderValue, f: impl FnOnce() -> T) -> T {
+    /// Restore the previous request id even if `f` panics.
+    struct Guard(Option<(HeaderName, HeaderValue)>);
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            let prev = self.0.take();
+            REQUEST_ID.with(|v| *v.borrow_mut() = prev);
+        }
+    }
+
+    let prev = REQUEST_ID.with(|v| v.replace(Some((name.clone(), id.clone()))));
+    let _guard = Guard(prev);
+
+    f()
+}
+
+/// Insert the current request id into headers.
+pub(crate) fn insert_request_id_header(headers: &mut HeaderMap) {
+    REQUEST_ID.with(|v| {
+        if let Some((name, id)) = v.borrow().as_ref() {
+            if !headers.contains_key(name) {
+                headers.insert(name.clone(), id.clone());
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_request_id_header() {
+        let name = HeaderName::from_static("x-request-id");
+
+        let mut headers = HeaderMap::new();
+        insert_request_id_header(&mut headers);
+        assert!(headers.is_empty());
+
+        with_request_id_header(&name, &HeaderValue::from_static("outer"), || {
+            with_request_id_header(&name, &HeaderValue::from_static("inner"), || {
+                let mut headers = HeaderMap::new();
+                insert_request_id_header(&mut headers);
+                assert_eq!(headers.get(&name).unwrap(), "inner");
+            });
+
+            // Request id set by users will not be overwritten.
+            let mut headers = HeaderMap::new();
+            headers.insert(name.clone(), HeaderValue::from_static("user"));
+            insert_request_id_header(&mut headers);
+            assert_eq!(headers.get(&name).unwrap(), "user");
+
+            let mut headers = HeaderMap::new();
+            insert_request_id_header(&mut headers);
+            assert_eq!(headers.get(&name).unwrap(), "outer");
+        });
+
+        let mut headers = HeaderMap::new();
+        insert_request_id_header(&mut headers);
+        assert!(headers.is_empty());
+    }
+}
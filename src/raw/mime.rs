@@ -0,0 +1,104 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::raw::get_basename;
+
+/// Guess content type from the extension of given path.
+///
+/// Returns `None` if the path is a dir, has no extension or the extension
+/// is not known.
+pub fn guess_content_type(path: &str) -> Option<&'static str> {
+    if path.ends_with('/') {
+        return None;
+    }
+
+    let name = get_basename(path);
+    let ext = match name.rsplit_once('.') {
+        // Hidden files like `.png` don't have an extension.
+        Some((stem, ext)) if !stem.is_empty() => ext,
+        _ => return None,
+    };
+
+    let mime = match ext.to_ascii_lowercase().as_str() {
+        // Text
+        "css" => "text/css",
+        "csv" => "text/csv",
+        "htm" | "html" => "text/html",
+        "js" | "mjs" => "text/javascript",
+        "md" => "text/markdown",
+        "txt" | "log" => "text/plain",
+        "xml" => "application/xml",
+        // Application
+        "avro" => "application/avro",
+        "gz" => "application/gzip",
+        "json" => "application/json",
+        "ndjson" | "jsonl" => "application/x-ndjson",
+        "parquet" => "application/vnd.apache.parquet",
+        "pdf" => "application/pdf",
+        "tar" => "application/x-tar",
+        "toml" => "application/toml",
+        "wasm" => "application/wasm",
+        "yaml" | "yml" => "application/yaml",
+        "zip" => "application/zip",
+        "zst" => "application/zstd",
+        // Image
+        "bmp" => "image/bmp",
+        "gif" => "image/gif",
+        "ico" => "image/vnd.microsoft.icon",
+        "jpeg" | "jpg" => "image/jpeg",
+        "png" => "image/png",
+        "svg" => "image/svg+xml",
+        "webp" => "image/webp",
+        // Audio & Video
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        _ => return None,
+    };
+
+    Some(mime)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guess_content_type() {
+        let cases = vec![
+            ("json file", "foo.json", Some("application/json")),
+            ("upper case ext", "dir/FOO.JSON", Some("application/json")),
+            ("multiple dots", "abc/def.tar.gz", Some("application/gzip")),
+            ("xml", "a.xml", Some("application/xml")),
+            (
+                "parquet",
+                "warehouse/part-0.parquet",
+                Some("application/vnd.apache.parquet"),
+            ),
+            ("unknown ext", "bar.bin", None),
+            ("no ext", "abc/def", None),
+            ("dot in dir", "abc.json/def", None),
+            ("dir path", "abc.json/", None),
+            ("hidden file", ".png", None),
+        ];
+
+        for (name, input, expect) in cases {
+            assert_eq!(guess_content_type(input), expect, "{name}")
+        }
+    }
+}
@@ -49,6 +49,9 @@ pub use path::normalize_path;
 pub use path::normalize_root;
 pub use path::validate_path;
 
+mod mime;
+pub use mime::guess_content_type;
+
 mod operation;
 pub use operation::Operation;
 
@@ -0,0 +1,384 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cmp;
+use std::io::SeekFrom;
+use std::sync::Arc;
+
+use bytes::Bytes;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::*;
+
+/// Convert given blocking reader into [`oio::BlockingReader`] by range.
+///
+/// # Notes
+///
+/// This operation is not zero cost. If the accessor already returns a
+/// seekable reader, please don't use this.
+pub fn by_range<A: Accessor>(
+    acc: Arc<A>,
+    path: &str,
+    reader: A::BlockingReader,
+    offset: u64,
+    size: u64,
+) -> RangeReader<A> {
+    RangeReader {
+        acc,
+        path: path.to_string(),
+        offset,
+        size,
+        cur: 0,
+        reader: Some(reader),
+        sink: Vec::new(),
+        args: OpRead::new(),
+    }
+}
+
+/// RangeReader that can do seek on non-seekable blocking reader.
+pub struct RangeReader<A: Accessor> {
+    acc: Arc<A>,
+    path: String,
+
+    offset: u64,
+    size: u64,
+    cur: u64,
+    reader: Option<A::BlockingReader>,
+
+    /// sink is to consume bytes for seek optimize.
+    sink: Vec<u8>,
+    /// The args used to send new ranged reads.
+    args: OpRead,
+}
+
+impl<A: Accessor> RangeReader<A> {
+    /// Set the args used while sending new ranged reads, for example to
+    /// keep reading the same version. The range of args will be ignored.
+    pub fn with_args(mut self, args: OpRead) -> Self {
+        self.args = args;
+        self
+    }
+
+    /// Get the current reader, start a new ranged read if there is none.
+    fn reader(&mut self) -> Result<&mut A::BlockingReader> {
+        if self.reader.is_none() {
+            let op = self.args.clone().with_range(BytesRange::new(
+                Some(self.offset + self.cur),
+                Some(self.size - self.cur),
+            ));
+
+            // TODO
+            //
+            // we can use RpRead returned here to correct size.
+            let (_, r) = self.acc.blocking_read(&self.path, op)?;
+            self.reader = Some(r);
+        }
+
+        Ok(self.reader.as_mut().expect("reader must be valid"))
+    }
+
+    /// calculate the seek position.
+    ///
+    /// This operation will not update the `self.cur`.
+    fn seek_pos(&self, pos: SeekFrom) -> Result<u64> {
+        let (base, amt) = match pos {
+            SeekFrom::Start(n) => (0, n as i64),
+            SeekFrom::End(n) => (self.size as i64, n),
+            SeekFrom::Current(n) => (self.cur as i64, n),
+        };
+
+        match base.checked_add(amt) {
+            Some(n) if n >= 0 => Ok(n as u64),
+            _ => Err(Error::new(
+                ErrorKind::Unexpected,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+}
+
+impl<A: Accessor> oio::BlockingRead for RangeReader<A> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if self.reader.is_none() && self.cur >= self.size {
+            return Ok(0);
+        }
+
+        match self.reader()?.read(buf) {
+            Ok(0) => {
+                // Reset reader after all data has been consumed.
+                self.reader = None;
+                Ok(0)
+            }
+            Ok(n) => {
+                self.cur += n as u64;
+                Ok(n)
+            }
+            Err(e) => {
+                self.reader = None;
+                Err(e)
+            }
+        }
+    }
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let seek_pos = self.seek_pos(pos)?;
+
+        let r = match &mut self.reader {
+            Some(r) => r,
+            None => {
+                self.cur = seek_pos;
+                return Ok(self.cur);
+            }
+        };
+
+        // If the next seek pos is close enough, we can just
+        // read the cnt instead of dropping the reader.
+        //
+        // TODO: make this value configurable
+        if seek_pos > self.cur && seek_pos - self.cur < 1024 * 1024 {
+            while self.cur < seek_pos {
+                // 212992 is the default read mem buffer of archlinux.
+                // Ideally we should make this configurable.
+                //
+                // TODO: make this value configurable
+                let consume = cmp::min((seek_pos - self.cur) as usize, 212992);
+                self.sink.resize(consume, 0);
+
+                match r.read(&mut self.sink) {
+                    Ok(n) if n > 0 => self.cur += n as u64,
+                    // If we are hitting errors or EOF while read ahead.
+                    // It's better to drop this reader and seek to
+                    // correct position directly.
+                    _ => break,
+                }
+            }
+
+            if self.cur == seek_pos {
+                return Ok(self.cur);
+            }
+        } else if seek_pos == self.cur {
+            return Ok(self.cur);
+        }
+
+        // If we are trying to seek to far more away.
+        // Let's just drop the reader.
+        self.reader = None;
+        self.cur = seek_pos;
+        Ok(self.cur)
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        if self.reader.is_none() && self.cur >= self.size {
+            return None;
+        }
+
+        let r = match self.reader() {
+            Ok(r) => r,
+            Err(err) => return Some(Err(err)),
+        };
+
+        match r.next() {
+            Some(Ok(bs)) => {
+                self.cur += bs.len() as u64;
+                Some(Ok(bs))
+            }
+            Some(Err(err)) => {
+                self.reader = None;
+                Some(Err(err))
+            }
+            None => {
+                self.reader = None;
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+    use std::io::Read;
+    use std::io::Seek;
+
+    use async_trait::async_trait;
+    use rand::prelude::*;
+    use sha2::Digest;
+    use sha2::Sha256;
+
+    use super::*;
+
+    // Generate bytes between [4MiB, 16MiB)
+    fn gen_bytes() -> (Bytes, usize) {
+        let mut rng = thread_rng();
+
+        let size = rng.gen_range(4 * 1024 * 1024..16 * 1024 * 1024);
+        let mut content = vec![0; size];
+        rng.fill_bytes(&mut content);
+
+        (Bytes::from(content), size)
+    }
+
+    #[derive(Debug, Clone, Default)]
+    struct MockReadService {
+        data: Bytes,
+    }
+
+    impl MockReadService {
+        fn new(data: Bytes) -> Self {
+            Self { data }
+        }
+    }
+
+    #[async_trait]
+    impl Accessor for MockReadService {
+        type Reader = ();
+        type BlockingReader = MockReader;
+        type Writer = ();
+        type BlockingWriter = ();
+        type Pager = ();
+        type BlockingPager = ();
+
+        fn info(&self) -> AccessorInfo {
+            let mut am = AccessorInfo::default();
+            am.set_capabilities(AccessorCapability::Read | AccessorCapability::Blocking);
+
+            am
+        }
+
+        fn blocking_read(&self, _: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+            let bs = args.range().apply_on_bytes(self.data.clone());
+
+            Ok((
+                RpRead::new(bs.len() as u64),
+                MockReader {
+                    inner: std::io::Cursor::new(bs.into()),
+                },
+            ))
+        }
+    }
+
+    #[derive(Debug, Clone, Default)]
+    struct MockReader {
+        inner: std::io::Cursor<Vec<u8>>,
+    }
+
+    impl oio::BlockingRead for MockReader {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            self.inner.read(buf).map_err(|err| {
+                Error::new(ErrorKind::Unexpected, "read data from mock").set_source(err)
+            })
+        }
+
+        fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+            let _ = pos;
+
+            Err(Error::new(
+                ErrorKind::Unsupported,
+                "output reader doesn't support seeking",
+            ))
+        }
+
+        fn next(&mut self) -> Option<Result<Bytes>> {
+            let mut bs = vec![0; 4 * 1024];
+            match oio::BlockingRead::read(self, &mut bs) {
+                Ok(0) => None,
+                Ok(n) => Some(Ok(Bytes::from(bs[..n].to_vec()))),
+                Err(err) => Some(Err(err)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_all() -> anyhow::Result<()> {
+        let (bs, _) = gen_bytes();
+        let acc = Arc::new(MockReadService::new(bs.clone()));
+
+        let r = MockReader {
+            inner: std::io::Cursor::new(bs.to_vec()),
+        };
+        let r = Box::new(by_range(acc, "x", r, 0, bs.len() as u64)) as oio::BlockingReader;
+        let mut r = BufReader::new(r);
+
+        let mut buf = Vec::new();
+        r.read_to_end(&mut buf)?;
+        assert_eq!(bs.len(), buf.len(), "read size");
+        assert_eq!(
+            format!("{:x}", Sha256::digest(&bs)),
+            format!("{:x}", Sha256::digest(&buf)),
+            "read content"
+        );
+
+        let n = r.seek(SeekFrom::Start(0))?;
+        assert_eq!(n, 0, "seek position must be 0");
+
+        let mut buf = Vec::new();
+        r.read_to_end(&mut buf)?;
+        assert_eq!(bs.len(), buf.len(), "read twice size");
+        assert_eq!(
+            format!("{:x}", Sha256::digest(&bs)),
+            format!("{:x}", Sha256::digest(&buf)),
+            "read twice content"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_part() -> anyhow::Result<()> {
+        let (bs, _) = gen_bytes();
+        let acc = Arc::new(MockReadService::new(bs.clone()));
+
+        let r = MockReader {
+            inner: std::io::Cursor::new(bs[4096..4096 + 4096].to_vec()),
+        };
+        let mut r = Box::new(by_range(acc, "x", r, 4096, 4096)) as oio::BlockingReader;
+
+        let mut buf = Vec::new();
+        r.read_to_end(&mut buf)?;
+        assert_eq!(4096, buf.len(), "read size");
+        assert_eq!(
+            format!("{:x}", Sha256::digest(&bs[4096..4096 + 4096])),
+            format!("{:x}", Sha256::digest(&buf)),
+            "read content"
+        );
+
+        let n = r.seek(SeekFrom::Start(1024))?;
+        assert_eq!(1024, n, "seek to 1024");
+
+        let mut buf = vec![0; 1024];
+        r.read_exact(&mut buf)?;
+        assert_eq!(
+            format!("{:x}", Sha256::digest(&bs[4096 + 1024..4096 + 2048])),
+            format!("{:x}", Sha256::digest(&buf)),
+            "read after seek 1024"
+        );
+
+        let n = r.seek(SeekFrom::Current(1024))?;
+        assert_eq!(3072, n, "seek to 3072");
+
+        let mut buf = vec![0; 1024];
+        r.read_exact(&mut buf)?;
+        assert_eq!(
+            format!("{:x}", Sha256::digest(&bs[4096 + 3072..4096 + 3072 + 1024])),
+            format!("{:x}", Sha256::digest(&buf)),
+            "read after seek to 3072"
+        );
+
+        Ok(())
+    }
+}
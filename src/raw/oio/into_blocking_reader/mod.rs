@@ -18,6 +18,10 @@
 //! into_blocking_reader will provide different implementations to convert
 //! into [`oio::BlockingRead`][crate::raw::oio::BlockingRead]
 
+mod by_range;
+pub use by_range::by_range;
+pub use by_range::RangeReader;
+
 mod from_fd;
 pub use from_fd::from_fd;
 pub use from_fd::FdReader;
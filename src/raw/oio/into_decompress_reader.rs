@@ -0,0 +1,384 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cmp;
+use std::io;
+use std::io::SeekFrom;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use async_compression::futures::bufread::BrotliDecoder;
+use async_compression::futures::bufread::GzipDecoder;
+use async_compression::futures::bufread::ZlibDecoder;
+use async_compression::futures::bufread::ZstdDecoder;
+use bytes::Buf;
+use bytes::Bytes;
+use bytes::BytesMut;
+use futures::ready;
+use futures::AsyncBufRead;
+use futures::AsyncRead;
+
+use crate::raw::*;
+use crate::*;
+
+/// The max size of bytes returned by `poll_next` of [`DecompressReader`].
+const DECOMPRESS_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Convert given reader into a reader that decompresses the content
+/// encoded with given `Content-Encoding`.
+///
+/// # Notes
+///
+/// - `gzip` (and its alias `x-gzip`), `deflate`, `zstd` and `br` are
+///   supported, content encoded with `identity` will be returned as is.
+/// - The returned reader can't seek since the position of decompressed
+///   content can't be mapped back.
+pub fn into_decompress_reader<R: oio::Read>(r: R, encoding: &str) -> Result<DecompressReader<R>> {
+    let r = BufReader {
+        r,
+        buf: Bytes::new(),
+    };
+
+    let decoder = match encoding.trim().to_ascii_lowercase().as_str() {
+        "" | "identity" => Decoder::Identity(r),
+        "gzip" | "x-gzip" => {
+            let mut d = GzipDecoder::new(r);
+            // Concatenated gzip members are valid gzip content.
+            d.multiple_members(true);
+            Decoder::Gzip(d)
+        }
+        "deflate" => Decoder::Zlib(ZlibDecoder::new(r)),
+        "zstd" => Decoder::Zstd(ZstdDecoder::new(r)),
+        "br" => Decoder::Brotli(BrotliDecoder::new(r)),
+        _ => {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "content encoding is not supported to decompress",
+            )
+            .with_operation("oio::into_decompress_reader")
+            .with_context("content_encoding", encoding))
+        }
+    };
+
+    Ok(DecompressReader {
+        decoder,
+        buf: BytesMut::new(),
+    })
+}
+
+/// Reader that decompresses content while reading.
+pub struct DecompressReader<R> {
+    decoder: Decoder<R>,
+    buf: BytesMut,
+}
+
+enum Decoder<R> {
+    Identity(BufReader<R>),
+    Gzip(GzipDecoder<BufReader<R>>),
+    Zlib(ZlibDecoder<BufReader<R>>),
+    Zstd(ZstdDecoder<BufReader<R>>),
+    Brotli(BrotliDecoder<BufReader<R>>),
+}
+
+impl<R: oio::Read> oio::Read for DecompressReader<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        let res = match &mut self.decoder {
+            Decoder::Identity(r) => return r.r.poll_read(cx, buf),
+            Decoder::Gzip(d) => Pin::new(d).poll_read(cx, buf),
+            Decoder::Zlib(d) => Pin::new(d).poll_read(cx, buf),
+            Decoder::Zstd(d) => Pin::new(d).poll_read(cx, buf),
+            Decoder::Brotli(d) => Pin::new(d).poll_read(cx, buf),
+        };
+
+        Poll::Ready(ready!(res).map_err(parse_io_error))
+    }
+
+    fn poll_seek(&mut self, _: &mut Context<'_>, _: SeekFrom) -> Poll<Result<u64>> {
+        Poll::Ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "decompress reader doesn't support seeking",
+        )
+        .with_operation(oio::ReadOperation::Seek)))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        if let Decoder::Identity(r) = &mut self.decoder {
+            return r.r.poll_next(cx);
+        }
+
+        if self.buf.len() < DECOMPRESS_CHUNK_SIZE {
+            self.buf.resize(DECOMPRESS_CHUNK_SIZE, 0);
+        }
+
+        // Take the buffer out to read decompressed content into it.
+        let mut buf = std::mem::take(&mut self.buf);
+        let res = self.poll_read(cx, &mut buf);
+        self.buf = buf;
+
+        match ready!(res) {
+            Ok(0) => Poll::Ready(None),
+            Ok(n) => Poll::Ready(Some(Ok(self.buf.split_to(n).freeze()))),
+            Err(err) => Poll::Ready(Some(Err(err))),
+        }
+    }
+}
+
+/// Convert io error returned by decoders back to [`Error`].
+///
+/// Errors of the inner reader are returned as is, and others are caused by
+/// invalid content.
+fn parse_io_error(err: io::Error) -> Error {
+    if err.get_ref().map_or(false, |e| e.is::<Error>()) {
+        return *err
+            .into_inner()
+            .expect("inner error must exist")
+            .downcast::<Error>()
+            .expect("inner error must be opendal error");
+    }
+
+    Error::new(ErrorKind::Unexpected, "decompress content failed")
+        .with_operation(oio::ReadOperation::Read)
+        .set_source(err)
+}
+
+/// BufReader adapts [`oio::Read`] into [`AsyncBufRead`] which is required by
+/// decoders, chunks returned by `poll_next` are used as buffer directly.
+struct BufReader<R> {
+    r: R,
+    buf: Bytes,
+}
+
+impl<R: oio::Read> AsyncRead for BufReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.buf.is_empty() {
+            return self.r.poll_read(cx, buf).map_err(io::Error::from);
+        }
+
+        let n = cmp::min(buf.len(), self.buf.len());
+        buf[..n].copy_from_slice(&self.buf[..n]);
+        self.buf.advance(n);
+        Poll::Ready(Ok(n))
+    }
+}
+
+impl<R: oio::Read> AsyncBufRead for BufReader<R> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+
+        while this.buf.is_empty() {
+            match ready!(this.r.poll_next(cx)) {
+                Some(Ok(bs)) => this.buf = bs,
+                Some(Err(err)) => return Poll::Ready(Err(err.into())),
+                None => break,
+            }
+        }
+
+        Poll::Ready(Ok(&this.buf))
+    }
+
+    fn consume(mut self: Pin<&mut Self>, amt: usize) {
+        self.buf.advance(amt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use oio::ReadExt;
+
+    use super::*;
+
+    const GZIP_FIXED: &[u8] = &[
+        0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0x33, 0xb0, 0x52, 0xf0, 0x48,
+        0xcd, 0xc9, 0xc9, 0xd7, 0x51, 0x08, 0xcf, 0x2f, 0xca, 0x49, 0x51, 0x54, 0x08, 0xc9, 0x48,
+        0x55, 0x28, 0x2c, 0xcd, 0x4c, 0xce, 0x56, 0x48, 0x2a, 0xca, 0x2f, 0xcf, 0x53, 0x48, 0xcb,
+        0xaf, 0x50, 0xc8, 0x2a, 0xcd, 0x2d, 0x28, 0x56, 0xc8, 0x2f, 0x4b, 0x2d, 0x52, 0x28, 0x01,
+        0x4a, 0xe7, 0x24, 0x56, 0x55, 0x2a, 0xa4, 0xe4, 0xa7, 0xeb, 0x71, 0x19, 0x52, 0xa4, 0xdb,
+        0x88, 0x22, 0xdd, 0xc6, 0x14, 0xe9, 0x36, 0xa1, 0x48, 0xb7, 0x29, 0x45, 0xba, 0xcd, 0x28,
+        0xd2, 0x6d, 0x4e, 0x91, 0x6e, 0x0b, 0x8a, 0x74, 0x5b, 0x52, 0x96, 0x5a, 0x0c, 0x28, 0xd3,
+        0x4e, 0x59, 0x6a, 0x33, 0xa4, 0x2c, 0xb9, 0x19, 0x52, 0x96, 0xde, 0x0c, 0x29, 0x4b, 0x70,
+        0x86, 0x94, 0xa5, 0x38, 0x43, 0xca, 0x92, 0x9c, 0x21, 0x65, 0x69, 0xce, 0x90, 0xb2, 0x44,
+        0x67, 0x48, 0x59, 0xaa, 0x33, 0xa2, 0x2c, 0xd5, 0x19, 0x51, 0x58, 0xc6, 0x51, 0x96, 0xea,
+        0x8c, 0x28, 0x4b, 0x75, 0x46, 0x94, 0xa5, 0x3a, 0x23, 0xca, 0x52, 0x9d, 0x11, 0x65, 0xa9,
+        0xce, 0x88, 0xb2, 0x54, 0x67, 0x44, 0x59, 0xaa, 0x33, 0xa2, 0x2c, 0xd5, 0x19, 0x53, 0x96,
+        0xea, 0x8c, 0x29, 0x4b, 0x75, 0x00, 0xbf, 0x74, 0xe0, 0x9d, 0xd6, 0x07, 0x00, 0x00,
+    ];
+    const GZIP_DYNAMIC: &[u8] = &[
+        0x1f, 0x8b, 0x08, 0x08, 0x00, 0x00, 0x00, 0x00, 0x02, 0xff, 0x68, 0x65, 0x6c, 0x6c, 0x6f,
+        0x2e, 0x74, 0x78, 0x74, 0x00, 0xa5, 0xd4, 0xb9, 0x0d, 0x02, 0x41, 0x10, 0x44, 0x51, 0x9f,
+        0x28, 0x0a, 0x1f, 0xa1, 0xed, 0x1e, 0xee, 0x08, 0x08, 0x00, 0x09, 0x1b, 0xd8, 0xe5, 0x1c,
+        0x68, 0x18, 0x58, 0xae, 0xe8, 0x21, 0x87, 0x6f, 0x97, 0xbe, 0xf7, 0x54, 0xd5, 0x4c, 0xf3,
+        0x26, 0xe7, 0xe8, 0x69, 0x19, 0x25, 0xd7, 0x5d, 0x2d, 0xf6, 0x8d, 0x6e, 0xed, 0x61, 0x73,
+        0xd2, 0xba, 0xc4, 0xeb, 0xa2, 0x6d, 0xbc, 0x75, 0x6c, 0xcf, 0xd7, 0xbb, 0xe2, 0xd9, 0x14,
+        0x3d, 0xfe, 0x73, 0x5e, 0x7d, 0x3f, 0xaa, 0x63, 0xd7, 0xef, 0x18, 0xaa, 0x1d, 0xd5, 0x09,
+        0xd5, 0x03, 0x54, 0x0f, 0x51, 0x3d, 0x42, 0xf5, 0x18, 0xd5, 0x13, 0x54, 0x4f, 0x99, 0x96,
+        0x8a, 0xe5, 0x4c, 0x9b, 0x31, 0x6e, 0xc6, 0xbc, 0x19, 0x03, 0x67, 0x4c, 0x9c, 0x31, 0x72,
+        0xc6, 0xcc, 0x19, 0x43, 0x67, 0x4c, 0x9d, 0x33, 0x75, 0x0e, 0x3f, 0x8e, 0xa9, 0x73, 0xa6,
+        0xce, 0x99, 0x3a, 0x67, 0xea, 0x9c, 0xa9, 0x73, 0xa6, 0xce, 0x99, 0x3a, 0x67, 0xea, 0x12,
+        0x53, 0x97, 0x98, 0xba, 0x1f, 0xbf, 0x74, 0xe0, 0x9d, 0xd6, 0x07, 0x00, 0x00,
+    ];
+    const ZLIB: &[u8] = &[
+        0x78, 0xda, 0xa5, 0xd4, 0xb9, 0x0d, 0x02, 0x41, 0x10, 0x44, 0x51, 0x9f, 0x28, 0x0a, 0x1f,
+        0xa1, 0xed, 0x1e, 0xee, 0x08, 0x08, 0x00, 0x09, 0x1b, 0xd8, 0xe5, 0x1c, 0x68, 0x18, 0x58,
+        0xae, 0xe8, 0x21, 0x87, 0x6f, 0x97, 0xbe, 0xf7, 0x54, 0xd5, 0x4c, 0xf3, 0x26, 0xe7, 0xe8,
+        0x69, 0x19, 0x25, 0xd7, 0x5d, 0x2d, 0xf6, 0x8d, 0x6e, 0xed, 0x61, 0x73, 0xd2, 0xba, 0xc4,
+        0xeb, 0xa2, 0x6d, 0xbc, 0x75, 0x6c, 0xcf, 0xd7, 0xbb, 0xe2, 0xd9, 0x14, 0x3d, 0xfe, 0x73,
+        0x5e, 0x7d, 0x3f, 0xaa, 0x63, 0xd7, 0xef, 0x18, 0xaa, 0x1d, 0xd5, 0x09, 0xd5, 0x03, 0x54,
+        0x0f, 0x51, 0x3d, 0x42, 0xf5, 0x18, 0xd5, 0x13, 0x54, 0x4f, 0x99, 0x96, 0x8a, 0xe5, 0x4c,
+        0x9b, 0x31, 0x6e, 0xc6, 0xbc, 0x19, 0x03, 0x67, 0x4c, 0x9c, 0x31, 0x72, 0xc6, 0xcc, 0x19,
+        0x43, 0x67, 0x4c, 0x9d, 0x33, 0x75, 0x0e, 0x3f, 0x8e, 0xa9, 0x73, 0xa6, 0xce, 0x99, 0x3a,
+        0x67, 0xea, 0x9c, 0xa9, 0x73, 0xa6, 0xce, 0x99, 0x3a, 0x67, 0xea, 0x12, 0x53, 0x97, 0x98,
+        0xba, 0x1f, 0x09, 0x3f, 0xa9, 0x6b,
+    ];
+
+    fn content() -> Vec<u8> {
+        (0..32)
+            .flat_map(|i| {
+                format!("{i}: Hello, World! The quick brown fox jumps over the lazy dog.\n")
+                    .into_bytes()
+            })
+            .collect()
+    }
+
+    /// Decompress given content which is fed in chunks of `cap` bytes.
+    async fn decompress(encoding: &str, compressed: &[u8], cap: usize) -> Result<Vec<u8>> {
+        let r = oio::into_streamable_reader(oio::Cursor::from(compressed.to_vec()), cap);
+        let mut r = into_decompress_reader(r, encoding)?;
+
+        let mut bs = Vec::new();
+        while let Some(b) = r.next().await {
+            bs.extend_from_slice(&b?);
+        }
+        Ok(bs)
+    }
+
+    #[tokio::test]
+    async fn test_decompress_gzip() {
+        for fixture in [GZIP_FIXED, GZIP_DYNAMIC] {
+            for cap in [1, 7, 64, 4096] {
+                let bs = decompress("gzip", fixture, cap)
+                    .await
+                    .expect("decompress must succeed");
+                assert_eq!(bs, content(), "cap: {cap}");
+            }
+        }
+    }
+
+    /// Compress given content with encoders of `async-compression`.
+    async fn compress(encoding: &str, content: &[u8]) -> Vec<u8> {
+        use async_compression::futures::bufread::BrotliEncoder;
+        use async_compression::futures::bufread::ZstdEncoder;
+        use futures::AsyncReadExt;
+
+        let mut bs = Vec::new();
+        match encoding {
+            "zstd" => ZstdEncoder::new(content).read_to_end(&mut bs).await,
+            "br" => BrotliEncoder::new(content).read_to_end(&mut bs).await,
+            _ => unreachable!("encoding {encoding} is not used in tests"),
+        }
+        .expect("compress must succeed");
+        bs
+    }
+
+    #[tokio::test]
+    async fn test_decompress_zstd_and_br() {
+        for encoding in ["zstd", "br"] {
+            let compressed = compress(encoding, &content()).await;
+            for cap in [1, 7, 4096] {
+                let bs = decompress(encoding, &compressed, cap)
+                    .await
+                    .expect("decompress must succeed");
+                assert_eq!(bs, content(), "{encoding} with cap: {cap}");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_decompress_gzip_multi_members() {
+        let compressed = [GZIP_FIXED, GZIP_DYNAMIC].concat();
+        let bs = decompress("gzip", &compressed, 17)
+            .await
+            .expect("decompress must succeed");
+        assert_eq!(bs, [content(), content()].concat());
+    }
+
+    #[tokio::test]
+    async fn test_decompress_deflate() {
+        let bs = decompress("deflate", ZLIB, 5)
+            .await
+            .expect("decompress must succeed");
+        assert_eq!(bs, content());
+    }
+
+    #[tokio::test]
+    async fn test_decompress_identity() {
+        let bs = decompress("identity", b"Hello, World!", 4)
+            .await
+            .expect("read must succeed");
+        assert_eq!(bs, b"Hello, World!");
+    }
+
+    #[tokio::test]
+    async fn test_decompress_poll_read() {
+        let r = oio::Cursor::from(GZIP_DYNAMIC.to_vec());
+        let mut r = into_decompress_reader(r, "gzip").expect("must create");
+
+        let mut bs = Vec::new();
+        let mut buf = [0; 100];
+        loop {
+            let n = r.read(&mut buf).await.expect("read must succeed");
+            if n == 0 {
+                break;
+            }
+            bs.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(bs, content());
+    }
+
+    #[tokio::test]
+    async fn test_decompress_invalid() {
+        let err = into_decompress_reader(oio::Cursor::from(Vec::new()), "compress")
+            .err()
+            .expect("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        // Truncated content.
+        let err = decompress("gzip", &GZIP_DYNAMIC[..100], 16)
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        // Corrupted checksum.
+        let mut corrupted = GZIP_DYNAMIC.to_vec();
+        let n = corrupted.len();
+        corrupted[n - 5] ^= 0xff;
+        let err = decompress("gzip", &corrupted, 16)
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        // Not gzip at all.
+        let err = decompress("gzip", b"Hello, World!", 16)
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+}
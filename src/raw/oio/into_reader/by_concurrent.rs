@@ -0,0 +1,392 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cmp;
+use std::io::SeekFrom;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::Bytes;
+use bytes::BytesMut;
+use futures::ready;
+use futures::stream;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+
+use crate::ops::*;
+use crate::raw::oio::ReadExt;
+use crate::raw::*;
+use crate::*;
+
+/// Convert given range into [`oio::Reader`] that prefetches chunks
+/// concurrently.
+///
+/// # Notes
+///
+/// Content will be split into chunks of `chunk` bytes, and at most
+/// `concurrent` chunks will be fetched ahead of the consumer. So the
+/// memory usage is bounded by `chunk * concurrent`.
+///
+/// Chunks are always returned in order, errors of prefetched chunks will
+/// be returned when consumer reaches them. Outstanding requests will be
+/// cancelled while reader is dropped.
+pub fn by_concurrent<A: Accessor>(
+    acc: Arc<A>,
+    path: &str,
+    offset: u64,
+    size: u64,
+    chunk: u64,
+    concurrent: usize,
+) -> ConcurrentReader<A> {
+    debug_assert!(chunk > 0, "chunk size must be larger than 0");
+    debug_assert!(concurrent > 0, "concurrent must be larger than 0");
+
+    ConcurrentReader {
+        acc,
+        path: path.to_string(),
+        offset,
+        size,
+        chunk,
+        concurrent,
+        cur: 0,
+        stream: None,
+        buf: Bytes::new(),
+        args: OpRead::new(),
+    }
+}
+
+/// ConcurrentReader that prefetches ranges concurrently.
+pub struct ConcurrentReader<A: Accessor> {
+    acc: Arc<A>,
+    path: String,
+
+    offset: u64,
+    size: u64,
+    chunk: u64,
+    concurrent: usize,
+
+    /// The position that has been returned to the consumer.
+    cur: u64,
+    /// The stream is wrapped by `Mutex` to make reader Sync, it will only
+    /// be accessed via `get_mut` so there is no lock contention.
+    stream: Option<Mutex<BoxStream<'static, Result<Bytes>>>>,
+    /// Fetched chunk that has not been consumed yet.
+    buf: Bytes,
+    /// The args used to fetch chunks.
+    args: OpRead,
+}
+
+impl<A: Accessor> ConcurrentReader<A> {
+    /// Set the args used while fetching chunks, for example to keep
+    /// reading the same version. The range of args will be ignored.
+    pub fn with_args(mut self, args: OpRead) -> Self {
+        self.args = args;
+        self
+    }
+
+    /// Build the stream of chunks starting from current position.
+    fn chunk_stream(&self) -> BoxStream<'static, Result<Bytes>> {
+        let (acc, path, args) = (self.acc.clone(), self.path.clone(), self.args.clone());
+        let (start, end, chunk) = (self.offset + self.cur, self.offset + self.size, self.chunk);
+
+        let ranges = (start..end)
+            .step_by(chunk as usize)
+            .map(move |offset| BytesRange::new(Some(offset), Some(cmp::min(chunk, end - offset))));
+
+        stream::iter(ranges)
+            .map(move |range| {
+                let (acc, path) = (acc.clone(), path.clone());
+                let args = args.clone().with_range(range);
+
+                // Requests in flight are polled together while consumer is
+                // waiting for the next chunk, no task will be spawned.
+                fetch(acc, path, args)
+            })
+            .buffered(self.concurrent)
+            .boxed()
+    }
+
+    fn poll_chunk(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        if !self.buf.is_empty() {
+            return Poll::Ready(Some(Ok(self.buf.split_off(0))));
+        }
+
+        if self.cur >= self.size {
+            return Poll::Ready(None);
+        }
+
+        if self.stream.is_none() {
+            self.stream = Some(Mutex::new(self.chunk_stream()));
+        }
+        let stream = self
+            .stream
+            .as_mut()
+            .expect("stream must be initiated")
+            .get_mut()
+            .expect("lock must succeed");
+
+        match ready!(stream.poll_next_unpin(cx)) {
+            Some(Ok(bs)) => Poll::Ready(Some(Ok(bs))),
+            Some(Err(err)) => {
+                // Drop the stream so that the failed chunk will be fetched
+                // again if users keep reading.
+                self.stream = None;
+                Poll::Ready(Some(Err(err)))
+            }
+            None => {
+                self.stream = None;
+                Poll::Ready(None)
+            }
+        }
+    }
+}
+
+/// Fetch the whole range into memory.
+async fn fetch<A: Accessor>(acc: Arc<A>, path: String, args: OpRead) -> Result<Bytes> {
+    let range = args.range();
+    let (_, mut r) = acc.read(&path, args).await?;
+
+    let mut buf = BytesMut::with_capacity(range.size().unwrap_or_default() as usize);
+    while let Some(bs) = r.next().await {
+        buf.extend_from_slice(&bs?);
+    }
+
+    Ok(buf.freeze())
+}
+
+impl<A: Accessor> oio::Read for ConcurrentReader<A> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        if self.buf.is_empty() {
+            match ready!(self.poll_chunk(cx)) {
+                Some(Ok(bs)) => self.buf = bs,
+                Some(Err(err)) => return Poll::Ready(Err(err)),
+                None => return Poll::Ready(Ok(0)),
+            }
+        }
+
+        let n = cmp::min(buf.len(), self.buf.len());
+        buf[..n].copy_from_slice(&self.buf.split_to(n));
+        self.cur += n as u64;
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_seek(&mut self, _: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        let (base, amt) = match pos {
+            SeekFrom::Start(n) => (0, n as i64),
+            SeekFrom::End(n) => (self.size as i64, n),
+            SeekFrom::Current(n) => (self.cur as i64, n),
+        };
+
+        let seek_pos = match base.checked_add(amt) {
+            Some(n) if n >= 0 => n as u64,
+            _ => {
+                return Poll::Ready(Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "invalid seek to a negative or overflowing position",
+                )))
+            }
+        };
+
+        if seek_pos != self.cur {
+            // Drop all prefetched chunks and start over from the new position.
+            self.stream = None;
+            self.buf = Bytes::new();
+            self.cur = seek_pos;
+        }
+
+        Poll::Ready(Ok(self.cur))
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        let res = ready!(self.poll_chunk(cx));
+        if let Some(Ok(bs)) = &res {
+            self.cur += bs.len() as u64;
+        }
+        Poll::Ready(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use futures::AsyncReadExt;
+    use tokio::sync::Barrier;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct MockReadService {
+        data: Bytes,
+        /// Block requests until enough of them are in flight.
+        barrier: Option<Barrier>,
+        fail_offset: Option<u64>,
+        requests: AtomicUsize,
+        inflight: AtomicUsize,
+        max_inflight: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Accessor for MockReadService {
+        type Reader = oio::Cursor;
+        type BlockingReader = ();
+        type Writer = ();
+        type BlockingWriter = ();
+        type Pager = ();
+        type BlockingPager = ();
+
+        fn info(&self) -> AccessorInfo {
+            let mut am = AccessorInfo::default();
+            am.set_capabilities(AccessorCapability::Read);
+
+            am
+        }
+
+        async fn read(&self, _: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            let inflight = self.inflight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_inflight.fetch_max(inflight, Ordering::SeqCst);
+            if let Some(barrier) = &self.barrier {
+                barrier.wait().await;
+            }
+            self.inflight.fetch_sub(1, Ordering::SeqCst);
+
+            if args.range().offset().is_some() && args.range().offset() == self.fail_offset {
+                return Err(Error::new(ErrorKind::Unexpected, "injected error"));
+            }
+
+            let bs = args.range().apply_on_bytes(self.data.clone());
+            Ok((RpRead::new(bs.len() as u64), oio::Cursor::from(bs)))
+        }
+    }
+
+    fn gen_bytes(size: usize) -> Bytes {
+        Bytes::from((0..size).map(|v| (v % 251) as u8).collect::<Vec<_>>())
+    }
+
+    async fn read_all(r: impl oio::Read + 'static) -> Result<Vec<u8>> {
+        let mut r: oio::Reader = Box::new(r);
+        let mut content = Vec::new();
+        r.read_to_end(&mut content)
+            .await
+            .map_err(|err| Error::new(ErrorKind::Unexpected, "read failed").set_source(err))?;
+        Ok(content)
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_reader_prefetch_in_order() {
+        let data = gen_bytes(16 * 1024);
+        // Requests are blocked until 8 of them are in flight, so the read
+        // can only finish if chunks are really fetched concurrently.
+        let acc = Arc::new(MockReadService {
+            data: data.clone(),
+            barrier: Some(Barrier::new(8)),
+            ..Default::default()
+        });
+
+        let content = tokio::time::timeout(
+            Duration::from_secs(10),
+            read_all(by_concurrent(acc.clone(), "x", 0, 16 * 1024, 1024, 8)),
+        )
+        .await
+        .expect("chunks must be fetched concurrently")
+        .expect("read must succeed");
+
+        assert_eq!(content, data);
+        assert_eq!(acc.requests.load(Ordering::SeqCst), 16);
+        // Prefetching is bounded by the concurrent window.
+        assert_eq!(acc.max_inflight.load(Ordering::SeqCst), 8);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_reader_smaller_than_chunk() {
+        let data = gen_bytes(100);
+        let acc = Arc::new(MockReadService {
+            data: data.clone(),
+            ..Default::default()
+        });
+
+        let content = read_all(by_concurrent(acc.clone(), "x", 10, 90, 1024, 4))
+            .await
+            .expect("read must succeed");
+        assert_eq!(content, data[10..]);
+        assert_eq!(acc.requests.load(Ordering::SeqCst), 1);
+
+        let content = read_all(by_concurrent(acc, "x", 0, 0, 1024, 4))
+            .await
+            .expect("read must succeed");
+        assert!(content.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_reader_error_in_order() {
+        let data = gen_bytes(4096);
+        let acc = Arc::new(MockReadService {
+            data: data.clone(),
+            fail_offset: Some(2048),
+            ..Default::default()
+        });
+
+        let mut r = by_concurrent(acc, "x", 0, 4096, 1024, 4);
+
+        // Chunks before the failed one must be returned first.
+        for i in 0..2 {
+            let bs = r.next().await.expect("must have chunk").expect("must ok");
+            assert_eq!(bs, data.slice(i * 1024..(i + 1) * 1024));
+        }
+        let err = r
+            .next()
+            .await
+            .expect("must have chunk")
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_reader_seek() {
+        let data = gen_bytes(4096);
+        let acc = Arc::new(MockReadService {
+            data: data.clone(),
+            ..Default::default()
+        });
+
+        let mut r: oio::Reader = Box::new(by_concurrent(acc, "x", 0, 4096, 1024, 4));
+        let mut buf = vec![0; 100];
+        r.read_exact(&mut buf).await.expect("read must succeed");
+        assert_eq!(buf, data[..100]);
+
+        let pos = r
+            .seek(SeekFrom::Start(3000))
+            .await
+            .expect("seek must succeed");
+        assert_eq!(pos, 3000);
+        let mut content = Vec::new();
+        r.read_to_end(&mut content)
+            .await
+            .expect("read must succeed");
+        assert_eq!(content, data[3000..]);
+    }
+}
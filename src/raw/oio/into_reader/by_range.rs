@@ -23,6 +23,9 @@ use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
 
+use bytes::Buf;
+use bytes::Bytes;
+use bytes::BytesMut;
 use futures::future::BoxFuture;
 use futures::ready;
 use tokio::io::ReadBuf;
@@ -31,6 +34,10 @@ use crate::ops::*;
 use crate::raw::*;
 use crate::*;
 
+/// Forward seeks within this distance will read and discard the data
+/// instead of dropping the reader.
+const DRAIN_THRESHOLD: u64 = 1024 * 1024;
+
 /// Convert given reader into [`oio::Reader`] by range.
 ///
 /// # Notes
@@ -50,9 +57,14 @@ pub fn by_range<A: Accessor>(
         offset,
         size,
         cur: 0,
+        stream_pos: 0,
         state: State::Reading(reader),
         last_seek_pos: None,
         sink: Vec::new(),
+        buffer_size: 0,
+        window: BytesMut::new(),
+        window_start: 0,
+        args: OpRead::new(),
     }
 }
 
@@ -64,6 +76,10 @@ pub struct RangeReader<A: Accessor> {
     offset: u64,
     size: u64,
     cur: u64,
+    /// The position of underlying reader, only valid while `Reading`.
+    ///
+    /// It could be larger than `cur` if we seek back into the window.
+    stream_pos: u64,
     state: State<A::Reader>,
 
     /// Seek operation could return Pending which may lead
@@ -74,6 +90,16 @@ pub struct RangeReader<A: Accessor> {
     last_seek_pos: Option<u64>,
     /// sink is to consume bytes for seek optimize.
     sink: Vec<u8>,
+
+    /// The max size of window that keeps the latest read data.
+    buffer_size: usize,
+    /// window keeps the latest data read from underlying reader so that
+    /// seeking back into it doesn't need to send a new request.
+    window: BytesMut,
+    /// The position of the first byte in window.
+    window_start: u64,
+    /// The args used to send new ranged reads.
+    args: OpRead,
 }
 
 enum State<R: oio::Read> {
@@ -86,10 +112,26 @@ enum State<R: oio::Read> {
 unsafe impl<R: oio::Read> Sync for State<R> {}
 
 impl<A: Accessor> RangeReader<A> {
+    /// Set the size of window that keeps the latest read data.
+    ///
+    /// Seeking back within the window will be served from memory instead
+    /// of sending a new request. Default to `0` which disables the window.
+    pub fn with_buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size;
+        self
+    }
+
+    /// Set the args used while sending new ranged reads, for example to
+    /// keep reading the same version. The range of args will be ignored.
+    pub fn with_args(mut self, args: OpRead) -> Self {
+        self.args = args;
+        self
+    }
+
     fn read_future(&self) -> BoxFuture<'static, Result<(RpRead, A::Reader)>> {
         let acc = self.acc.clone();
         let path = self.path.clone();
-        let op = OpRead::default().with_range(BytesRange::new(
+        let op = self.args.clone().with_range(BytesRange::new(
             Some(self.offset + self.cur),
             Some(self.size - self.cur),
         ));
@@ -97,6 +139,51 @@ impl<A: Accessor> RangeReader<A> {
         Box::pin(async move { acc.read(&path, op).await })
     }
 
+    /// Send a new read request starts from `cur`.
+    fn start_reading(&mut self) {
+        self.state = State::Sending(self.read_future());
+        self.stream_pos = self.cur;
+
+        // Window must be continuous with underlying reader.
+        if self.window_end() != self.stream_pos {
+            self.window.clear();
+            self.window_start = self.stream_pos;
+        }
+    }
+
+    fn window_end(&self) -> u64 {
+        self.window_start + self.window.len() as u64
+    }
+
+    /// Get the data in window starts from `cur`.
+    fn window_data(&self) -> Option<&[u8]> {
+        if self.cur < self.window_start || self.cur >= self.window_end() {
+            return None;
+        }
+
+        Some(&self.window[(self.cur - self.window_start) as usize..])
+    }
+
+    /// Keep data read from underlying reader in window.
+    fn fill_window(&mut self, bs: &[u8]) {
+        if self.buffer_size == 0 {
+            return;
+        }
+
+        debug_assert_eq!(
+            self.window_end(),
+            self.stream_pos,
+            "window must be continuous with underlying reader"
+        );
+
+        self.window.extend_from_slice(bs);
+        if self.window.len() > self.buffer_size {
+            let excess = self.window.len() - self.buffer_size;
+            self.window.advance(excess);
+            self.window_start += excess as u64;
+        }
+    }
+
     /// calculate the seek position.
     ///
     /// This operation will not update the `self.cur`.
@@ -126,13 +213,20 @@ impl<A: Accessor> RangeReader<A> {
 
 impl<A: Accessor> oio::Read for RangeReader<A> {
     fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        if let Some(data) = self.window_data() {
+            let n = cmp::min(data.len(), buf.len());
+            buf[..n].copy_from_slice(&data[..n]);
+            self.cur += n as u64;
+            return Poll::Ready(Ok(n));
+        }
+
         match &mut self.state {
             State::Idle => {
                 if self.cur >= self.size {
                     return Poll::Ready(Ok(0));
                 }
 
-                self.state = State::Sending(self.read_future());
+                self.start_reading();
                 self.poll_read(cx, buf)
             }
             State::Sending(fut) => {
@@ -156,7 +250,9 @@ impl<A: Accessor> oio::Read for RangeReader<A> {
                     Poll::Ready(Ok(0))
                 }
                 Ok(n) => {
+                    self.fill_window(&buf[..n]);
                     self.cur += n as u64;
+                    self.stream_pos += n as u64;
                     Poll::Ready(Ok(n))
                 }
                 Err(e) => {
@@ -184,21 +280,28 @@ impl<A: Accessor> oio::Read for RangeReader<A> {
                 self.poll_seek(cx, SeekFrom::Start(seek_pos))
             }
             State::Reading(r) => {
-                if seek_pos == self.cur {
+                // If the seek pos is still in window (or at the position
+                // of underlying reader), we can serve it from window
+                // without touching the reader.
+                let window_start = if self.window.is_empty() {
+                    self.stream_pos
+                } else {
+                    self.window_start
+                };
+                if seek_pos >= window_start && seek_pos <= self.stream_pos {
+                    self.cur = seek_pos;
                     self.last_seek_pos = None;
                     return Poll::Ready(Ok(self.cur));
                 }
 
                 // If the next seek pos is close enough, we can just
                 // read the cnt instead of dropping the reader.
-                //
-                // TODO: make this value configurable
-                if seek_pos > self.cur && seek_pos - self.cur < 1024 * 1024 {
+                if seek_pos > self.stream_pos && seek_pos - self.stream_pos < DRAIN_THRESHOLD {
                     // 212992 is the default read mem buffer of archlinux.
                     // Ideally we should make this configurable.
                     //
                     // TODO: make this value configurable
-                    let consume = cmp::min((seek_pos - self.cur) as usize, 212992);
+                    let consume = cmp::min((seek_pos - self.stream_pos) as usize, 212992);
                     self.sink.reserve(consume);
 
                     let mut buf = ReadBuf::uninit(self.sink.spare_capacity_mut());
@@ -207,7 +310,12 @@ impl<A: Accessor> oio::Read for RangeReader<A> {
                     match ready!(Pin::new(r).poll_read(cx, buf.initialized_mut())) {
                         Ok(n) => {
                             assert!(n > 0, "consumed bytes must be valid");
-                            self.cur += n as u64;
+                            if self.buffer_size > 0 {
+                                let drained = buf.initialized()[..n].to_vec();
+                                self.fill_window(&drained);
+                            }
+                            self.stream_pos += n as u64;
+                            self.cur = self.stream_pos;
                             // Make sure the pos is absolute from start.
                             self.poll_seek(cx, SeekFrom::Start(seek_pos))
                         }
@@ -233,14 +341,20 @@ impl<A: Accessor> oio::Read for RangeReader<A> {
         }
     }
 
-    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<bytes::Bytes>>> {
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        if let Some(data) = self.window_data() {
+            let bs = Bytes::copy_from_slice(data);
+            self.cur += bs.len() as u64;
+            return Poll::Ready(Some(Ok(bs)));
+        }
+
         match &mut self.state {
             State::Idle => {
                 if self.cur >= self.size {
                     return Poll::Ready(None);
                 }
 
-                self.state = State::Sending(self.read_future());
+                self.start_reading();
                 self.poll_next(cx)
             }
             State::Sending(fut) => {
@@ -259,7 +373,9 @@ impl<A: Accessor> oio::Read for RangeReader<A> {
             }
             State::Reading(r) => match ready!(Pin::new(r).poll_next(cx)) {
                 Some(Ok(bs)) => {
+                    self.fill_window(&bs);
                     self.cur += bs.len() as u64;
+                    self.stream_pos += bs.len() as u64;
                     Poll::Ready(Some(Ok(bs)))
                 }
                 Some(Err(err)) => {
@@ -278,6 +394,8 @@ impl<A: Accessor> oio::Read for RangeReader<A> {
 #[cfg(test)]
 mod tests {
     use std::io::SeekFrom;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
 
     use async_trait::async_trait;
     use bytes::Bytes;
@@ -304,11 +422,16 @@ mod tests {
     #[derive(Debug, Clone, Default)]
     struct MockReadService {
         data: Bytes,
+        /// The count of read requests.
+        reads: Arc<AtomicUsize>,
     }
 
     impl MockReadService {
         fn new(data: Bytes) -> Self {
-            Self { data }
+            Self {
+                data,
+                reads: Arc::default(),
+            }
         }
     }
 
@@ -329,6 +452,7 @@ mod tests {
         }
 
         async fn read(&self, _: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
             let bs = args.range().apply_on_bytes(self.data.clone());
 
             Ok((
@@ -465,4 +589,74 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_seek_back_within_buffer() -> anyhow::Result<()> {
+        let (bs, _) = gen_bytes();
+        let acc = Arc::new(MockReadService::new(bs.clone()));
+
+        let r = MockReader {
+            inner: futures::io::Cursor::new(bs.to_vec()),
+        };
+        let mut r =
+            Box::new(by_range(acc.clone(), "x", r, 0, bs.len() as u64).with_buffer_size(4096))
+                as oio::Reader;
+
+        let mut buf = vec![0; 8192];
+        r.read_exact(&mut buf).await?;
+        assert_eq!(&buf[..], &bs[..8192], "read content");
+
+        // Seek back into the buffered window.
+        let n = r.seek(SeekFrom::Current(-2048)).await?;
+        assert_eq!(n, 6144, "seek back to 6144");
+        let mut buf = vec![0; 4096];
+        r.read_exact(&mut buf).await?;
+        assert_eq!(&buf[..], &bs[6144..10240], "read after seek back");
+
+        // Forward seek within threshold will drain the reader.
+        let n = r.seek(SeekFrom::Start(20480)).await?;
+        assert_eq!(n, 20480, "seek forward to 20480");
+        let n = r.seek(SeekFrom::Start(18432)).await?;
+        assert_eq!(n, 18432, "seek back to drained data");
+        let mut buf = vec![0; 4096];
+        r.read_exact(&mut buf).await?;
+        assert_eq!(&buf[..], &bs[18432..22528], "read after seek");
+
+        assert_eq!(acc.reads.load(Ordering::SeqCst), 0, "no new request");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_seek_back_beyond_buffer() -> anyhow::Result<()> {
+        let (bs, _) = gen_bytes();
+        let acc = Arc::new(MockReadService::new(bs.clone()));
+
+        let r = MockReader {
+            inner: futures::io::Cursor::new(bs.to_vec()),
+        };
+        let mut r =
+            Box::new(by_range(acc.clone(), "x", r, 0, bs.len() as u64).with_buffer_size(4096))
+                as oio::Reader;
+
+        let mut buf = vec![0; 8192];
+        r.read_exact(&mut buf).await?;
+
+        let n = r.seek(SeekFrom::Start(1024)).await?;
+        assert_eq!(n, 1024, "seek back to 1024");
+        let mut buf = vec![0; 8192];
+        r.read_exact(&mut buf).await?;
+        assert_eq!(&buf[..], &bs[1024..9216], "read after seek back");
+
+        assert_eq!(acc.reads.load(Ordering::SeqCst), 1, "one new request");
+
+        // The new request fills the window again.
+        let n = r.seek(SeekFrom::Current(-4096)).await?;
+        assert_eq!(n, 5120, "seek back to 5120");
+        let mut buf = vec![0; 1024];
+        r.read_exact(&mut buf).await?;
+        assert_eq!(&buf[..], &bs[5120..6144], "read after seek back");
+        assert_eq!(acc.reads.load(Ordering::SeqCst), 1, "no more request");
+
+        Ok(())
+    }
 }
@@ -37,6 +37,10 @@
 //! user call `poll_read` first, we can get the total_size from returning
 //! reader. In this way, we can save 40ms in average for every s3 read call.
 
+mod by_concurrent;
+pub use by_concurrent::by_concurrent;
+pub use by_concurrent::ConcurrentReader;
+
 mod by_range;
 pub use by_range::by_range;
 pub use by_range::RangeReader;
@@ -48,6 +48,10 @@ mod into_streamable;
 pub use into_streamable::into_streamable_reader;
 pub use into_streamable::IntoStreamableReader;
 
+mod into_decompress_reader;
+pub use into_decompress_reader::into_decompress_reader;
+pub use into_decompress_reader::DecompressReader;
+
 mod entry;
 pub use entry::Entry;
 
@@ -65,3 +69,7 @@ pub use to_flat_pager::ToFlatPager;
 mod to_hierarchy_pager;
 pub use to_hierarchy_pager::to_hierarchy_pager;
 pub use to_hierarchy_pager::ToHierarchyPager;
+
+mod to_sorted_pager;
+pub use to_sorted_pager::to_sorted_pager;
+pub use to_sorted_pager::ToSortedPager;
@@ -157,7 +157,7 @@ impl futures::AsyncRead for dyn Read {
     ) -> Poll<io::Result<usize>> {
         let this: &mut dyn Read = &mut *self;
         this.poll_read(cx, buf)
-            .map_err(|err| io::Error::new(io::ErrorKind::Interrupted, err))
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
     }
 }
 
@@ -169,7 +169,7 @@ impl futures::AsyncSeek for dyn Read {
     ) -> Poll<io::Result<u64>> {
         let this: &mut dyn Read = &mut *self;
         this.poll_seek(cx, pos)
-            .map_err(|err| io::Error::new(io::ErrorKind::Interrupted, err))
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
     }
 }
 
@@ -351,7 +351,7 @@ impl std::io::Read for dyn BlockingRead {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         let this: &mut dyn BlockingRead = &mut *self;
         this.read(buf)
-            .map_err(|err| io::Error::new(io::ErrorKind::Interrupted, err))
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
     }
 }
 
@@ -360,7 +360,7 @@ impl std::io::Seek for dyn BlockingRead {
     fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
         let this: &mut dyn BlockingRead = &mut *self;
         this.seek(pos)
-            .map_err(|err| io::Error::new(io::ErrorKind::Interrupted, err))
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
     }
 }
 
@@ -33,6 +33,7 @@ pub fn to_hierarchy_pager<P>(pager: P, path: &str) -> ToHierarchyPager<P> {
     ToHierarchyPager {
         pager,
         path,
+        start_after: None,
         visited: HashSet::default(),
     }
 }
@@ -50,15 +51,22 @@ pub fn to_hierarchy_pager<P>(pager: P, path: &str) -> ToHierarchyPager<P> {
 pub struct ToHierarchyPager<P> {
     pager: P,
     path: String,
+    start_after: Option<String>,
     visited: HashSet<String>,
 }
 
 impl<P> ToHierarchyPager<P> {
+    /// Only return entries whose path are greater than `start_after`.
+    pub fn with_start_after(mut self, start_after: Option<&str>) -> Self {
+        self.start_after = start_after.map(|v| v.to_string());
+        self
+    }
+
     /// TODO: use retain_mut instead after we bump MSRV to 1.61.
     fn filter_entries(&mut self, entries: Vec<oio::Entry>) -> Vec<oio::Entry> {
         entries
             .into_iter()
-            .filter_map(|mut e| {
+            .filter_map(|e| {
                 // If path is not started with prefix, drop it.
                 //
                 // Ideally, it should never happen. But we just tolerate
@@ -84,9 +92,12 @@ impl<P> ToHierarchyPager<P> {
 
                 // idx == path.len() means it's contain only one `/` at the
                 // end of path.
+                //
+                // The dir could have been returned as a common prefix of
+                // its children, skip it in this case.
                 if idx == e.path().len() {
-                    if !self.visited.contains(e.path()) {
-                        self.visited.insert(e.path().to_string());
+                    if !self.visited.insert(e.path().to_string()) {
+                        return None;
                     }
                     return Some(e);
                 }
@@ -103,8 +114,9 @@ impl<P> ToHierarchyPager<P> {
                         path.to_string()
                     };
 
-                    e.set_path(&path);
-                    e.set_mode(EntryMode::DIR);
+                    // Metadata of the child doesn't belong to the common
+                    // prefix, return a plain dir entry instead.
+                    let e = oio::Entry::new(&path, Metadata::new(EntryMode::DIR));
                     self.visited.insert(path);
 
                     return Some(e);
@@ -112,6 +124,10 @@ impl<P> ToHierarchyPager<P> {
 
                 None
             })
+            .filter(|e| match &self.start_after {
+                Some(start_after) => e.path() > start_after.as_str(),
+                None => true,
+            })
             .collect()
     }
 }
@@ -237,4 +253,29 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_blocking_list_dedup_common_prefixes() -> Result<()> {
+        let _ = env_logger::try_init();
+
+        // Dir markers could be returned after their children.
+        let pager = MockPager::new(&["a/b", "a/c/d", "a/", "a/c/", "b", "c/d/e", "c/"]);
+        let mut pager = to_hierarchy_pager(pager, "");
+
+        let mut entries = Vec::default();
+        while let Some(e) = pager.next()? {
+            entries.extend_from_slice(&e)
+        }
+
+        assert_eq!(
+            entries,
+            vec![
+                oio::Entry::new("a/", Metadata::new(EntryMode::DIR)),
+                oio::Entry::new("b", Metadata::new(EntryMode::FILE)),
+                oio::Entry::new("c/", Metadata::new(EntryMode::DIR)),
+            ]
+        );
+
+        Ok(())
+    }
 }
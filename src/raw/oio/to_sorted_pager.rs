@@ -0,0 +1,198 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::collections::VecDeque;
+
+use async_trait::async_trait;
+
+use crate::raw::*;
+use crate::*;
+
+/// to_sorted_pager is used to make a pager return entries in
+/// lexicographic order of their paths.
+pub fn to_sorted_pager<P>(pager: P) -> ToSortedPager<P> {
+    ToSortedPager {
+        pager,
+        ordered_pages: false,
+        buf: None,
+        page_size: 0,
+    }
+}
+
+/// ToSortedPager will sort entries returned by the underlying pager.
+///
+/// # Notes
+///
+/// By default, ToSortedPager has to buffer the whole listing in memory
+/// before returning the first page, which could be expensive for large
+/// dirs.
+///
+/// If pages are already returned in order (all entries of one page are
+/// less than the entries of next page), we only need to sort entries
+/// within every page, which is cheap.
+pub struct ToSortedPager<P> {
+    pager: P,
+    ordered_pages: bool,
+    /// The sorted entries of whole listing, only used while pages are not
+    /// ordered.
+    buf: Option<VecDeque<oio::Entry>>,
+    /// The largest page size returned by underlying pager, so that we can
+    /// return sorted entries in similar pages.
+    page_size: usize,
+}
+
+impl<P> ToSortedPager<P> {
+    /// Mark pages returned by the underlying pager are already ordered.
+    pub fn with_ordered_pages(mut self, ordered_pages: bool) -> Self {
+        self.ordered_pages = ordered_pages;
+        self
+    }
+
+    fn sort(entries: &mut [oio::Entry]) {
+        entries.sort_by(|a, b| a.path().cmp(b.path()));
+    }
+
+    fn push(&mut self, mut entries: Vec<oio::Entry>) {
+        self.page_size = self.page_size.max(entries.len());
+        self.buf
+            .get_or_insert_with(VecDeque::default)
+            .extend(entries.drain(..));
+    }
+
+    fn next_sorted_page(&mut self) -> Option<Vec<oio::Entry>> {
+        let buf = self.buf.as_mut().expect("buf must be valid");
+        if buf.is_empty() {
+            return None;
+        }
+
+        let n = self.page_size.max(1).min(buf.len());
+        Some(buf.drain(..n).collect())
+    }
+
+    fn finish(&mut self) {
+        let buf = self.buf.get_or_insert_with(VecDeque::default);
+        Self::sort(buf.make_contiguous());
+    }
+}
+
+#[async_trait]
+impl<P: oio::Page> oio::Page for ToSortedPager<P> {
+    async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.ordered_pages {
+            let mut entries = self.pager.next().await?;
+            if let Some(entries) = entries.as_mut() {
+                Self::sort(entries);
+            }
+            return Ok(entries);
+        }
+
+        if self.buf.is_none() {
+            while let Some(entries) = self.pager.next().await? {
+                self.push(entries);
+            }
+            self.finish();
+        }
+
+        Ok(self.next_sorted_page())
+    }
+}
+
+impl<P: oio::BlockingPage> oio::BlockingPage for ToSortedPager<P> {
+    fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+        if self.ordered_pages {
+            let mut entries = self.pager.next()?;
+            if let Some(entries) = entries.as_mut() {
+                Self::sort(entries);
+            }
+            return Ok(entries);
+        }
+
+        if self.buf.is_none() {
+            while let Some(entries) = self.pager.next()? {
+                self.push(entries);
+            }
+            self.finish();
+        }
+
+        Ok(self.next_sorted_page())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use oio::BlockingPage;
+
+    use super::*;
+
+    struct MockPager {
+        pages: VecDeque<Vec<&'static str>>,
+    }
+
+    impl MockPager {
+        fn new(pages: Vec<Vec<&'static str>>) -> Self {
+            Self {
+                pages: pages.into(),
+            }
+        }
+    }
+
+    impl BlockingPage for MockPager {
+        fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+            Ok(self.pages.pop_front().map(|page| {
+                page.into_iter()
+                    .map(|path| {
+                        if path.ends_with('/') {
+                            oio::Entry::new(path, Metadata::new(EntryMode::DIR))
+                        } else {
+                            oio::Entry::new(path, Metadata::new(EntryMode::FILE))
+                        }
+                    })
+                    .collect()
+            }))
+        }
+    }
+
+    fn collect(mut pager: impl BlockingPage) -> Vec<Vec<String>> {
+        let mut pages = Vec::new();
+        while let Some(entries) = pager.next().expect("must succeed") {
+            pages.push(entries.iter().map(|e| e.path().to_string()).collect());
+        }
+        pages
+    }
+
+    #[test]
+    fn test_sort_whole_listing() {
+        let pager = MockPager::new(vec![vec!["d", "b", "e"], vec!["a", "c"]]);
+
+        let pages = collect(to_sorted_pager(pager));
+        assert_eq!(pages, vec![vec!["a", "b", "c"], vec!["d", "e"]]);
+    }
+
+    #[test]
+    fn test_sort_ordered_pages() {
+        let pager = MockPager::new(vec![vec!["b/", "a", "c"], vec!["e", "d/"]]);
+
+        let pages = collect(to_sorted_pager(pager).with_ordered_pages(true));
+        assert_eq!(pages, vec![vec!["a", "b/", "c"], vec!["d/", "e"]]);
+    }
+
+    #[test]
+    fn test_sort_empty() {
+        let pager = MockPager::new(vec![]);
+
+        assert!(collect(to_sorted_pager(pager)).is_empty());
+    }
+}
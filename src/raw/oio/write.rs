@@ -33,12 +33,16 @@ pub enum WriteOperation {
     Append,
     /// Operation for [`Write::close`]
     Close,
+    /// Operation for [`Write::abort`]
+    Abort,
     /// Operation for [`BlockingWrite::write`]
     BlockingWrite,
     /// Operation for [`BlockingWrite::append`]
     BlockingAppend,
     /// Operation for [`BlockingWrite::close`]
     BlockingClose,
+    /// Operation for [`BlockingWrite::abort`]
+    BlockingAbort,
 }
 
 impl WriteOperation {
@@ -62,9 +66,11 @@ impl From<WriteOperation> for &'static str {
             Write => "Writer::write",
             Append => "Writer::append",
             Close => "Writer::close",
+            Abort => "Writer::abort",
             BlockingWrite => "BlockingWriter::write",
             BlockingAppend => "BlockingWriter::append",
             BlockingClose => "BlockingWriter::close",
+            BlockingAbort => "BlockingWriter::abort",
         }
     }
 }
@@ -89,6 +95,29 @@ pub trait Write: Unpin + Send + Sync {
 
     /// Close the writer and make sure all data has been flushed.
     async fn close(&mut self) -> Result<()>;
+
+    /// Abort the writer and clean up all data that has been appended.
+    ///
+    /// The writer MUST NOT be used after abort. Writers that can't clean
+    /// up written data will return `Unsupported`.
+    async fn abort(&mut self) -> Result<()> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "output writer doesn't support abort",
+        ))
+    }
+
+    /// Fetch the multipart state of current writer so that it can be
+    /// resumed later.
+    ///
+    /// Only writers that support [`AccessorCapability::ResumableWrite`][crate::raw::AccessorCapability::ResumableWrite]
+    /// need to implement this.
+    fn state(&self) -> Result<MultipartState> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "output writer doesn't support state",
+        ))
+    }
 }
 
 #[async_trait]
@@ -114,6 +143,13 @@ impl Write for () {
             "output writer doesn't support close",
         ))
     }
+
+    async fn abort(&mut self) -> Result<()> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "output writer doesn't support abort",
+        ))
+    }
 }
 
 /// `Box<dyn Write>` won't implement `Write` automatically. To make Writer
@@ -131,6 +167,14 @@ impl<T: Write + ?Sized> Write for Box<T> {
     async fn close(&mut self) -> Result<()> {
         (**self).close().await
     }
+
+    async fn abort(&mut self) -> Result<()> {
+        (**self).abort().await
+    }
+
+    fn state(&self) -> Result<MultipartState> {
+        (**self).state()
+    }
 }
 
 /// BlockingWriter is a type erased [`BlockingWrite`]
@@ -146,6 +190,17 @@ pub trait BlockingWrite: Send + Sync + 'static {
 
     /// Close the writer and make sure all data has been flushed.
     fn close(&mut self) -> Result<()>;
+
+    /// Abort the writer and clean up all data that has been appended.
+    ///
+    /// The writer MUST NOT be used after abort. Writers that can't clean
+    /// up written data will return `Unsupported`.
+    fn abort(&mut self) -> Result<()> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "output writer doesn't support abort",
+        ))
+    }
 }
 
 impl BlockingWrite for () {
@@ -170,6 +225,13 @@ impl BlockingWrite for () {
             "output writer doesn't support close",
         ))
     }
+
+    fn abort(&mut self) -> Result<()> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "output writer doesn't support abort",
+        ))
+    }
 }
 
 /// `Box<dyn BlockingWrite>` won't implement `BlockingWrite` automatically.
@@ -186,4 +248,8 @@ impl<T: BlockingWrite + ?Sized> BlockingWrite for Box<T> {
     fn close(&mut self) -> Result<()> {
         (**self).close()
     }
+
+    fn abort(&mut self) -> Result<()> {
+        (**self).abort()
+    }
 }
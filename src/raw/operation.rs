@@ -34,6 +34,10 @@ pub enum Operation {
     Stat,
     /// Operation for [`crate::raw::Accessor::delete`]
     Delete,
+    /// Operation for [`crate::raw::Accessor::copy`]
+    Copy,
+    /// Operation for [`crate::raw::Accessor::rename`]
+    Rename,
     /// Operation for [`crate::raw::Accessor::list`]
     List,
     /// Operation for [`crate::raw::Accessor::scan`]
@@ -52,6 +56,10 @@ pub enum Operation {
     BlockingStat,
     /// Operation for [`crate::raw::Accessor::blocking_delete`]
     BlockingDelete,
+    /// Operation for [`crate::raw::Accessor::blocking_copy`]
+    BlockingCopy,
+    /// Operation for [`crate::raw::Accessor::blocking_rename`]
+    BlockingRename,
     /// Operation for [`crate::raw::Accessor::blocking_list`]
     BlockingList,
     /// Operation for [`crate::raw::Accessor::blocking_scan`]
@@ -86,6 +94,8 @@ impl From<Operation> for &'static str {
             Operation::Write => "write",
             Operation::Stat => "stat",
             Operation::Delete => "delete",
+            Operation::Copy => "copy",
+            Operation::Rename => "rename",
             Operation::List => "list",
             Operation::Scan => "scan",
             Operation::Presign => "presign",
@@ -95,6 +105,8 @@ impl From<Operation> for &'static str {
             Operation::BlockingWrite => "blocking_write",
             Operation::BlockingStat => "blocking_stat",
             Operation::BlockingDelete => "blocking_delete",
+            Operation::BlockingCopy => "blocking_copy",
+            Operation::BlockingRename => "blocking_rename",
             Operation::BlockingList => "blocking_list",
             Operation::BlockingScan => "blocking_scan",
         }
@@ -218,6 +218,7 @@ pub fn validate_path(path: &str, mode: EntryMode) -> bool {
     match mode {
         EntryMode::FILE => !path.ends_with('/'),
         EntryMode::DIR => path.ends_with('/'),
+        EntryMode::SYMLINK => !path.ends_with('/'),
         EntryMode::Unknown => false,
     }
 }
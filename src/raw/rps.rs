@@ -23,6 +23,14 @@ use crate::*;
 #[derive(Debug, Clone, Default)]
 pub struct RpCreate {}
 
+/// Reply for `copy` operation
+#[derive(Debug, Clone, Default)]
+pub struct RpCopy {}
+
+/// Reply for `rename` operation
+#[derive(Debug, Clone, Default)]
+pub struct RpRename {}
+
 /// Reply for `delete` operation
 #[derive(Debug, Clone, Default)]
 pub struct RpDelete {}
@@ -153,9 +161,12 @@ impl RpBatch {
 }
 
 /// Batch results of `batch` operations.
+#[non_exhaustive]
 pub enum BatchedResults {
     /// results of delete batch operation
     Delete(Vec<(String, Result<RpDelete>)>),
+    /// results of write batch operation
+    Write(Vec<(String, Result<RpWrite>)>),
 }
 
 impl BatchedResults {
@@ -164,6 +175,7 @@ impl BatchedResults {
         use BatchedResults::*;
         match self {
             Delete(v) => v.len(),
+            Write(v) => v.len(),
         }
     }
 
@@ -172,6 +184,7 @@ impl BatchedResults {
         use BatchedResults::*;
         match self {
             Delete(v) => v.is_empty(),
+            Write(v) => v.is_empty(),
         }
     }
 
@@ -180,6 +193,7 @@ impl BatchedResults {
         use BatchedResults::*;
         match self {
             Delete(v) => v.iter().filter(|v| v.1.is_ok()).count(),
+            Write(v) => v.iter().filter(|v| v.1.is_ok()).count(),
         }
     }
 
@@ -188,6 +202,7 @@ impl BatchedResults {
         use BatchedResults::*;
         match self {
             Delete(v) => v.iter().filter(|v| v.1.is_err()).count(),
+            Write(v) => v.iter().filter(|v| v.1.is_err()).count(),
         }
     }
 }
@@ -25,6 +25,10 @@ use async_trait::async_trait;
 use http::header::HeaderName;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
+use http::header::IF_MATCH;
+use http::header::IF_MODIFIED_SINCE;
+use http::header::IF_NONE_MATCH;
+use http::header::IF_UNMODIFIED_SINCE;
 use http::Request;
 use http::Response;
 use http::StatusCode;
@@ -40,6 +44,7 @@ use crate::types::Metadata;
 use crate::*;
 
 const X_MS_BLOB_TYPE: &str = "x-ms-blob-type";
+const X_MS_ACCESS_TIER: &str = "x-ms-access-tier";
 
 /// Known endpoint suffix Azure Storage Blob services resource URI syntax.
 /// Azure public cloud: https://accountname.blob.core.windows.net
@@ -59,6 +64,7 @@ const KNOWN_AZBLOB_ENDPOINT_SUFFIX: &[&str] = &[
 ///
 /// - [x] read
 /// - [x] write
+/// - [x] append
 /// - [x] list
 /// - [x] scan
 /// - [ ] presign
@@ -471,14 +477,33 @@ impl Accessor for AzblobBackend {
         am.set_scheme(Scheme::Azblob)
             .set_root(&self.root)
             .set_name(&self.container)
-            .set_capabilities(Read | Write | List | Scan)
-            .set_hints(ReadStreamable);
+            .set_capabilities(
+                Read | Write
+                    | CreateDir
+                    | List
+                    | Scan
+                    | ConditionalStat
+                    | WriteWithIfMatch
+                    | WriteWithIfNoneMatch
+                    | WriteWithIfUnmodifiedSince
+                    | WriteCanAppend
+                    | DeleteWithIfUnmodifiedSince,
+            )
+            .set_hints(ReadStreamable)
+            .set_list_metakeys(
+                Metakey::ContentLength
+                    | Metakey::ContentMd5
+                    | Metakey::ContentType
+                    | Metakey::Etag
+                    | Metakey::LastModified,
+            );
 
         am
     }
 
     async fn create(&self, path: &str, _: OpCreate) -> Result<RpCreate> {
-        let mut req = self.azblob_put_blob_request(path, Some(0), None, AsyncBody::Empty)?;
+        let mut req =
+            self.azblob_put_blob_request(path, Some(0), &OpWrite::new(), AsyncBody::Empty)?;
 
         self.signer.sign(&mut req).map_err(new_request_sign_error)?;
 
@@ -496,7 +521,18 @@ impl Accessor for AzblobBackend {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let resp = self.azblob_get_blob(path, args.range()).await?;
+        let mut range = args.range();
+        if range.is_suffix() {
+            // azblob doesn't support read with suffix range,
+            // so we need to stat first to resolve the absolute range.
+            //
+            // ref: https://learn.microsoft.com/en-us/rest/api/storageservices/specifying-the-range-header-for-blob-service-operations
+            let meta = self.stat(path, OpStat::new()).await?.into_metadata();
+            range = range.resolve_suffix(meta.content_length());
+        }
+
+        let resp = self.azblob_get_blob(path, range).await?;
+        let resp = slice_ignored_range(resp, range)?;
 
         let status = resp.status();
 
@@ -524,18 +560,24 @@ impl Accessor for AzblobBackend {
         ))
     }
 
-    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         // Stat root always returns a DIR.
         if path == "/" {
             return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
         }
 
-        let resp = self.azblob_get_blob_properties(path).await?;
+        let resp = self.azblob_get_blob_properties(path, &args).await?;
 
         let status = resp.status();
 
         match status {
-            StatusCode::OK => parse_into_metadata(path, resp.headers()).map(RpStat::new),
+            StatusCode::OK => {
+                let mut meta = parse_into_metadata(path, resp.headers())?;
+                if let Some(v) = parse_header(resp.headers(), X_MS_ACCESS_TIER)? {
+                    meta.set_storage_class(parse_storage_class(v));
+                }
+                Ok(RpStat::new(meta))
+            }
             StatusCode::NOT_FOUND if path.ends_with('/') => {
                 Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
             }
@@ -543,8 +585,8 @@ impl Accessor for AzblobBackend {
         }
     }
 
-    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
-        let resp = self.azblob_delete_blob(path).await?;
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let resp = self.azblob_delete_blob(path, &args).await?;
 
         let status = resp.status();
 
@@ -597,16 +639,6 @@ impl AzblobBackend {
         let mut req = Request::get(&url);
 
         if !range.is_full() {
-            // azblob doesn't support read with suffix range.
-            //
-            // ref: https://learn.microsoft.com/en-us/rest/api/storageservices/specifying-the-range-header-for-blob-service-operations
-            if range.offset().is_none() && range.size().is_some() {
-                return Err(Error::new(
-                    ErrorKind::Unsupported,
-                    "azblob doesn't support read with suffix range",
-                ));
-            }
-
             req = req.header(http::header::RANGE, range.to_header());
         }
 
@@ -623,7 +655,7 @@ impl AzblobBackend {
         &self,
         path: &str,
         size: Option<usize>,
-        content_type: Option<&str>,
+        args: &OpWrite,
         body: AsyncBody,
     ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
@@ -641,10 +673,22 @@ impl AzblobBackend {
             req = req.header(CONTENT_LENGTH, size)
         }
 
-        if let Some(ty) = content_type {
+        if let Some(ty) = args.content_type() {
             req = req.header(CONTENT_TYPE, ty)
         }
 
+        if let Some(etag) = args.if_match() {
+            req = req.header(IF_MATCH, etag)
+        }
+
+        if let Some(etag) = args.if_none_match() {
+            req = req.header(IF_NONE_MATCH, etag)
+        }
+
+        if let Some(t) = args.if_unmodified_since() {
+            req = req.header(IF_UNMODIFIED_SINCE, format_http_date(t)?)
+        }
+
         req = req.header(HeaderName::from_static(X_MS_BLOB_TYPE), "BlockBlob");
 
         // Set body
@@ -653,7 +697,12 @@ impl AzblobBackend {
         Ok(req)
     }
 
-    async fn azblob_get_blob_properties(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+    /// Create an empty append blob, existing blob will be kept untouched.
+    pub fn azblob_init_append_blob_request(
+        &self,
+        path: &str,
+        args: &OpWrite,
+    ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!(
@@ -663,7 +712,70 @@ impl AzblobBackend {
             percent_encode_path(&p)
         );
 
-        let req = Request::head(&url);
+        let mut req = Request::put(&url)
+            .header(CONTENT_LENGTH, 0)
+            // Make sure we will not overwrite the blob created by others.
+            .header(IF_NONE_MATCH, "*");
+
+        if let Some(ty) = args.content_type() {
+            req = req.header(CONTENT_TYPE, ty)
+        }
+
+        req = req.header(HeaderName::from_static(X_MS_BLOB_TYPE), "AppendBlob");
+
+        let req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        Ok(req)
+    }
+
+    /// Append a block to the end of an existing append blob.
+    pub fn azblob_append_block_request(
+        &self,
+        path: &str,
+        size: usize,
+        body: AsyncBody,
+    ) -> Result<Request<AsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}/{}?comp=appendblock",
+            self.endpoint,
+            self.container,
+            percent_encode_path(&p)
+        );
+
+        let req = Request::put(&url)
+            .header(CONTENT_LENGTH, size)
+            .body(body)
+            .map_err(new_request_build_error)?;
+
+        Ok(req)
+    }
+
+    async fn azblob_get_blob_properties(
+        &self,
+        path: &str,
+        args: &OpStat,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}/{}",
+            self.endpoint,
+            self.container,
+            percent_encode_path(&p)
+        );
+
+        let mut req = Request::head(&url);
+
+        if let Some(etag) = args.if_none_match() {
+            req = req.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(t) = args.if_modified_since() {
+            req = req.header(IF_MODIFIED_SINCE, format_http_date(t)?);
+        }
 
         let mut req = req
             .body(AsyncBody::Empty)
@@ -674,7 +786,11 @@ impl AzblobBackend {
         self.client.send_async(req).await
     }
 
-    async fn azblob_delete_blob(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+    async fn azblob_delete_blob(
+        &self,
+        path: &str,
+        args: &OpDelete,
+    ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!(
@@ -684,7 +800,11 @@ impl AzblobBackend {
             percent_encode_path(&p)
         );
 
-        let req = Request::delete(&url);
+        let mut req = Request::delete(&url);
+
+        if let Some(t) = args.if_unmodified_since() {
+            req = req.header(IF_UNMODIFIED_SINCE, format_http_date(t)?);
+        }
 
         let mut req = req
             .body(AsyncBody::Empty)
@@ -904,4 +1024,94 @@ SharedAccessSignature=sv=2021-01-01&ss=b&srt=c&sp=rwdlaciytfx&se=2022-01-01T11:0
         assert_eq!(builder.account_name, None);
         assert_eq!(builder.account_key, None);
     }
+
+    #[tokio::test]
+    async fn test_write_and_delete_with_if_unmodified_since() {
+        use http::header::LAST_MODIFIED;
+        use http::HeaderMap;
+        use time::Duration;
+        use time::OffsetDateTime;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::Request;
+        use wiremock::ResponseTemplate;
+
+        use crate::ops::*;
+        use crate::raw::*;
+        use crate::*;
+
+        // Time the client has seen the blobs, `changed` has been modified
+        // after that.
+        let seen = OffsetDateTime::now_utc() - Duration::hours(1);
+        let unchanged = seen;
+        let changed = seen + Duration::minutes(30);
+
+        // Reject the request with `412` if the blob has been modified
+        // since `If-Unmodified-Since`, just like azblob.
+        let respond = |last_modified: OffsetDateTime| {
+            move |req: &Request| {
+                if let Some(v) = req.headers.get(&"if-unmodified-since".into()) {
+                    // Http date contains `,` which is split as multiple values.
+                    let v: Vec<_> = v.iter().map(|v| v.as_str()).collect();
+                    let mut headers = HeaderMap::new();
+                    headers.insert(LAST_MODIFIED, v.join(", ").parse().unwrap());
+                    let t = parse_last_modified(&headers)
+                        .expect("must be valid http date")
+                        .expect("must exist");
+                    if last_modified.unix_timestamp() > t.unix_timestamp() {
+                        return ResponseTemplate::new(412);
+                    }
+                }
+
+                match req.method.to_string().as_str() {
+                    "PUT" => ResponseTemplate::new(201),
+                    _ => ResponseTemplate::new(202),
+                }
+            }
+        };
+
+        let server = MockServer::start().await;
+        for name in ["unchanged", "changed"] {
+            let last_modified = if name == "changed" {
+                changed
+            } else {
+                unchanged
+            };
+            Mock::given(path(format!("/container/{name}")))
+                .respond_with(respond(last_modified))
+                .mount(&server)
+                .await;
+        }
+
+        let mut builder = AzblobBuilder::default();
+        builder
+            .endpoint(&server.uri())
+            .container("container")
+            .account_name("devstoreaccount1")
+            .account_key("Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==");
+        let op = Operator::new(builder).expect("must init").finish();
+        assert!(op.info().can_write_with_if_unmodified_since());
+        assert!(op.info().can_delete_with_if_unmodified_since());
+
+        let args = OpWrite::new().with_if_unmodified_since(seen);
+        op.write_with("unchanged", args.clone(), "Hello, World!")
+            .await
+            .expect("write unchanged must succeed");
+        let err = op
+            .write_with("changed", args, "Hello, World!")
+            .await
+            .expect_err("write changed must fail");
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+
+        let args = OpDelete::new().with_if_unmodified_since(seen);
+        op.delete_with("unchanged", args.clone())
+            .await
+            .expect("delete unchanged must succeed");
+        let err = op
+            .delete_with("changed", args)
+            .await
+            .expect_err("delete changed must fail");
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+    }
 }
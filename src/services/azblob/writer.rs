@@ -36,15 +36,87 @@ impl AzblobWriter {
     pub fn new(backend: AzblobBackend, op: OpWrite, path: String) -> Self {
         AzblobWriter { backend, op, path }
     }
+
+    async fn init_append_blob(&self) -> Result<()> {
+        let mut req = self
+            .backend
+            .azblob_init_append_blob_request(&self.path, &self.op)?;
+
+        self.backend
+            .signer
+            .sign(&mut req)
+            .map_err(new_request_sign_error)?;
+
+        let resp = self.backend.client.send_async(req).await?;
+
+        let status = resp.status();
+
+        match status {
+            // The blob has been created by others, we can append to it
+            // directly.
+            StatusCode::CREATED
+            | StatusCode::OK
+            | StatusCode::CONFLICT
+            | StatusCode::PRECONDITION_FAILED => {
+                resp.into_body().consume().await?;
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    /// Returns `Ok(false)` if the append blob doesn't exist yet.
+    async fn append_block(&self, bs: Bytes) -> Result<bool> {
+        let mut req =
+            self.backend
+                .azblob_append_block_request(&self.path, bs.len(), AsyncBody::Bytes(bs))?;
+
+        self.backend
+            .signer
+            .sign(&mut req)
+            .map_err(new_request_sign_error)?;
+
+        let resp = self.backend.client.send_async(req).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::CREATED | StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(true)
+            }
+            StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                Ok(false)
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
 }
 
 #[async_trait]
 impl oio::Write for AzblobWriter {
     async fn write(&mut self, bs: Bytes) -> Result<()> {
+        if self.op.append_existing() {
+            if self.append_block(bs.clone()).await? {
+                return Ok(());
+            }
+
+            self.init_append_blob().await?;
+            return if self.append_block(bs).await? {
+                Ok(())
+            } else {
+                Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "append blob not found after creation",
+                ))
+            };
+        }
+
         let mut req = self.backend.azblob_put_blob_request(
             &self.path,
             Some(bs.len()),
-            self.op.content_type(),
+            &self.op,
             AsyncBody::Bytes(bs),
         )?;
 
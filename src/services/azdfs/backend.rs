@@ -324,9 +324,13 @@ impl Accessor for AzdfsBackend {
             .set_root(&self.root)
             .set_name(&self.filesystem)
             .set_capabilities(
-                AccessorCapability::Read | AccessorCapability::Write | AccessorCapability::List,
+                AccessorCapability::Read
+                    | AccessorCapability::Write
+                    | AccessorCapability::CreateDir
+                    | AccessorCapability::List,
             )
-            .set_hints(AccessorHint::ReadStreamable);
+            .set_hints(AccessorHint::ReadStreamable)
+            .set_list_metakeys(Metakey::ContentLength | Metakey::Etag | Metakey::LastModified);
 
         am
     }
@@ -356,7 +360,18 @@ impl Accessor for AzdfsBackend {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let resp = self.azdfs_read(path, args.range()).await?;
+        let mut range = args.range();
+        if range.is_suffix() {
+            // azdfs doesn't support read with suffix range,
+            // so we need to stat first to resolve the absolute range.
+            //
+            // ref: https://learn.microsoft.com/en-us/rest/api/storageservices/specifying-the-range-header-for-blob-service-operations
+            let meta = self.stat(path, OpStat::new()).await?.into_metadata();
+            range = range.resolve_suffix(meta.content_length());
+        }
+
+        let resp = self.azdfs_read(path, range).await?;
+        let resp = slice_ignored_range(resp, range)?;
 
         let status = resp.status();
 
@@ -443,16 +458,6 @@ impl AzdfsBackend {
         let mut req = Request::get(&url);
 
         if !range.is_full() {
-            // azblob doesn't support read with suffix range.
-            //
-            // ref: https://learn.microsoft.com/en-us/rest/api/storageservices/specifying-the-range-header-for-blob-service-operations
-            if range.offset().is_none() && range.size().is_some() {
-                return Err(Error::new(
-                    ErrorKind::Unsupported,
-                    "azblob doesn't support read with suffix range",
-                ));
-            }
-
             req = req.header(http::header::RANGE, range.to_header());
         }
 
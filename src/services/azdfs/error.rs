@@ -94,7 +94,9 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
         }
     }
 
-    let mut err = Error::new(kind, &message).with_context("response", format!("{parts:?}"));
+    let mut err = Error::new(kind, &message)
+        .with_context("response", format!("{parts:?}"))
+        .with_status_code(parts.status);
 
     if retryable {
         err = err.set_temporary();
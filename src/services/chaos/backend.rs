@@ -0,0 +1,442 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use rand::Rng;
+
+use crate::ops::*;
+use crate::raw::*;
+use crate::services::Memory;
+use crate::*;
+
+/// Operations that chaos service can inject latency and errors into.
+const CHAOS_OPERATIONS: [Operation; 14] = [
+    Operation::Create,
+    Operation::Read,
+    Operation::Write,
+    Operation::Stat,
+    Operation::Delete,
+    Operation::List,
+    Operation::Scan,
+    Operation::BlockingCreate,
+    Operation::BlockingRead,
+    Operation::BlockingWrite,
+    Operation::BlockingStat,
+    Operation::BlockingDelete,
+    Operation::BlockingList,
+    Operation::BlockingScan,
+];
+
+/// In memory service with configurable latency and error injection.
+///
+/// This service is designed for testing layers like `RetryLayer` and
+/// `TimeoutLayer`, all data is stored in memory like [`Memory`].
+///
+/// # Capabilities
+///
+/// This service can be used to:
+///
+/// - [x] read
+/// - [x] write
+/// - [ ] ~~list~~
+/// - [x] scan
+/// - [ ] ~~presign~~
+/// - [x] blocking
+///
+/// # Configuration
+///
+/// All injections are configured per [`Operation`]:
+///
+/// - `latency`: Sleep for given duration before every call.
+/// - `failures`: Fail the first N calls, and succeed after that.
+/// - `error_ratio`: Fail calls randomly with given ratio in `[0.0, 1.0]`.
+///
+/// Injected errors are temporary, so they can be retried by `RetryLayer`.
+///
+/// You can refer to [`ChaosBuilder`]'s docs for more information.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use anyhow::Result;
+/// use opendal::layers::RetryLayer;
+/// use opendal::raw::Operation;
+/// use opendal::services::Chaos;
+/// use opendal::Operator;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<()> {
+///     let mut builder = Chaos::default();
+///     builder
+///         .latency(Operation::Read, Duration::from_millis(10))
+///         .failures(Operation::Stat, 2)
+///         .error_ratio(Operation::Write, 0.1);
+///
+///     let op: Operator = Operator::new(builder)?
+///         .layer(RetryLayer::new())
+///         .finish();
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Default, Debug, Clone)]
+pub struct ChaosBuilder {
+    latency: HashMap<Operation, Duration>,
+    failures: HashMap<Operation, usize>,
+    error_ratio: HashMap<Operation, f64>,
+}
+
+impl ChaosBuilder {
+    /// Set latency for given operation.
+    ///
+    /// Every call of this operation will sleep for `latency` first.
+    pub fn latency(&mut self, op: Operation, latency: Duration) -> &mut Self {
+        self.latency.insert(op, latency);
+        self
+    }
+
+    /// Set deterministic failures for given operation.
+    ///
+    /// The first `times` calls of this operation will fail, and all calls
+    /// after that will be passed to the underlying storage.
+    pub fn failures(&mut self, op: Operation, times: usize) -> &mut Self {
+        self.failures.insert(op, times);
+        self
+    }
+
+    /// Set error ratio for given operation.
+    ///
+    /// Every call of this operation will fail with probability `ratio`.
+    /// Deterministic failures set by [`ChaosBuilder::failures`] will be
+    /// consumed first.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if input ratio is not in `[0.0, 1.0]`.
+    pub fn error_ratio(&mut self, op: Operation, ratio: f64) -> &mut Self {
+        assert!(
+            (0.0..=1.0).contains(&ratio),
+            "error ratio must be in [0.0, 1.0]"
+        );
+
+        self.error_ratio.insert(op, ratio);
+        self
+    }
+}
+
+impl Builder for ChaosBuilder {
+    const SCHEME: Scheme = Scheme::Chaos;
+    type Accessor = ChaosBackend;
+
+    /// Keys are in the format of `<operation>_<injection>`, for example:
+    ///
+    /// - `read_latency_ms`: `100`
+    /// - `stat_failures`: `3`
+    /// - `blocking_write_error_ratio`: `0.5`
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = ChaosBuilder::default();
+
+        for op in CHAOS_OPERATIONS {
+            let name = op.into_static();
+
+            if let Some(v) = map
+                .get(&format!("{name}_latency_ms"))
+                .and_then(|v| v.parse::<u64>().ok())
+            {
+                builder.latency(op, Duration::from_millis(v));
+            }
+            if let Some(v) = map
+                .get(&format!("{name}_failures"))
+                .and_then(|v| v.parse::<usize>().ok())
+            {
+                builder.failures(op, v);
+            }
+            if let Some(v) = map
+                .get(&format!("{name}_error_ratio"))
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|v| (0.0..=1.0).contains(v))
+            {
+                builder.error_ratio(op, v);
+            }
+        }
+
+        builder
+    }
+
+    fn build(&mut self) -> Result<Self::Accessor> {
+        let inner = Memory::default().build()?;
+
+        Ok(ChaosBackend {
+            inner,
+            latency: Arc::new(self.latency.clone()),
+            failures: Arc::new(Mutex::new(self.failures.clone())),
+            error_ratio: Arc::new(self.error_ratio.clone()),
+        })
+    }
+}
+
+/// Backend for chaos service.
+#[derive(Debug, Clone)]
+pub struct ChaosBackend {
+    inner: <Memory as Builder>::Accessor,
+    latency: Arc<HashMap<Operation, Duration>>,
+    failures: Arc<Mutex<HashMap<Operation, usize>>>,
+    error_ratio: Arc<HashMap<Operation, f64>>,
+}
+
+impl ChaosBackend {
+    /// Decide whether this call of given operation should fail.
+    fn inject(&self, op: Operation, path: &str) -> Result<()> {
+        let fail = match self.failures.lock().get_mut(&op) {
+            Some(n) if *n > 0 => {
+                *n -= 1;
+                true
+            }
+            _ => false,
+        };
+        let fail = fail
+            || self
+                .error_ratio
+                .get(&op)
+                .map_or(false, |ratio| rand::thread_rng().gen_bool(*ratio));
+
+        if fail {
+            return Err(
+                Error::new(ErrorKind::Unexpected, "injected by chaos service")
+                    .with_operation(op)
+                    .with_context("path", path)
+                    .set_temporary(),
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn chaos(&self, op: Operation, path: &str) -> Result<()> {
+        if let Some(latency) = self.latency.get(&op) {
+            tokio::time::sleep(*latency).await;
+        }
+
+        self.inject(op, path)
+    }
+
+    fn blocking_chaos(&self, op: Operation, path: &str) -> Result<()> {
+        if let Some(latency) = self.latency.get(&op) {
+            std::thread::sleep(*latency);
+        }
+
+        self.inject(op, path)
+    }
+}
+
+#[async_trait]
+impl Accessor for ChaosBackend {
+    type Reader = <<Memory as Builder>::Accessor as Accessor>::Reader;
+    type BlockingReader = <<Memory as Builder>::Accessor as Accessor>::BlockingReader;
+    type Writer = <<Memory as Builder>::Accessor as Accessor>::Writer;
+    type BlockingWriter = <<Memory as Builder>::Accessor as Accessor>::BlockingWriter;
+    type Pager = <<Memory as Builder>::Accessor as Accessor>::Pager;
+    type BlockingPager = <<Memory as Builder>::Accessor as Accessor>::BlockingPager;
+
+    fn info(&self) -> AccessorInfo {
+        let mut am = self.inner.info();
+        am.set_scheme(Scheme::Chaos);
+
+        am
+    }
+
+    async fn create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        self.chaos(Operation::Create, path).await?;
+        self.inner.create(path, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.chaos(Operation::Read, path).await?;
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.chaos(Operation::Write, path).await?;
+        self.inner.write(path, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.chaos(Operation::Stat, path).await?;
+        self.inner.stat(path, args).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.chaos(Operation::Delete, path).await?;
+        self.inner.delete(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+        self.chaos(Operation::List, path).await?;
+        self.inner.list(path, args).await
+    }
+
+    async fn scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::Pager)> {
+        self.chaos(Operation::Scan, path).await?;
+        self.inner.scan(path, args).await
+    }
+
+    fn blocking_create(&self, path: &str, args: OpCreate) -> Result<RpCreate> {
+        self.blocking_chaos(Operation::BlockingCreate, path)?;
+        self.inner.blocking_create(path, args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.blocking_chaos(Operation::BlockingRead, path)?;
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.blocking_chaos(Operation::BlockingWrite, path)?;
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.blocking_chaos(Operation::BlockingStat, path)?;
+        self.inner.blocking_stat(path, args)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.blocking_chaos(Operation::BlockingDelete, path)?;
+        self.inner.blocking_delete(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
+        self.blocking_chaos(Operation::BlockingList, path)?;
+        self.inner.blocking_list(path, args)
+    }
+
+    fn blocking_scan(&self, path: &str, args: OpScan) -> Result<(RpScan, Self::BlockingPager)> {
+        self.blocking_chaos(Operation::BlockingScan, path)?;
+        self.inner.blocking_scan(path, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+    use crate::layers::RetryLayer;
+
+    fn new_operator(builder: ChaosBuilder, max_times: usize) -> Operator {
+        Operator::new(builder)
+            .unwrap()
+            .layer(
+                RetryLayer::new()
+                    .with_min_delay(Duration::from_millis(1))
+                    .with_max_times(max_times),
+            )
+            .finish()
+    }
+
+    #[tokio::test]
+    async fn test_retry_exactly_n_times() {
+        // 3 failures can be covered by 3 retries.
+        let mut builder = ChaosBuilder::default();
+        builder.failures(Operation::Stat, 3);
+        let op = new_operator(builder, 3);
+
+        op.write("test", "Hello, World!").await.unwrap();
+        let meta = op.stat("test").await.expect("stat must succeed");
+        assert_eq!(meta.content_length(), 13);
+
+        // 4 failures can't be covered by 3 retries.
+        let mut builder = ChaosBuilder::default();
+        builder.failures(Operation::Stat, 4);
+        let op = new_operator(builder, 3);
+
+        op.write("test", "Hello, World!").await.unwrap();
+        let err = op.stat("test").await.expect_err("stat must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(!err.is_temporary(), "retried error must be persistent");
+
+        // Failures have been consumed, the next call will succeed.
+        assert!(op.stat("test").await.is_ok());
+    }
+
+    #[test]
+    fn test_blocking_retry_exactly_n_times() {
+        let mut builder = ChaosBuilder::default();
+        builder.failures(Operation::BlockingRead, 2);
+        let op = new_operator(builder, 2);
+
+        op.blocking().write("test", "Hello, World!").unwrap();
+        let bs = op.blocking().read("test").expect("read must succeed");
+        assert_eq!(bs, b"Hello, World!");
+    }
+
+    #[tokio::test]
+    async fn test_error_ratio() {
+        let mut builder = ChaosBuilder::default();
+        builder
+            .error_ratio(Operation::Stat, 1.0)
+            .error_ratio(Operation::Write, 0.0);
+        let op = Operator::new(builder).unwrap().finish();
+
+        for _ in 0..10 {
+            op.write("test", "Hello, World!").await.unwrap();
+            assert!(op.stat("test").await.is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_latency() {
+        let mut builder = ChaosBuilder::default();
+        builder.latency(Operation::Stat, Duration::from_millis(50));
+        let op = Operator::new(builder).unwrap().finish();
+
+        op.write("test", "Hello, World!").await.unwrap();
+
+        let now = Instant::now();
+        op.stat("test").await.unwrap();
+        assert!(now.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_from_map() {
+        let builder = ChaosBuilder::from_map(HashMap::from([
+            ("read_latency_ms".to_string(), "100".to_string()),
+            ("stat_failures".to_string(), "3".to_string()),
+            ("blocking_write_error_ratio".to_string(), "0.5".to_string()),
+            ("write_error_ratio".to_string(), "2".to_string()),
+        ]));
+
+        assert_eq!(
+            builder.latency.get(&Operation::Read),
+            Some(&Duration::from_millis(100))
+        );
+        assert_eq!(builder.failures.get(&Operation::Stat), Some(&3));
+        assert_eq!(
+            builder.error_ratio.get(&Operation::BlockingWrite),
+            Some(&0.5)
+        );
+        // Invalid ratio will be ignored.
+        assert_eq!(builder.error_ratio.get(&Operation::Write), None);
+    }
+}
@@ -31,6 +31,8 @@ use uuid::Uuid;
 
 use super::error::parse_io_error;
 use super::pager::FsPager;
+use super::reader::FsReader;
+use super::reader::MmapReader;
 use super::writer::FsWriter;
 use crate::ops::*;
 use crate::raw::*;
@@ -44,7 +46,9 @@ use crate::*;
 ///
 /// - [x] read
 /// - [x] write
+/// - [x] append
 /// - [x] list
+/// - [x] rename
 /// - [ ] ~~scan~~
 /// - [ ] ~~presign~~
 /// - [x] blocking
@@ -86,6 +90,7 @@ pub struct FsBuilder {
     root: Option<PathBuf>,
     atomic_write_dir: Option<PathBuf>,
     enable_path_check: bool,
+    enable_mmap: bool,
 }
 
 impl FsBuilder {
@@ -122,6 +127,26 @@ impl FsBuilder {
 
         self
     }
+
+    /// Read files via a read-only memory map instead of file descriptor.
+    ///
+    /// mmap makes random access like seeking and range reading cheaper
+    /// since no syscall is needed, and chunks returned by reader are slices
+    /// of the mapped pages without copying. Reading will fallback to file
+    /// descriptor if the file can't be mapped.
+    ///
+    /// # Safety
+    ///
+    /// Files read by this service MUST NOT be truncated or modified, by this
+    /// process or any other, while their readers or returned bytes are
+    /// alive. Truncating a mapped file will kill the process by `SIGBUS`,
+    /// and modifying it will change content behind `&[u8]`, which is
+    /// undefined behavior.
+    pub unsafe fn enable_mmap(&mut self) -> &mut Self {
+        self.enable_mmap = true;
+
+        self
+    }
 }
 
 impl Builder for FsBuilder {
@@ -211,6 +236,7 @@ impl Builder for FsBuilder {
             root,
             atomic_write_dir,
             enable_path_check: self.enable_path_check,
+            enable_mmap: self.enable_mmap,
         })
     }
 }
@@ -221,6 +247,7 @@ pub struct FsBackend {
     root: PathBuf,
     atomic_write_dir: Option<PathBuf>,
     enable_path_check: bool,
+    enable_mmap: bool,
 }
 
 #[inline]
@@ -287,8 +314,8 @@ impl FsBackend {
 
 #[async_trait]
 impl Accessor for FsBackend {
-    type Reader = oio::into_reader::FdReader<Compat<tokio::fs::File>>;
-    type BlockingReader = oio::into_blocking_reader::FdReader<std::fs::File>;
+    type Reader = FsReader<oio::into_reader::FdReader<Compat<tokio::fs::File>>>;
+    type BlockingReader = FsReader<oio::into_blocking_reader::FdReader<std::fs::File>>;
     type Writer = FsWriter<tokio::fs::File>;
     type BlockingWriter = FsWriter<std::fs::File>;
     type Pager = Option<FsPager<tokio::fs::ReadDir>>;
@@ -301,8 +328,12 @@ impl Accessor for FsBackend {
             .set_capabilities(
                 AccessorCapability::Read
                     | AccessorCapability::Write
+                    | AccessorCapability::CreateDir
                     | AccessorCapability::List
-                    | AccessorCapability::Blocking,
+                    | AccessorCapability::Rename
+                    | AccessorCapability::Blocking
+                    | AccessorCapability::WriteCanAppend
+                    | AccessorCapability::DeleteWithStrict,
             )
             .set_hints(AccessorHint::ReadSeekable);
 
@@ -366,21 +397,22 @@ impl Accessor for FsBackend {
             .await
             .map_err(parse_io_error)?;
 
+        let meta = f.metadata().await.map_err(parse_io_error)?;
+        if meta.is_dir() {
+            return Err(Error::new(
+                ErrorKind::IsADirectory,
+                "given path is a directory",
+            ));
+        }
+
         let total_length = if self.enable_path_check {
-            // Get fs metadata of file at given path, ensuring it is not a false-positive due to slash normalization.
-            let meta = f.metadata().await.map_err(parse_io_error)?;
-            if meta.is_dir() != path.ends_with('/') {
+            // Ensuring it is not a false-positive due to slash normalization.
+            if path.ends_with('/') {
                 return Err(Error::new(
                     ErrorKind::NotFound,
                     "file mode is not match with its path",
                 ));
             }
-            if meta.is_dir() {
-                return Err(Error::new(
-                    ErrorKind::IsADirectory,
-                    "given path is a directory",
-                ));
-            }
 
             meta.len()
         } else {
@@ -389,8 +421,6 @@ impl Accessor for FsBackend {
             f.seek(SeekFrom::End(0)).await.map_err(parse_io_error)?
         };
 
-        let f = Compat::new(f);
-
         let br = args.range();
         let (start, end) = match (br.offset(), br.size()) {
             // Read a specific range.
@@ -410,15 +440,44 @@ impl Accessor for FsBackend {
             (None, None) => (0, total_length),
         };
 
+        if self.enable_mmap {
+            let std_f = f.into_std().await;
+            // SAFETY: users promise files are not modified while mapped by
+            // enabling mmap, see `FsBuilder::enable_mmap`.
+            match unsafe { MmapReader::map(&std_f, start, end) } {
+                Ok(r) => return Ok((RpRead::new(end - start), FsReader::Mmap(r))),
+                Err(err) => {
+                    debug!("mmap {path} failed, fallback to read via fd: {err:?}");
+                    f = fs::File::from_std(std_f);
+                }
+            }
+        }
+
+        let f = Compat::new(f);
         let mut r = oio::into_reader::from_fd(f, start, end);
 
         // Rewind to make sure we are on the correct offset.
         r.seek(SeekFrom::Start(0)).await?;
 
-        Ok((RpRead::new(end - start), r))
+        Ok((RpRead::new(end - start), FsReader::Fd(r)))
     }
 
-    async fn write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        // Append must go to the target file directly, atomic write is not
+        // applicable here.
+        if args.append_existing() {
+            let target_path = Self::ensure_write_abs_path(&self.root, path).await?;
+
+            let f = tokio::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&target_path)
+                .await
+                .map_err(parse_io_error)?;
+
+            return Ok((RpWrite::new(), FsWriter::new(target_path, None, f, false)));
+        }
+
         let (target_path, tmp_path) = if let Some(atomic_write_dir) = &self.atomic_write_dir {
             let target_path = Self::ensure_write_abs_path(&self.root, path).await?;
             let tmp_path =
@@ -430,21 +489,43 @@ impl Accessor for FsBackend {
             (p, None)
         };
 
-        let f = tokio::fs::OpenOptions::new()
-            .create(true)
-            .truncate(true)
+        // Try to create the file first so that we know whether it's safe
+        // to be removed while aborting.
+        let p = tmp_path.as_ref().unwrap_or(&target_path);
+        let (f, created) = match tokio::fs::OpenOptions::new()
+            .create_new(true)
             .write(true)
-            .open(tmp_path.as_ref().unwrap_or(&target_path))
+            .open(p)
             .await
-            .map_err(parse_io_error)?;
+        {
+            Ok(f) => (f, true),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                let f = tokio::fs::OpenOptions::new()
+                    .truncate(true)
+                    .write(true)
+                    .open(p)
+                    .await
+                    .map_err(parse_io_error)?;
+                (f, false)
+            }
+            Err(err) => return Err(parse_io_error(err)),
+        };
 
-        Ok((RpWrite::new(), FsWriter::new(target_path, tmp_path, f)))
+        Ok((
+            RpWrite::new(),
+            FsWriter::new(target_path, tmp_path, f, created),
+        ))
     }
 
-    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         let p = self.root.join(path.trim_end_matches('/'));
 
-        let meta = tokio::fs::metadata(&p).await.map_err(parse_io_error)?;
+        let meta = if args.follow_symlink() {
+            fs::metadata(&p).await
+        } else {
+            fs::symlink_metadata(&p).await
+        }
+        .map_err(parse_io_error)?;
 
         if self.enable_path_check && meta.is_dir() != path.ends_with('/') {
             return Err(Error::new(
@@ -457,6 +538,8 @@ impl Accessor for FsBackend {
             EntryMode::DIR
         } else if meta.is_file() {
             EntryMode::FILE
+        } else if meta.file_type().is_symlink() {
+            EntryMode::SYMLINK
         } else {
             EntryMode::Unknown
         };
@@ -471,7 +554,7 @@ impl Accessor for FsBackend {
         Ok(RpStat::new(m))
     }
 
-    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         let p = self.root.join(path.trim_end_matches('/'));
 
         let meta = tokio::fs::metadata(&p).await;
@@ -486,26 +569,53 @@ impl Accessor for FsBackend {
 
                 Ok(RpDelete::default())
             }
-            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(RpDelete::default()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound && !args.strict() => {
+                Ok(RpDelete::default())
+            }
             Err(err) => Err(parse_io_error(err)),
         }
     }
 
+    async fn rename(&self, from: &str, to: &str, _: OpRename) -> Result<RpRename> {
+        let from = self.root.join(from);
+
+        // Make sure source exists before creating parent dirs of target.
+        fs::metadata(&from).await.map_err(parse_io_error)?;
+
+        let to = Self::ensure_write_abs_path(&self.root, to).await?;
+
+        fs::rename(&from, &to).await.map_err(parse_io_error)?;
+
+        Ok(RpRename::default())
+    }
+
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
         let p = self.root.join(path.trim_end_matches('/'));
 
         let f = match tokio::fs::read_dir(&p).await {
             Ok(rd) => rd,
             Err(e) => {
-                return if e.kind() == io::ErrorKind::NotFound {
-                    Ok((RpList::default(), None))
-                } else {
-                    Err(parse_io_error(e))
-                };
+                if e.kind() == io::ErrorKind::NotFound {
+                    return Ok((RpList::default(), None));
+                }
+                // Listing a file returns `NotADirectory` which is not stable
+                // in io::ErrorKind yet.
+                if tokio::fs::metadata(&p)
+                    .await
+                    .map(|m| m.is_file())
+                    .unwrap_or_default()
+                {
+                    return Err(Error::new(
+                        ErrorKind::NotADirectory,
+                        "given path is not a directory",
+                    )
+                    .set_source(e));
+                }
+                return Err(parse_io_error(e));
             }
         };
 
-        let rd = FsPager::new(&self.root, f, args.limit());
+        let rd = FsPager::new(&self.root, f, args.limit(), args.follow_symlink());
 
         Ok((RpList::default(), Some(rd)))
     }
@@ -555,21 +665,22 @@ impl Accessor for FsBackend {
             .open(p)
             .map_err(parse_io_error)?;
 
+        let meta = f.metadata().map_err(parse_io_error)?;
+        if meta.is_dir() {
+            return Err(Error::new(
+                ErrorKind::IsADirectory,
+                "given path is a directory",
+            ));
+        }
+
         let total_length = if self.enable_path_check {
-            // Get fs metadata of file at given path, ensuring it is not a false-positive due to slash normalization.
-            let meta = f.metadata().map_err(parse_io_error)?;
-            if meta.is_dir() != path.ends_with('/') {
+            // Ensuring it is not a false-positive due to slash normalization.
+            if path.ends_with('/') {
                 return Err(Error::new(
                     ErrorKind::NotFound,
                     "file mode is not match with its path",
                 ));
             }
-            if meta.is_dir() {
-                return Err(Error::new(
-                    ErrorKind::IsADirectory,
-                    "given path is a directory",
-                ));
-            }
 
             meta.len()
         } else {
@@ -597,12 +708,21 @@ impl Accessor for FsBackend {
             (None, None) => (0, total_length),
         };
 
+        if self.enable_mmap {
+            // SAFETY: users promise files are not modified while mapped by
+            // enabling mmap, see `FsBuilder::enable_mmap`.
+            match unsafe { MmapReader::map(&f, start, end) } {
+                Ok(r) => return Ok((RpRead::new(end - start), FsReader::Mmap(r))),
+                Err(err) => debug!("mmap {path} failed, fallback to read via fd: {err:?}"),
+            }
+        }
+
         let mut r = oio::into_blocking_reader::from_fd(f, start, end);
 
         // Rewind to make sure we are on the correct offset.
         r.seek(SeekFrom::Start(0))?;
 
-        Ok((RpRead::new(end - start), r))
+        Ok((RpRead::new(end - start), FsReader::Fd(r)))
     }
 
     fn blocking_write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
@@ -617,20 +737,41 @@ impl Accessor for FsBackend {
             (p, None)
         };
 
-        let f = std::fs::OpenOptions::new()
-            .create(true)
-            .truncate(true)
+        // Try to create the file first so that we know whether it's safe
+        // to be removed while aborting.
+        let p = tmp_path.as_ref().unwrap_or(&target_path);
+        let (f, created) = match std::fs::OpenOptions::new()
+            .create_new(true)
             .write(true)
-            .open(tmp_path.as_ref().unwrap_or(&target_path))
-            .map_err(parse_io_error)?;
+            .open(p)
+        {
+            Ok(f) => (f, true),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                let f = std::fs::OpenOptions::new()
+                    .truncate(true)
+                    .write(true)
+                    .open(p)
+                    .map_err(parse_io_error)?;
+                (f, false)
+            }
+            Err(err) => return Err(parse_io_error(err)),
+        };
 
-        Ok((RpWrite::new(), FsWriter::new(target_path, tmp_path, f)))
+        Ok((
+            RpWrite::new(),
+            FsWriter::new(target_path, tmp_path, f, created),
+        ))
     }
 
-    fn blocking_stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         let p = self.root.join(path.trim_end_matches('/'));
 
-        let meta = std::fs::metadata(p).map_err(parse_io_error)?;
+        let meta = if args.follow_symlink() {
+            std::fs::metadata(p)
+        } else {
+            std::fs::symlink_metadata(p)
+        }
+        .map_err(parse_io_error)?;
 
         if self.enable_path_check && meta.is_dir() != path.ends_with('/') {
             return Err(Error::new(
@@ -643,6 +784,8 @@ impl Accessor for FsBackend {
             EntryMode::DIR
         } else if meta.is_file() {
             EntryMode::FILE
+        } else if meta.file_type().is_symlink() {
+            EntryMode::SYMLINK
         } else {
             EntryMode::Unknown
         };
@@ -657,7 +800,7 @@ impl Accessor for FsBackend {
         Ok(RpStat::new(m))
     }
 
-    fn blocking_delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         let p = self.root.join(path.trim_end_matches('/'));
 
         let meta = std::fs::metadata(&p);
@@ -672,26 +815,52 @@ impl Accessor for FsBackend {
 
                 Ok(RpDelete::default())
             }
-            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(RpDelete::default()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound && !args.strict() => {
+                Ok(RpDelete::default())
+            }
             Err(err) => Err(parse_io_error(err)),
         }
     }
 
+    fn blocking_rename(&self, from: &str, to: &str, _: OpRename) -> Result<RpRename> {
+        let from = self.root.join(from);
+
+        // Make sure source exists before creating parent dirs of target.
+        std::fs::metadata(&from).map_err(parse_io_error)?;
+
+        let to = Self::blocking_ensure_write_abs_path(&self.root, to)?;
+
+        std::fs::rename(&from, to).map_err(parse_io_error)?;
+
+        Ok(RpRename::default())
+    }
+
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingPager)> {
         let p = self.root.join(path.trim_end_matches('/'));
 
-        let f = match std::fs::read_dir(p) {
+        let f = match std::fs::read_dir(&p) {
             Ok(rd) => rd,
             Err(e) => {
-                return if e.kind() == io::ErrorKind::NotFound {
-                    Ok((RpList::default(), None))
-                } else {
-                    Err(parse_io_error(e))
-                };
+                if e.kind() == io::ErrorKind::NotFound {
+                    return Ok((RpList::default(), None));
+                }
+                // Listing a file returns `NotADirectory` which is not stable
+                // in io::ErrorKind yet.
+                if std::fs::metadata(&p)
+                    .map(|m| m.is_file())
+                    .unwrap_or_default()
+                {
+                    return Err(Error::new(
+                        ErrorKind::NotADirectory,
+                        "given path is not a directory",
+                    )
+                    .set_source(e));
+                }
+                return Err(parse_io_error(e));
             }
         };
 
-        let rd = FsPager::new(&self.root, f, args.limit());
+        let rd = FsPager::new(&self.root, f, args.limit(), args.follow_symlink());
 
         Ok((RpList::default(), Some(rd)))
     }
@@ -699,6 +868,10 @@ impl Accessor for FsBackend {
 
 #[cfg(test)]
 mod tests {
+    use std::env;
+
+    use futures::TryStreamExt;
+
     use super::*;
 
     #[test]
@@ -715,4 +888,217 @@ mod tests {
             assert!(tmp_file.starts_with(expected_prefix));
         }
     }
+
+    #[tokio::test]
+    async fn test_dir_and_file_mismatch() {
+        let root = env::temp_dir().join(format!("opendal-{}", Uuid::new_v4()));
+        let mut builder = FsBuilder::default();
+        builder.root(&root.to_string_lossy());
+        let op = Operator::new(builder).unwrap().finish();
+
+        op.create_dir("dir/").await.unwrap();
+        op.write("file", "Hello, World!").await.unwrap();
+
+        let err = op.read("dir").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::IsADirectory);
+        let err = op.blocking().read("dir").expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::IsADirectory);
+
+        let err = op.list("file/").await.err().expect("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotADirectory);
+        let err = op.blocking().list("file/").err().expect("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotADirectory);
+
+        // Non-exist dir is still listed as empty.
+        let lister = op.list("not_exist/").await.unwrap();
+        assert_eq!(lister.try_collect::<Vec<_>>().await.unwrap().len(), 0);
+
+        fs::remove_dir_all(root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_read_with_mmap() {
+        use std::io::Read;
+        use std::io::Seek;
+
+        use futures::AsyncReadExt;
+        use futures::AsyncSeekExt;
+
+        let root = env::temp_dir().join(format!("opendal-{}", Uuid::new_v4()));
+        let mut builder = FsBuilder::default();
+        builder.root(&root.to_string_lossy());
+        let op = Operator::new(builder).unwrap().finish();
+        let mut builder = FsBuilder::default();
+        builder.root(&root.to_string_lossy());
+        // SAFETY: files of this test are not modified while reading.
+        unsafe { builder.enable_mmap() };
+        let mmap_op = Operator::new(builder).unwrap().finish();
+
+        let content: Vec<u8> = (0..1024 * 1024).map(|i| (i % 251) as u8).collect();
+        op.write("file", content.clone()).await.unwrap();
+        // Empty file can't be mapped, read will fallback to fd.
+        op.write("empty", "").await.unwrap();
+
+        for path in ["file", "empty"] {
+            assert_eq!(
+                mmap_op.read(path).await.unwrap(),
+                op.read(path).await.unwrap(),
+                "{path}"
+            );
+            assert_eq!(
+                mmap_op.blocking().read(path).unwrap(),
+                op.blocking().read(path).unwrap(),
+                "{path}"
+            );
+        }
+        for range in [0..4096, 4096..1024 * 1024, 1000..1000] {
+            assert_eq!(
+                mmap_op.range_read("file", range.clone()).await.unwrap(),
+                op.range_read("file", range.clone()).await.unwrap(),
+            );
+            assert_eq!(
+                mmap_op
+                    .blocking()
+                    .range_read("file", range.clone())
+                    .unwrap(),
+                op.blocking().range_read("file", range).unwrap(),
+            );
+        }
+
+        let mut r = mmap_op.range_reader("file", 1024..8192).await.unwrap();
+        r.seek(SeekFrom::Start(1024)).await.unwrap();
+        let mut bs = vec![0; 1024];
+        r.read_exact(&mut bs).await.unwrap();
+        assert_eq!(bs, content[2048..3072]);
+        assert_eq!(r.seek(SeekFrom::End(0)).await.unwrap(), 7168);
+
+        let mut r = mmap_op.blocking().reader("file").unwrap();
+        r.seek(SeekFrom::End(-16)).unwrap();
+        let mut bs = Vec::new();
+        r.read_to_end(&mut bs).unwrap();
+        assert_eq!(bs, content[content.len() - 16..]);
+
+        fs::remove_dir_all(root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_writer_abort() {
+        let root = env::temp_dir().join(format!("opendal-{}", Uuid::new_v4()));
+        let mut builder = FsBuilder::default();
+        builder.root(&root.to_string_lossy());
+        let op = Operator::new(builder).unwrap().finish();
+
+        // Files created by writer will be removed.
+        let mut w = op.writer("new").await.unwrap();
+        w.abort().await.unwrap();
+        assert!(!op.is_exist("new").await.unwrap());
+
+        // Existing files must not be removed.
+        op.write("exist", "Hello, World!").await.unwrap();
+        let mut w = op.writer("exist").await.unwrap();
+        let err = w.abort().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert!(op.is_exist("exist").await.unwrap());
+
+        fs::remove_dir_all(root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_writer_with_content_length_mismatch() {
+        let root = env::temp_dir().join(format!("opendal-{}", Uuid::new_v4()));
+        let mut builder = FsBuilder::default();
+        builder.root(&root.to_string_lossy());
+        let op = Operator::new(builder).unwrap().finish();
+
+        // Target must not be truncated before the length is checked.
+        op.write("exist", "Hello, World!").await.unwrap();
+        let args = OpWrite::new().with_content_length(13);
+        let mut w = op.writer_with("exist", args).await.unwrap();
+        w.append("Hello").await.unwrap();
+        w.close().await.unwrap_err();
+        assert_eq!(op.read("exist").await.unwrap(), b"Hello, World!");
+
+        fs::remove_dir_all(root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_dir() {
+        let root = env::temp_dir().join(format!("opendal-{}", Uuid::new_v4()));
+        let mut builder = FsBuilder::default();
+        builder.root(&root.to_string_lossy());
+        let op = Operator::new(builder).unwrap().finish();
+        assert!(op.info().can_create_dir());
+
+        op.create_dir("a/b/").await.unwrap();
+        // Create dir is idempotent.
+        op.create_dir("a/b/").await.unwrap();
+        op.blocking().create_dir("a/b/").unwrap();
+
+        assert!(root.join("a").is_dir());
+        assert!(root.join("a/b").is_dir());
+
+        let meta = op.stat("a/b/").await.unwrap();
+        assert_eq!(meta.mode(), EntryMode::DIR);
+
+        fs::remove_dir_all(root).await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_symlink() {
+        let root = env::temp_dir().join(format!("opendal-{}", Uuid::new_v4()));
+        let mut builder = FsBuilder::default();
+        builder.root(&root.to_string_lossy());
+        let op = Operator::new(builder).unwrap().finish();
+
+        op.write("dir/file", "Hello, World!").await.unwrap();
+        std::os::unix::fs::symlink(root.join("dir/file"), root.join("dir/link")).unwrap();
+        std::os::unix::fs::symlink(root.join("dir/not_exist"), root.join("dir/dangling")).unwrap();
+
+        // Stat follows symlinks by default.
+        let meta = op.stat("dir/link").await.unwrap();
+        assert_eq!(meta.mode(), EntryMode::FILE);
+        assert_eq!(meta.content_length(), 13);
+
+        let args = OpStat::new().with_follow_symlink(false);
+        let meta = op.stat_with("dir/link", args.clone()).await.unwrap();
+        assert_eq!(meta.mode(), EntryMode::SYMLINK);
+        let meta = op.stat_with("dir/dangling", args).await.unwrap();
+        assert_eq!(meta.mode(), EntryMode::SYMLINK);
+
+        let list = |follow: bool| {
+            let op = op.clone();
+            async move {
+                let mut entries = Vec::new();
+                let mut lister = op
+                    .list_with("dir/", OpList::new().with_follow_symlink(follow))
+                    .await
+                    .unwrap();
+                while let Some(de) = lister.try_next().await.unwrap() {
+                    let meta = op.metadata(&de, Metakey::Mode).await.unwrap();
+                    entries.push((de.path().to_string(), meta.mode()));
+                }
+                entries.sort_by(|a, b| a.0.cmp(&b.0));
+                entries
+            }
+        };
+        assert_eq!(
+            list(false).await,
+            vec![
+                ("dir/dangling".to_string(), EntryMode::SYMLINK),
+                ("dir/file".to_string(), EntryMode::FILE),
+                ("dir/link".to_string(), EntryMode::SYMLINK),
+            ]
+        );
+        assert_eq!(
+            list(true).await,
+            vec![
+                ("dir/dangling".to_string(), EntryMode::SYMLINK),
+                ("dir/file".to_string(), EntryMode::FILE),
+                ("dir/link".to_string(), EntryMode::FILE),
+            ]
+        );
+
+        fs::remove_dir_all(root).await.unwrap();
+    }
 }
@@ -20,4 +20,5 @@ pub use backend::FsBuilder as Fs;
 
 mod error;
 mod pager;
+mod reader;
 mod writer;
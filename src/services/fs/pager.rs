@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -30,14 +31,16 @@ pub struct FsPager<P> {
     root: PathBuf,
 
     size: usize,
+    follow_symlink: bool,
     rd: P,
 }
 
 impl<P> FsPager<P> {
-    pub fn new(root: &Path, rd: P, limit: Option<usize>) -> Self {
+    pub fn new(root: &Path, rd: P, limit: Option<usize>, follow_symlink: bool) -> Self {
         Self {
             root: root.to_owned(),
             size: limit.unwrap_or(1000),
+            follow_symlink,
             rd,
         }
     }
@@ -67,13 +70,23 @@ impl oio::Page for FsPager<tokio::fs::ReadDir> {
             // (no extra system calls needed), but some Unix platforms may
             // require the equivalent call to symlink_metadata to learn about
             // the target file type.
-            let file_type = de.file_type().await.map_err(parse_io_error)?;
+            let mut file_type = de.file_type().await.map_err(parse_io_error)?;
+            if file_type.is_symlink() && self.follow_symlink {
+                match tokio::fs::metadata(&entry_path).await {
+                    Ok(meta) => file_type = meta.file_type(),
+                    // Keep dangling symlinks as is.
+                    Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                    Err(err) => return Err(parse_io_error(err)),
+                }
+            }
 
             let d = if file_type.is_file() {
                 oio::Entry::new(&rel_path, Metadata::new(EntryMode::FILE))
             } else if file_type.is_dir() {
                 // Make sure we are returning the correct path.
                 oio::Entry::new(&format!("{rel_path}/"), Metadata::new(EntryMode::DIR))
+            } else if file_type.is_symlink() {
+                oio::Entry::new(&rel_path, Metadata::new(EntryMode::SYMLINK))
             } else {
                 oio::Entry::new(&rel_path, Metadata::new(EntryMode::Unknown))
             };
@@ -108,13 +121,23 @@ impl oio::BlockingPage for FsPager<std::fs::ReadDir> {
             // (no extra system calls needed), but some Unix platforms may
             // require the equivalent call to symlink_metadata to learn about
             // the target file type.
-            let file_type = de.file_type().map_err(parse_io_error)?;
+            let mut file_type = de.file_type().map_err(parse_io_error)?;
+            if file_type.is_symlink() && self.follow_symlink {
+                match std::fs::metadata(&entry_path) {
+                    Ok(meta) => file_type = meta.file_type(),
+                    // Keep dangling symlinks as is.
+                    Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                    Err(err) => return Err(parse_io_error(err)),
+                }
+            }
 
             let d = if file_type.is_file() {
                 oio::Entry::new(&rel_path, Metadata::new(EntryMode::FILE))
             } else if file_type.is_dir() {
                 // Make sure we are returning the correct path.
                 oio::Entry::new(&format!("{rel_path}/"), Metadata::new(EntryMode::DIR))
+            } else if file_type.is_symlink() {
+                oio::Entry::new(&rel_path, Metadata::new(EntryMode::SYMLINK))
             } else {
                 oio::Entry::new(&rel_path, Metadata::new(EntryMode::Unknown))
             };
@@ -0,0 +1,166 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cmp::min;
+use std::io;
+use std::io::SeekFrom;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::Bytes;
+
+use crate::raw::*;
+use crate::*;
+
+/// The max size of bytes returned by `next` of [`MmapReader`].
+const MMAP_CHUNK_SIZE: usize = 256 * 1024;
+
+/// FsReader is the reader returned by fs service.
+///
+/// Files will be read via fd by default, or via [`MmapReader`] if mmap
+/// is enabled and the file can be mapped.
+pub enum FsReader<R> {
+    /// Read via file descriptor.
+    Fd(R),
+    /// Read via a read-only memory map.
+    Mmap(MmapReader),
+}
+
+impl<R: oio::Read> oio::Read for FsReader<R> {
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
+        match self {
+            FsReader::Fd(r) => r.poll_read(cx, buf),
+            FsReader::Mmap(r) => Poll::Ready(Ok(r.read(buf))),
+        }
+    }
+
+    fn poll_seek(&mut self, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64>> {
+        match self {
+            FsReader::Fd(r) => r.poll_seek(cx, pos),
+            FsReader::Mmap(r) => Poll::Ready(r.seek(pos)),
+        }
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        match self {
+            FsReader::Fd(r) => r.poll_next(cx),
+            FsReader::Mmap(r) => Poll::Ready(r.next().map(Ok)),
+        }
+    }
+}
+
+impl<R: oio::BlockingRead> oio::BlockingRead for FsReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        match self {
+            FsReader::Fd(r) => r.read(buf),
+            FsReader::Mmap(r) => Ok(r.read(buf)),
+        }
+    }
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        match self {
+            FsReader::Fd(r) => r.seek(pos),
+            FsReader::Mmap(r) => r.seek(pos),
+        }
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        match self {
+            FsReader::Fd(r) => r.next(),
+            FsReader::Mmap(r) => r.next().map(Ok),
+        }
+    }
+}
+
+/// MmapReader reads the range `[start, end)` of a memory mapped file.
+///
+/// Seeking is free since no syscall is needed, and `next` returns slices
+/// of the mapped pages without copying.
+pub struct MmapReader {
+    bs: Bytes,
+    pos: usize,
+}
+
+impl MmapReader {
+    /// Map given file and create a new MmapReader for the range `[start, end)`.
+    ///
+    /// Callers should fallback to normal reads if mapping failed.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be truncated or modified while the returned reader
+    /// is alive, see [`FsBuilder::enable_mmap`](super::Fs::enable_mmap).
+    pub(super) unsafe fn map(f: &std::fs::File, start: u64, end: u64) -> io::Result<Self> {
+        let (start, end) = match (usize::try_from(start), usize::try_from(end)) {
+            (Ok(start), Ok(end)) => (start, end),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "file is too large to be mapped",
+                ))
+            }
+        };
+
+        let mmap = memmap2::MmapOptions::new().len(end).map(f)?;
+        let bs = Bytes::from_owner(mmap).slice(start..end);
+
+        Ok(MmapReader { bs, pos: 0 })
+    }
+
+    /// Returns the remaining bytes.
+    fn remaining(&self) -> &[u8] {
+        &self.bs[min(self.pos, self.bs.len())..]
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> usize {
+        let bs = self.remaining();
+        let n = min(bs.len(), buf.len());
+        buf[..n].copy_from_slice(&bs[..n]);
+        self.pos += n;
+        n
+    }
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let (base, amt) = match pos {
+            SeekFrom::Start(n) => (0, n as i64),
+            SeekFrom::End(n) => (self.bs.len() as i64, n),
+            SeekFrom::Current(n) => (self.pos as i64, n),
+        };
+
+        match base.checked_add(amt) {
+            Some(n) if n >= 0 => {
+                self.pos = n as usize;
+                Ok(self.pos as u64)
+            }
+            _ => Err(Error::new(
+                ErrorKind::Unexpected,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+
+    fn next(&mut self) -> Option<Bytes> {
+        if self.pos >= self.bs.len() {
+            return None;
+        }
+
+        let end = min(self.pos + MMAP_CHUNK_SIZE, self.bs.len());
+        let bs = self.bs.slice(self.pos..end);
+        self.pos = end;
+        Some(bs)
+    }
+}
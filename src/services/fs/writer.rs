@@ -34,15 +34,18 @@ pub struct FsWriter<F> {
     tmp_path: Option<PathBuf>,
     f: F,
     pos: u64,
+    /// Whether the file written to is created by this writer.
+    created: bool,
 }
 
 impl<F> FsWriter<F> {
-    pub fn new(target_path: PathBuf, tmp_path: Option<PathBuf>, f: F) -> Self {
+    pub fn new(target_path: PathBuf, tmp_path: Option<PathBuf>, f: F, created: bool) -> Self {
         Self {
             target_path,
             tmp_path,
             f,
             pos: 0,
+            created,
         }
     }
 }
@@ -86,6 +89,31 @@ impl oio::Write for FsWriter<tokio::fs::File> {
 
         Ok(())
     }
+
+    /// # Notes
+    ///
+    /// Only the file created by this writer will be removed. If atomic
+    /// write is enabled, only the tmp file will be removed and target will
+    /// be kept untouched.
+    ///
+    /// Existing files that have been truncated or appended can't be
+    /// restored, `Unsupported` will be returned instead.
+    async fn abort(&mut self) -> Result<()> {
+        if !self.created {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "abort write to existing file is not supported without atomic write",
+            )
+            .with_context("path", self.target_path.to_string_lossy()));
+        }
+        let path = self.tmp_path.as_ref().unwrap_or(&self.target_path);
+
+        match tokio::fs::remove_file(path).await {
+            Ok(_) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(parse_io_error(err)),
+        }
+    }
 }
 
 impl oio::BlockingWrite for FsWriter<std::fs::File> {
@@ -123,4 +151,25 @@ impl oio::BlockingWrite for FsWriter<std::fs::File> {
 
         Ok(())
     }
+
+    /// # Notes
+    ///
+    /// Same as the async abort, only the file created by this writer will
+    /// be removed.
+    fn abort(&mut self) -> Result<()> {
+        if !self.created {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "abort write to existing file is not supported without atomic write",
+            )
+            .with_context("path", self.target_path.to_string_lossy()));
+        }
+        let path = self.tmp_path.as_ref().unwrap_or(&self.target_path);
+
+        match std::fs::remove_file(path) {
+            Ok(_) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(parse_io_error(err)),
+        }
+    }
 }
@@ -24,6 +24,7 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
+use http::HeaderMap;
 use http::Request;
 use http::Response;
 use http::StatusCode;
@@ -358,8 +359,14 @@ impl Accessor for GcsBackend {
         am.set_scheme(Scheme::Gcs)
             .set_root(&self.root)
             .set_name(&self.bucket)
-            .set_capabilities(Read | Write | List | Scan)
-            .set_hints(ReadStreamable);
+            .set_capabilities(Read | Write | CreateDir | List | Scan)
+            .set_hints(ReadStreamable)
+            .set_list_metakeys(
+                Metakey::ContentLength
+                    | Metakey::ContentMd5
+                    | Metakey::Etag
+                    | Metakey::LastModified,
+            );
         am
     }
 
@@ -380,9 +387,18 @@ impl Accessor for GcsBackend {
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
         let resp = self.gcs_get_object(path, args.range()).await?;
+        let resp = slice_ignored_range(resp, args.range())?;
 
         if resp.status().is_success() {
-            let meta = parse_into_metadata(path, resp.headers())?;
+            let mut meta = parse_into_metadata(path, resp.headers())?;
+            if let Some(v) = parse_header(resp.headers(), "x-goog-generation")? {
+                meta.set_version(v);
+            }
+            let user_meta = parse_user_metadata(resp.headers(), "x-goog-meta-");
+            if !user_meta.is_empty() {
+                meta.set_user_metadata(user_meta);
+            }
+            parse_goog_hash(resp.headers(), &mut meta)?;
             Ok((RpRead::with_metadata(meta), resp.into_body()))
         } else {
             Err(parse_error(resp).await?)
@@ -426,6 +442,9 @@ impl Accessor for GcsBackend {
 
             m.set_etag(&meta.etag);
             m.set_content_md5(&meta.md5_hash);
+            if !meta.crc32c.is_empty() {
+                m.set_content_crc32c(&meta.crc32c);
+            }
 
             let size = meta
                 .size
@@ -435,6 +454,12 @@ impl Accessor for GcsBackend {
             if !meta.content_type.is_empty() {
                 m.set_content_type(&meta.content_type);
             }
+            if !meta.generation.is_empty() {
+                m.set_version(&meta.generation);
+            }
+            if !meta.storage_class.is_empty() {
+                m.set_storage_class(parse_storage_class(&meta.storage_class));
+            }
 
             let datetime = OffsetDateTime::parse(&meta.updated, &Rfc3339).map_err(|e| {
                 Error::new(ErrorKind::Unexpected, "parse date time with rfc 3339").set_source(e)
@@ -625,6 +650,37 @@ impl GcsBackend {
     }
 }
 
+/// Parse checksums from `x-goog-hash` headers into metadata.
+///
+/// GCS returns checksums like `x-goog-hash: crc32c=n03x6A==,md5=Ojk9c3dhfxgoKVVHYwFbHQ==`,
+/// which could also be split into multiple headers.
+fn parse_goog_hash(headers: &HeaderMap, meta: &mut Metadata) -> Result<()> {
+    for v in headers.get_all("x-goog-hash") {
+        let v = v.to_str().map_err(|e| {
+            Error::new(
+                ErrorKind::Unexpected,
+                "header value is not valid utf-8 string",
+            )
+            .with_operation("gcs::parse_goog_hash")
+            .set_source(e)
+        })?;
+
+        for kv in v.split(',') {
+            match kv.trim().split_once('=') {
+                Some(("crc32c", v)) => {
+                    meta.set_content_crc32c(v);
+                }
+                Some(("md5", v)) => {
+                    meta.set_content_md5(v);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// The raw json response returned by [`get`](https://cloud.google.com/storage/docs/json_api/v1/objects/get)
 #[derive(Debug, Default, Deserialize)]
 #[serde(default, rename_all = "camelCase")]
@@ -645,10 +701,22 @@ struct GetObjectJsonResponse {
     ///
     /// For example: `"md5Hash": "fHcEH1vPwA6eTPqxuasXcg=="`
     md5_hash: String,
+    /// Content crc32c checksum
+    ///
+    /// For example: `"crc32c": "j/un9g=="`
+    crc32c: String,
     /// Content type of this object.
     ///
     /// For example: `"contentType": "image/png",`
     content_type: String,
+    /// Generation of this object, used as version.
+    ///
+    /// For example: `"generation": "1660563214863653"`
+    generation: String,
+    /// Storage class of this object.
+    ///
+    /// For example: `"storageClass": "STANDARD"`
+    storage_class: String,
 }
 
 #[cfg(test)]
@@ -683,7 +751,37 @@ mod tests {
         assert_eq!(meta.size, "56535");
         assert_eq!(meta.updated, "2022-08-15T11:33:34.866Z");
         assert_eq!(meta.md5_hash, "fHcEH1vPwA6eTPqxuasXcg==");
+        assert_eq!(meta.crc32c, "j/un9g==");
         assert_eq!(meta.etag, "CKWasoTgyPkCEAE=");
         assert_eq!(meta.content_type, "image/png");
+        assert_eq!(meta.generation, "1660563214863653");
+        assert_eq!(meta.storage_class, "STANDARD");
+    }
+
+    #[test]
+    fn test_parse_goog_hash() {
+        let mut headers = HeaderMap::new();
+        headers.append("x-goog-hash", "crc32c=n03x6A==".parse().unwrap());
+        headers.append(
+            "x-goog-hash",
+            "md5=Ojk9c3dhfxgoKVVHYwFbHQ==".parse().unwrap(),
+        );
+
+        let mut meta = Metadata::new(EntryMode::FILE);
+        parse_goog_hash(&headers, &mut meta).expect("must parse");
+        assert_eq!(meta.content_crc32c(), Some("n03x6A=="));
+        assert_eq!(meta.content_md5(), Some("Ojk9c3dhfxgoKVVHYwFbHQ=="));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-goog-hash",
+            "crc32c=n03x6A==, md5=Ojk9c3dhfxgoKVVHYwFbHQ=="
+                .parse()
+                .unwrap(),
+        );
+        let mut meta = Metadata::new(EntryMode::FILE);
+        parse_goog_hash(&headers, &mut meta).expect("must parse");
+        assert_eq!(meta.content_crc32c(), Some("n03x6A=="));
+        assert_eq!(meta.content_md5(), Some("Ojk9c3dhfxgoKVVHYwFbHQ=="));
     }
 }
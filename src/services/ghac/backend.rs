@@ -300,7 +300,12 @@ impl Accessor for GhacBackend {
         am.set_scheme(Scheme::Ghac)
             .set_root(&self.root)
             .set_name(&self.version)
-            .set_capabilities(AccessorCapability::Read | AccessorCapability::Write)
+            // Dirs are implicit in ghac, creating them is a no-op.
+            .set_capabilities(
+                AccessorCapability::Read
+                    | AccessorCapability::Write
+                    | AccessorCapability::CreateDir,
+            )
             .set_hints(AccessorHint::ReadStreamable);
         am
     }
@@ -364,6 +369,16 @@ impl Accessor for GhacBackend {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let mut range = args.range();
+        if range.is_suffix() {
+            // ghac is backed by azblob which doesn't support read with suffix range,
+            // so we need to stat first to resolve the absolute range.
+            //
+            // ref: https://learn.microsoft.com/en-us/rest/api/storageservices/specifying-the-range-header-for-blob-service-operations
+            let meta = self.stat(path, OpStat::new()).await?.into_metadata();
+            range = range.resolve_suffix(meta.content_length());
+        }
+
         let req = self.ghac_query(path).await?;
 
         let resp = self.client.send_async(req).await?;
@@ -377,8 +392,9 @@ impl Accessor for GhacBackend {
             return Err(parse_error(resp).await?);
         };
 
-        let req = self.ghac_get_location(&location, args.range()).await?;
+        let req = self.ghac_get_location(&location, range).await?;
         let resp = self.client.send_async(req).await?;
+        let resp = slice_ignored_range(resp, range)?;
 
         let status = resp.status();
         match status {
@@ -505,17 +521,6 @@ impl GhacBackend {
         let mut req = Request::get(location);
 
         if !range.is_full() {
-            // ghac is backed by azblob, and azblob doesn't support
-            // read with suffix range
-            //
-            // ref: https://learn.microsoft.com/en-us/rest/api/storageservices/specifying-the-range-header-for-blob-service-operations
-            if range.offset().is_none() && range.size().is_some() {
-                return Err(Error::new(
-                    ErrorKind::Unsupported,
-                    "ghac doesn't support read with suffix range",
-                ));
-            }
-
             req = req.header(http::header::RANGE, range.to_header());
         }
 
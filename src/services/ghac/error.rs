@@ -41,11 +41,16 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
 
     let bs = body.bytes().await?;
     let mut err = Error::new(kind, &String::from_utf8_lossy(&bs))
-        .with_context("response", format!("{parts:?}"));
+        .with_context("response", format!("{parts:?}"))
+        .with_status_code(parts.status);
 
     if retryable {
         err = err.set_temporary();
     }
 
+    if let Ok(Some(v)) = parse_retry_after(&parts.headers) {
+        err = err.with_retry_after(v);
+    }
+
     Ok(err)
 }
@@ -236,10 +236,12 @@ impl Accessor for HdfsBackend {
             .set_capabilities(
                 AccessorCapability::Read
                     | AccessorCapability::Write
+                    | AccessorCapability::CreateDir
                     | AccessorCapability::List
                     | AccessorCapability::Blocking,
             )
-            .set_hints(AccessorHint::ReadSeekable);
+            .set_hints(AccessorHint::ReadSeekable)
+            .set_list_metakeys(Metakey::ContentLength | Metakey::LastModified);
 
         am
     }
@@ -279,7 +281,7 @@ impl Accessor for HdfsBackend {
 
                 Ok(RpCreate::default())
             }
-            EntryMode::Unknown => unreachable!(),
+            EntryMode::SYMLINK | EntryMode::Unknown => unreachable!(),
         }
     }
 
@@ -345,7 +347,7 @@ impl Accessor for HdfsBackend {
             .await
             .map_err(parse_io_error)?;
 
-        Ok((RpWrite::new(), HdfsWriter::new(f)))
+        Ok((RpWrite::new(), HdfsWriter::new(self.client.clone(), p, f)))
     }
 
     async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
@@ -448,7 +450,7 @@ impl Accessor for HdfsBackend {
 
                 Ok(RpCreate::default())
             }
-            EntryMode::Unknown => unreachable!(),
+            EntryMode::SYMLINK | EntryMode::Unknown => unreachable!(),
         }
     }
 
@@ -512,7 +514,7 @@ impl Accessor for HdfsBackend {
             .open(&p)
             .map_err(parse_io_error)?;
 
-        Ok((RpWrite::new(), HdfsWriter::new(f)))
+        Ok((RpWrite::new(), HdfsWriter::new(self.client.clone(), p, f)))
     }
 
     fn blocking_stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
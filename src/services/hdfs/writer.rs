@@ -18,6 +18,7 @@
 use std::io::Seek;
 use std::io::SeekFrom;
 use std::io::Write;
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use bytes::Bytes;
@@ -29,13 +30,20 @@ use crate::raw::*;
 use crate::*;
 
 pub struct HdfsWriter<F> {
+    client: Arc<hdrs::Client>,
+    path: String,
     f: F,
     pos: u64,
 }
 
 impl<F> HdfsWriter<F> {
-    pub fn new(f: F) -> Self {
-        Self { f, pos: 0 }
+    pub fn new(client: Arc<hdrs::Client>, path: String, f: F) -> Self {
+        Self {
+            client,
+            path,
+            f,
+            pos: 0,
+        }
     }
 }
 
@@ -75,6 +83,18 @@ impl oio::Write for HdfsWriter<hdrs::AsyncFile> {
 
         Ok(())
     }
+
+    /// # Notes
+    ///
+    /// The partial written file will be removed.
+    async fn abort(&mut self) -> Result<()> {
+        self.f.close().await.map_err(parse_io_error)?;
+        self.client
+            .remove_file(&self.path)
+            .map_err(parse_io_error)?;
+
+        Ok(())
+    }
 }
 
 impl oio::BlockingWrite for HdfsWriter<hdrs::File> {
@@ -108,4 +128,16 @@ impl oio::BlockingWrite for HdfsWriter<hdrs::File> {
 
         Ok(())
     }
+
+    /// # Notes
+    ///
+    /// The partial written file will be removed.
+    fn abort(&mut self) -> Result<()> {
+        self.f.flush().map_err(parse_io_error)?;
+        self.client
+            .remove_file(&self.path)
+            .map_err(parse_io_error)?;
+
+        Ok(())
+    }
 }
@@ -256,14 +256,15 @@ impl Accessor for HttpBackend {
         let mut ma = AccessorInfo::default();
         ma.set_scheme(Scheme::Http)
             .set_root(&self.root)
-            .set_capabilities(AccessorCapability::Read)
+            .set_capabilities(AccessorCapability::Read | AccessorCapability::ReadWithHeader)
             .set_hints(AccessorHint::ReadStreamable);
 
         ma
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let resp = self.http_get(path, args.range()).await?;
+        let resp = self.http_get(path, &args).await?;
+        let resp = slice_ignored_range(resp, args.range())?;
 
         let status = resp.status();
 
@@ -299,7 +300,7 @@ impl Accessor for HttpBackend {
 }
 
 impl HttpBackend {
-    async fn http_get(&self, path: &str, range: BytesRange) -> Result<Response<IncomingAsyncBody>> {
+    async fn http_get(&self, path: &str, args: &OpRead) -> Result<Response<IncomingAsyncBody>> {
         let p = build_rooted_abs_path(&self.root, path);
 
         let url = format!("{}{}", self.endpoint, percent_encode_path(&p));
@@ -310,14 +311,17 @@ impl HttpBackend {
             req = req.header(header::AUTHORIZATION, auth.clone())
         }
 
+        let range = args.range();
         if !range.is_full() {
             req = req.header(header::RANGE, range.to_header());
         }
 
-        let req = req
+        let mut req = req
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
+        insert_custom_headers(&mut req, args.headers())?;
+
         self.client.send_async(req).await
     }
 
@@ -345,6 +349,7 @@ mod tests {
     use anyhow::Result;
     use wiremock::matchers::basic_auth;
     use wiremock::matchers::bearer_token;
+    use wiremock::matchers::header;
     use wiremock::matchers::method;
     use wiremock::matchers::path;
     use wiremock::Mock;
@@ -380,6 +385,98 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_read_with_range_ignored() -> Result<()> {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        // Server ignores the Range header and returns the whole content.
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/hello"))
+            .and(header("range", "bytes=7-11"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "13")
+                    .set_body_string("Hello, World!"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = HttpBuilder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.root("/");
+        let op = Operator::new(builder)?.finish();
+
+        let bs = op.range_read("hello", 7..12).await?;
+
+        assert_eq!(bs, b"World");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_error_with_status_code() -> Result<()> {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/hello"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = HttpBuilder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.root("/");
+        let op = Operator::new(builder)?.finish();
+
+        let err = op.stat("hello").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        assert_eq!(err.status_code(), Some(StatusCode::FORBIDDEN));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_with_custom_header() -> Result<()> {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/hello"))
+            .and(header("x-tenant-id", "tenant-a"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "13")
+                    .set_body_string("Hello, World!"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let mut builder = HttpBuilder::default();
+        builder.endpoint(&mock_server.uri());
+        builder.root("/");
+        builder.token("token");
+        let op = Operator::new(builder)?.finish();
+
+        let bs = op
+            .read_with(
+                "hello",
+                OpRead::new().with_header("x-tenant-id", "tenant-a"),
+            )
+            .await?;
+        assert_eq!(bs, b"Hello, World!");
+
+        let err = op
+            .read_with(
+                "hello",
+                OpRead::new().with_header("Authorization", "Bearer other"),
+            )
+            .await
+            .expect_err("overriding authorization must be rejected");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 1);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_read_via_basic_auth() -> Result<()> {
         let _ = env_logger::builder().is_test(true).try_init();
@@ -227,6 +227,7 @@ impl Accessor for IpfsBackend {
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
         let resp = self.ipfs_get(path, args.range()).await?;
+        let resp = slice_ignored_range(resp, args.range())?;
 
         let status = resp.status();
 
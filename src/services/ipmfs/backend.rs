@@ -74,9 +74,13 @@ impl Accessor for IpmfsBackend {
         am.set_scheme(Scheme::Ipmfs)
             .set_root(&self.root)
             .set_capabilities(
-                AccessorCapability::Read | AccessorCapability::Write | AccessorCapability::List,
+                AccessorCapability::Read
+                    | AccessorCapability::Write
+                    | AccessorCapability::CreateDir
+                    | AccessorCapability::List,
             )
-            .set_hints(AccessorHint::ReadStreamable);
+            .set_hints(AccessorHint::ReadStreamable)
+            .set_list_metakeys(Metakey::ContentLength);
 
         am
     }
@@ -38,15 +38,37 @@ use crate::*;
 /// - [x] scan
 /// - [ ] ~~presign~~
 /// - [x] blocking
+///
+/// # Configuration
+///
+/// - `root`: Set the working directory of `OpenDAL`
 #[derive(Default)]
-pub struct MemoryBuilder {}
+pub struct MemoryBuilder {
+    root: Option<String>,
+}
+
+impl MemoryBuilder {
+    /// Set the working directory, all operations will be performed under it.
+    ///
+    /// default: "/"
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        if !root.is_empty() {
+            self.root = Some(root.to_owned());
+        }
+        self
+    }
+}
 
 impl Builder for MemoryBuilder {
     const SCHEME: Scheme = Scheme::Memory;
     type Accessor = MemoryBackend;
 
-    fn from_map(_: HashMap<String, String>) -> Self {
-        Self::default()
+    fn from_map(map: HashMap<String, String>) -> Self {
+        let mut builder = MemoryBuilder::default();
+
+        map.get("root").map(|v| builder.root(v));
+
+        builder
     }
 
     fn build(&mut self) -> Result<Self::Accessor> {
@@ -54,7 +76,8 @@ impl Builder for MemoryBuilder {
             inner: Arc::new(Mutex::new(BTreeMap::default())),
         };
 
-        Ok(MemoryBackend::new(adapter))
+        let root = normalize_root(self.root.as_deref().unwrap_or("/"));
+        Ok(MemoryBackend::new(adapter).with_root(&root))
     }
 }
 
@@ -129,6 +152,8 @@ impl kv::Adapter for Adapter {
 
 #[cfg(test)]
 mod tests {
+    use futures::TryStreamExt;
+
     use super::*;
 
     #[test]
@@ -139,4 +164,81 @@ mod tests {
         let b2 = MemoryBuilder::default().build().unwrap();
         assert_ne!(b1.info().name(), b2.info().name())
     }
+
+    #[test]
+    fn test_operator_info() {
+        let mut builder = MemoryBuilder::default();
+        builder.root("path/to/dir");
+        let op = Operator::new(builder).unwrap().finish();
+
+        let info = op.info();
+        assert_eq!(info.scheme(), Scheme::Memory);
+        assert_eq!(info.scheme().into_static(), "memory");
+        assert_eq!(info.root(), "/path/to/dir/");
+        assert!(info.capability().supports(Operation::Read));
+        assert!(info.capability().supports(Operation::Write));
+
+        let op = Operator::new(MemoryBuilder::default()).unwrap().finish();
+        assert_eq!(op.info().root(), "/");
+    }
+
+    #[tokio::test]
+    async fn test_list_with_root() {
+        let mut builder = MemoryBuilder::default();
+        builder.root("path/to/dir");
+        let op = Operator::new(builder).unwrap().finish();
+
+        op.write("dir/file", "Hello, World!").await.unwrap();
+
+        let entries: Vec<String> = op
+            .scan("/")
+            .await
+            .unwrap()
+            .map_ok(|e| e.path().to_string())
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(entries, vec!["dir/file".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_dir_and_file_mismatch() {
+        let op = Operator::new(MemoryBuilder::default()).unwrap().finish();
+
+        op.create_dir("dir/").await.unwrap();
+        op.write("file", "Hello, World!").await.unwrap();
+
+        let err = op.read("dir").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::IsADirectory);
+        let err = op.read("not_exist").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        let err = op.list("file/").await.err().expect("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotADirectory);
+        let err = op.blocking().list("file/").err().expect("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotADirectory);
+    }
+
+    #[tokio::test]
+    async fn test_create_dir() {
+        use futures::TryStreamExt;
+
+        let op = Operator::new(MemoryBuilder::default()).unwrap().finish();
+        assert!(op.info().can_create_dir());
+
+        op.create_dir("a/b/").await.unwrap();
+        // Create dir is idempotent.
+        op.create_dir("a/b/").await.unwrap();
+
+        let meta = op.stat("a/b/").await.unwrap();
+        assert_eq!(meta.mode(), EntryMode::DIR);
+        assert_eq!(meta.content_length(), 0);
+
+        let entries: Vec<_> = op.list("a/").await.unwrap().try_collect().await.unwrap();
+        let paths: Vec<_> = entries.iter().map(|e| e.path()).collect();
+        assert_eq!(paths, vec!["a/b/"]);
+
+        let err = op.create_dir("a/c").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotADirectory);
+    }
 }
@@ -25,6 +25,11 @@ pub use azblob::Azblob;
 mod azdfs;
 pub use azdfs::Azdfs;
 
+#[cfg(feature = "services-chaos")]
+mod chaos;
+#[cfg(feature = "services-chaos")]
+pub use chaos::Chaos;
+
 #[cfg(feature = "services-dashmap")]
 mod dashmap;
 #[cfg(feature = "services-dashmap")]
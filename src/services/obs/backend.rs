@@ -312,8 +312,9 @@ impl Accessor for ObsBackend {
         am.set_scheme(Scheme::Obs)
             .set_root(&self.root)
             .set_name(&self.bucket)
-            .set_capabilities(Read | Write | List | Scan)
-            .set_hints(ReadStreamable);
+            .set_capabilities(Read | Write | CreateDir | List | Scan)
+            .set_hints(ReadStreamable)
+            .set_list_metakeys(Metakey::ContentLength);
 
         am
     }
@@ -338,6 +339,7 @@ impl Accessor for ObsBackend {
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
         let resp = self.obs_get_object(path, args.range()).await?;
+        let resp = slice_ignored_range(resp, args.range())?;
 
         let status = resp.status();
 
@@ -27,6 +27,8 @@ use bytes::Bytes;
 use http::header::CONTENT_DISPOSITION;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
+use http::header::IF_MODIFIED_SINCE;
+use http::header::IF_NONE_MATCH;
 use http::header::RANGE;
 use http::Request;
 use http::Response;
@@ -414,8 +416,11 @@ impl Accessor for OssBackend {
         am.set_scheme(Scheme::Oss)
             .set_root(&self.root)
             .set_name(&self.bucket)
-            .set_capabilities(Read | Write | List | Scan | Presign | Batch)
-            .set_hints(ReadStreamable);
+            .set_capabilities(
+                Read | Write | CreateDir | List | Scan | Presign | Batch | ConditionalStat,
+            )
+            .set_hints(ReadStreamable | ListOrderedPages)
+            .set_list_metakeys(Metakey::ContentLength | Metakey::Etag | Metakey::LastModified);
 
         am
     }
@@ -437,6 +442,7 @@ impl Accessor for OssBackend {
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
         let resp = self.oss_get_object(path, args.range()).await?;
+        let resp = slice_ignored_range(resp, args.range())?;
 
         let status = resp.status();
 
@@ -463,13 +469,13 @@ impl Accessor for OssBackend {
         ))
     }
 
-    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         if path == "/" {
             let m = Metadata::new(EntryMode::DIR);
             return Ok(RpStat::new(m));
         }
 
-        let resp = self.oss_head_object(path).await?;
+        let resp = self.oss_head_object(path, &args).await?;
 
         let status = resp.status();
 
@@ -513,7 +519,7 @@ impl Accessor for OssBackend {
     fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
         // We will not send this request out, just for signing.
         let mut req = match args.operation() {
-            PresignOperation::Stat(_) => self.oss_head_object_request(path, true)?,
+            PresignOperation::Stat(v) => self.oss_head_object_request(path, true, v)?,
             PresignOperation::Read(v) => self.oss_get_object_request(path, v.range(), true)?,
             PresignOperation::Write(v) => self.oss_put_object_request(
                 path,
@@ -596,6 +602,10 @@ impl Accessor for OssBackend {
                     Err(parse_error(resp).await?)
                 }
             }
+            BatchOperations::Write(_) => Err(Error::new(
+                ErrorKind::Unsupported,
+                "oss services doesn't support batch write",
+            )),
         }
     }
 }
@@ -670,12 +680,24 @@ impl OssBackend {
         Ok(req)
     }
 
-    fn oss_head_object_request(&self, path: &str, is_presign: bool) -> Result<Request<AsyncBody>> {
+    fn oss_head_object_request(
+        &self,
+        path: &str,
+        is_presign: bool,
+        args: &OpStat,
+    ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
         let endpoint = self.get_endpoint(is_presign);
         let url = format!("{}/{}", endpoint, percent_encode_path(&p));
 
-        let req = Request::head(&url);
+        let mut req = Request::head(&url);
+
+        if let Some(etag) = args.if_none_match() {
+            req = req.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(t) = args.if_modified_since() {
+            req = req.header(IF_MODIFIED_SINCE, format_http_date(t)?);
+        }
         let req = req
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
@@ -720,8 +742,12 @@ impl OssBackend {
         self.client.send_async(req).await
     }
 
-    async fn oss_head_object(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
-        let mut req = self.oss_head_object_request(path, false)?;
+    async fn oss_head_object(
+        &self,
+        path: &str,
+        args: &OpStat,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let mut req = self.oss_head_object_request(path, false, args)?;
 
         self.signer.sign(&mut req).map_err(new_request_sign_error)?;
         self.client.send_async(req).await
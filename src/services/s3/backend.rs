@@ -30,6 +30,9 @@ use http::header::HeaderName;
 use http::header::CONTENT_DISPOSITION;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
+use http::header::IF_MATCH;
+use http::header::IF_MODIFIED_SINCE;
+use http::header::IF_NONE_MATCH;
 use http::HeaderValue;
 use http::Request;
 use http::Response;
@@ -76,6 +79,10 @@ mod constants {
     pub const X_AMZ_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID: &str =
         "x-amz-server-side-encryption-aws-kms-key-id";
     pub const X_AMZ_BUCKET_REGION: &str = "x-amz-bucket-region";
+    pub const X_AMZ_VERSION_ID: &str = "x-amz-version-id";
+    pub const X_AMZ_DELETE_MARKER: &str = "x-amz-delete-marker";
+    pub const X_AMZ_META_PREFIX: &str = "x-amz-meta-";
+    pub const X_AMZ_STORAGE_CLASS: &str = "x-amz-storage-class";
 }
 
 /// Aws S3 and compatible services (including minio, digitalocean space and so on) support
@@ -145,6 +152,10 @@ mod constants {
 ///
 /// After SSE have been configured, all requests send by this backed will attach those headers.
 ///
+/// SSE can also be set per request via `OpWrite::with_encryption` and
+/// `OpRead::with_encryption`, which will override the backend's config.
+/// Please note that the same SSE-C key must be provided while reading.
+///
 /// Reference: [Protecting data using server-side encryption](https://docs.aws.amazon.com/AmazonS3/latest/userguide/serv-side-encryption.html)
 ///
 /// # Example
@@ -943,7 +954,14 @@ impl Builder for S3Builder {
         if let Some(v) = &self.secret_access_key {
             cfg.set_secret_access_key(v);
         }
-        if let Some(v) = &self.security_token {
+        let security_token = format_authorization_by_aws_session_token(
+            self.security_token.as_deref(),
+        )
+        .map_err(|err| {
+            err.with_operation("Builder::build")
+                .with_context("service", Scheme::S3)
+        })?;
+        if let Some(v) = &security_token {
             cfg.set_session_token(v);
         }
         if let Some(v) = &self.role_arn {
@@ -1102,6 +1120,88 @@ impl S3Backend {
 
         req
     }
+
+    /// Insert SSE headers from [`Encryption`] which will override the
+    /// encryption configured in backend.
+    ///
+    /// Only SSE-C headers will be inserted while reading.
+    pub(crate) fn insert_encryption_headers(
+        &self,
+        mut req: http::request::Builder,
+        is_write: bool,
+        encryption: Option<&Encryption>,
+    ) -> Result<http::request::Builder> {
+        let encryption = match encryption {
+            Some(v) => v,
+            None => return Ok(self.insert_sse_headers(req, is_write)),
+        };
+
+        let sensitive_value = |v: &str| {
+            let mut v = HeaderValue::from_str(v).map_err(|e| {
+                Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "server side encryption is not a valid header value",
+                )
+                .set_source(e)
+            })?;
+            v.set_sensitive(true);
+            Ok::<_, Error>(v)
+        };
+
+        match encryption {
+            Encryption::S3 => {
+                if is_write {
+                    req = req.header(
+                        HeaderName::from_static(constants::X_AMZ_SERVER_SIDE_ENCRYPTION),
+                        sensitive_value("AES256")?,
+                    );
+                }
+            }
+            Encryption::Kms { key_id } => {
+                if is_write {
+                    req = req.header(
+                        HeaderName::from_static(constants::X_AMZ_SERVER_SIDE_ENCRYPTION),
+                        sensitive_value("aws:kms")?,
+                    );
+                    if let Some(key_id) = key_id {
+                        req = req.header(
+                            HeaderName::from_static(
+                                constants::X_AMZ_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID,
+                            ),
+                            sensitive_value(key_id)?,
+                        );
+                    }
+                }
+            }
+            Encryption::Customer { algorithm, key } => {
+                if key.is_empty() {
+                    return Err(Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "customer key is required by server side encryption",
+                    ));
+                }
+
+                req = req.header(
+                    HeaderName::from_static(
+                        constants::X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM,
+                    ),
+                    sensitive_value(algorithm)?,
+                );
+                req = req.header(
+                    HeaderName::from_static(constants::X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY),
+                    sensitive_value(&BASE64_STANDARD.encode(key))?,
+                );
+                req = req.header(
+                    HeaderName::from_static(
+                        constants::X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY_MD5,
+                    ),
+                    sensitive_value(&BASE64_STANDARD.encode(Md5::digest(key).as_slice()))?,
+                );
+            }
+        }
+
+        Ok(req)
+    }
 }
 
 #[async_trait]
@@ -1121,14 +1221,39 @@ impl Accessor for S3Backend {
         am.set_scheme(Scheme::S3)
             .set_root(&self.root)
             .set_name(&self.bucket)
-            .set_capabilities(Read | Write | List | Scan | Presign | Batch)
-            .set_hints(ReadStreamable);
+            .set_capabilities(
+                Read | Write
+                    | CreateDir
+                    | List
+                    | Scan
+                    | Presign
+                    | Batch
+                    | ConditionalStat
+                    | WriteWithIfMatch
+                    | WriteWithIfNoneMatch
+                    | ResumableWrite
+                    | WriteWithContentMd5
+                    | ListWithStartAfter
+                    | WriteWithEncryption
+                    | WriteWithConcurrent
+                    | ReadWithHeader
+                    | WriteWithHeader
+                    | ReadWithVersion,
+            )
+            .set_hints(ReadStreamable | ListOrderedPages)
+            .set_list_metakeys(
+                Metakey::ContentLength
+                    | Metakey::ContentMd5
+                    | Metakey::Etag
+                    | Metakey::LastModified,
+            );
 
         am
     }
 
     async fn create(&self, path: &str, _: OpCreate) -> Result<RpCreate> {
-        let mut req = self.s3_put_object_request(path, Some(0), None, None, AsyncBody::Empty)?;
+        let mut req =
+            self.s3_put_object_request(path, Some(0), &OpWrite::new(), AsyncBody::Empty)?;
 
         self.signer.sign(&mut req).map_err(new_request_sign_error)?;
 
@@ -1146,13 +1271,27 @@ impl Accessor for S3Backend {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let resp = self.s3_get_object(path, args.range()).await?;
+        let resp = self.s3_get_object(path, &args).await?;
+        let resp = slice_ignored_range(resp, args.range())?;
 
         let status = resp.status();
 
         match status {
             StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
-                let meta = parse_into_metadata(path, resp.headers())?;
+                let mut meta = parse_into_metadata(path, resp.headers())?;
+                if let Some(v) = parse_header(resp.headers(), constants::X_AMZ_VERSION_ID)? {
+                    meta.set_version(v);
+                }
+                if parse_delete_marker(resp.headers(), constants::X_AMZ_DELETE_MARKER)? {
+                    meta.set_is_deleted(true);
+                }
+                let user_meta = parse_user_metadata(resp.headers(), constants::X_AMZ_META_PREFIX);
+                if !user_meta.is_empty() {
+                    meta.set_user_metadata(user_meta);
+                }
+                if let Some(v) = parse_header(resp.headers(), constants::X_AMZ_STORAGE_CLASS)? {
+                    meta.set_storage_class(parse_storage_class(v));
+                }
                 Ok((RpRead::with_metadata(meta), resp.into_body()))
             }
             _ => Err(parse_error(resp).await?),
@@ -1160,8 +1299,19 @@ impl Accessor for S3Backend {
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        if let (true, Some(state)) = (args.append(), args.resume()) {
+            self.s3_check_multipart_state(path, state).await?;
+
+            let upload_id = state.upload_id().to_string();
+            let parts = state.parts().to_vec();
+            return Ok((
+                RpWrite::default(),
+                S3Writer::new(self.clone(), args, path.to_string(), Some(upload_id), parts),
+            ));
+        }
+
         let upload_id = if args.append() {
-            let resp = self.s3_initiate_multipart_upload(path).await?;
+            let resp = self.s3_initiate_multipart_upload(path, &args).await?;
 
             let status = resp.status();
 
@@ -1183,22 +1333,38 @@ impl Accessor for S3Backend {
 
         Ok((
             RpWrite::default(),
-            S3Writer::new(self.clone(), args, path.to_string(), upload_id),
+            S3Writer::new(self.clone(), args, path.to_string(), upload_id, vec![]),
         ))
     }
 
-    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         // Stat root always returns a DIR.
         if path == "/" {
             return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
         }
 
-        let resp = self.s3_head_object(path).await?;
+        let resp = self.s3_head_object(path, &args).await?;
 
         let status = resp.status();
 
         match status {
-            StatusCode::OK => parse_into_metadata(path, resp.headers()).map(RpStat::new),
+            StatusCode::OK => {
+                let mut meta = parse_into_metadata(path, resp.headers())?;
+                if let Some(v) = parse_header(resp.headers(), constants::X_AMZ_VERSION_ID)? {
+                    meta.set_version(v);
+                }
+                if parse_delete_marker(resp.headers(), constants::X_AMZ_DELETE_MARKER)? {
+                    meta.set_is_deleted(true);
+                }
+                let user_meta = parse_user_metadata(resp.headers(), constants::X_AMZ_META_PREFIX);
+                if !user_meta.is_empty() {
+                    meta.set_user_metadata(user_meta);
+                }
+                if let Some(v) = parse_header(resp.headers(), constants::X_AMZ_STORAGE_CLASS)? {
+                    meta.set_storage_class(parse_storage_class(v));
+                }
+                Ok(RpStat::new(meta))
+            }
             StatusCode::NOT_FOUND if path.ends_with('/') => {
                 Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
             }
@@ -1220,7 +1386,8 @@ impl Accessor for S3Backend {
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
         Ok((
             RpList::default(),
-            S3Pager::new(Arc::new(self.clone()), &self.root, path, "/", args.limit()),
+            S3Pager::new(Arc::new(self.clone()), &self.root, path, "/", args.limit())
+                .with_start_after(args.start_after()),
         ))
     }
 
@@ -1234,10 +1401,10 @@ impl Accessor for S3Backend {
     fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
         // We will not send this request out, just for signing.
         let mut req = match args.operation() {
-            PresignOperation::Stat(_) => self.s3_head_object_request(path)?,
-            PresignOperation::Read(v) => self.s3_get_object_request(path, v.range())?,
-            PresignOperation::Write(_) => {
-                self.s3_put_object_request(path, None, None, None, AsyncBody::Empty)?
+            PresignOperation::Stat(v) => self.s3_head_object_request(path, v)?,
+            PresignOperation::Read(v) => self.s3_get_object_request(path, v)?,
+            PresignOperation::Write(v) => {
+                self.s3_put_object_request(path, None, v, AsyncBody::Empty)?
             }
         };
 
@@ -1300,19 +1467,27 @@ impl Accessor for S3Backend {
                     Err(parse_error(resp).await?)
                 }
             }
+            BatchOperations::Write(_) => Err(Error::new(
+                ErrorKind::Unsupported,
+                "s3 services doesn't support batch write",
+            )),
         }
     }
 }
 
 impl S3Backend {
-    fn s3_head_object_request(&self, path: &str) -> Result<Request<AsyncBody>> {
+    fn s3_head_object_request(&self, path: &str, args: &OpStat) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+        let mut url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+        if let Some(v) = args.version() {
+            write!(url, "?versionId={}", percent_encode_path(v))
+                .expect("write into string must succeed");
+        }
 
         let mut req = Request::head(&url);
 
-        req = self.insert_sse_headers(req, false);
+        req = self.insert_encryption_headers(req, false, args.encryption())?;
 
         let req = req
             .body(AsyncBody::Empty)
@@ -1321,34 +1496,41 @@ impl S3Backend {
         Ok(req)
     }
 
-    fn s3_get_object_request(&self, path: &str, range: BytesRange) -> Result<Request<AsyncBody>> {
+    fn s3_get_object_request(&self, path: &str, args: &OpRead) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+        let mut url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+        if let Some(v) = args.version() {
+            write!(url, "?versionId={}", percent_encode_path(v))
+                .expect("write into string must succeed");
+        }
 
         let mut req = Request::get(&url);
 
+        let range = args.range();
         if !range.is_full() {
             req = req.header(http::header::RANGE, range.to_header());
         }
 
         // Set SSE headers.
         // TODO: how will this work with presign?
-        req = self.insert_sse_headers(req, false);
+        req = self.insert_encryption_headers(req, false, args.encryption())?;
 
-        let req = req
+        let mut req = req
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
+        insert_custom_headers(&mut req, args.headers())?;
+
         Ok(req)
     }
 
     async fn s3_get_object(
         &self,
         path: &str,
-        range: BytesRange,
+        args: &OpRead,
     ) -> Result<Response<IncomingAsyncBody>> {
-        let mut req = self.s3_get_object_request(path, range)?;
+        let mut req = self.s3_get_object_request(path, args)?;
 
         self.signer.sign(&mut req).map_err(new_request_sign_error)?;
 
@@ -1359,8 +1541,7 @@ impl S3Backend {
         &self,
         path: &str,
         size: Option<usize>,
-        content_type: Option<&str>,
-        content_disposition: Option<&str>,
+        args: &OpWrite,
         body: AsyncBody,
     ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
@@ -1373,32 +1554,57 @@ impl S3Backend {
             req = req.header(CONTENT_LENGTH, size)
         }
 
-        if let Some(mime) = content_type {
+        if let Some(mime) = args.content_type() {
             req = req.header(CONTENT_TYPE, mime)
         }
 
-        if let Some(pos) = content_disposition {
+        if let Some(pos) = args.content_disposition() {
             req = req.header(CONTENT_DISPOSITION, pos)
         }
 
+        if let Some(etag) = args.if_match() {
+            req = req.header(IF_MATCH, etag)
+        }
+
+        if let Some(etag) = args.if_none_match() {
+            req = req.header(IF_NONE_MATCH, etag)
+        }
+
         // Set SSE headers.
-        req = self.insert_sse_headers(req, true);
+        req = self.insert_encryption_headers(req, true, args.encryption())?;
 
         // Set body
-        let req = req.body(body).map_err(new_request_build_error)?;
+        let mut req = req.body(body).map_err(new_request_build_error)?;
+
+        insert_custom_headers(&mut req, args.headers())?;
 
         Ok(req)
     }
 
-    async fn s3_head_object(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
+    async fn s3_head_object(
+        &self,
+        path: &str,
+        args: &OpStat,
+    ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+        let mut url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
+        if let Some(v) = args.version() {
+            write!(url, "?versionId={}", percent_encode_path(v))
+                .expect("write into string must succeed");
+        }
 
         let mut req = Request::head(&url);
 
         // Set SSE headers.
-        req = self.insert_sse_headers(req, false);
+        req = self.insert_encryption_headers(req, false, args.encryption())?;
+
+        if let Some(etag) = args.if_none_match() {
+            req = req.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(t) = args.if_modified_since() {
+            req = req.header(IF_MODIFIED_SINCE, format_http_date(t)?);
+        }
 
         let mut req = req
             .body(AsyncBody::Empty)
@@ -1431,6 +1637,7 @@ impl S3Backend {
         continuation_token: &str,
         delimiter: &str,
         limit: Option<usize>,
+        start_after: Option<&str>,
     ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
@@ -1442,6 +1649,16 @@ impl S3Backend {
         if let Some(limit) = limit {
             write!(url, "&max-keys={limit}").expect("write into string must succeed");
         }
+        if let Some(start_after) = start_after {
+            // start-after will be ignored by S3 once continuation-token
+            // is present, so it's fine to always send it.
+            write!(
+                url,
+                "&start-after={}",
+                percent_encode_path(&build_abs_path(&self.root, start_after))
+            )
+            .expect("write into string must succeed");
+        }
         if !continuation_token.is_empty() {
             // AWS S3 could return continuation-token that contains `=`
             // which could lead `reqsign` parse query wrongly.
@@ -1467,6 +1684,7 @@ impl S3Backend {
     async fn s3_initiate_multipart_upload(
         &self,
         path: &str,
+        args: &OpWrite,
     ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
@@ -1475,12 +1693,14 @@ impl S3Backend {
         let req = Request::post(&url);
 
         // Set SSE headers.
-        let req = self.insert_sse_headers(req, true);
+        let req = self.insert_encryption_headers(req, true, args.encryption())?;
 
         let mut req = req
             .body(AsyncBody::Empty)
             .map_err(new_request_build_error)?;
 
+        insert_custom_headers(&mut req, args.headers())?;
+
         self.signer.sign(&mut req).map_err(new_request_sign_error)?;
 
         self.client.send_async(req).await
@@ -1492,6 +1712,7 @@ impl S3Backend {
         upload_id: &str,
         part_number: usize,
         size: Option<u64>,
+        args: &OpWrite,
         body: AsyncBody,
     ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
@@ -1511,10 +1732,12 @@ impl S3Backend {
         }
 
         // Set SSE headers.
-        req = self.insert_sse_headers(req, true);
+        req = self.insert_encryption_headers(req, true, args.encryption())?;
 
         // Set body
-        let req = req.body(body).map_err(new_request_build_error)?;
+        let mut req = req.body(body).map_err(new_request_build_error)?;
+
+        insert_custom_headers(&mut req, args.headers())?;
 
         Ok(req)
     }
@@ -1524,6 +1747,7 @@ impl S3Backend {
         path: &str,
         upload_id: &str,
         parts: &[CompleteMultipartUploadRequestPart],
+        args: &OpWrite,
     ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
@@ -1534,10 +1758,23 @@ impl S3Backend {
             upload_id
         );
 
-        let req = Request::post(&url);
+        let mut req = Request::post(&url);
+
+        // The object will only be visible after completion, so conditions
+        // must be checked here.
+        if let Some(etag) = args.if_match() {
+            req = req.header(IF_MATCH, etag)
+        }
+
+        if let Some(etag) = args.if_none_match() {
+            req = req.header(IF_NONE_MATCH, etag)
+        }
 
         // Set SSE headers.
-        let req = self.insert_sse_headers(req, true);
+        //
+        // Use the per-request encryption so that the SSE-C key matches the
+        // one used while uploading parts.
+        let req = self.insert_encryption_headers(req, true, args.encryption())?;
 
         let content = quick_xml::se::to_string(&CompleteMultipartUploadRequest {
             part: parts.to_vec(),
@@ -1557,6 +1794,111 @@ impl S3Backend {
         self.client.send_async(req).await
     }
 
+    async fn s3_list_parts(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number_marker: Option<usize>,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = format!(
+            "{}/{}?uploadId={}",
+            self.endpoint,
+            percent_encode_path(&p),
+            upload_id
+        );
+        if let Some(marker) = part_number_marker {
+            write!(url, "&part-number-marker={marker}").expect("write into string must succeed");
+        }
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+
+        self.client.send_async(req).await
+    }
+
+    /// Check the persisted multipart state against the parts that service
+    /// has received.
+    ///
+    /// Returns `NotFound` if the upload has been completed or aborted, and
+    /// `ConditionNotMatch` if any part's etag doesn't match.
+    async fn s3_check_multipart_state(&self, path: &str, state: &MultipartState) -> Result<()> {
+        let mut uploaded = HashMap::new();
+        let mut marker = None;
+
+        loop {
+            let resp = self.s3_list_parts(path, state.upload_id(), marker).await?;
+
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+
+            let bs = resp.into_body().bytes().await?;
+            let output: ListPartsOutput =
+                quick_xml::de::from_reader(bs.reader()).map_err(new_xml_deserialize_error)?;
+
+            for part in output.part {
+                uploaded.insert(part.part_number, part.etag);
+            }
+
+            if !output.is_truncated {
+                break;
+            }
+            marker = Some(output.next_part_number_marker);
+        }
+
+        for part in state.parts() {
+            let etag = uploaded.get(&part.part_number()).ok_or_else(|| {
+                Error::new(
+                    ErrorKind::ConditionNotMatch,
+                    "part in multipart state has not been uploaded",
+                )
+                .with_context("upload_id", state.upload_id())
+                .with_context("part_number", part.part_number().to_string())
+            })?;
+
+            if etag.trim_matches('"') != part.etag().trim_matches('"') {
+                return Err(Error::new(
+                    ErrorKind::ConditionNotMatch,
+                    "etag of part in multipart state is not match",
+                )
+                .with_context("upload_id", state.upload_id())
+                .with_context("part_number", part.part_number().to_string())
+                .with_context("expect", part.etag())
+                .with_context("actual", etag));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn s3_abort_multipart_upload(
+        &self,
+        path: &str,
+        upload_id: &str,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}?uploadId={}",
+            self.endpoint,
+            percent_encode_path(&p),
+            upload_id
+        );
+
+        let mut req = Request::delete(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.signer.sign(&mut req).map_err(new_request_sign_error)?;
+
+        self.client.send_async(req).await
+    }
+
     async fn s3_delete_objects(&self, paths: Vec<String>) -> Result<Response<IncomingAsyncBody>> {
         let url = format!("{}/?delete", self.endpoint);
 
@@ -1596,6 +1938,23 @@ struct InitiateMultipartUploadResult {
     upload_id: String,
 }
 
+/// Output of ListParts
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct ListPartsOutput {
+    is_truncated: bool,
+    next_part_number_marker: usize,
+    part: Vec<ListPartsOutputPart>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct ListPartsOutputPart {
+    part_number: usize,
+    #[serde(rename = "ETag")]
+    etag: String,
+}
+
 /// Request of CompleteMultipartUploadRequest
 #[derive(Default, Debug, Serialize)]
 #[serde(default, rename = "CompleteMultipartUpload", rename_all = "PascalCase")]
@@ -1768,6 +2127,45 @@ mod tests {
         )
     }
 
+    /// This example is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_ListParts.html#API_ListParts_Examples
+    #[test]
+    fn test_deserialize_list_parts_output() {
+        let bs = Bytes::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <ListPartsResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+              <Bucket>example-bucket</Bucket>
+              <Key>example-object</Key>
+              <UploadId>XXBsb2FkIElEIGZvciBlbHZpbmcncyVcdS1tb3ZpZS5tMnRzEEEwbG9hZA</UploadId>
+              <PartNumberMarker>1</PartNumberMarker>
+              <NextPartNumberMarker>3</NextPartNumberMarker>
+              <MaxParts>2</MaxParts>
+              <IsTruncated>true</IsTruncated>
+              <Part>
+                <PartNumber>2</PartNumber>
+                <LastModified>2010-11-10T20:48:34.000Z</LastModified>
+                <ETag>"7778aef83f66abc1fa1e8477f296d394"</ETag>
+                <Size>10485760</Size>
+              </Part>
+              <Part>
+                <PartNumber>3</PartNumber>
+                <LastModified>2010-11-10T20:48:33.000Z</LastModified>
+                <ETag>"aaaa18db4cc2f85cedef654fccc4a4x8"</ETag>
+                <Size>10485760</Size>
+              </Part>
+            </ListPartsResult>"#,
+        );
+
+        let out: ListPartsOutput = quick_xml::de::from_reader(bs.reader()).expect("must success");
+
+        assert!(out.is_truncated);
+        assert_eq!(out.next_part_number_marker, 3);
+        assert_eq!(out.part.len(), 2);
+        assert_eq!(out.part[0].part_number, 2);
+        assert_eq!(out.part[0].etag, "\"7778aef83f66abc1fa1e8477f296d394\"");
+        assert_eq!(out.part[1].part_number, 3);
+        assert_eq!(out.part[1].etag, "\"aaaa18db4cc2f85cedef654fccc4a4x8\"");
+    }
+
     /// This example is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_CompleteMultipartUpload.html#API_CompleteMultipartUpload_Examples
     #[test]
     fn test_serialize_complete_multipart_upload_request() {
@@ -1871,4 +2269,1325 @@ mod tests {
         assert_eq!(out.error[0].code, "AccessDenied");
         assert_eq!(out.error[0].message, "Access Denied");
     }
+
+    #[test]
+    fn test_presign() {
+        let mut builder = S3Builder::default();
+        builder
+            .bucket("test")
+            .endpoint("https://s3.amazonaws.com")
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key")
+            .disable_config_load();
+        let op = Operator::new(builder).expect("must init").finish();
+        assert!(op.info().can_presign());
+
+        let req = op
+            .presign_read("test.txt", time::Duration::hours(1))
+            .expect("presign read must succeed");
+        assert_eq!(req.method(), http::Method::GET);
+        assert_eq!(req.uri().path(), "/test/test.txt");
+        let query = req.uri().query().expect("query must exist");
+        assert!(query.contains("X-Amz-Expires=3600"), "{query}");
+        assert!(query.contains("X-Amz-Signature="), "{query}");
+
+        let req = op
+            .presign_write("test.txt", time::Duration::minutes(5))
+            .expect("presign write must succeed");
+        assert_eq!(req.method(), http::Method::PUT);
+        assert!(req
+            .uri()
+            .query()
+            .expect("query must exist")
+            .contains("X-Amz-Expires=300"));
+
+        let req = op
+            .presign_stat("test.txt", time::Duration::hours(1))
+            .expect("presign stat must succeed");
+        assert_eq!(req.method(), http::Method::HEAD);
+    }
+
+    #[tokio::test]
+    async fn test_resume_multipart_upload() {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/test/file"))
+            .and(query_param("uploads", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<InitiateMultipartUploadResult><UploadId>upload</UploadId></InitiateMultipartUploadResult>",
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        for part in 1..=3 {
+            Mock::given(method("PUT"))
+                .and(path("/test/file"))
+                .and(query_param("partNumber", part.to_string()))
+                .and(query_param("uploadId", "upload"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("ETag", format!("\"etag-{part}\"").as_str()),
+                )
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/test/file"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListPartsResult>
+                    <IsTruncated>false</IsTruncated>
+                    <Part><PartNumber>1</PartNumber><ETag>"etag-1"</ETag><Size>7</Size></Part>
+                    <Part><PartNumber>2</PartNumber><ETag>"etag-2"</ETag><Size>5</Size></Part>
+                </ListPartsResult>"#,
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/test/file"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder
+            .bucket("test")
+            .endpoint(&server.uri())
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key")
+            .disable_config_load();
+        let op = Operator::new(builder).expect("must init").finish();
+        assert!(op.info().can_resumable_write());
+
+        let mut w = op.writer("file").await.expect("must create writer");
+        w.append("Hello, ").await.expect("must append");
+        w.append("World").await.expect("must append");
+        let state =
+            serde_json::to_string(&w.state().expect("must have state")).expect("must serialize");
+        drop(w);
+
+        let state: MultipartState = serde_json::from_str(&state).expect("must deserialize");
+        assert_eq!(state.upload_id(), "upload");
+        assert_eq!(state.uploaded_size(), 12);
+
+        let mut tampered = MultipartState::new(state.upload_id());
+        tampered.push_part(MultipartPart::new(1, "\"etag-x\"", 7));
+        let err = op
+            .writer_with("file", OpWrite::new().with_resume(tampered))
+            .await
+            .err()
+            .expect("must fail");
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+
+        let mut w = op
+            .writer_with("file", OpWrite::new().with_resume(state))
+            .await
+            .expect("must resume writer");
+        w.append("!").await.expect("must append");
+        w.close().await.expect("must close");
+
+        let requests = server.received_requests().await.expect("must record");
+        let mut parts: Vec<_> = requests
+            .iter()
+            .filter(|req| req.method.to_string() == "PUT")
+            .map(|req| {
+                let (_, part_number) = req
+                    .url
+                    .query_pairs()
+                    .find(|(k, _)| k == "partNumber")
+                    .expect("part number must exist");
+                (part_number.parse::<usize>().unwrap(), req.body.clone())
+            })
+            .collect();
+        parts.sort_by_key(|(part_number, _)| *part_number);
+        let content: Vec<u8> = parts.into_iter().flat_map(|(_, bs)| bs).collect();
+        assert_eq!(content, b"Hello, World!");
+
+        let complete = requests
+            .iter()
+            .rfind(|req| req.method.to_string() == "POST")
+            .expect("complete request must exist");
+        let body = String::from_utf8_lossy(&complete.body);
+        for part in 1..=3 {
+            assert!(
+                body.contains(&format!("<PartNumber>{part}</PartNumber>")),
+                "{body}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resume_multipart_upload_with_truncated_parts() {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::matchers::query_param_is_missing;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/test/file"))
+            .and(query_param("uploadId", "upload"))
+            .and(query_param_is_missing("part-number-marker"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListPartsResult>
+                    <IsTruncated>true</IsTruncated>
+                    <NextPartNumberMarker>1</NextPartNumberMarker>
+                    <Part><PartNumber>1</PartNumber><ETag>"etag-1"</ETag><Size>7</Size></Part>
+                </ListPartsResult>"#,
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/test/file"))
+            .and(query_param("uploadId", "upload"))
+            .and(query_param("part-number-marker", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListPartsResult>
+                    <IsTruncated>false</IsTruncated>
+                    <Part><PartNumber>2</PartNumber><ETag>"etag-2"</ETag><Size>5</Size></Part>
+                </ListPartsResult>"#,
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/test/file"))
+            .and(query_param("uploadId", "aborted"))
+            .respond_with(ResponseTemplate::new(404).set_body_string(
+                "<Error><Code>NoSuchUpload</Code><Message>aborted</Message></Error>",
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(query_param("partNumber", "3"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(200).insert_header("ETag", "\"etag-3\""))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/test/file"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder
+            .bucket("test")
+            .endpoint(&server.uri())
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key")
+            .disable_config_load();
+        let op = Operator::new(builder).expect("must init").finish();
+
+        // Upload has been aborted.
+        let mut aborted = MultipartState::new("aborted");
+        aborted.push_part(MultipartPart::new(1, "\"etag-1\"", 7));
+        let err = op
+            .writer_with("file", OpWrite::new().with_resume(aborted))
+            .await
+            .err()
+            .expect("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        // Part 3 is not listed in any page.
+        let mut missing = MultipartState::new("upload");
+        missing.push_part(MultipartPart::new(1, "\"etag-1\"", 7));
+        missing.push_part(MultipartPart::new(3, "\"etag-3\"", 1));
+        let err = op
+            .writer_with("file", OpWrite::new().with_resume(missing))
+            .await
+            .err()
+            .expect("must fail");
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+
+        // Part 2 is only listed in the second page.
+        let mut state = MultipartState::new("upload");
+        state.push_part(MultipartPart::new(1, "\"etag-1\"", 7));
+        state.push_part(MultipartPart::new(2, "etag-2", 5));
+        let state: MultipartState =
+            serde_json::from_str(&serde_json::to_string(&state).expect("must serialize"))
+                .expect("must deserialize");
+
+        let mut w = op
+            .writer_with("file", OpWrite::new().with_resume(state))
+            .await
+            .expect("must resume writer");
+        w.append("!").await.expect("must append");
+        w.close().await.expect("must close");
+
+        let requests = server.received_requests().await.expect("must record");
+        let complete = requests
+            .iter()
+            .rfind(|req| req.method.to_string() == "POST")
+            .expect("complete request must exist");
+        let body = String::from_utf8_lossy(&complete.body);
+        for part in 1..=3 {
+            assert!(
+                body.contains(&format!("<PartNumber>{part}</PartNumber>")),
+                "{body}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_multipart_state_with_concurrent_parts() {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/test/file"))
+            .and(query_param("uploads", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<InitiateMultipartUploadResult><UploadId>upload</UploadId></InitiateMultipartUploadResult>",
+            ))
+            .mount(&server)
+            .await;
+        // Part 1 will never finish before the writer is dropped.
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(query_param("partNumber", "1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"etag-1\"")
+                    .set_delay(std::time::Duration::from_secs(600)),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(query_param("partNumber", "2"))
+            .respond_with(ResponseTemplate::new(200).insert_header("ETag", "\"etag-2\""))
+            .mount(&server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder
+            .bucket("test")
+            .endpoint(&server.uri())
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key")
+            .disable_config_load();
+        let op = Operator::new(builder).expect("must init").finish();
+
+        let mut w = op
+            .writer_with("file", OpWrite::new().with_concurrent(2))
+            .await
+            .expect("must create writer");
+        w.append("Hello, ").await.expect("must append");
+        w.append("World").await.expect("must append");
+        // The third append waits for a free slot, which must be part 2.
+        w.append("!").await.expect("must append");
+
+        // Part 2 has finished but part 1 hasn't, resuming from part 2
+        // would leave a gap.
+        let state = w.state().expect("must have state");
+        assert_eq!(state.upload_id(), "upload");
+        assert!(state.parts().is_empty(), "{:?}", state.parts());
+        assert_eq!(state.uploaded_size(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_write_with_content_md5() {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let server = MockServer::start().await;
+        // md5 of `Hello, World!`
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(header("content-md5", "ZajifYh5KDgxtmS9i38K1A=="))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/test/multipart"))
+            .and(query_param("uploads", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<InitiateMultipartUploadResult><UploadId>upload</UploadId></InitiateMultipartUploadResult>",
+            ))
+            .mount(&server)
+            .await;
+        // md5 of `Hello, ` and `World!`
+        for (part, md5) in [
+            (1, "yEyruuvumpYxyL4jSsZMJg=="),
+            (2, "5QlGXvUTFUmI4IjWrTwhvw=="),
+        ] {
+            Mock::given(method("PUT"))
+                .and(path("/test/multipart"))
+                .and(query_param("partNumber", part.to_string()))
+                .and(header("content-md5", md5))
+                .respond_with(ResponseTemplate::new(200).insert_header("ETag", "\"etag\""))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("POST"))
+            .and(path("/test/multipart"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder
+            .bucket("test")
+            .endpoint(&server.uri())
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key")
+            .disable_config_load();
+        let op = Operator::new(builder).expect("must init").finish();
+        assert!(op.info().can_write_with_content_md5());
+
+        let args = OpWrite::new().with_checksum(ChecksumAlgorithm::Md5);
+        op.write_with("file", args.clone(), "Hello, World!")
+            .await
+            .expect("must write");
+
+        let mut w = op
+            .writer_with("multipart", args)
+            .await
+            .expect("must create writer");
+        w.append("Hello, ").await.expect("must append");
+        w.append("World!").await.expect("must append");
+        w.close().await.expect("must close");
+    }
+
+    #[tokio::test]
+    async fn test_write_with_user_supplied_content_md5() {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let server = MockServer::start().await;
+        // The digest will be forwarded as is without computing again, so
+        // we use the md5 of `Hello, World!` for different content here.
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(header("content-md5", "ZajifYh5KDgxtmS9i38K1A=="))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder
+            .bucket("test")
+            .endpoint(&server.uri())
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key")
+            .disable_config_load();
+        let op = Operator::new(builder).expect("must init").finish();
+
+        let args = OpWrite::new().with_content_md5("ZajifYh5KDgxtmS9i38K1A==");
+        op.write_with("file", args, "Hello, OpenDAL!")
+            .await
+            .expect("must write");
+
+        // Invalid digest will be rejected before sending request.
+        for v in ["not a digest", "65a8e27d8879283831b664bd8b7f0ad4"] {
+            let err = op
+                .write_with("file", OpWrite::new().with_content_md5(v), "Hello")
+                .await
+                .expect_err("invalid content md5 must be rejected");
+            assert_eq!(err.kind(), ErrorKind::Unexpected);
+        }
+
+        let err = op
+            .writer_with(
+                "file",
+                OpWrite::new().with_content_md5("ZajifYh5KDgxtmS9i38K1A=="),
+            )
+            .await
+            .err()
+            .expect("writer with content md5 must be rejected");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[tokio::test]
+    async fn test_encryption_with_stat_and_multipart() {
+        use futures::AsyncReadExt;
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockBuilder;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        // Requests without the SSE-C key will not be matched.
+        // base64 and md5 of `0123456789abcdef0123456789abcdef`
+        let sse_c = |m: &str| -> MockBuilder {
+            Mock::given(method(m))
+                .and(path("/test/sse-c"))
+                .and(header(
+                    "x-amz-server-side-encryption-customer-algorithm",
+                    "AES256",
+                ))
+                .and(header(
+                    "x-amz-server-side-encryption-customer-key",
+                    "MDEyMzQ1Njc4OWFiY2RlZjAxMjM0NTY3ODlhYmNkZWY=",
+                ))
+                .and(header(
+                    "x-amz-server-side-encryption-customer-key-md5",
+                    "hRasmdxgYDKV3nvbahU1MA==",
+                ))
+        };
+
+        let server = MockServer::start().await;
+        sse_c("HEAD")
+            .respond_with(ResponseTemplate::new(200).insert_header("content-length", "13"))
+            .mount(&server)
+            .await;
+        for (range, content) in [
+            (None, "Hello, World!"),
+            (Some("bytes=0-7"), "Hello, W"),
+            (Some("bytes=8-12"), "orld!"),
+        ] {
+            let mut m = sse_c("GET");
+            if let Some(range) = range {
+                m = m.and(header("range", range));
+            }
+            m.respond_with(ResponseTemplate::new(200).set_body_string(content))
+                .mount(&server)
+                .await;
+        }
+        sse_c("POST")
+            .and(query_param("uploads", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<InitiateMultipartUploadResult><UploadId>upload</UploadId></InitiateMultipartUploadResult>",
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        sse_c("PUT")
+            .and(query_param("partNumber", "1"))
+            .respond_with(ResponseTemplate::new(200).insert_header("ETag", "\"etag-1\""))
+            .expect(1)
+            .mount(&server)
+            .await;
+        sse_c("POST")
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder
+            .bucket("test")
+            .endpoint(&server.uri())
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key")
+            .disable_config_load();
+        let op = Operator::new(builder).expect("must init").finish();
+        let key = Encryption::customer_key(b"0123456789abcdef0123456789abcdef");
+
+        let meta = op
+            .stat_with("sse-c", OpStat::new().with_encryption(key.clone()))
+            .await
+            .expect("must stat");
+        assert_eq!(meta.content_length(), 13);
+
+        // Stat before reading must carry the key too.
+        for args in [
+            OpRead::new().with_decompress(true),
+            OpRead::new().with_concurrent(2).with_chunk(8),
+        ] {
+            let mut r = op
+                .reader_with("sse-c", args.with_encryption(key.clone()))
+                .await
+                .expect("must create reader");
+            let mut bs = Vec::new();
+            r.read_to_end(&mut bs).await.expect("must read");
+            assert_eq!(bs, b"Hello, World!");
+        }
+
+        let mut w = op
+            .writer_with("sse-c", OpWrite::new().with_encryption(key))
+            .await
+            .expect("must create writer");
+        w.append("Hello, World!").await.expect("must append");
+        w.close().await.expect("must close");
+    }
+
+    #[tokio::test]
+    async fn test_list_continuation_token() {
+        use futures::TryStreamExt;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let server = MockServer::start().await;
+        // Common prefixes are listed before contents by s3.
+        Mock::given(method("GET"))
+            .and(path("/test"))
+            .and(query_param("list-type", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListBucketResult>
+                    <IsTruncated>false</IsTruncated>
+                    <Contents>
+                        <Key>dir/a</Key>
+                        <LastModified>2016-04-30T23:51:29.000Z</LastModified>
+                        <ETag>"etag"</ETag>
+                        <Size>1</Size>
+                    </Contents>
+                    <Contents>
+                        <Key>dir/c</Key>
+                        <LastModified>2016-04-30T23:51:29.000Z</LastModified>
+                        <ETag>"etag"</ETag>
+                        <Size>1</Size>
+                    </Contents>
+                    <CommonPrefixes><Prefix>dir/b/</Prefix></CommonPrefixes>
+                </ListBucketResult>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder
+            .bucket("test")
+            .endpoint(&server.uri())
+            .region("us-east-1")
+            .disable_config_load();
+        let op = Operator::new(builder).expect("must init").finish();
+
+        let mut lister = op.list("dir/").await.expect("must list");
+        let mut paths = Vec::new();
+        while let Some(de) = lister.try_next().await.expect("must list") {
+            paths.push(de.path().to_string());
+        }
+        assert_eq!(paths, vec!["dir/a", "dir/b/", "dir/c"]);
+        assert_eq!(lister.continuation_token(), Some("dir/c"));
+    }
+
+    #[tokio::test]
+    async fn test_write_with_encryption() {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/test/sse-s3"))
+            .and(header("x-amz-server-side-encryption", "AES256"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/test/sse-kms"))
+            .and(header("x-amz-server-side-encryption", "aws:kms"))
+            .and(header(
+                "x-amz-server-side-encryption-aws-kms-key-id",
+                "key-id",
+            ))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // base64 and md5 of `0123456789abcdef0123456789abcdef`
+        for m in ["PUT", "GET"] {
+            Mock::given(method(m))
+                .and(path("/test/sse-c"))
+                .and(header(
+                    "x-amz-server-side-encryption-customer-algorithm",
+                    "AES256",
+                ))
+                .and(header(
+                    "x-amz-server-side-encryption-customer-key",
+                    "MDEyMzQ1Njc4OWFiY2RlZjAxMjM0NTY3ODlhYmNkZWY=",
+                ))
+                .and(header(
+                    "x-amz-server-side-encryption-customer-key-md5",
+                    "hRasmdxgYDKV3nvbahU1MA==",
+                ))
+                .respond_with(ResponseTemplate::new(200).set_body_string("Hello, World!"))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let mut builder = S3Builder::default();
+        builder
+            .bucket("test")
+            .endpoint(&server.uri())
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key")
+            .disable_config_load();
+        let op = Operator::new(builder).expect("must init").finish();
+        assert!(op.info().can_write_with_encryption());
+
+        op.write_with(
+            "sse-s3",
+            OpWrite::new().with_encryption(Encryption::S3),
+            "Hello, World!",
+        )
+        .await
+        .expect("must write");
+        op.write_with(
+            "sse-kms",
+            OpWrite::new().with_encryption(Encryption::Kms {
+                key_id: Some("key-id".to_string()),
+            }),
+            "Hello, World!",
+        )
+        .await
+        .expect("must write");
+
+        let sse_c = Encryption::customer_key(b"0123456789abcdef0123456789abcdef");
+        op.write_with(
+            "sse-c",
+            OpWrite::new().with_encryption(sse_c.clone()),
+            "Hello, World!",
+        )
+        .await
+        .expect("must write");
+        let bs = op
+            .read_with("sse-c", OpRead::new().with_encryption(sse_c))
+            .await
+            .expect("must read");
+        assert_eq!(bs, b"Hello, World!");
+
+        // SSE-C without key will be rejected before sending request.
+        let err = op
+            .write_with(
+                "sse-c",
+                OpWrite::new().with_encryption(Encryption::customer_key(b"")),
+                "Hello, World!",
+            )
+            .await
+            .expect_err("sse-c without key must be rejected");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        let err = op
+            .read_with(
+                "sse-c",
+                OpRead::new().with_encryption(Encryption::customer_key(b"")),
+            )
+            .await
+            .expect_err("sse-c without key must be rejected");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
+    #[tokio::test]
+    async fn test_write_with_custom_header() {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/test/custom"))
+            .and(header("x-tenant-id", "tenant-a"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder
+            .bucket("test")
+            .endpoint(&server.uri())
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key")
+            .disable_config_load();
+        let op = Operator::new(builder).expect("must init").finish();
+        assert!(op.info().can_write_with_header());
+
+        op.write_with(
+            "custom",
+            OpWrite::new().with_header("x-tenant-id", "tenant-a"),
+            "Hello, World!",
+        )
+        .await
+        .expect("must write");
+
+        // Headers set by OpenDAL can't be overridden.
+        for (name, value) in [
+            ("Authorization", "AWS4-HMAC-SHA256 fake"),
+            ("content-type", "text/plain"),
+        ] {
+            let err = op
+                .write_with(
+                    "custom",
+                    OpWrite::new()
+                        .with_content_type("application/json")
+                        .with_header(name, value),
+                    "Hello, World!",
+                )
+                .await
+                .expect_err("overriding headers must be rejected");
+            assert_eq!(err.kind(), ErrorKind::ConfigInvalid, "{name}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_with_version() {
+        use std::io::SeekFrom;
+
+        use futures::AsyncReadExt;
+        use futures::AsyncSeekExt;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let server = MockServer::start().await;
+        for (version, content) in [("v1", "old content"), ("v2", "new content")] {
+            for m in ["GET", "HEAD"] {
+                Mock::given(method(m))
+                    .and(path("/test/versioned"))
+                    .and(query_param("versionId", version))
+                    .respond_with(
+                        ResponseTemplate::new(200)
+                            .insert_header(constants::X_AMZ_VERSION_ID, version)
+                            .set_body_string(content),
+                    )
+                    .mount(&server)
+                    .await;
+            }
+        }
+        Mock::given(method("GET"))
+            .and(path("/test/versioned"))
+            .and(query_param("versionId", "v3"))
+            .respond_with(ResponseTemplate::new(404).set_body_string(
+                "<Error><Code>NoSuchVersion</Code><Message>The specified version does not exist.</Message></Error>",
+            ))
+            .mount(&server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder
+            .bucket("test")
+            .endpoint(&server.uri())
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key")
+            .disable_config_load();
+        let op = Operator::new(builder).expect("must init").finish();
+        assert!(op.info().can_read_with_version());
+
+        for (version, content) in [("v1", "old content"), ("v2", "new content")] {
+            let bs = op
+                .read_with("versioned", OpRead::new().with_version(version))
+                .await
+                .expect("must read");
+            assert_eq!(bs, content.as_bytes(), "{version}");
+
+            let meta = op
+                .stat_with("versioned", OpStat::new().with_version(version))
+                .await
+                .expect("must stat");
+            assert_eq!(meta.version(), Some(version));
+            assert_eq!(meta.content_length(), content.len() as u64);
+
+            // Seeking must keep reading the same version.
+            let mut r = op
+                .reader_with("versioned", OpRead::new().with_version(version))
+                .await
+                .expect("must create reader");
+            r.seek(SeekFrom::Start(4)).await.expect("must seek");
+            let mut buf = Vec::new();
+            r.read_to_end(&mut buf).await.expect("must read");
+            assert_eq!(buf, &content.as_bytes()[4..], "{version}");
+        }
+
+        let err = op
+            .read_with("versioned", OpRead::new().with_version("v3"))
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_read_into_empty_object() {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/test/empty"))
+            .respond_with(ResponseTemplate::new(416).set_body_string(
+                "<Error><Code>InvalidRange</Code><Message>The requested range is not satisfiable</Message></Error>",
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/test/empty"))
+            .respond_with(ResponseTemplate::new(200).insert_header("Content-Length", "0"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/test/missing"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/test/missing"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder
+            .bucket("test")
+            .endpoint(&server.uri())
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key")
+            .disable_config_load();
+        let op = Operator::new(builder).expect("must init").finish();
+
+        let mut buf = vec![0; 32];
+        let n = op.read_into("empty", &mut buf).await.expect("must read");
+        assert_eq!(n, 0);
+
+        let err = op
+            .read_into("missing", &mut buf)
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_read_with_decompress() {
+        use futures::AsyncReadExt;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        // `Hello, World!` repeated 16 times, compressed by gzip.
+        let gzipped: &[u8] = &[
+            0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0xf3, 0x48, 0xcd, 0xc9,
+            0xc9, 0xd7, 0x51, 0x08, 0xcf, 0x2f, 0xca, 0x49, 0x51, 0xf4, 0x18, 0xfa, 0x1c, 0x00,
+            0xbb, 0x74, 0xfa, 0x3b, 0xd0, 0x00, 0x00, 0x00,
+        ];
+        let content = "Hello, World!".repeat(16);
+
+        let server = MockServer::start().await;
+        for (name, encoding, body) in [
+            ("gzipped", Some("gzip"), gzipped.to_vec()),
+            ("plain", None, content.as_bytes().to_vec()),
+            ("compressed", Some("compress"), vec![0x1f, 0x9d]),
+        ] {
+            for m in ["GET", "HEAD"] {
+                let mut resp = ResponseTemplate::new(200).set_body_bytes(body.clone());
+                if let Some(encoding) = encoding {
+                    resp = resp.insert_header("Content-Encoding", encoding);
+                }
+                Mock::given(method(m))
+                    .and(path(format!("/test/{name}")))
+                    .respond_with(resp)
+                    .mount(&server)
+                    .await;
+            }
+        }
+
+        let mut builder = S3Builder::default();
+        builder
+            .bucket("test")
+            .endpoint(&server.uri())
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key")
+            .disable_config_load();
+        let op = Operator::new(builder).expect("must init").finish();
+
+        let meta = op.stat("gzipped").await.expect("must stat");
+        assert_eq!(meta.content_encoding(), Some("gzip"));
+
+        // Content is returned as is without decompress.
+        let bs = op.read("gzipped").await.expect("must read");
+        assert_eq!(bs, gzipped);
+
+        for name in ["gzipped", "plain"] {
+            let bs = op
+                .read_with(name, OpRead::new().with_decompress(true))
+                .await
+                .expect("must read");
+            assert_eq!(bs, content.as_bytes(), "{name}");
+
+            let mut r = op
+                .reader_with(name, OpRead::new().with_decompress(true))
+                .await
+                .expect("must create reader");
+            let mut buf = Vec::new();
+            r.read_to_end(&mut buf).await.expect("must read");
+            assert_eq!(buf, content.as_bytes(), "{name}");
+        }
+
+        let err = op
+            .read_with("compressed", OpRead::new().with_decompress(true))
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        let err = op
+            .read_with(
+                "gzipped",
+                OpRead::new()
+                    .with_decompress(true)
+                    .with_range(BytesRange::new(Some(0), Some(10))),
+            )
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
+    #[tokio::test]
+    async fn test_writer_with_content_length() {
+        use wiremock::matchers::body_bytes;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let server = MockServer::start().await;
+        // Content is sent in one request without multipart upload.
+        Mock::given(method("PUT"))
+            .and(path("/test/known_size"))
+            .and(body_bytes("Hello, World!"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder
+            .bucket("test")
+            .endpoint(&server.uri())
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key")
+            .disable_config_load();
+        let op = Operator::new(builder).expect("must init").finish();
+
+        let args = OpWrite::new().with_content_length(13);
+        let mut w = op
+            .writer_with("known_size", args)
+            .await
+            .expect("must create writer");
+        w.append("Hello, ").await.expect("must append");
+        w.append("World!").await.expect("must append");
+        w.close().await.expect("must close");
+
+        // Short write must not send any request.
+        let args = OpWrite::new().with_content_length(13);
+        let mut w = op
+            .writer_with("known_size", args)
+            .await
+            .expect("must create writer");
+        w.append("Hello").await.expect("must append");
+        let err = w.close().await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+
+    #[tokio::test]
+    async fn test_writer_with_content_length_resume() {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/test/file"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ListPartsResult>
+                    <IsTruncated>false</IsTruncated>
+                    <Part><PartNumber>1</PartNumber><ETag>"etag-1"</ETag><Size>7</Size></Part>
+                    <Part><PartNumber>2</PartNumber><ETag>"etag-2"</ETag><Size>5</Size></Part>
+                </ListPartsResult>"#,
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/test/file"))
+            .and(query_param("partNumber", "3"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(200).insert_header("ETag", "\"etag-3\""))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/test/file"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // Mismatched length must abort the multipart upload.
+        Mock::given(method("DELETE"))
+            .and(path("/test/file"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder
+            .bucket("test")
+            .endpoint(&server.uri())
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key")
+            .disable_config_load();
+        let op = Operator::new(builder).expect("must init").finish();
+
+        let mut state = MultipartState::new("upload");
+        state
+            .push_part(MultipartPart::new(1, "\"etag-1\"", 7))
+            .push_part(MultipartPart::new(2, "\"etag-2\"", 5));
+
+        // Resumed parts are counted in the declared length.
+        let args = OpWrite::new()
+            .with_resume(state.clone())
+            .with_content_length(13);
+        let mut w = op
+            .writer_with("file", args)
+            .await
+            .expect("must resume writer");
+        w.append("!").await.expect("must append");
+        w.close().await.expect("must close");
+
+        let args = OpWrite::new().with_resume(state).with_content_length(20);
+        let mut w = op
+            .writer_with("file", args)
+            .await
+            .expect("must resume writer");
+        w.append("!").await.expect("must append");
+        let err = w.close().await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+
+    #[tokio::test]
+    async fn test_write_with_concurrent() {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let server = MockServer::start().await;
+        // `failed` will be uploaded by both `write_with` and `writer_with`.
+        for (name, uploads) in [("file", 1), ("failed", 2)] {
+            Mock::given(method("POST"))
+                .and(path(format!("/test/{name}")))
+                .and(query_param("uploads", ""))
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    "<InitiateMultipartUploadResult><UploadId>upload</UploadId></InitiateMultipartUploadResult>",
+                ))
+                .expect(uploads)
+                .mount(&server)
+                .await;
+        }
+        // The second part of `failed` will be rejected.
+        Mock::given(method("PUT"))
+            .and(path("/test/failed"))
+            .and(query_param("partNumber", "2"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+        for part in 1..=5 {
+            Mock::given(method("PUT"))
+                .and(query_param("partNumber", part.to_string()))
+                .and(query_param("uploadId", "upload"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("ETag", format!("\"etag-{part}\"").as_str())
+                        .set_delay(std::time::Duration::from_millis(50)),
+                )
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("POST"))
+            .and(path("/test/file"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/test/failed"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/test/failed"))
+            .and(query_param("uploadId", "upload"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let mut builder = S3Builder::default();
+        builder
+            .bucket("test")
+            .endpoint(&server.uri())
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key")
+            .disable_config_load();
+        let op = Operator::new(builder).expect("must init").finish();
+        assert!(op.info().can_write_with_concurrent());
+
+        // 4 full parts of 8MiB and a small one.
+        let content: Vec<u8> = (0..4 * 8 * 1024 * 1024 + 123)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        let args = OpWrite::new().with_concurrent(4);
+        op.write_with("file", args.clone(), content.clone())
+            .await
+            .expect("must write");
+
+        let requests = server.received_requests().await.expect("must record");
+        let mut parts: Vec<_> = requests
+            .iter()
+            .filter(|req| req.method.to_string() == "PUT" && req.url.path() == "/test/file")
+            .map(|req| {
+                let (_, part_number) = req
+                    .url
+                    .query_pairs()
+                    .find(|(k, _)| k == "partNumber")
+                    .expect("part number must exist");
+                (part_number.parse::<usize>().unwrap(), req.body.clone())
+            })
+            .collect();
+        assert_eq!(parts.len(), 5);
+        parts.sort_by_key(|(part_number, _)| *part_number);
+        let assembled: Vec<u8> = parts.into_iter().flat_map(|(_, bs)| bs).collect();
+        assert!(assembled == content, "assembled content must match");
+
+        // Parts must be completed in order.
+        let complete = requests
+            .iter()
+            .find(|req| {
+                req.method.to_string() == "POST" && req.url.query() == Some("uploadId=upload")
+            })
+            .expect("complete request must exist");
+        let body = String::from_utf8_lossy(&complete.body);
+        let positions: Vec<_> = (1..=5)
+            .map(|part| {
+                body.find(&format!("<PartNumber>{part}</PartNumber>"))
+                    .expect("part must be completed")
+            })
+            .collect();
+        assert!(positions.windows(2).all(|v| v[0] < v[1]), "{body}");
+
+        let err = op
+            .write_with("failed", args.clone(), content.clone())
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+        // Writer aborts the upload by itself if any part failed.
+        let mut w = op
+            .writer_with("failed", args)
+            .await
+            .expect("must create writer");
+        let res = async {
+            for chunk in content.chunks(8 * 1024 * 1024) {
+                w.append(chunk.to_vec()).await?;
+            }
+            w.close().await
+        }
+        .await;
+        assert_eq!(res.expect_err("must fail").kind(), ErrorKind::Unexpected);
+        // The upload has been aborted, abort again is a no-op.
+        w.abort().await.expect("abort must succeed");
+    }
+
+    #[tokio::test]
+    async fn test_check_unreachable_endpoint() {
+        // Take a free port and close it so that nothing is listening.
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .expect("must bind")
+            .local_addr()
+            .expect("must have addr");
+
+        let mut builder = S3Builder::default();
+        builder
+            .bucket("test")
+            .endpoint(&format!("http://{addr}"))
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key")
+            .disable_config_load();
+        let op = Operator::new(builder).expect("must init").finish();
+
+        let err = op.check().await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.is_temporary());
+        assert!(
+            err.to_string().contains("failed to connect to service"),
+            "{err}"
+        );
+        assert!(err.to_string().contains("Operator::check"), "{err}");
+    }
 }
@@ -44,6 +44,10 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     let (mut kind, mut retryable) = match parts.status {
         StatusCode::NOT_FOUND => (ErrorKind::NotFound, false),
         StatusCode::FORBIDDEN => (ErrorKind::PermissionDenied, false),
+        StatusCode::NOT_MODIFIED | StatusCode::PRECONDITION_FAILED => {
+            (ErrorKind::ConditionNotMatch, false)
+        }
+        StatusCode::TOO_MANY_REQUESTS => (ErrorKind::RateLimited, true),
         StatusCode::INTERNAL_SERVER_ERROR
         | StatusCode::BAD_GATEWAY
         | StatusCode::SERVICE_UNAVAILABLE
@@ -63,16 +67,28 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
             //
             // It's Ok for us to retry it again.
             "RequestTimeout" => (ErrorKind::Unexpected, true),
+            // > A conflicting operation occurred. If using PutObject you can
+            // > retry the request.
+            "ConditionalRequestConflict" => (ErrorKind::ConditionNotMatch, false),
+            // > Indicates that the version ID specified in the request does
+            // > not match an existing version.
+            "NoSuchVersion" => (ErrorKind::NotFound, false),
             _ => (kind, retryable),
         }
     }
 
-    let mut err = Error::new(kind, &message).with_context("response", format!("{parts:?}"));
+    let mut err = Error::new(kind, &message)
+        .with_context("response", format!("{parts:?}"))
+        .with_status_code(parts.status);
 
     if retryable {
         err = err.set_temporary();
     }
 
+    if let Ok(Some(v)) = parse_retry_after(&parts.headers) {
+        err = err.with_retry_after(v);
+    }
+
     Ok(err)
 }
 
@@ -103,4 +119,24 @@ mod tests {
         assert_eq!(out.resource, "/mybucket/myfoto.jpg");
         assert_eq!(out.request_id, "4442587FB7D0A2F9");
     }
+
+    #[tokio::test]
+    async fn test_parse_error_with_status_code() {
+        let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Error>
+  <Code>AccessDenied</Code>
+  <Message>Access Denied</Message>
+</Error>"#;
+        let resp = Response::builder()
+            .status(StatusCode::FORBIDDEN)
+            .body(IncomingAsyncBody::new(
+                Box::new(futures::stream::iter(vec![Ok(bytes::Bytes::from(body))])),
+                None,
+            ))
+            .expect("must build");
+
+        let err = parse_error(resp).await.expect("must parse");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        assert_eq!(err.status_code(), Some(StatusCode::FORBIDDEN));
+    }
 }
@@ -39,6 +39,7 @@ pub struct S3Pager {
     path: String,
     delimiter: String,
     limit: Option<usize>,
+    start_after: Option<String>,
 
     token: String,
     done: bool,
@@ -58,11 +59,17 @@ impl S3Pager {
             path: path.to_string(),
             delimiter: delimiter.to_string(),
             limit,
+            start_after: None,
 
             token: "".to_string(),
             done: false,
         }
     }
+
+    pub fn with_start_after(mut self, start_after: Option<&str>) -> Self {
+        self.start_after = start_after.map(|v| v.to_string());
+        self
+    }
 }
 
 #[async_trait]
@@ -74,7 +81,13 @@ impl oio::Page for S3Pager {
 
         let resp = self
             .backend
-            .s3_list_objects(&self.path, &self.token, &self.delimiter, self.limit)
+            .s3_list_objects(
+                &self.path,
+                &self.token,
+                &self.delimiter,
+                self.limit,
+                self.start_after.as_deref(),
+            )
             .await?;
 
         if resp.status() != http::StatusCode::OK {
@@ -145,6 +158,10 @@ impl oio::Page for S3Pager {
             entries.push(de);
         }
 
+        // Both common prefixes and contents are sorted by s3, merge them so
+        // that the whole page is in lexicographic order.
+        entries.sort_unstable_by(|a, b| a.path().cmp(b.path()));
+
         Ok(Some(entries))
     }
 }
@@ -17,11 +17,19 @@
 
 use async_trait::async_trait;
 use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::stream::FuturesUnordered;
+use futures::FutureExt;
+use futures::StreamExt;
+use http::HeaderValue;
 use http::StatusCode;
+use log::warn;
+use parking_lot::Mutex;
 
 use super::backend::CompleteMultipartUploadRequestPart;
 use super::backend::S3Backend;
 use super::error::parse_error;
+use crate::ops::ChecksumAlgorithm;
 use crate::ops::OpWrite;
 use crate::raw::*;
 use crate::*;
@@ -33,18 +41,165 @@ pub struct S3Writer {
     path: String,
 
     upload_id: Option<String>,
-    parts: Vec<CompleteMultipartUploadRequestPart>,
+    parts: Vec<MultipartPart>,
+    /// AWS S3 requires part number must between [1..=10000]
+    next_part_number: usize,
+    /// Parts that are uploading, at most `op.concurrent()`.
+    ///
+    /// They are polled only inside `append` and `close`, so no task will be
+    /// spawned. Wrapped by `Mutex` to make writer Sync, it will only be
+    /// accessed via `get_mut` so there is no lock contention.
+    uploading: Mutex<FuturesUnordered<BoxFuture<'static, Result<MultipartPart>>>>,
+    /// The multipart upload has been aborted since a part failed.
+    aborted: bool,
 }
 
 impl S3Writer {
-    pub fn new(backend: S3Backend, op: OpWrite, path: String, upload_id: Option<String>) -> Self {
+    pub fn new(
+        backend: S3Backend,
+        op: OpWrite,
+        path: String,
+        upload_id: Option<String>,
+        parts: Vec<MultipartPart>,
+    ) -> Self {
+        let next_part_number = parts.last().map(|v| v.part_number() + 1).unwrap_or(1);
+
         S3Writer {
             backend,
             op,
             path,
             upload_id,
-            parts: vec![],
+            parts,
+            next_part_number,
+            uploading: Mutex::new(FuturesUnordered::new()),
+            aborted: false,
+        }
+    }
+
+    /// Build `Content-MD5` of given content if required by users.
+    ///
+    /// Every part of multipart upload will be checked independently.
+    fn content_md5(&self, bs: &[u8]) -> Option<HeaderValue> {
+        match self.op.checksum() {
+            Some(ChecksumAlgorithm::Md5) => {
+                // base64 encoded digest always be a valid header value.
+                Some(HeaderValue::try_from(format_content_md5(bs)).expect("must be valid"))
+            }
+            _ => None,
+        }
+    }
+
+    /// Wait for the next uploading part to finish.
+    ///
+    /// The multipart upload will be aborted if the part failed, so that no
+    /// orphaned parts will be left.
+    async fn wait_uploading(&mut self) -> Result<()> {
+        let part = match self.uploading.get_mut().next().await {
+            Some(Ok(part)) => part,
+            Some(Err(err)) => {
+                if let Err(e) = self.abort_upload().await {
+                    warn!("abort multipart upload of {} failed: {e:?}", self.path);
+                }
+                self.aborted = true;
+                return Err(err);
+            }
+            None => return Ok(()),
+        };
+
+        self.parts.push(part);
+        Ok(())
+    }
+
+    fn check_aborted(&self) -> Result<()> {
+        if self.aborted {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "multipart upload has been aborted since a part failed",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Abort the multipart upload, uploading parts will be dropped first,
+    /// otherwise they could be uploaded after the upload has been aborted.
+    async fn abort_upload(&mut self) -> Result<()> {
+        self.uploading.get_mut().clear();
+
+        let upload_id = if let Some(upload_id) = &self.upload_id {
+            upload_id
+        } else {
+            return Ok(());
+        };
+
+        let resp = self
+            .backend
+            .s3_abort_multipart_upload(&self.path, upload_id)
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            // AWS S3 returns `NO_CONTENT` for aborted uploads, and
+            // `NOT_FOUND` if the upload has already been aborted.
+            StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+}
+
+/// Upload given content as part `part_number` of the multipart upload.
+async fn upload_part(
+    backend: S3Backend,
+    path: String,
+    upload_id: String,
+    part_number: usize,
+    op: OpWrite,
+    content_md5: Option<HeaderValue>,
+    bs: Bytes,
+) -> Result<MultipartPart> {
+    let size = bs.len() as u64;
+
+    let mut req = backend.s3_upload_part_request(
+        &path,
+        &upload_id,
+        part_number,
+        Some(size),
+        &op,
+        AsyncBody::Bytes(bs),
+    )?;
+    if let Some(v) = content_md5 {
+        req.headers_mut().insert("content-md5", v);
+    }
+
+    backend
+        .signer
+        .sign(&mut req)
+        .map_err(new_request_sign_error)?;
+
+    let resp = backend.client.send_async(req).await?;
+
+    let status = resp.status();
+
+    match status {
+        StatusCode::OK => {
+            let etag = parse_etag(resp.headers())?
+                .ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::Unexpected,
+                        "ETag not present in returning response",
+                    )
+                })?
+                .to_string();
+
+            resp.into_body().consume().await?;
+
+            Ok(MultipartPart::new(part_number, &etag, size))
         }
+        _ => Err(parse_error(resp).await?),
     }
 }
 
@@ -56,13 +211,26 @@ impl oio::Write for S3Writer {
             "Writer initiated with upload id, but users trying to call write, must be buggy"
         );
 
+        // Prefer the pre-computed content md5 supplied by users.
+        let content_md5 = match self.op.content_md5() {
+            Some(v) => Some(HeaderValue::try_from(v).map_err(|e| {
+                Error::new(
+                    ErrorKind::Unexpected,
+                    "content md5 is not a valid header value",
+                )
+                .set_source(e)
+            })?),
+            None => self.content_md5(&bs),
+        };
         let mut req = self.backend.s3_put_object_request(
             &self.path,
             Some(bs.len()),
-            self.op.content_type(),
-            self.op.content_disposition(),
+            &self.op,
             AsyncBody::Bytes(bs),
         )?;
+        if let Some(v) = content_md5 {
+            req.headers_mut().insert("content-md5", v);
+        }
 
         self.backend
             .signer
@@ -83,61 +251,68 @@ impl oio::Write for S3Writer {
     }
 
     async fn append(&mut self, bs: Bytes) -> Result<()> {
-        let upload_id = self.upload_id.as_ref().expect(
+        self.check_aborted()?;
+
+        let upload_id = self.upload_id.clone().expect(
             "Writer doesn't have upload id, but users trying to call append, must be buggy",
         );
-        // AWS S3 requires part number must between [1..=10000]
-        let part_number = self.parts.len() + 1;
+        let part_number = self.next_part_number;
+        self.next_part_number += 1;
 
-        let mut req = self.backend.s3_upload_part_request(
-            &self.path,
+        let content_md5 = self.content_md5(&bs);
+        let fut = upload_part(
+            self.backend.clone(),
+            self.path.clone(),
             upload_id,
             part_number,
-            Some(bs.len() as u64),
-            AsyncBody::Bytes(bs),
-        )?;
-
-        self.backend
-            .signer
-            .sign(&mut req)
-            .map_err(new_request_sign_error)?;
-
-        let resp = self.backend.client.send_async(req).await?;
+            self.op.clone(),
+            content_md5,
+            bs,
+        );
 
-        let status = resp.status();
+        if self.op.concurrent() <= 1 {
+            let part = fut.await?;
+            self.parts.push(part);
+            return Ok(());
+        }
 
-        match status {
-            StatusCode::OK => {
-                let etag = parse_etag(resp.headers())?
-                    .ok_or_else(|| {
-                        Error::new(
-                            ErrorKind::Unexpected,
-                            "ETag not present in returning response",
-                        )
-                    })?
-                    .to_string();
+        // Wait for a free slot so that at most `concurrent` parts are
+        // buffered in memory.
+        while self.uploading.get_mut().len() >= self.op.concurrent() {
+            self.wait_uploading().await?;
+        }
+        self.uploading.get_mut().push(fut.boxed());
 
-                resp.into_body().consume().await?;
+        Ok(())
+    }
 
-                self.parts
-                    .push(CompleteMultipartUploadRequestPart { part_number, etag });
+    async fn close(&mut self) -> Result<()> {
+        self.check_aborted()?;
 
-                Ok(())
-            }
-            _ => Err(parse_error(resp).await?),
+        while !self.uploading.get_mut().is_empty() {
+            self.wait_uploading().await?;
         }
-    }
 
-    async fn close(&mut self) -> Result<()> {
         let upload_id = if let Some(upload_id) = &self.upload_id {
             upload_id
         } else {
             return Ok(());
         };
 
+        // Parts could be finished out of order while uploading concurrently.
+        self.parts.sort_by_key(|v| v.part_number());
+        let parts: Vec<_> = self
+            .parts
+            .iter()
+            .map(|v| CompleteMultipartUploadRequestPart {
+                part_number: v.part_number(),
+                etag: v.etag().to_string(),
+            })
+            .collect();
+
         let resp = self
             .backend
-            .s3_complete_multipart_upload(&self.path, upload_id, &self.parts)
+            .s3_complete_multipart_upload(&self.path, upload_id, &parts, &self.op)
             .await?;
 
         let status = resp.status();
@@ -151,4 +326,42 @@ impl oio::Write for S3Writer {
             _ => Err(parse_error(resp).await?),
         }
     }
+
+    fn state(&self) -> Result<MultipartState> {
+        let upload_id = self.upload_id.as_ref().ok_or_else(|| {
+            Error::new(
+                ErrorKind::Unsupported,
+                "writer is not a multipart upload, no state to fetch",
+            )
+        })?;
+
+        // Parts could be finished out of order while uploading concurrently,
+        // so only the contiguous prefix of finished parts will be included.
+        // Parts after the first unfinished one need to be uploaded again
+        // while resuming, otherwise the resumed upload will have gaps.
+        let mut parts = self.parts.clone();
+        parts.sort_by_key(|v| v.part_number());
+
+        let mut state = MultipartState::new(upload_id);
+        for (idx, part) in parts.into_iter().enumerate() {
+            if part.part_number() != idx + 1 {
+                break;
+            }
+            state.push_part(part);
+        }
+        Ok(state)
+    }
+
+    /// # Notes
+    ///
+    /// Nothing will be uploaded until `write` called for non-multipart
+    /// writer, so only multipart upload needs to be aborted.
+    async fn abort(&mut self) -> Result<()> {
+        // The upload has been aborted already.
+        if self.aborted {
+            return Ok(());
+        }
+
+        self.abort_upload().await
+    }
 }
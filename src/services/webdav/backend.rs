@@ -44,6 +44,7 @@ use crate::*;
 /// - [x] read
 /// - [x] write
 /// - [x] list
+/// - [x] rename
 /// - [ ] ~~scan~~
 /// - [ ] ~~presign~~
 /// - [ ] blocking
@@ -263,7 +264,11 @@ impl Accessor for WebdavBackend {
         ma.set_scheme(Scheme::Webdav)
             .set_root(&self.root)
             .set_capabilities(
-                AccessorCapability::Read | AccessorCapability::Write | AccessorCapability::List,
+                AccessorCapability::Read
+                    | AccessorCapability::Write
+                    | AccessorCapability::CreateDir
+                    | AccessorCapability::List
+                    | AccessorCapability::Rename,
             )
             .set_hints(AccessorHint::ReadStreamable);
 
@@ -291,6 +296,7 @@ impl Accessor for WebdavBackend {
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
         let resp = self.webdav_get(path, args.range()).await?;
+        let resp = slice_ignored_range(resp, args.range())?;
 
         let status = resp.status();
 
@@ -348,6 +354,21 @@ impl Accessor for WebdavBackend {
         }
     }
 
+    async fn rename(&self, from: &str, to: &str, _: OpRename) -> Result<RpRename> {
+        let resp = self.webdav_move(from, to).await?;
+
+        let status = resp.status();
+
+        match status {
+            // `CREATED` for new target, `NO_CONTENT` for overwritten target.
+            StatusCode::CREATED | StatusCode::NO_CONTENT => {
+                resp.into_body().consume().await?;
+                Ok(RpRename::default())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
     async fn list(&self, path: &str, _: OpList) -> Result<(RpList, Self::Pager)> {
         // XML body must start without a new line. Otherwise, the server will panic: `xmlParseChunk() failed`
         let all_prop_xml_body = r#"<?xml version="1.0" encoding="utf-8" ?>
@@ -544,6 +565,30 @@ impl WebdavBackend {
         self.client.send_async(req).await
     }
 
+    async fn webdav_move(&self, from: &str, to: &str) -> Result<Response<IncomingAsyncBody>> {
+        let source = build_abs_path(&self.root, from);
+        let target = build_abs_path(&self.root, to);
+
+        let url = format!("{}/{}", self.endpoint, percent_encode_path(&source));
+        let destination = format!("{}/{}", self.endpoint, percent_encode_path(&target));
+
+        let mut req = Request::builder()
+            .method("MOVE")
+            .uri(&url)
+            .header("Destination", destination)
+            .header("Overwrite", "T");
+
+        if let Some(auth) = &self.authorization {
+            req = req.header(header::AUTHORIZATION, auth.clone())
+        }
+
+        let req = req
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.client.send_async(req).await
+    }
+
     async fn create_internal(&self, abs_path: &str) -> Result<RpCreate> {
         let resp = if abs_path.ends_with('/') {
             self.webdav_mkcol(abs_path, None, None, AsyncBody::Empty)
@@ -110,6 +110,7 @@ pub struct WebhdfsBuilder {
     root: Option<String>,
     endpoint: Option<String>,
     delegation: Option<String>,
+    http_client: Option<HttpClient>,
 }
 
 impl Debug for WebhdfsBuilder {
@@ -170,6 +171,17 @@ impl WebhdfsBuilder {
         }
         self
     }
+
+    /// Specify the http client that used by this service.
+    ///
+    /// # Notes
+    ///
+    /// This API is part of OpenDAL's Raw API. `HttpClient` could be changed
+    /// during minor updates.
+    pub fn http_client(&mut self, client: HttpClient) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
 }
 
 impl WebhdfsBuilder {
@@ -228,7 +240,14 @@ impl Builder for WebhdfsBuilder {
         debug!("backend use endpoint {}", endpoint);
 
         let auth = self.auth_str();
-        let client = HttpClient::new()?;
+        let client = if let Some(client) = self.http_client.take() {
+            client
+        } else {
+            HttpClient::new().map_err(|err| {
+                err.with_operation("Builder::build")
+                    .with_context("service", Scheme::Webhdfs)
+            })?
+        };
 
         let backend = WebhdfsBackend {
             root: root.clone(),
@@ -542,7 +561,10 @@ impl Accessor for WebhdfsBackend {
         am.set_scheme(Scheme::Webhdfs)
             .set_root(&self.root)
             .set_capabilities(
-                AccessorCapability::Read | AccessorCapability::Write | AccessorCapability::List,
+                AccessorCapability::Read
+                    | AccessorCapability::Write
+                    | AccessorCapability::CreateDir
+                    | AccessorCapability::List,
             )
             .set_hints(AccessorHint::ReadStreamable);
         am
@@ -590,6 +612,9 @@ impl Accessor for WebhdfsBackend {
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
         let range = args.range();
+        // Webhdfs sends range via `offset` and `length` query params instead
+        // of `Range` header, so the response is always `200 OK` without
+        // `Content-Range` and must not be sliced again.
         let resp = self.webhdfs_get_object(path, range).await?;
         match resp.status() {
             StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
@@ -691,3 +716,96 @@ impl Accessor for WebhdfsBackend {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_http_client() {
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let server = MockServer::start().await;
+        // Root check and stat should both be sent via the injected client.
+        Mock::given(method("GET"))
+            .and(path("/webhdfs/v1/"))
+            .and(query_param("op", "GETFILESTATUS"))
+            .and(header("x-injected-client", "true"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"FileStatus":{"length":0,"modificationTime":0,"pathSuffix":"","type":"DIRECTORY"}}"#,
+            ))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let mut headers = http::HeaderMap::new();
+        headers.insert("x-injected-client", "true".parse().unwrap());
+        let async_client = reqwest::ClientBuilder::new()
+            .default_headers(headers)
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap();
+        let sync_client = ureq::AgentBuilder::new().redirects(0).build();
+
+        let mut builder = WebhdfsBuilder::default();
+        builder
+            .endpoint(&server.uri())
+            .http_client(HttpClient::with_client(async_client, sync_client));
+        let op = Operator::new(builder).unwrap().finish();
+
+        let meta = op.stat("/").await.unwrap();
+        assert!(meta.mode().is_dir());
+    }
+
+    #[tokio::test]
+    async fn test_range_read() {
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+        use wiremock::matchers::query_param;
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/webhdfs/v1/"))
+            .and(query_param("op", "GETFILESTATUS"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"FileStatus":{"length":0,"modificationTime":0,"pathSuffix":"","type":"DIRECTORY"}}"#,
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/webhdfs/v1/hello"))
+            .and(query_param("op", "OPEN"))
+            .and(query_param("offset", "7"))
+            .and(query_param("length", "5"))
+            .respond_with(ResponseTemplate::new(307).insert_header(
+                "location",
+                format!("{}/datanode/hello?offset=7&length=5", server.uri()).as_str(),
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // Datanode returns the requested range with `200 OK`.
+        Mock::given(method("GET"))
+            .and(path("/datanode/hello"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("World"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut builder = WebhdfsBuilder::default();
+        builder.endpoint(&server.uri());
+        let op = Operator::new(builder).unwrap().finish();
+
+        let bs = op.range_read("hello", 7..12).await.unwrap();
+        assert_eq!(bs, b"World");
+    }
+}
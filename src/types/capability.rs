@@ -0,0 +1,204 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use flagset::FlagSet;
+
+use crate::raw::*;
+
+/// Capability describes what operations an [`Operator`][crate::Operator]
+/// supports.
+///
+/// Capability is a snapshot of operator's capability, users can use it to
+/// decide which operations to call at runtime.
+///
+/// # Examples
+///
+/// ```
+/// use opendal::raw::Operation;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let op = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .finish();
+///
+/// let cap = op.capability();
+/// assert!(cap.supports(Operation::Read));
+/// assert!(!cap.supports(Operation::Presign));
+/// println!("memory supports: {cap}");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Capability(FlagSet<AccessorCapability>);
+
+impl Capability {
+    pub(crate) fn new(capabilities: FlagSet<AccessorCapability>) -> Self {
+        Capability(capabilities)
+    }
+
+    /// Check if given operation is supported.
+    ///
+    /// # Notes
+    ///
+    /// - `list` and `scan` can be emulated by each other, so both of them
+    ///   are supported if service supports any of them.
+    /// - Blocking operations require the service to support blocking.
+    pub fn supports(&self, op: Operation) -> bool {
+        let has = |cap: AccessorCapability| self.0.contains(cap);
+
+        match op {
+            Operation::Info => true,
+            Operation::Read | Operation::Stat => has(AccessorCapability::Read),
+            Operation::Create | Operation::Write | Operation::Delete => {
+                has(AccessorCapability::Write)
+            }
+            Operation::List | Operation::Scan => {
+                has(AccessorCapability::List) || has(AccessorCapability::Scan)
+            }
+            Operation::Copy => has(AccessorCapability::Copy),
+            Operation::Rename => has(AccessorCapability::Rename),
+            Operation::Presign => has(AccessorCapability::Presign),
+            Operation::Batch => has(AccessorCapability::Batch),
+            Operation::BlockingCreate => self.supports_blocking(Operation::Create),
+            Operation::BlockingRead => self.supports_blocking(Operation::Read),
+            Operation::BlockingWrite => self.supports_blocking(Operation::Write),
+            Operation::BlockingStat => self.supports_blocking(Operation::Stat),
+            Operation::BlockingDelete => self.supports_blocking(Operation::Delete),
+            Operation::BlockingCopy => self.supports_blocking(Operation::Copy),
+            Operation::BlockingRename => self.supports_blocking(Operation::Rename),
+            Operation::BlockingList => self.supports_blocking(Operation::List),
+            Operation::BlockingScan => self.supports_blocking(Operation::Scan),
+        }
+    }
+
+    fn supports_blocking(&self, op: Operation) -> bool {
+        self.0.contains(AccessorCapability::Blocking) && self.supports(op)
+    }
+}
+
+impl Display for Capability {
+    /// Summarize enabled features like `read, write, list, scan, blocking`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut features: Vec<&str> = [
+            Operation::Read,
+            Operation::Write,
+            Operation::List,
+            Operation::Scan,
+            Operation::Copy,
+            Operation::Rename,
+            Operation::Presign,
+            Operation::Batch,
+        ]
+        .into_iter()
+        .filter(|op| self.supports(*op))
+        .map(|op| op.into_static())
+        .collect();
+
+        for (cap, name) in [
+            (AccessorCapability::Blocking, "blocking"),
+            (AccessorCapability::CreateDir, "create_dir"),
+            (AccessorCapability::BatchWrite, "batch_write"),
+            (AccessorCapability::ConditionalStat, "conditional_stat"),
+            (AccessorCapability::WriteWithIfMatch, "write_with_if_match"),
+            (
+                AccessorCapability::WriteWithIfNoneMatch,
+                "write_with_if_none_match",
+            ),
+            (
+                AccessorCapability::WriteWithIfUnmodifiedSince,
+                "write_with_if_unmodified_since",
+            ),
+            (
+                AccessorCapability::DeleteWithIfUnmodifiedSince,
+                "delete_with_if_unmodified_since",
+            ),
+            (AccessorCapability::DeleteWithStrict, "delete_with_strict"),
+            (
+                AccessorCapability::CopyWithIfNoneMatch,
+                "copy_with_if_none_match",
+            ),
+            (AccessorCapability::ResumableWrite, "resumable_write"),
+            (
+                AccessorCapability::WriteWithContentMd5,
+                "write_with_content_md5",
+            ),
+            (AccessorCapability::WriteCanAppend, "write_can_append"),
+            (
+                AccessorCapability::WriteWithConcurrent,
+                "write_with_concurrent",
+            ),
+            (
+                AccessorCapability::WriteWithEncryption,
+                "write_with_encryption",
+            ),
+            (
+                AccessorCapability::ListWithStartAfter,
+                "list_with_start_after",
+            ),
+            (AccessorCapability::ReadWithHeader, "read_with_header"),
+            (AccessorCapability::WriteWithHeader, "write_with_header"),
+            (AccessorCapability::ReadWithVersion, "read_with_version"),
+        ] {
+            if self.0.contains(cap) {
+                features.push(name);
+            }
+        }
+
+        if features.is_empty() {
+            write!(f, "none")
+        } else {
+            write!(f, "{}", features.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services;
+    use crate::Operator;
+
+    #[test]
+    fn test_memory_capability() {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .finish();
+        let cap = op.capability();
+
+        assert!(cap.supports(Operation::Read));
+        assert!(cap.supports(Operation::Write));
+        assert!(cap.supports(Operation::List));
+        assert!(cap.supports(Operation::Scan));
+        assert!(!cap.supports(Operation::Presign));
+        assert!(!cap.supports(Operation::Copy));
+
+        let summary = cap.to_string();
+        assert!(summary.starts_with("read, write, list, scan"), "{summary}");
+        assert!(!summary.contains("presign"), "{summary}");
+    }
+
+    #[test]
+    fn test_capability_display() {
+        assert_eq!(Capability::default().to_string(), "none");
+
+        let cap = Capability::new(AccessorCapability::Read | AccessorCapability::Presign);
+        assert_eq!(cap.to_string(), "read, presign");
+        assert!(!cap.supports(Operation::BlockingRead));
+    }
+}
@@ -40,6 +40,9 @@ use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::io;
+use std::time::Duration;
+
+use http::StatusCode;
 
 /// Result that is a wrapper of `Result<T, opendal::Error>`
 pub type Result<T> = std::result::Result<T, Error>;
@@ -68,6 +71,13 @@ pub enum ErrorKind {
     AlreadyExists,
     /// Requests that sent to this path is over the limit, please slow down.
     RateLimited,
+    /// The condition of this operation is not match.
+    ///
+    /// For example, `stat` with `if_none_match` will return this error if
+    /// the etag is still the same, which means the path is not modified.
+    /// And `write` with `if_match` will return this error if the etag has
+    /// been changed by others.
+    ConditionNotMatch,
 }
 
 impl ErrorKind {
@@ -95,6 +105,7 @@ impl From<ErrorKind> for &'static str {
             ErrorKind::NotADirectory => "NotADirectory",
             ErrorKind::AlreadyExists => "AlreadyExists",
             ErrorKind::RateLimited => "RateLimited",
+            ErrorKind::ConditionNotMatch => "ConditionNotMatch",
         }
     }
 }
@@ -140,6 +151,9 @@ pub struct Error {
     operation: &'static str,
     context: Vec<(&'static str, String)>,
     source: Option<anyhow::Error>,
+    retry_after: Option<Duration>,
+    request_id: Option<String>,
+    status_code: Option<StatusCode>,
 }
 
 impl Display for Error {
@@ -183,6 +197,9 @@ impl Debug for Error {
             de.field("operation", &self.operation);
             de.field("context", &self.context);
             de.field("source", &self.source);
+            de.field("retry_after", &self.retry_after);
+            de.field("request_id", &self.request_id);
+            de.field("status_code", &self.status_code);
             return de.finish();
         }
 
@@ -225,6 +242,9 @@ impl Error {
             operation: "",
             context: Vec::default(),
             source: None,
+            retry_after: None,
+            request_id: None,
+            status_code: None,
         }
     }
 
@@ -261,6 +281,31 @@ impl Error {
         self
     }
 
+    /// Set the delay that services asked us to wait before retrying.
+    ///
+    /// This is usually parsed from the `Retry-After` header, and will be
+    /// preferred over the computed backoff by [`RetryLayer`][crate::layers::RetryLayer].
+    pub fn with_retry_after(mut self, delay: Duration) -> Self {
+        self.retry_after = Some(delay);
+        self
+    }
+
+    /// Set the request id of the operation that returns this error.
+    ///
+    /// The request id will also be added into context so that it can be
+    /// found in logs.
+    pub fn with_request_id(mut self, id: &str) -> Self {
+        self.request_id = Some(id.to_string());
+        self.context.push(("request_id", id.to_string()));
+        self
+    }
+
+    /// Set the http status code of the response that returns this error.
+    pub fn with_status_code(mut self, code: StatusCode) -> Self {
+        self.status_code = Some(code);
+        self
+    }
+
     /// Operate on error with map.
     pub fn map<F>(self, f: F) -> Self
     where
@@ -300,6 +345,25 @@ impl Error {
     pub fn is_temporary(&self) -> bool {
         self.status == ErrorStatus::Temporary
     }
+
+    /// Return the delay that services asked us to wait before retrying.
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.retry_after
+    }
+
+    /// Return the request id of the operation that returns this error.
+    ///
+    /// Only available while [`RequestIdLayer`][crate::layers::RequestIdLayer] is used.
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+
+    /// Return the http status code of the response that returns this error.
+    ///
+    /// Only available for errors parsed from http responses of services.
+    pub fn status_code(&self) -> Option<StatusCode> {
+        self.status_code
+    }
 }
 
 impl From<Error> for io::Error {
@@ -331,6 +395,9 @@ mod tests {
             ("called", "send_async".to_string()),
         ],
         source: Some(anyhow!("networking error")),
+        retry_after: None,
+        request_id: None,
+        status_code: None,
     });
 
     #[test]
@@ -21,11 +21,13 @@ use std::pin::Pin;
 use std::task::Context;
 use std::task::Poll;
 
+use flagset::FlagSet;
 use futures::future::BoxFuture;
 use futures::ready;
 use futures::FutureExt;
 use futures::Stream;
 
+use crate::ops::*;
 use crate::raw::*;
 use crate::*;
 
@@ -36,7 +38,15 @@ use crate::*;
 ///
 /// User can use lister as `Stream<Item = Result<Entry>>` or
 /// call `next_page` directly.
+///
+/// If lister is constructed with a metakey (for example, by `list_with`),
+/// entries whose cached metadata doesn't cover the metakey will be
+/// stat-ed before being returned.
 pub struct Lister {
+    acc: FusedAccessor,
+    metakey: FlagSet<Metakey>,
+    pattern: Option<ListPattern>,
+    files_only: bool,
     pager: Option<oio::Pager>,
 
     buf: VecDeque<oio::Entry>,
@@ -45,16 +55,51 @@ pub struct Lister {
     /// a future.
     #[allow(clippy::type_complexity)]
     fut: Option<BoxFuture<'static, (oio::Pager, Result<Option<Vec<oio::Entry>>>)>>,
+    /// The ongoing stat for an entry that doesn't have required metadata.
+    stat_fut: Option<BoxFuture<'static, Result<Entry>>>,
+    /// The path of the last returned entry.
+    last_path: Option<String>,
+    /// Whether entries have been returned in lexicographic order so far.
+    ordered: bool,
 }
 
 impl Lister {
     /// Create a new lister.
-    pub(crate) fn new(pager: oio::Pager) -> Self {
+    pub(crate) fn new(acc: FusedAccessor, pager: oio::Pager, metakey: FlagSet<Metakey>) -> Self {
         Self {
+            acc,
+            metakey,
+            pattern: None,
+            files_only: false,
             pager: Some(pager),
             buf: VecDeque::default(),
             fut: None,
+            stat_fut: None,
+            last_path: None,
+            ordered: true,
+        }
+    }
+
+    /// Record the path of returned entry for continuation token.
+    fn set_last_path(&mut self, path: &str) {
+        if let Some(last) = &self.last_path {
+            if path <= last.as_str() {
+                self.ordered = false;
+            }
         }
+        self.last_path = Some(path.to_string());
+    }
+
+    /// Only return entries that match given pattern.
+    pub(crate) fn with_pattern(mut self, pattern: Option<ListPattern>) -> Self {
+        self.pattern = pattern;
+        self
+    }
+
+    /// Only return file entries.
+    pub(crate) fn with_files_only(mut self, files_only: bool) -> Self {
+        self.files_only = files_only;
+        self
     }
 
     /// next_page can be used to fetch a new page.
@@ -65,7 +110,7 @@ impl Lister {
     /// Always using the same calling style.
     pub async fn next_page(&mut self) -> Result<Option<Vec<Entry>>> {
         debug_assert!(
-            self.fut.is_none(),
+            self.fut.is_none() && self.stat_fut.is_none(),
             "there are ongoing futures for next page"
         );
 
@@ -87,7 +132,66 @@ impl Lister {
             }
         };
 
-        Ok(Some(entries.into_iter().map(|v| v.into_entry()).collect()))
+        let mut des = Vec::with_capacity(entries.len());
+        for oe in entries {
+            if !is_matched(&oe, self.pattern.as_ref(), self.files_only) {
+                continue;
+            }
+
+            let de = oe.into_entry();
+            if is_satisfied(&de, self.metakey) {
+                des.push(de);
+            } else {
+                des.push(stat_entry(self.acc.clone(), de).await?);
+            }
+        }
+
+        for de in des.iter() {
+            self.set_last_path(de.path());
+        }
+        Ok(Some(des))
+    }
+
+    /// Get the continuation token of this lister.
+    ///
+    /// The token is the path of the last returned entry, or `None` if no
+    /// entry has been returned yet. Pass it to
+    /// [`OpList::with_start_after`] to resume listing from here later.
+    ///
+    /// # Notes
+    ///
+    /// The token is only valid if entries are returned in lexicographic
+    /// order, otherwise entries before it could be skipped while resuming.
+    /// Use [`OpList::with_sorted`] if the service doesn't return sorted
+    /// entries. `None` will be returned once an entry is returned out of
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// use opendal::ops::OpList;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut lister = op.list_with("dir/", OpList::new().with_limit(100)).await?;
+    /// let _ = lister.next_page().await?;
+    ///
+    /// if let Some(token) = lister.continuation_token() {
+    ///     let mut lister = op
+    ///         .list_with("dir/", OpList::new().with_start_after(token))
+    ///         .await?;
+    ///     let _ = lister.next_page().await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn continuation_token(&self) -> Option<&str> {
+        if !self.ordered {
+            return None;
+        }
+        self.last_path.as_deref()
     }
 }
 
@@ -95,8 +199,24 @@ impl Stream for Lister {
     type Item = Result<Entry>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(fut) = self.stat_fut.as_mut() {
+            let res = ready!(fut.poll_unpin(cx));
+            self.stat_fut = None;
+            if let Ok(de) = &res {
+                self.set_last_path(de.path());
+            }
+            return Poll::Ready(Some(res));
+        }
+
         if let Some(oe) = self.buf.pop_front() {
-            return Poll::Ready(Some(Ok(oe.into_entry())));
+            let de = oe.into_entry();
+            if is_satisfied(&de, self.metakey) {
+                self.set_last_path(de.path());
+                return Poll::Ready(Some(Ok(de)));
+            }
+
+            self.stat_fut = Some(Box::pin(stat_entry(self.acc.clone(), de)));
+            return self.poll_next(cx);
         }
 
         if let Some(fut) = self.fut.as_mut() {
@@ -106,7 +226,10 @@ impl Stream for Lister {
             return match res? {
                 Some(oes) => {
                     self.fut = None;
-                    self.buf = oes.into();
+                    self.buf = oes
+                        .into_iter()
+                        .filter(|oe| is_matched(oe, self.pattern.as_ref(), self.files_only))
+                        .collect();
                     self.poll_next(cx)
                 }
                 None => {
@@ -132,19 +255,43 @@ impl Stream for Lister {
 ///
 /// Users can construct Lister by `blocking_list` or `blocking_scan`.
 pub struct BlockingLister {
+    acc: FusedAccessor,
+    metakey: FlagSet<Metakey>,
+    pattern: Option<ListPattern>,
+    files_only: bool,
     pager: oio::BlockingPager,
     buf: VecDeque<oio::Entry>,
 }
 
 impl BlockingLister {
     /// Create a new lister.
-    pub(crate) fn new(pager: oio::BlockingPager) -> Self {
+    pub(crate) fn new(
+        acc: FusedAccessor,
+        pager: oio::BlockingPager,
+        metakey: FlagSet<Metakey>,
+    ) -> Self {
         Self {
+            acc,
+            metakey,
+            pattern: None,
+            files_only: false,
             pager,
             buf: VecDeque::default(),
         }
     }
 
+    /// Only return entries that match given pattern.
+    pub(crate) fn with_pattern(mut self, pattern: Option<ListPattern>) -> Self {
+        self.pattern = pattern;
+        self
+    }
+
+    /// Only return file entries.
+    pub(crate) fn with_files_only(mut self, files_only: bool) -> Self {
+        self.files_only = files_only;
+        self
+    }
+
     /// next_page can be used to fetch a new page.
     pub fn next_page(&mut self) -> Result<Option<Vec<Entry>>> {
         let entries = if !self.buf.is_empty() {
@@ -159,7 +306,22 @@ impl BlockingLister {
             }
         };
 
-        Ok(Some(entries.into_iter().map(|v| v.into_entry()).collect()))
+        entries
+            .into_iter()
+            .filter(|oe| is_matched(oe, self.pattern.as_ref(), self.files_only))
+            .map(|oe| self.complete(oe.into_entry()))
+            .collect::<Result<Vec<_>>>()
+            .map(Some)
+    }
+
+    /// Stat the entry if its cached metadata doesn't cover required metakey.
+    fn complete(&self, de: Entry) -> Result<Entry> {
+        if is_satisfied(&de, self.metakey) {
+            return Ok(de);
+        }
+
+        let rp = self.acc.blocking_stat(de.path(), stat_args(&de))?;
+        Ok(Entry::new_with(de.path().to_string(), rp.into_metadata()))
     }
 }
 
@@ -169,10 +331,14 @@ impl Iterator for BlockingLister {
 
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(oe) = self.buf.pop_front() {
-            return Some(Ok(oe.into_entry()));
+            return Some(self.complete(oe.into_entry()));
         }
 
         self.buf = match self.pager.next() {
+            Ok(Some(entries)) if self.pattern.is_some() || self.files_only => entries
+                .into_iter()
+                .filter(|oe| is_matched(oe, self.pattern.as_ref(), self.files_only))
+                .collect(),
             // Ideally, the convert from `Vec` to `VecDeque` will not do reallocation.
             //
             // However, this could be changed as described in [impl<T, A> From<Vec<T, A>> for VecDeque<T, A>](https://doc.rust-lang.org/std/collections/struct.VecDeque.html#impl-From%3CVec%3CT%2C%20A%3E%3E-for-VecDeque%3CT%2C%20A%3E)
@@ -184,3 +350,587 @@ impl Iterator for BlockingLister {
         self.next()
     }
 }
+
+/// Glob pattern that entries returned by lister must match.
+///
+/// Entries are matched by their path relative to `base`, with the
+/// trailing `/` of dirs removed.
+#[derive(Debug, Clone)]
+pub(crate) struct ListPattern {
+    base: String,
+    segments: Vec<String>,
+}
+
+impl ListPattern {
+    /// Create a new pattern for entries listed under `base`.
+    pub(crate) fn new(base: &str, pattern: &str) -> Self {
+        Self {
+            base: base.to_string(),
+            segments: pattern
+                .trim_start_matches('/')
+                .split('/')
+                .map(|v| v.to_string())
+                .collect(),
+        }
+    }
+
+    /// Move the leading dirs of pattern without wildcards into base so
+    /// that services only need to list the matched dir.
+    ///
+    /// The last segment will always be kept since it matches the entry
+    /// itself.
+    pub(crate) fn push_down(mut self) -> Self {
+        let n = self
+            .segments
+            .iter()
+            .take(self.segments.len() - 1)
+            .take_while(|v| !v.is_empty() && !v.contains(['*', '?']))
+            .count();
+
+        for seg in self.segments.drain(..n) {
+            if self.base == "/" {
+                self.base.clear();
+            }
+            self.base.push_str(&seg);
+            self.base.push('/');
+        }
+        self
+    }
+
+    /// The dir that entries should be listed from.
+    pub(crate) fn base(&self) -> &str {
+        &self.base
+    }
+
+    fn is_match(&self, path: &str) -> bool {
+        let rel = if self.base == "/" {
+            path
+        } else {
+            match path.strip_prefix(&self.base) {
+                Some(v) => v,
+                None => return false,
+            }
+        };
+        let rel = rel.trim_end_matches('/');
+        if rel.is_empty() {
+            return false;
+        }
+
+        let parts: Vec<&str> = rel.split('/').collect();
+        match_segments(&self.segments, &parts)
+    }
+}
+
+fn is_matched(oe: &oio::Entry, pattern: Option<&ListPattern>, files_only: bool) -> bool {
+    if files_only && oe.mode().is_dir() {
+        return false;
+    }
+    pattern.map(|v| v.is_match(oe.path())).unwrap_or(true)
+}
+
+/// Match path segments, `**` matches any number of segments.
+fn match_segments(pattern: &[String], parts: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => parts.is_empty(),
+        Some((seg, rest)) if seg == "**" => {
+            (0..=parts.len()).any(|i| match_segments(rest, &parts[i..]))
+        }
+        Some((seg, rest)) => match parts.split_first() {
+            Some((part, remain)) => match_wildcard(seg, part) && match_segments(rest, remain),
+            None => false,
+        },
+    }
+}
+
+/// Match a single segment with `?` and `*`.
+fn match_wildcard(pattern: &str, s: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let s: Vec<char> = s.chars().collect();
+
+    let (mut pi, mut si) = (0, 0);
+    // The position of last `*` in pattern and the matched position in s.
+    let mut star: Option<(usize, usize)> = None;
+    while si < s.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == s[si]) {
+            pi += 1;
+            si += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, si));
+            pi += 1;
+        } else if let Some((sp, ss)) = star {
+            pi = sp + 1;
+            si = ss + 1;
+            star = Some((sp, ss + 1));
+        } else {
+            return false;
+        }
+    }
+
+    p[pi..].iter().all(|c| *c == '*')
+}
+
+/// Check if entry's cached metadata covers the given metakey.
+fn is_satisfied(de: &Entry, metakey: FlagSet<Metakey>) -> bool {
+    match de.metadata() {
+        Some(meta) => meta.bit().contains(metakey) || meta.bit().contains(Metakey::Complete),
+        None => false,
+    }
+}
+
+/// Build the args to stat given entry.
+///
+/// Symlinks returned by lister must not be followed, otherwise the mode
+/// will be changed.
+fn stat_args(de: &Entry) -> OpStat {
+    match de.metadata() {
+        Some(meta) if meta.mode().is_symlink() => OpStat::new().with_follow_symlink(false),
+        _ => OpStat::new(),
+    }
+}
+
+async fn stat_entry(acc: FusedAccessor, de: Entry) -> Result<Entry> {
+    let rp = acc.stat(de.path(), stat_args(&de)).await?;
+    Ok(Entry::new_with(de.path().to_string(), rp.into_metadata()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::services;
+
+    #[derive(Default, Clone)]
+    struct MockBuilder {
+        stat_count: Arc<AtomicUsize>,
+    }
+
+    impl Builder for MockBuilder {
+        const SCHEME: Scheme = Scheme::Custom("mock");
+        type Accessor = MockService;
+
+        fn from_map(_: HashMap<String, String>) -> Self {
+            Self::default()
+        }
+
+        fn build(&mut self) -> Result<Self::Accessor> {
+            Ok(MockService {
+                stat_count: self.stat_count.clone(),
+            })
+        }
+    }
+
+    #[derive(Debug, Clone, Default)]
+    struct MockService {
+        stat_count: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Accessor for MockService {
+        type Reader = ();
+        type BlockingReader = ();
+        type Writer = ();
+        type BlockingWriter = ();
+        type Pager = MockPager;
+        type BlockingPager = MockPager;
+
+        fn info(&self) -> AccessorInfo {
+            let mut am = AccessorInfo::default();
+            am.set_capabilities(AccessorCapability::List | AccessorCapability::Blocking)
+                .set_list_metakeys(Metakey::ContentLength);
+            am
+        }
+
+        async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+            self.blocking_stat(path, args)
+        }
+
+        async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Pager)> {
+            self.blocking_list(path, args)
+        }
+
+        fn blocking_stat(&self, _: &str, _: OpStat) -> Result<RpStat> {
+            self.stat_count.fetch_add(1, Ordering::SeqCst);
+
+            Ok(RpStat::new(
+                Metadata::new(EntryMode::FILE)
+                    .with_content_length(13)
+                    .with_etag("\"etag\"".to_string()),
+            ))
+        }
+
+        fn blocking_list(&self, _: &str, _: OpList) -> Result<(RpList, Self::BlockingPager)> {
+            Ok((RpList::default(), MockPager { done: false }))
+        }
+    }
+
+    struct MockPager {
+        done: bool,
+    }
+
+    impl MockPager {
+        fn entries(&mut self) -> Option<Vec<oio::Entry>> {
+            if self.done {
+                return None;
+            }
+            self.done = true;
+
+            Some(vec![
+                oio::Entry::new("dir/", Metadata::new(EntryMode::DIR)),
+                oio::Entry::new("a", Metadata::new(EntryMode::FILE).with_content_length(13)),
+                oio::Entry::new("b", Metadata::new(EntryMode::FILE).with_content_length(13)),
+            ])
+        }
+    }
+
+    #[async_trait]
+    impl oio::Page for MockPager {
+        async fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+            Ok(self.entries())
+        }
+    }
+
+    impl oio::BlockingPage for MockPager {
+        fn next(&mut self) -> Result<Option<Vec<oio::Entry>>> {
+            Ok(self.entries())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_with_metakey_from_list_response() {
+        let builder = MockBuilder::default();
+        let op = Operator::new(builder.clone()).unwrap().finish();
+        assert!(op.info().list_metakeys().contains(Metakey::ContentLength));
+
+        let args = OpList::new().with_metakey(Metakey::ContentLength);
+        let entries: Vec<Entry> = op
+            .list_with("/", args)
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 3);
+
+        for de in &entries {
+            let meta = op.metadata(de, Metakey::ContentLength).await.unwrap();
+            if meta.mode().is_file() {
+                assert_eq!(meta.content_length(), 13);
+            }
+        }
+        assert_eq!(builder.stat_count.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_list_with_metakey_fallback_to_stat() {
+        let builder = MockBuilder::default();
+        let op = Operator::new(builder.clone()).unwrap().finish();
+
+        let args = OpList::new().with_metakey(Metakey::ContentLength | Metakey::Etag);
+        let mut lister = op.list_with("/", args).await.unwrap();
+        let entries = lister.next_page().await.unwrap().unwrap();
+        assert_eq!(entries.len(), 3);
+        // Only file entries need extra stat, dir entries are always complete.
+        assert_eq!(builder.stat_count.load(Ordering::SeqCst), 2);
+
+        for de in &entries {
+            let meta = op.metadata(de, Metakey::Etag).await.unwrap();
+            if meta.mode().is_file() {
+                assert_eq!(meta.etag(), Some("\"etag\""));
+            }
+        }
+        assert_eq!(builder.stat_count.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_blocking_list_with_metakey() {
+        let builder = MockBuilder::default();
+        let op = Operator::new(builder.clone()).unwrap().finish().blocking();
+
+        let args = OpList::new().with_metakey(Metakey::ContentLength);
+        let entries = op
+            .list_with("/", args)
+            .unwrap()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(builder.stat_count.load(Ordering::SeqCst), 0);
+
+        let args = OpList::new().with_metakey(Metakey::Etag);
+        let entries = op
+            .list_with("/", args)
+            .unwrap()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(builder.stat_count.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_match_wildcard() {
+        let cases = vec![
+            ("*.parquet", "a.parquet", true),
+            ("*.parquet", "a.parquet.tmp", false),
+            ("*.parquet", ".parquet", true),
+            ("data_?.csv", "data_1.csv", true),
+            ("data_?.csv", "data_10.csv", false),
+            ("*_*", "a_b_c", true),
+            ("*", "", true),
+            ("abc", "abd", false),
+        ];
+
+        for (pattern, s, expected) in cases {
+            assert_eq!(match_wildcard(pattern, s), expected, "{pattern} {s}");
+        }
+    }
+
+    #[test]
+    fn test_list_pattern() {
+        let cases = vec![
+            ("/", "*.csv", "a.csv", true),
+            ("/", "*.csv", "dir/a.csv", false),
+            ("dir/", "*.csv", "dir/a.csv", true),
+            ("dir/", "*.csv", "other/a.csv", false),
+            ("dir/", "sub", "dir/sub/", true),
+            ("/", "**/data/*.csv", "data/a.csv", true),
+            ("/", "**/data/*.csv", "x/y/data/a.csv", true),
+            ("/", "**/data/*.csv", "x/data/y/a.csv", false),
+            ("/", "**", "x/y/z", true),
+        ];
+
+        for (base, pattern, path, expected) in cases {
+            let p = ListPattern::new(base, pattern);
+            assert_eq!(p.is_match(path), expected, "{base} {pattern} {path}");
+        }
+    }
+
+    #[test]
+    fn test_list_pattern_push_down() {
+        let p = ListPattern::new("/", "logs/2023/*.csv").push_down();
+        assert_eq!(p.base(), "logs/2023/");
+        assert!(p.is_match("logs/2023/a.csv"));
+        assert!(!p.is_match("logs/2022/a.csv"));
+
+        let p = ListPattern::new("dir/", "**/data/*.csv").push_down();
+        assert_eq!(p.base(), "dir/");
+
+        let p = ListPattern::new("dir/", "a.csv").push_down();
+        assert_eq!(p.base(), "dir/");
+    }
+
+    #[tokio::test]
+    async fn test_list_with_pattern() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        for path in [
+            "dir/a.parquet",
+            "dir/b.csv",
+            "dir/c.parquet",
+            "dir/sub/d.parquet",
+        ] {
+            op.write(path, "test").await.unwrap();
+        }
+
+        let args = OpList::new().with_pattern("*.parquet");
+        let mut entries: Vec<String> = op
+            .list_with("dir/", args)
+            .await
+            .unwrap()
+            .map_ok(|de| de.path().to_string())
+            .try_collect()
+            .await
+            .unwrap();
+        entries.sort();
+        assert_eq!(entries, vec!["dir/a.parquet", "dir/c.parquet"]);
+    }
+
+    #[tokio::test]
+    async fn test_scan_with_pattern() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        for path in [
+            "data/a.csv",
+            "data/b.json",
+            "x/data/c.csv",
+            "x/y/data/d.csv",
+            "x/data/z/e.csv",
+            "logs/2023/f.csv",
+        ] {
+            op.write(path, "test").await.unwrap();
+        }
+
+        let args = OpScan::new().with_pattern("**/data/*.csv");
+        let mut entries: Vec<String> = op
+            .scan_with("/", args)
+            .await
+            .unwrap()
+            .map_ok(|de| de.path().to_string())
+            .try_collect()
+            .await
+            .unwrap();
+        entries.sort();
+        assert_eq!(
+            entries,
+            vec!["data/a.csv", "x/data/c.csv", "x/y/data/d.csv"]
+        );
+
+        let args = OpScan::new().with_pattern("logs/2023/*.csv");
+        let entries: Vec<Entry> = op
+            .scan_with("/", args)
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path(), "logs/2023/f.csv");
+
+        // Pushed down dir doesn't exist.
+        let args = OpScan::new().with_pattern("not_exist/*.csv");
+        let entries: Vec<Entry> = op
+            .blocking()
+            .scan_with("/", args)
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert!(entries.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_scan_files_only() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        for path in ["x/", "x/y", "x/x/", "x/x/y", "x/x/x/", "x/x/x/y.csv", "z"] {
+            if path.ends_with('/') {
+                op.create_dir(path).await.unwrap();
+            } else {
+                op.write(path, "test").await.unwrap();
+            }
+        }
+
+        let mut entries: Vec<String> = op
+            .scan_with("x/", OpScan::new().with_files_only(true))
+            .await
+            .unwrap()
+            .map_ok(|de| de.path().to_string())
+            .try_collect()
+            .await
+            .unwrap();
+        entries.sort();
+        assert_eq!(entries, vec!["x/x/x/y.csv", "x/x/y", "x/y"]);
+
+        // Dirs are returned by default.
+        let entries: Vec<Entry> = op.scan("x/").await.unwrap().try_collect().await.unwrap();
+        assert!(entries.iter().any(|de| de.path() == "x/x/"));
+
+        // Compose with pattern.
+        let args = OpScan::new().with_files_only(true).with_pattern("**/*.csv");
+        let entries: Vec<Entry> = op
+            .blocking()
+            .scan_with("x/", args)
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path(), "x/x/x/y.csv");
+
+        let args = OpScan::new().with_files_only(true);
+        let entries: Vec<Entry> = op
+            .blocking()
+            .scan_with("x/", args)
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(entries.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_list_with_start_after() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        assert!(op.info().can_list_with_start_after());
+        for path in ["dir/a", "dir/b", "dir/c", "dir/sub/d"] {
+            op.write(path, "test").await.unwrap();
+        }
+
+        let args = OpList::new().with_start_after("dir/b");
+        let mut entries: Vec<String> = op
+            .list_with("dir/", args)
+            .await
+            .unwrap()
+            .map_ok(|de| de.path().to_string())
+            .try_collect()
+            .await
+            .unwrap();
+        entries.sort();
+        assert_eq!(entries, vec!["dir/c", "dir/sub/"]);
+
+        let args = OpList::new().with_start_after("dir/sub/");
+        let entries: Vec<Entry> = op
+            .list_with("dir/", args)
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert!(entries.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_list_resume_from_continuation_token() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        for path in ["dir/a", "dir/b", "dir/c"] {
+            op.write(path, "test").await.unwrap();
+        }
+
+        let mut lister = op.list("dir/").await.unwrap();
+        assert_eq!(lister.continuation_token(), None);
+        let first = lister.try_next().await.unwrap().expect("must have entry");
+        assert_eq!(lister.continuation_token(), Some(first.path()));
+
+        let args = OpList::new().with_start_after(first.path());
+        let mut entries: Vec<String> = op
+            .list_with("dir/", args)
+            .await
+            .unwrap()
+            .map_ok(|de| de.path().to_string())
+            .try_collect()
+            .await
+            .unwrap();
+        entries.push(first.path().to_string());
+        entries.sort();
+        assert_eq!(entries, vec!["dir/a", "dir/b", "dir/c"]);
+    }
+
+    #[tokio::test]
+    async fn test_continuation_token_out_of_order() {
+        let op = Operator::new(MockBuilder::default()).unwrap().finish();
+
+        // Mock pager returns `dir/` before `a`.
+        let mut lister = op.list("/").await.unwrap();
+        assert_eq!(lister.try_next().await.unwrap().unwrap().path(), "dir/");
+        assert_eq!(lister.continuation_token(), Some("dir/"));
+        assert_eq!(lister.try_next().await.unwrap().unwrap().path(), "a");
+        assert_eq!(lister.continuation_token(), None);
+
+        let mut lister = op
+            .list_with("/", OpList::new().with_sorted(true))
+            .await
+            .unwrap();
+        let entries = lister.next_page().await.unwrap().unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(lister.continuation_token(), Some("dir/"));
+    }
+
+    #[tokio::test]
+    async fn test_list_with_start_after_unsupported() {
+        let op = Operator::new(MockBuilder::default()).unwrap().finish();
+        assert!(!op.info().can_list_with_start_after());
+
+        let args = OpList::new().with_start_after("a");
+        let res = op.list_with("/", args).await;
+        assert_eq!(res.err().map(|e| e.kind()), Some(ErrorKind::Unsupported));
+    }
+}
@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
+
 use flagset::flags;
 use flagset::FlagSet;
 use time::OffsetDateTime;
@@ -36,13 +38,22 @@ pub struct Metadata {
 
     mode: EntryMode,
 
+    cache_control: Option<String>,
     content_disposition: Option<String>,
+    content_encoding: Option<String>,
     content_length: Option<u64>,
     content_md5: Option<String>,
+    content_crc32c: Option<String>,
     content_range: Option<BytesContentRange>,
     content_type: Option<String>,
     etag: Option<String>,
+    expires: Option<OffsetDateTime>,
     last_modified: Option<OffsetDateTime>,
+    version: Option<String>,
+    user_metadata: Option<HashMap<String, String>>,
+    storage_class: Option<StorageClass>,
+    is_deleted: bool,
+    object_count: Option<u64>,
 }
 
 impl Metadata {
@@ -59,13 +70,22 @@ impl Metadata {
             bit,
             mode,
 
+            cache_control: None,
             content_length: None,
             content_md5: None,
+            content_crc32c: None,
             content_type: None,
             content_range: None,
             last_modified: None,
+            expires: None,
             etag: None,
             content_disposition: None,
+            content_encoding: None,
+            version: None,
+            user_metadata: None,
+            storage_class: None,
+            is_deleted: false,
+            object_count: None,
         }
     }
 
@@ -114,6 +134,44 @@ impl Metadata {
         self
     }
 
+    /// Cache control of this entry.
+    ///
+    /// Cache-Control is defined by [RFC 7234](https://httpwg.org/specs/rfc7234.html#header.cache-control)
+    /// Refer to [MDN Cache-Control](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Cache-Control) for more information.
+    ///
+    /// OpenDAL will return this value AS-IS like the following:
+    ///
+    /// - "no-cache"
+    /// - "max-age=3600, public"
+    pub fn cache_control(&self) -> Option<&str> {
+        debug_assert!(
+            self.bit.contains(Metakey::CacheControl) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: cache_control, maybe a bug"
+        );
+
+        self.cache_control.as_deref()
+    }
+
+    /// Set cache control of this entry.
+    ///
+    /// Cache-Control is defined by [RFC 7234](https://httpwg.org/specs/rfc7234.html#header.cache-control)
+    /// Refer to [MDN Cache-Control](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Cache-Control) for more information.
+    pub fn set_cache_control(&mut self, cache_control: &str) -> &mut Self {
+        self.cache_control = Some(cache_control.to_string());
+        self.bit |= Metakey::CacheControl;
+        self
+    }
+
+    /// Set cache control of this entry.
+    ///
+    /// Cache-Control is defined by [RFC 7234](https://httpwg.org/specs/rfc7234.html#header.cache-control)
+    /// Refer to [MDN Cache-Control](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Cache-Control) for more information.
+    pub fn with_cache_control(mut self, cache_control: String) -> Self {
+        self.cache_control = Some(cache_control);
+        self.bit |= Metakey::CacheControl;
+        self
+    }
+
     /// Content length of this entry.
     ///
     /// `Content-Length` is defined by [RFC 7230](https://httpwg.org/specs/rfc7230.html#header.content-length)
@@ -181,6 +239,34 @@ impl Metadata {
         self
     }
 
+    /// Content CRC32C of this entry.
+    ///
+    /// The value is the base64 encoded big-endian CRC32C checksum of the
+    /// whole content like `n03x6A==`, which is returned by services like
+    /// GCS.
+    pub fn content_crc32c(&self) -> Option<&str> {
+        debug_assert!(
+            self.bit.contains(Metakey::ContentCrc32c) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: content_crc32c, maybe a bug"
+        );
+
+        self.content_crc32c.as_deref()
+    }
+
+    /// Set content CRC32C of this entry.
+    pub fn set_content_crc32c(&mut self, content_crc32c: &str) -> &mut Self {
+        self.content_crc32c = Some(content_crc32c.to_string());
+        self.bit |= Metakey::ContentCrc32c;
+        self
+    }
+
+    /// Set content CRC32C of this entry.
+    pub fn with_content_crc32c(mut self, content_crc32c: String) -> Self {
+        self.content_crc32c = Some(content_crc32c);
+        self.bit |= Metakey::ContentCrc32c;
+        self
+    }
+
     /// Content Type of this entry.
     ///
     /// Content Type is defined by [RFC 9110](https://httpwg.org/specs/rfc9110.html#field.content-type).
@@ -193,6 +279,35 @@ impl Metadata {
         self.content_type.as_deref()
     }
 
+    /// Media type of this entry without parameters.
+    ///
+    /// Media types are case-insensitive, so the returned value will be in
+    /// lowercase. For example, `application/json` will be returned for
+    /// `Application/JSON; charset=utf-8`.
+    pub fn content_type_essence(&self) -> Option<String> {
+        let essence = self.content_type()?.split(';').next()?.trim();
+        if essence.is_empty() {
+            return None;
+        }
+
+        Some(essence.to_ascii_lowercase())
+    }
+
+    /// Charset parameter of content type of this entry.
+    ///
+    /// The charset will be in lowercase, and quotes around it will be
+    /// removed. For example, `utf-8` will be returned for
+    /// `text/plain; charset="UTF-8"`.
+    pub fn content_type_charset(&self) -> Option<String> {
+        self.content_type()?
+            .split(';')
+            .skip(1)
+            .filter_map(|param| param.split_once('='))
+            .find(|(k, _)| k.trim().eq_ignore_ascii_case("charset"))
+            .map(|(_, v)| v.trim().trim_matches('"').to_ascii_lowercase())
+            .filter(|v| !v.is_empty())
+    }
+
     /// Set Content Type of this entry.
     ///
     /// Content Type is defined by [RFC 9110](https://httpwg.org/specs/rfc9110.html#field.content-type).
@@ -276,6 +391,35 @@ impl Metadata {
         self
     }
 
+    /// Expires of this entry.
+    ///
+    /// `Expires` is defined by [RFC 7234](https://httpwg.org/specs/rfc7234.html#header.expires)
+    /// Refer to [MDN Expires](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Expires) for more information.
+    ///
+    /// The content is considered stale after this time.
+    pub fn expires(&self) -> Option<OffsetDateTime> {
+        debug_assert!(
+            self.bit.contains(Metakey::Expires) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: expires, maybe a bug"
+        );
+
+        self.expires
+    }
+
+    /// Set Expires of this entry.
+    pub fn set_expires(&mut self, expires: OffsetDateTime) -> &mut Self {
+        self.expires = Some(expires);
+        self.bit |= Metakey::Expires;
+        self
+    }
+
+    /// Set Expires of this entry.
+    pub fn with_expires(mut self, expires: OffsetDateTime) -> Self {
+        self.expires = Some(expires);
+        self.bit |= Metakey::Expires;
+        self
+    }
+
     /// ETag of this entry.
     ///
     /// `ETag` is defined by [RFC 7232](https://httpwg.org/specs/rfc7232.html#header.etag)
@@ -350,6 +494,18 @@ impl Metadata {
         self.content_disposition.as_deref()
     }
 
+    /// Filename carried by Content-Disposition of this entry.
+    ///
+    /// Both `filename="x"` and RFC 5987 encoded `filename*=UTF-8''x` are
+    /// supported, and `filename*` will be preferred if both exist.
+    ///
+    /// Returns `None` if Content-Disposition is not set or doesn't carry
+    /// a filename, like `inline`.
+    pub fn content_disposition_filename(&self) -> Option<String> {
+        self.content_disposition()
+            .and_then(parse_content_disposition_filename)
+    }
+
     /// Set Content-Disposition of this entry
     ///
     /// `Content-Disposition` is defined by [RFC 2616](https://www.rfc-editor/rfcs/2616) and
@@ -383,6 +539,164 @@ impl Metadata {
         self.bit |= Metakey::ContentDisposition;
         self
     }
+
+    /// Content-Encoding of this entry.
+    ///
+    /// `Content-Encoding` is defined by [RFC 7231](https://httpwg.org/specs/rfc7231.html#header.content-encoding),
+    /// like `gzip`. Use [`OpRead::with_decompress`][crate::ops::OpRead::with_decompress]
+    /// to decompress the content while reading.
+    pub fn content_encoding(&self) -> Option<&str> {
+        debug_assert!(
+            self.bit.contains(Metakey::ContentEncoding) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: content_encoding, maybe a bug"
+        );
+
+        self.content_encoding.as_deref()
+    }
+
+    /// Set Content-Encoding of this entry.
+    pub fn with_content_encoding(mut self, content_encoding: String) -> Self {
+        self.content_encoding = Some(content_encoding);
+        self.bit |= Metakey::ContentEncoding;
+        self
+    }
+
+    /// Set Content-Encoding of this entry.
+    pub fn set_content_encoding(&mut self, content_encoding: &str) -> &mut Self {
+        self.content_encoding = Some(content_encoding.to_string());
+        self.bit |= Metakey::ContentEncoding;
+        self
+    }
+
+    /// Version of this entry.
+    ///
+    /// Version is a service-specific string that identifies a specific
+    /// version of an object, for example:
+    ///
+    /// - `x-amz-version-id` of s3: `3/L4kqtJlcpXroDTDmJ+rmSpXd3dIbrHY`
+    /// - `x-goog-generation` of gcs: `1660563214863653`
+    ///
+    /// This value will be `None` if service doesn't support versioning.
+    pub fn version(&self) -> Option<&str> {
+        debug_assert!(
+            self.bit.contains(Metakey::Version) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: version, maybe a bug"
+        );
+
+        self.version.as_deref()
+    }
+
+    /// Set version of this entry.
+    pub fn set_version(&mut self, version: &str) -> &mut Self {
+        self.version = Some(version.to_string());
+        self.bit |= Metakey::Version;
+        self
+    }
+
+    /// Set version of this entry.
+    pub fn with_version(mut self, version: String) -> Self {
+        self.version = Some(version);
+        self.bit |= Metakey::Version;
+        self
+    }
+
+    /// User defined metadata of this entry.
+    ///
+    /// User metadata is returned by services via headers with a
+    /// service-specific prefix like `x-amz-meta-` or `x-goog-meta-`. The
+    /// keys are lowercased with the prefix stripped.
+    pub fn user_metadata(&self) -> Option<&HashMap<String, String>> {
+        debug_assert!(
+            self.bit.contains(Metakey::UserMetadata) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: user_metadata, maybe a bug"
+        );
+
+        self.user_metadata.as_ref()
+    }
+
+    /// Set user defined metadata of this entry.
+    pub fn set_user_metadata(&mut self, user_metadata: HashMap<String, String>) -> &mut Self {
+        self.user_metadata = Some(user_metadata);
+        self.bit |= Metakey::UserMetadata;
+        self
+    }
+
+    /// Set user defined metadata of this entry.
+    pub fn with_user_metadata(mut self, user_metadata: HashMap<String, String>) -> Self {
+        self.user_metadata = Some(user_metadata);
+        self.bit |= Metakey::UserMetadata;
+        self
+    }
+
+    /// Storage class of this entry.
+    ///
+    /// This value will be `None` if service doesn't return storage class.
+    pub fn storage_class(&self) -> Option<&StorageClass> {
+        debug_assert!(
+            self.bit.contains(Metakey::StorageClass) || self.bit.contains(Metakey::Complete),
+            "visiting not set metadata: storage_class, maybe a bug"
+        );
+
+        self.storage_class.as_ref()
+    }
+
+    /// Set storage class of this entry.
+    pub fn set_storage_class(&mut self, storage_class: StorageClass) -> &mut Self {
+        self.storage_class = Some(storage_class);
+        self.bit |= Metakey::StorageClass;
+        self
+    }
+
+    /// Set storage class of this entry.
+    pub fn with_storage_class(mut self, storage_class: StorageClass) -> Self {
+        self.storage_class = Some(storage_class);
+        self.bit |= Metakey::StorageClass;
+        self
+    }
+
+    /// Check if this entry is a tombstone of a deleted version.
+    ///
+    /// On versioned buckets, services could return a delete marker like
+    /// `x-amz-delete-marker: true` of s3 to indicate that this version has
+    /// been deleted.
+    ///
+    /// This value will be `false` if service doesn't support versioning.
+    pub fn is_deleted(&self) -> bool {
+        self.is_deleted
+    }
+
+    /// Set the deleted marker of this entry.
+    pub fn set_is_deleted(&mut self, is_deleted: bool) -> &mut Self {
+        self.is_deleted = is_deleted;
+        self
+    }
+
+    /// Set the deleted marker of this entry.
+    pub fn with_is_deleted(mut self, is_deleted: bool) -> Self {
+        self.is_deleted = is_deleted;
+        self
+    }
+
+    /// Count of files under this dir.
+    ///
+    /// Only available while stat dir with
+    /// [`OpStat::with_recursive_size`][crate::ops::OpStat::with_recursive_size],
+    /// `None` will be returned otherwise.
+    pub fn object_count(&self) -> Option<u64> {
+        self.object_count
+    }
+
+    /// Set count of files under this dir.
+    pub fn set_object_count(&mut self, count: u64) -> &mut Self {
+        self.object_count = Some(count);
+        self
+    }
+
+    /// Set count of files under this dir.
+    pub fn with_object_count(mut self, count: u64) -> Self {
+        self.object_count = Some(count);
+        self
+    }
 }
 
 flags! {
@@ -406,19 +720,89 @@ flags! {
 
         /// Key for mode.
         Mode,
+        /// Key for cache control.
+        CacheControl,
         /// Key for content disposition.
         ContentDisposition,
+        /// Key for content encoding.
+        ContentEncoding,
         /// Key for content length.
         ContentLength,
         /// Key for content md5.
         ContentMd5,
+        /// Key for content crc32c.
+        ContentCrc32c,
         /// Key for content range.
         ContentRange,
         /// Key for content type.
         ContentType,
         /// Key for etag.
         Etag,
+        /// Key for expires.
+        Expires,
         /// Key for last last modified.
         LastModified,
+        /// Key for version.
+        Version,
+        /// Key for user metadata.
+        UserMetadata,
+        /// Key for storage class.
+        StorageClass,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_metadata(content_type: &str) -> Metadata {
+        Metadata::new(EntryMode::FILE).with_content_type(content_type.to_string())
+    }
+
+    #[test]
+    fn test_content_type_essence_and_charset() {
+        let cases = vec![
+            (
+                "bare type",
+                "application/json",
+                Some("application/json"),
+                None,
+            ),
+            (
+                "type with charset",
+                "text/plain; charset=utf-8",
+                Some("text/plain"),
+                Some("utf-8"),
+            ),
+            (
+                "mixed case with whitespace",
+                " Text/HTML ;CharSet = \"UTF-8\" ",
+                Some("text/html"),
+                Some("utf-8"),
+            ),
+            (
+                "multiple parameters",
+                "multipart/form-data; boundary=something; charset=ISO-8859-1",
+                Some("multipart/form-data"),
+                Some("iso-8859-1"),
+            ),
+            (
+                "parameters without charset",
+                "multipart/form-data; boundary=something",
+                Some("multipart/form-data"),
+                None,
+            ),
+            ("empty", "", None, None),
+        ];
+
+        for (name, input, essence, charset) in cases {
+            let meta = new_metadata(input);
+            assert_eq!(meta.content_type_essence().as_deref(), essence, "{name}");
+            assert_eq!(meta.content_type_charset().as_deref(), charset, "{name}");
+        }
+
+        let meta = Metadata::new(EntryMode::FILE).with_bit(Metakey::Complete);
+        assert_eq!(meta.content_type_essence(), None);
+        assert_eq!(meta.content_type_charset(), None);
     }
 }
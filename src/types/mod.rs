@@ -25,16 +25,27 @@ mod metadata;
 pub use metadata::Metadata;
 pub use metadata::Metakey;
 
+mod storage_class;
+pub use storage_class::StorageClass;
+
 mod reader;
 pub use reader::BlockingReader;
+pub use reader::ChunkedStream;
+pub use reader::Lines;
 pub use reader::Reader;
 
 mod writer;
 pub use writer::BlockingWriter;
 pub use writer::Writer;
+pub use writer::WriterSink;
+
+mod multipart;
+pub use multipart::MultipartPart;
+pub use multipart::MultipartState;
 
 mod list;
 pub use list::BlockingLister;
+pub(crate) use list::ListPattern;
 pub use list::Lister;
 
 mod operator;
@@ -54,4 +65,9 @@ pub use error::Result;
 mod scheme;
 pub use scheme::Scheme;
 
+mod capability;
+pub use capability::Capability;
+
+pub use crate::raw::BytesRange;
+
 pub mod ops;
@@ -25,6 +25,8 @@ pub enum EntryMode {
     FILE,
     /// DIR means the path can be listed.
     DIR,
+    /// SYMLINK means the path is a symbolic link that has not been followed.
+    SYMLINK,
     /// Unknown means we don't know what we can do on this path.
     Unknown,
 }
@@ -38,6 +40,10 @@ impl EntryMode {
     pub fn is_dir(self) -> bool {
         self == EntryMode::DIR
     }
+    /// Check if this mode is SYMLINK.
+    pub fn is_symlink(self) -> bool {
+        self == EntryMode::SYMLINK
+    }
 }
 
 impl Default for EntryMode {
@@ -51,6 +57,7 @@ impl Display for EntryMode {
         match self {
             EntryMode::FILE => write!(f, "file"),
             EntryMode::DIR => write!(f, "dir"),
+            EntryMode::SYMLINK => write!(f, "symlink"),
             EntryMode::Unknown => write!(f, "unknown"),
         }
     }
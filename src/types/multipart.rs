@@ -0,0 +1,143 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// MultipartState is the persisted state of an in-progress multipart
+/// upload.
+///
+/// It can be fetched by [`Writer::state`][crate::Writer::state] and
+/// serialized into any format that serde supports. Passing it to
+/// [`OpWrite::with_resume`][crate::ops::OpWrite::with_resume] will continue
+/// the interrupted upload instead of starting from zero.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use opendal::Result;
+/// # use opendal::Operator;
+/// use opendal::ops::OpWrite;
+///
+/// # #[tokio::main]
+/// # async fn test(op: Operator) -> Result<()> {
+/// let mut w = op.writer("path/to/file").await?;
+/// w.append(vec![0; 8 * 1024 * 1024]).await?;
+/// let state = w.state()?;
+///
+/// // Continue the upload after the program restarted.
+/// let mut w = op
+///     .writer_with("path/to/file", OpWrite::new().with_resume(state.clone()))
+///     .await?;
+/// // Skip the bytes that have been uploaded.
+/// let offset = state.uploaded_size() as usize;
+/// # let content = vec![0; 16 * 1024 * 1024];
+/// w.append(content[offset..].to_vec()).await?;
+/// w.close().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MultipartState {
+    upload_id: String,
+    parts: Vec<MultipartPart>,
+}
+
+impl MultipartState {
+    /// Create a new state for given upload id without any parts.
+    pub fn new(upload_id: &str) -> Self {
+        Self {
+            upload_id: upload_id.to_string(),
+            parts: vec![],
+        }
+    }
+
+    /// Service-specific id of this upload.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+
+    /// Parts that have been uploaded, ordered by part number.
+    pub fn parts(&self) -> &[MultipartPart] {
+        &self.parts
+    }
+
+    /// Push a newly uploaded part into state.
+    pub fn push_part(&mut self, part: MultipartPart) -> &mut Self {
+        self.parts.push(part);
+        self
+    }
+
+    /// Total bytes that have been uploaded.
+    ///
+    /// Users should continue appending from this offset after resumed.
+    pub fn uploaded_size(&self) -> u64 {
+        self.parts.iter().map(|v| v.size()).sum()
+    }
+}
+
+/// MultipartPart is an uploaded part of [`MultipartState`].
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MultipartPart {
+    part_number: usize,
+    etag: String,
+    size: u64,
+}
+
+impl MultipartPart {
+    /// Create a new part.
+    pub fn new(part_number: usize, etag: &str, size: u64) -> Self {
+        Self {
+            part_number,
+            etag: etag.to_string(),
+            size,
+        }
+    }
+
+    /// Number of this part, starts from 1.
+    pub fn part_number(&self) -> usize {
+        self.part_number
+    }
+
+    /// Etag of this part returned by service.
+    pub fn etag(&self) -> &str {
+        &self.etag
+    }
+
+    /// Size of this part.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multipart_state_serde() {
+        let mut state = MultipartState::new("upload_id");
+        state
+            .push_part(MultipartPart::new(1, "\"etag-1\"", 5))
+            .push_part(MultipartPart::new(2, "\"etag-2\"", 3));
+        assert_eq!(state.uploaded_size(), 8);
+
+        let s = serde_json::to_string(&state).expect("must serialize");
+        let de: MultipartState = serde_json::from_str(&s).expect("must deserialize");
+        assert_eq!(de, state);
+    }
+}
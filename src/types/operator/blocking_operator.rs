@@ -242,18 +242,24 @@ impl BlockingOperator {
 
     /// Check if this path exists or not.
     ///
+    /// # Notes
+    ///
+    /// This function is built on `stat` and never fetches the content.
+    /// `NotFound` will be returned as `Ok(false)`, while all other errors
+    /// like `PermissionDenied` will be returned as is.
+    ///
     /// # Example
     ///
     /// ```no_run
     /// use anyhow::Result;
     /// use opendal::BlockingOperator;
     /// fn test(op: BlockingOperator) -> Result<()> {
-    ///     let _ = op.is_exist("test")?;
+    ///     let _ = op.exists("test")?;
     ///
     ///     Ok(())
     /// }
     /// ```
-    pub fn is_exist(&self, path: &str) -> Result<bool> {
+    pub fn exists(&self, path: &str) -> Result<bool> {
         let r = self.stat(path);
         match r {
             Ok(_) => Ok(true),
@@ -264,6 +270,25 @@ impl BlockingOperator {
         }
     }
 
+    /// Check if this path exists or not.
+    ///
+    /// This is an alias of [`BlockingOperator::exists`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use anyhow::Result;
+    /// use opendal::BlockingOperator;
+    /// fn test(op: BlockingOperator) -> Result<()> {
+    ///     let _ = op.is_exist("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn is_exist(&self, path: &str) -> Result<bool> {
+        self.exists(path)
+    }
+
     /// Create a dir at given path.
     ///
     /// # Notes
@@ -276,6 +301,10 @@ impl BlockingOperator {
     ///
     /// - Create on existing dir will succeed.
     /// - Create dir is always recursive, works like `mkdir -p`
+    /// - On file systems, a real dir will be created. On object storage
+    ///   services, a zero-byte marker object will be written instead.
+    ///
+    /// Returns `Unsupported` if the service can't create dirs.
     ///
     /// # Examples
     ///
@@ -300,6 +329,16 @@ impl BlockingOperator {
             );
         }
 
+        if !self.info().can_create_dir() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "create_dir is not supported by this service",
+            )
+            .with_operation("BlockingOperator::create_dir")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
         self.inner()
             .blocking_create(&path, OpCreate::new(EntryMode::DIR))?;
 
@@ -415,7 +454,7 @@ impl BlockingOperator {
             );
         }
 
-        let op = OpRead::new().with_range(range.into());
+        let op = OpRead::new().with_range(range);
 
         BlockingReader::create(self.inner().clone(), &path, op)
     }
@@ -477,6 +516,20 @@ impl BlockingOperator {
             );
         }
 
+        let info = self.info();
+        if (args.if_match().is_some() && !info.can_write_with_if_match())
+            || (args.if_none_match().is_some() && !info.can_write_with_if_none_match())
+            || (args.if_unmodified_since().is_some() && !info.can_write_with_if_unmodified_since())
+        {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "conditional write is not supported by this service",
+            )
+            .with_operation("BlockingOperator::write_with")
+            .with_context("service", info.scheme().into_static())
+            .with_context("path", &path));
+        }
+
         let (_, mut w) = self.inner().blocking_write(&path, args)?;
         w.write(bs.into())?;
         w.close()?;
@@ -523,6 +576,81 @@ impl BlockingOperator {
         BlockingWriter::create(self.inner().clone(), &path, op)
     }
 
+    /// Copy a file from `from` to `to`.
+    ///
+    /// # Notes
+    ///
+    /// - `from` and `to` must be file paths.
+    /// - `to` will be overwritten if it already exists.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::BlockingOperator;
+    /// # fn test(op: BlockingOperator) -> Result<()> {
+    /// op.copy("path/to/file", "path/to/file2")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn copy(&self, from: &str, to: &str) -> Result<()> {
+        let from = normalize_path(from);
+        let to = normalize_path(to);
+
+        for path in [&from, &to] {
+            if !validate_path(path, EntryMode::FILE) {
+                return Err(
+                    Error::new(ErrorKind::IsADirectory, "copy path is a directory")
+                        .with_operation("BlockingOperator::copy")
+                        .with_context("service", self.info().scheme().into_static())
+                        .with_context("path", path),
+                );
+            }
+        }
+
+        let _ = self.inner().blocking_copy(&from, &to, OpCopy::new())?;
+
+        Ok(())
+    }
+
+    /// Rename a file from `from` to `to`.
+    ///
+    /// # Notes
+    ///
+    /// - `from` and `to` must be file paths.
+    /// - `to` will be overwritten if it already exists.
+    /// - Services that don't support rename natively will return `Unsupported`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::BlockingOperator;
+    /// # fn test(op: BlockingOperator) -> Result<()> {
+    /// op.rename("path/to/file", "path/to/file2")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rename(&self, from: &str, to: &str) -> Result<()> {
+        let from = normalize_path(from);
+        let to = normalize_path(to);
+
+        for path in [&from, &to] {
+            if !validate_path(path, EntryMode::FILE) {
+                return Err(
+                    Error::new(ErrorKind::IsADirectory, "rename path is a directory")
+                        .with_operation("BlockingOperator::rename")
+                        .with_context("service", self.info().scheme().into_static())
+                        .with_context("path", path),
+                );
+            }
+        }
+
+        let _ = self.inner().blocking_rename(&from, &to, OpRename::new())?;
+
+        Ok(())
+    }
+
     /// Delete given path.
     ///
     /// # Notes
@@ -541,11 +669,69 @@ impl BlockingOperator {
     /// # }
     /// ```
     pub fn delete(&self, path: &str) -> Result<()> {
+        self.delete_with(path, OpDelete::new())
+    }
+
+    /// Delete given path with extra options.
+    ///
+    /// # Notes
+    ///
+    /// - Delete not existing path won't return errors, `NotFound` returned
+    ///   by services will be treated as success.
+    /// - With [`OpDelete::with_strict`], `NotFound` will be returned if the
+    ///   path doesn't exist. Services that can't tell it from the delete
+    ///   response will `stat` the path first, see
+    ///   [`OperatorInfo::can_delete_with_strict`].
+    /// - [`OpDelete::with_if_unmodified_since`] requires the service to
+    ///   support it, an `Unsupported` error will be returned otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::BlockingOperator;
+    /// use opendal::ops::OpDelete;
+    ///
+    /// # fn test(op: BlockingOperator) -> Result<()> {
+    /// op.delete_with("path/to/file", OpDelete::new().with_strict(true))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn delete_with(&self, path: &str, args: OpDelete) -> Result<()> {
         let path = normalize_path(path);
 
-        let _ = self.inner().blocking_delete(&path, OpDelete::new())?;
+        if args.if_unmodified_since().is_some()
+            && !self.info().can_delete_with_if_unmodified_since()
+        {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "conditional delete is not supported by this service",
+            )
+            .with_operation("BlockingOperator::delete_with")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
 
-        Ok(())
+        // Most services won't tell us whether the path existed while
+        // deleting, so we need to check it first. This check is racy and
+        // costs an extra request, so services that can tell it from the
+        // delete response will handle strict mode by themselves.
+        //
+        // Only `NotFound` is returned here, other errors like denied stat
+        // should not block the delete.
+        if args.strict() && !self.info().can_delete_with_strict() {
+            if let Err(err) = self.inner().blocking_stat(&path, OpStat::new()) {
+                if err.kind() == ErrorKind::NotFound {
+                    return Err(err);
+                }
+            }
+        }
+
+        match self.inner().blocking_delete(&path, args.clone()) {
+            Ok(_) => Ok(()),
+            Err(err) if err.kind() == ErrorKind::NotFound && !args.strict() => Ok(()),
+            Err(err) => Err(err),
+        }
     }
 
     /// List current dir path.
@@ -575,13 +761,38 @@ impl BlockingOperator {
     ///         EntryMode::DIR => {
     ///             println!("Handling dir like start a new list via de.path()")
     ///         }
-    ///         EntryMode::Unknown => continue,
+    ///         EntryMode::SYMLINK | EntryMode::Unknown => continue,
     ///     }
     /// }
     /// # Ok(())
     /// # }
     /// ```
     pub fn list(&self, path: &str) -> Result<BlockingLister> {
+        self.list_with(path, OpList::new())
+    }
+
+    /// List current dir path with extra options.
+    ///
+    /// See [`Operator::list_with`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::Result;
+    /// # use opendal::BlockingOperator;
+    /// use opendal::ops::OpList;
+    /// use opendal::Metakey;
+    /// # fn test(op: BlockingOperator) -> Result<()> {
+    /// let ds = op.list_with("path/to/dir/", OpList::new().with_metakey(Metakey::ContentLength))?;
+    /// for de in ds {
+    ///     let de = de?;
+    ///     let meta = op.metadata(&de, Metakey::ContentLength)?;
+    ///     println!("{} has {} bytes", de.path(), meta.content_length());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_with(&self, path: &str, args: OpList) -> Result<BlockingLister> {
         let path = normalize_path(path);
 
         if !validate_path(&path, EntryMode::DIR) {
@@ -594,8 +805,29 @@ impl BlockingOperator {
             .with_context("path", &path));
         }
 
-        let (_, pager) = self.inner().blocking_list(&path, OpList::new())?;
-        Ok(BlockingLister::new(pager))
+        if args.start_after().is_some() && !self.info().can_list_with_start_after() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "list with start after is not supported by this service",
+            )
+            .with_operation("BlockingOperator::list")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
+        let metakey = args.metakey();
+        let pattern = args.pattern().map(|v| ListPattern::new(&path, v));
+        let sorted = args.sorted();
+        let (_, mut pager) = self.inner().blocking_list(&path, args)?;
+        if sorted {
+            let ordered_pages = self
+                .inner()
+                .info()
+                .hints()
+                .contains(AccessorHint::ListOrderedPages);
+            pager = Box::new(oio::to_sorted_pager(pager).with_ordered_pages(ordered_pages));
+        }
+        Ok(BlockingLister::new(self.inner().clone(), pager, metakey).with_pattern(pattern))
     }
 
     /// List dir in flat way.
@@ -625,13 +857,38 @@ impl BlockingOperator {
     ///         EntryMode::DIR => {
     ///             println!("Handling dir like start a new list via meta.path()")
     ///         }
-    ///         EntryMode::Unknown => continue,
+    ///         EntryMode::SYMLINK | EntryMode::Unknown => continue,
     ///     }
     /// }
     /// # Ok(())
     /// # }
     /// ```
     pub fn scan(&self, path: &str) -> Result<BlockingLister> {
+        self.scan_with(path, OpScan::new())
+    }
+
+    /// List dir in flat way with extra options.
+    ///
+    /// See [`Operator::list_with`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::Result;
+    /// # use opendal::BlockingOperator;
+    /// use opendal::ops::OpScan;
+    /// use opendal::Metakey;
+    /// # fn test(op: BlockingOperator) -> Result<()> {
+    /// let ds = op.scan_with("path/to/dir/", OpScan::new().with_metakey(Metakey::ContentLength))?;
+    /// for de in ds {
+    ///     let de = de?;
+    ///     let meta = op.metadata(&de, Metakey::ContentLength)?;
+    ///     println!("{} has {} bytes", de.path(), meta.content_length());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn scan_with(&self, path: &str, args: OpScan) -> Result<BlockingLister> {
         let path = normalize_path(path);
 
         if !validate_path(&path, EntryMode::DIR) {
@@ -644,7 +901,23 @@ impl BlockingOperator {
             .with_context("path", path));
         }
 
-        let (_, pager) = self.inner().blocking_scan(&path, OpScan::new())?;
-        Ok(BlockingLister::new(pager))
+        let metakey = args.metakey();
+        let files_only = args.files_only();
+        let pattern = args
+            .pattern()
+            .map(|v| ListPattern::new(&path, v).push_down());
+        let scan_path = pattern.as_ref().map_or(path.as_str(), |v| v.base());
+
+        let pager = match self.inner().blocking_scan(scan_path, args) {
+            Ok((_, pager)) => pager,
+            // The pushed down dir doesn't exist, so nothing could match.
+            Err(err) if err.kind() == ErrorKind::NotFound && scan_path != path => {
+                Box::new(()) as oio::BlockingPager
+            }
+            Err(err) => return Err(err),
+        };
+        Ok(BlockingLister::new(self.inner().clone(), pager, metakey)
+            .with_pattern(pattern)
+            .with_files_only(files_only))
     }
 }
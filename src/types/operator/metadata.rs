@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use flagset::FlagSet;
+
 use crate::raw::*;
 use crate::*;
 
@@ -47,6 +49,20 @@ impl OperatorInfo {
         self.0.name()
     }
 
+    /// Metakeys that current backend returns along with file entries in
+    /// [`Accessor::list`] and [`Accessor::scan`].
+    ///
+    /// Listing with metakeys included here will not send extra `stat`
+    /// requests.
+    pub fn list_metakeys(&self) -> FlagSet<Metakey> {
+        self.0.list_metakeys()
+    }
+
+    /// [`Capability`] of operator.
+    pub fn capability(&self) -> Capability {
+        Capability::new(self.0.capabilities())
+    }
+
     /// Check if current backend supports [`Accessor::read`] or not.
     pub fn can_read(&self) -> bool {
         self.0.capabilities().contains(AccessorCapability::Read)
@@ -57,11 +73,29 @@ impl OperatorInfo {
         self.0.capabilities().contains(AccessorCapability::Write)
     }
 
+    /// Check if current backend supports creating dirs via
+    /// [`Operator::create_dir`][crate::Operator::create_dir] or not.
+    pub fn can_create_dir(&self) -> bool {
+        self.0
+            .capabilities()
+            .contains(AccessorCapability::CreateDir)
+    }
+
     /// Check if current backend supports [`Accessor::list`] or not.
     pub fn can_list(&self) -> bool {
         self.0.capabilities().contains(AccessorCapability::List)
     }
 
+    /// Check if current backend supports [`Accessor::copy`] or not.
+    pub fn can_copy(&self) -> bool {
+        self.0.capabilities().contains(AccessorCapability::Copy)
+    }
+
+    /// Check if current backend supports [`Accessor::rename`] or not.
+    pub fn can_rename(&self) -> bool {
+        self.0.capabilities().contains(AccessorCapability::Rename)
+    }
+
     /// Check if current backend supports [`Accessor::scan`] or not.
     pub fn can_scan(&self) -> bool {
         self.0.capabilities().contains(AccessorCapability::Scan)
@@ -77,6 +111,130 @@ impl OperatorInfo {
         self.0.capabilities().contains(AccessorCapability::Batch)
     }
 
+    /// Check if current backend supports writing in batch via
+    /// [`Operator::write_many`][crate::Operator::write_many] or not.
+    pub fn can_batch_write(&self) -> bool {
+        self.0
+            .capabilities()
+            .contains(AccessorCapability::BatchWrite)
+    }
+
+    /// Check if current backend supports conditional stat like
+    /// [`OpStat::with_if_none_match`] or not.
+    pub fn can_conditional_stat(&self) -> bool {
+        self.0
+            .capabilities()
+            .contains(AccessorCapability::ConditionalStat)
+    }
+
+    /// Check if current backend supports [`OpWrite::with_if_match`] or not.
+    pub fn can_write_with_if_match(&self) -> bool {
+        self.0
+            .capabilities()
+            .contains(AccessorCapability::WriteWithIfMatch)
+    }
+
+    /// Check if current backend supports [`OpWrite::with_if_none_match`] or not.
+    pub fn can_write_with_if_none_match(&self) -> bool {
+        self.0
+            .capabilities()
+            .contains(AccessorCapability::WriteWithIfNoneMatch)
+    }
+
+    /// Check if current backend supports [`OpWrite::with_if_unmodified_since`] or not.
+    pub fn can_write_with_if_unmodified_since(&self) -> bool {
+        self.0
+            .capabilities()
+            .contains(AccessorCapability::WriteWithIfUnmodifiedSince)
+    }
+
+    /// Check if current backend supports [`OpDelete::with_if_unmodified_since`][crate::ops::OpDelete::with_if_unmodified_since] or not.
+    pub fn can_delete_with_if_unmodified_since(&self) -> bool {
+        self.0
+            .capabilities()
+            .contains(AccessorCapability::DeleteWithIfUnmodifiedSince)
+    }
+
+    /// Check if current backend supports [`OpDelete::with_strict`][crate::ops::OpDelete::with_strict] natively or not.
+    pub fn can_delete_with_strict(&self) -> bool {
+        self.0
+            .capabilities()
+            .contains(AccessorCapability::DeleteWithStrict)
+    }
+
+    /// Check if current backend supports [`OpCopy::with_if_none_match`][crate::ops::OpCopy::with_if_none_match] or not.
+    pub fn can_copy_with_if_none_match(&self) -> bool {
+        self.0
+            .capabilities()
+            .contains(AccessorCapability::CopyWithIfNoneMatch)
+    }
+
+    /// Check if current backend supports [`OpWrite::with_resume`] or not.
+    pub fn can_resumable_write(&self) -> bool {
+        self.0
+            .capabilities()
+            .contains(AccessorCapability::ResumableWrite)
+    }
+
+    /// Check if current backend supports [`OpWrite::with_checksum`] with
+    /// [`ChecksumAlgorithm::Md5`][crate::ops::ChecksumAlgorithm::Md5] or not.
+    pub fn can_write_with_content_md5(&self) -> bool {
+        self.0
+            .capabilities()
+            .contains(AccessorCapability::WriteWithContentMd5)
+    }
+
+    /// Check if current backend supports [`OpWrite::with_concurrent`] or not.
+    pub fn can_write_with_concurrent(&self) -> bool {
+        self.0
+            .capabilities()
+            .contains(AccessorCapability::WriteWithConcurrent)
+    }
+
+    /// Check if current backend supports [`OpWrite::with_encryption`] and
+    /// [`OpRead::with_encryption`][crate::ops::OpRead::with_encryption] or not.
+    pub fn can_write_with_encryption(&self) -> bool {
+        self.0
+            .capabilities()
+            .contains(AccessorCapability::WriteWithEncryption)
+    }
+
+    /// Check if current backend supports [`OpRead::with_header`][crate::ops::OpRead::with_header] or not.
+    pub fn can_read_with_header(&self) -> bool {
+        self.0
+            .capabilities()
+            .contains(AccessorCapability::ReadWithHeader)
+    }
+
+    /// Check if current backend supports [`OpWrite::with_header`] or not.
+    pub fn can_write_with_header(&self) -> bool {
+        self.0
+            .capabilities()
+            .contains(AccessorCapability::WriteWithHeader)
+    }
+
+    /// Check if current backend supports [`OpRead::with_version`][crate::ops::OpRead::with_version]
+    /// and [`OpStat::with_version`] or not.
+    pub fn can_read_with_version(&self) -> bool {
+        self.0
+            .capabilities()
+            .contains(AccessorCapability::ReadWithVersion)
+    }
+
+    /// Check if current backend supports [`Operator::append`][crate::Operator::append] or not.
+    pub fn can_write_can_append(&self) -> bool {
+        self.0
+            .capabilities()
+            .contains(AccessorCapability::WriteCanAppend)
+    }
+
+    /// Check if current backend supports [`OpList::with_start_after`][crate::ops::OpList::with_start_after] or not.
+    pub fn can_list_with_start_after(&self) -> bool {
+        self.0
+            .capabilities()
+            .contains(AccessorCapability::ListWithStartAfter)
+    }
+
     /// Check if current backend supports blocking operations or not.
     pub fn can_blocking(&self) -> bool {
         self.0.capabilities().contains(AccessorCapability::Blocking)
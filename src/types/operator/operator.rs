@@ -15,23 +15,30 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::cmp;
 use std::ops::RangeBounds;
 
 use bytes::Bytes;
+use bytes::BytesMut;
 use flagset::FlagSet;
 use futures::stream;
-use futures::AsyncReadExt;
 use futures::Stream;
 use futures::StreamExt;
 use futures::TryStreamExt;
+use log::warn;
 use time::Duration;
-use tokio::io::ReadBuf;
+use time::OffsetDateTime;
 
 use super::BlockingOperator;
 use crate::ops::*;
+use crate::raw::oio::ReadExt;
 use crate::raw::*;
 use crate::*;
 
+/// Part size used by [`Operator::write_from_stream`] and concurrent
+/// [`Operator::write_with`].
+const DEFAULT_WRITE_PART_SIZE: usize = 8 * 1024 * 1024;
+
 /// Operator is the entry for all public async APIs.
 ///
 /// Read [`concepts`][docs::concepts] for know more about [`Operator`].
@@ -68,7 +75,7 @@ pub struct Operator {
 
 /// # Operator basic API.
 impl Operator {
-    pub(super) fn inner(&self) -> &FusedAccessor {
+    pub(crate) fn inner(&self) -> &FusedAccessor {
         &self.accessor
     }
 
@@ -83,6 +90,171 @@ impl Operator {
         self.accessor
     }
 
+    /// Make sure current service supports all conditions of given write.
+    fn check_write_conditions(&self, op: &'static str, path: &str, args: &OpWrite) -> Result<()> {
+        let info = self.info();
+        if (args.if_match().is_some() && !info.can_write_with_if_match())
+            || (args.if_none_match().is_some() && !info.can_write_with_if_none_match())
+            || (args.if_unmodified_since().is_some() && !info.can_write_with_if_unmodified_since())
+        {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "conditional write is not supported by this service",
+            )
+            .with_operation(op)
+            .with_context("service", info.scheme().into_static())
+            .with_context("path", path));
+        }
+
+        Ok(())
+    }
+
+    /// Make sure current service supports given encryption and the
+    /// encryption is valid.
+    fn check_encryption(
+        &self,
+        op: &'static str,
+        path: &str,
+        encryption: Option<&Encryption>,
+    ) -> Result<()> {
+        let encryption = match encryption {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+
+        let info = self.info();
+        if !info.can_write_with_encryption() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "server side encryption is not supported by this service",
+            )
+            .with_operation(op)
+            .with_context("service", info.scheme().into_static())
+            .with_context("path", path));
+        }
+
+        if let Encryption::Customer { algorithm, key } = encryption {
+            if algorithm.is_empty() || key.is_empty() {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "customer key and algorithm are required by server side encryption",
+                )
+                .with_operation(op)
+                .with_context("service", info.scheme().into_static())
+                .with_context("path", path));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Make sure current service supports custom headers and the headers
+    /// are valid.
+    fn check_headers(
+        &self,
+        op: &'static str,
+        path: &str,
+        headers: &[(String, String)],
+        supported: bool,
+    ) -> Result<()> {
+        if headers.is_empty() {
+            return Ok(());
+        }
+
+        let info = self.info();
+        if !supported {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "custom header is not supported by this service",
+            )
+            .with_operation(op)
+            .with_context("service", info.scheme().into_static())
+            .with_context("path", path));
+        }
+
+        parse_custom_headers(headers).map_err(|err| {
+            err.with_operation(op)
+                .with_context("service", info.scheme().into_static())
+                .with_context("path", path)
+        })?;
+
+        Ok(())
+    }
+
+    /// Check decompress against given range.
+    ///
+    /// Range applies to the encoded content which can't be decompressed
+    /// partially.
+    fn check_decompress(&self, op: &'static str, path: &str, args: &OpRead) -> Result<()> {
+        if args.decompress() && !args.range().is_full() {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "decompress can't be used with range",
+            )
+            .with_operation(op)
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", path)
+            .with_context("range", args.range().to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Make sure current service supports reading given version.
+    fn check_version(&self, op: &'static str, path: &str, version: Option<&str>) -> Result<()> {
+        let version = match version {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+
+        let info = self.info();
+        if !info.can_read_with_version() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "read with version is not supported by this service",
+            )
+            .with_operation(op)
+            .with_context("service", info.scheme().into_static())
+            .with_context("path", path));
+        }
+
+        if version.is_empty() {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "version must not be empty")
+                    .with_operation(op)
+                    .with_context("service", info.scheme().into_static())
+                    .with_context("path", path),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Make sure current service supports uploading parts concurrently.
+    fn check_concurrent(&self, op: &'static str, path: &str, concurrent: usize) -> Result<()> {
+        let info = self.info();
+        if concurrent == 0 {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "concurrent must be larger than 0")
+                    .with_operation(op)
+                    .with_context("service", info.scheme().into_static())
+                    .with_context("path", path),
+            );
+        }
+
+        if concurrent > 1 && !info.can_write_with_concurrent() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "write with concurrent is not supported by this service",
+            )
+            .with_operation(op)
+            .with_context("service", info.scheme().into_static())
+            .with_context("path", path));
+        }
+
+        Ok(())
+    }
+
     /// Get current operator's limit
     pub fn limit(&self) -> usize {
         self.limit
@@ -99,6 +271,11 @@ impl Operator {
 
     /// Get information of underlying accessor.
     ///
+    /// [`OperatorInfo`] carries the scheme, the normalized root, the name
+    /// (bucket or container) and the capability of the service, which is
+    /// useful for logging and diagnostics. Credentials will never be
+    /// included.
+    ///
     /// # Examples
     ///
     /// ```
@@ -109,6 +286,12 @@ impl Operator {
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
     /// let info = op.info();
+    /// println!(
+    ///     "scheme: {}, name: {}, root: {}",
+    ///     info.scheme(),
+    ///     info.name(),
+    ///     info.root()
+    /// );
     /// # Ok(())
     /// # }
     /// ```
@@ -116,6 +299,27 @@ impl Operator {
         OperatorInfo::new(self.accessor.info())
     }
 
+    /// Get [`Capability`] of underlying accessor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use opendal::raw::Operation;
+    /// use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// if op.capability().supports(Operation::Presign) {
+    ///     let _ = op.presign_read("path/to/file", time::Duration::hours(1))?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn capability(&self) -> Capability {
+        self.info().capability()
+    }
+
     /// Create a new blocking operator.
     ///
     /// This operation is nearly no cost.
@@ -128,7 +332,19 @@ impl Operator {
 impl Operator {
     /// Check if this operator can work correctly.
     ///
-    /// We will send a `list` request to path and return any errors we met.
+    /// We will send a `list` request to root and return any errors we met.
+    /// No data will be changed by this operation.
+    ///
+    /// # Notes
+    ///
+    /// - If listing is denied (like write-only destinations) or not
+    ///   supported, we will `stat` a path that never exists instead.
+    /// - Invalid credentials will be returned as `PermissionDenied`.
+    /// - Unreachable endpoints will be returned as temporary `Unexpected`
+    ///   error with message `failed to connect to service`, which is
+    ///   classified by the http client while sending requests.
+    /// - Other errors will be returned as is, check [`Error::is_temporary`]
+    ///   to decide whether to retry.
     ///
     /// ```
     /// # use std::sync::Arc;
@@ -142,10 +358,44 @@ impl Operator {
     /// # }
     /// ```
     pub async fn check(&self) -> Result<()> {
-        let mut ds = self.list("/").await?;
+        let info = self.info();
+
+        let res = if info.can_list() || info.can_scan() {
+            match self.check_via_list().await {
+                Err(err) if err.kind() == ErrorKind::PermissionDenied => {
+                    self.check_via_stat().await
+                }
+                v => v,
+            }
+        } else {
+            self.check_via_stat().await
+        };
+
+        res.map_err(|err| {
+            err.with_operation("Operator::check")
+                .with_context("service", info.scheme().into_static())
+        })
+    }
 
-        match ds.next().await {
-            Some(Err(e)) if e.kind() != ErrorKind::NotFound => Err(e),
+    /// Check via listing the first page of root.
+    async fn check_via_list(&self) -> Result<()> {
+        let res = match self.inner().list("/", OpList::new()).await {
+            Ok((_, mut pager)) => pager.next().await.map(|_| ()),
+            Err(err) => Err(err),
+        };
+
+        match res {
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+            v => v,
+        }
+    }
+
+    /// Check via stat a path that never exists.
+    async fn check_via_stat(&self) -> Result<()> {
+        let path = format!(".opendal-check-{}", uuid::Uuid::new_v4());
+
+        match self.inner().stat(&path, OpStat::new()).await {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err),
             _ => Ok(()),
         }
     }
@@ -182,14 +432,173 @@ impl Operator {
     /// # }
     /// ```
     pub async fn stat(&self, path: &str) -> Result<Metadata> {
+        self.stat_with(path, OpStat::new()).await
+    }
+
+    /// Get current path's metadata **without cache** directly with extra options.
+    ///
+    /// # Notes
+    ///
+    /// Conditions like [`OpStat::with_if_none_match`] require the service to
+    /// support conditional stat, an `Unsupported` error will be returned
+    /// otherwise. If the condition is not match, for example the etag is
+    /// still the same, an error with `ErrorKind::ConditionNotMatch` will be
+    /// returned.
+    ///
+    /// With [`OpStat::with_recursive_size`], files under dir will be scanned
+    /// to sum their sizes. Use [`OpStat::with_recursive_size_limit`] to bound
+    /// the count of files to visit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use futures::io;
+    /// # use opendal::Operator;
+    /// use opendal::ops::OpStat;
+    /// use opendal::ErrorKind;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator, etag: &str) -> Result<()> {
+    /// match op
+    ///     .stat_with("test", OpStat::new().with_if_none_match(etag))
+    ///     .await
+    /// {
+    ///     Ok(meta) => println!("file has been changed: {:?}", meta.etag()),
+    ///     Err(e) if e.kind() == ErrorKind::ConditionNotMatch => println!("file not changed"),
+    ///     Err(e) => return Err(e.into()),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn stat_with(&self, path: &str, args: OpStat) -> Result<Metadata> {
         let path = normalize_path(path);
 
-        let rp = self.inner().stat(&path, OpStat::new()).await?;
-        let meta = rp.into_metadata();
+        if args.is_conditional() && !self.info().can_conditional_stat() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "conditional stat is not supported by this service",
+            )
+            .with_operation("Operator::stat_with")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
+        self.check_version("Operator::stat_with", &path, args.version())?;
+        self.check_encryption("Operator::stat_with", &path, args.encryption())?;
+
+        let recursive_size = args.recursive_size();
+        let limit = args.recursive_size_limit();
+
+        if !recursive_size {
+            return Ok(self.inner().stat(&path, args).await?.into_metadata());
+        }
+
+        // Services like fs with path check enabled only treat `dir/` as dir.
+        let dir = if path.ends_with('/') {
+            path.clone()
+        } else {
+            format!("{path}/")
+        };
+        let mut meta = match self.inner().stat(&path, args.clone()).await {
+            Ok(rp) => rp.into_metadata(),
+            Err(err) if err.kind() == ErrorKind::NotFound && dir != path => self
+                .inner()
+                .stat(&dir, args)
+                .await
+                .map_err(|_| err)?
+                .into_metadata(),
+            Err(err) => return Err(err),
+        };
+
+        if meta.mode().is_dir() {
+            let (size, count) = self.recursive_size(&dir, limit).await?;
+            meta.set_content_length(size).set_object_count(count);
+        }
 
         Ok(meta)
     }
 
+    /// Sum the sizes of all files under given dir via scan.
+    async fn recursive_size(&self, path: &str, limit: Option<usize>) -> Result<(u64, u64)> {
+        let (mut size, mut count) = (0, 0);
+
+        // Lister will only stat entries whose list response doesn't carry
+        // content length.
+        let args = OpScan::new()
+            .with_files_only(true)
+            .with_metakey(Metakey::ContentLength);
+        let mut lister = self.scan_with(path, args).await?;
+        while let Some(de) = lister.try_next().await? {
+            if limit.map(|v| count >= v as u64).unwrap_or_default() {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "too many files to sum recursive size",
+                )
+                .with_operation("Operator::stat_with")
+                .with_context("service", self.info().scheme().into_static())
+                .with_context("path", path)
+                .with_context("limit", limit.unwrap_or_default().to_string()));
+            }
+
+            size += de
+                .metadata()
+                .as_ref()
+                .map(|meta| meta.content_length())
+                .unwrap_or_default();
+            count += 1;
+        }
+
+        Ok((size, count))
+    }
+
+    /// Get metadata of many paths concurrently **without cache**.
+    ///
+    /// # Notes
+    ///
+    /// - At most [`Operator::limit`] stats will be in flight at the same
+    ///   time, use [`Operator::with_limit`] to change it.
+    /// - Results are returned in the order they complete instead of the
+    ///   order of input paths.
+    /// - Errors are returned per path, a failed stat will not abort others.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// use futures::StreamExt;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let paths = vec!["abc".to_string(), "def".to_string()];
+    /// let mut results = op.stat_many(paths);
+    /// while let Some((path, res)) = results.next().await {
+    ///     match res {
+    ///         Ok(meta) => println!("{path}: {}", meta.content_length()),
+    ///         Err(err) => println!("{path}: {err}"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stat_many(
+        &self,
+        paths: impl IntoIterator<Item = String>,
+    ) -> impl Stream<Item = (String, Result<Metadata>)> {
+        let op = self.clone();
+
+        stream::iter(paths)
+            .map(move |path| {
+                let op = op.clone();
+                async move {
+                    let res = op.stat(&path).await;
+                    (path, res)
+                }
+            })
+            .buffer_unordered(self.limit)
+    }
+
     /// Get current metadata with cache.
     ///
     /// `metadata` will check the given query with already cached metadata
@@ -293,6 +702,12 @@ impl Operator {
 
     /// Check if this path exists or not.
     ///
+    /// # Notes
+    ///
+    /// This function is built on `stat` and never fetches the content.
+    /// `NotFound` will be returned as `Ok(false)`, while all other errors
+    /// like `PermissionDenied` will be returned as is.
+    ///
     /// # Example
     ///
     /// ```
@@ -302,12 +717,12 @@ impl Operator {
     ///
     /// #[tokio::main]
     /// async fn test(op: Operator) -> Result<()> {
-    ///     let _ = op.is_exist("test").await?;
+    ///     let _ = op.exists("test").await?;
     ///
     ///     Ok(())
     /// }
     /// ```
-    pub async fn is_exist(&self, path: &str) -> Result<bool> {
+    pub async fn exists(&self, path: &str) -> Result<bool> {
         let r = self.stat(path).await;
         match r {
             Ok(_) => Ok(true),
@@ -318,6 +733,85 @@ impl Operator {
         }
     }
 
+    /// Check if this path exists or not.
+    ///
+    /// This is an alias of [`Operator::exists`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use anyhow::Result;
+    /// use futures::io;
+    /// use opendal::Operator;
+    ///
+    /// #[tokio::main]
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     let _ = op.is_exist("test").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn is_exist(&self, path: &str) -> Result<bool> {
+        self.exists(path).await
+    }
+
+    /// Fetch the last modified time of given path.
+    ///
+    /// # Notes
+    ///
+    /// This function is built on `stat`. `Ok(None)` will be returned if
+    /// the service doesn't report last modified of this path.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use anyhow::Result;
+    /// use opendal::Operator;
+    ///
+    /// #[tokio::main]
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     if let Some(t) = op.last_modified("test").await? {
+    ///         println!("test is modified at {t}");
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn last_modified(&self, path: &str) -> Result<Option<OffsetDateTime>> {
+        let meta = self.stat(path).await?;
+        Ok(meta.last_modified())
+    }
+
+    /// Check if this path has been modified after given time.
+    ///
+    /// # Notes
+    ///
+    /// This function is built on `stat`. Freshness is unknown if the service
+    /// doesn't report last modified of this path, `Ok(None)` will be returned
+    /// in this case, and callers should treat it as stale or fetch the
+    /// content to compare.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use anyhow::Result;
+    /// use opendal::Operator;
+    /// use time::OffsetDateTime;
+    ///
+    /// #[tokio::main]
+    /// async fn test(op: Operator, cached_at: OffsetDateTime) -> Result<()> {
+    ///     if op.is_fresher_than("test", cached_at).await? != Some(false) {
+    ///         // Reload the cache.
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn is_fresher_than(&self, path: &str, time: OffsetDateTime) -> Result<Option<bool>> {
+        let last_modified = self.last_modified(path).await?;
+        Ok(last_modified.map(|v| v > time))
+    }
+
     /// Create a dir at given path.
     ///
     /// # Notes
@@ -330,6 +824,15 @@ impl Operator {
     ///
     /// - Create on existing dir will succeed.
     /// - Create dir is always recursive, works like `mkdir -p`
+    /// - On file systems (like `fs` and `hdfs`), a real dir will be created.
+    /// - On object storage services (like `s3` and `memory`), a zero-byte
+    ///   marker object named `path/to/dir/` will be written, which can be
+    ///   listed like other entries.
+    /// - On services without dir concept (like `ghac`), nothing will be
+    ///   created and this call always succeeds.
+    ///
+    /// Returns `Unsupported` if the service can't create dirs, check
+    /// [`OperatorInfo::can_create_dir`] first.
     ///
     /// # Examples
     ///
@@ -355,6 +858,16 @@ impl Operator {
             );
         }
 
+        if !self.info().can_create_dir() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "create_dir is not supported by this service",
+            )
+            .with_operation("Operator::create_dir")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
         self.inner()
             .create(&path, OpCreate::new(EntryMode::DIR))
             .await?;
@@ -383,6 +896,47 @@ impl Operator {
         self.range_read(path, ..).await
     }
 
+    /// Read the path into given buffer without allocation.
+    ///
+    /// Returns the number of bytes read, which could be smaller than the
+    /// buffer if path is shorter. If the buffer is smaller than the path,
+    /// only the first `buf.len()` bytes will be read.
+    ///
+    /// Only `buf[..n]` will be written, the rest of buffer is untouched.
+    /// An empty buffer will return `0` without sending any request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut buf = vec![0; 4096];
+    /// let n = op.read_into("path/to/file", &mut buf).await?;
+    /// let content = &buf[..n];
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_into(&self, path: &str, buf: &mut [u8]) -> Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        // Only fetch the bytes that could be filled.
+        let mut r = match self.range_reader(path, 0..buf.len() as u64).await {
+            Ok(r) => r,
+            // Range read on an empty object is not satisfiable for most
+            // services, check it only after failure to avoid an extra stat.
+            Err(err) => match self.stat(path).await {
+                Ok(meta) if meta.mode().is_file() && meta.content_length() == 0 => return Ok(0),
+                _ => return Err(err),
+            },
+        };
+
+        r.read_into(buf).await
+    }
+
     /// Read the specified range of path into a bytes.
     ///
     /// This function will allocate a new bytes internally. For more precise memory control or
@@ -405,46 +959,156 @@ impl Operator {
     /// # }
     /// ```
     pub async fn range_read(&self, path: &str, range: impl RangeBounds<u64>) -> Result<Vec<u8>> {
-        let path = normalize_path(path);
+        self.read_with(path, OpRead::new().with_range(range)).await
+    }
 
-        if !validate_path(&path, EntryMode::FILE) {
+    /// Read path into a bytes with extra options.
+    ///
+    /// # Notes
+    ///
+    /// - If [`OpRead::with_max_size`] is set, an `Unexpected` error will be
+    ///   returned if the content is larger than it. `Content-Length` will be
+    ///   checked before reading if available, otherwise read will be aborted
+    ///   as soon as the limit is crossed.
+    /// - If [`OpRead::with_decompress`] is set, content will be decompressed
+    ///   according to its `Content-Encoding`. `gzip`, `deflate`, `zstd` and
+    ///   `br` are supported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// use opendal::ops::OpRead;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let args = OpRead::new().with_max_size(1024 * 1024);
+    /// let bs = op.read_with("path/to/file", args).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_with(&self, path: &str, args: OpRead) -> Result<Vec<u8>> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
             return Err(
                 Error::new(ErrorKind::IsADirectory, "read path is a directory")
-                    .with_operation("range_read")
+                    .with_operation("Operator::read_with")
                     .with_context("service", self.inner().info().scheme())
                     .with_context("path", &path),
             );
         }
 
-        let br = BytesRange::from(range);
-
-        let op = OpRead::new().with_range(br);
-
-        let (rp, mut s) = self.inner().read(&path, op).await?;
-
-        let length = rp.into_metadata().content_length() as usize;
-        let mut buffer = Vec::with_capacity(length);
+        if args.max_size() == Some(0) {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "max size of read must be greater than 0",
+            )
+            .with_operation("Operator::read_with")
+            .with_context("service", self.inner().info().scheme())
+            .with_context("path", &path));
+        }
 
-        let dst = buffer.spare_capacity_mut();
-        let mut buf = ReadBuf::uninit(dst);
+        self.check_encryption("Operator::read_with", &path, args.encryption())?;
+        self.check_headers(
+            "Operator::read_with",
+            &path,
+            args.headers(),
+            self.info().can_read_with_header(),
+        )?;
+        self.check_version("Operator::read_with", &path, args.version())?;
+        self.check_decompress("Operator::read_with", &path, &args)?;
+
+        let br = args.range();
+        let max_size = args.max_size();
+
+        // `length` is the exact size returned by service which must be
+        // matched after reading, while `hint` is only used to pre-size the
+        // buffer so that it will be allocated only once.
+        let (length, hint, mut r) = if args.decompress() {
+            // Length of the encoded content can't be used as a hint.
+            let r = Reader::create(self.inner().clone(), &path, args).await?;
+            (None, None, r)
+        } else {
+            let (rp, s) = self.inner().read(&path, args).await?;
 
-        // Safety: the input buffer is created with_capacity(length).
-        unsafe { buf.assume_init(length) };
+            let length = rp.into_metadata().content_length_raw();
+            (length, length.or_else(|| br.size()), Reader::new(s))
+        };
 
-        // TODO: use native read api
-        s.read_exact(buf.initialized_mut()).await.map_err(|err| {
+        let exceeds_max_size = |max_size: u64| {
+            Error::new(ErrorKind::Unexpected, "object exceeds max size")
+                .with_operation("Operator::read_with")
+                .with_context("service", self.inner().info().scheme().into_static())
+                .with_context("path", &path)
+                .with_context("max_size", max_size.to_string())
+        };
+        let read_error = |err: Error| {
             Error::new(ErrorKind::Unexpected, "read from storage")
-                .with_operation("range_read")
+                .with_operation("Operator::read_with")
                 .with_context("service", self.inner().info().scheme().into_static())
                 .with_context("path", &path)
                 .with_context("range", br.to_string())
                 .set_source(err)
-        })?;
+        };
+
+        // Make sure all bytes returned by service have been read, just like
+        // `read_exact`.
+        let check_length = |buf: Vec<u8>| match length {
+            Some(length) if buf.len() as u64 != length => Err(Error::new(
+                ErrorKind::Unexpected,
+                "read content length is not match with the response",
+            )
+            .with_operation("Operator::read_with")
+            .with_context("service", self.inner().info().scheme().into_static())
+            .with_context("path", &path)
+            .with_context("range", br.to_string())
+            .with_context("expect", length.to_string())
+            .with_context("actual", buf.len().to_string())),
+            _ => Ok(buf),
+        };
 
-        // Safety: read_exact makes sure this buffer has been filled.
-        unsafe { buffer.set_len(length) }
+        let max_size = match max_size {
+            Some(v) => v,
+            None => {
+                let buf = r
+                    .read_to_end_with_capacity(hint.unwrap_or_default() as usize)
+                    .await
+                    .map_err(read_error)?;
+                return check_length(buf);
+            }
+        };
+
+        if let Some(length) = length {
+            if length > max_size {
+                return Err(exceeds_max_size(max_size));
+            }
+        }
+
+        // Never hold more than `max_size + 1` bytes no matter what the
+        // service returns.
+        let limit = max_size.saturating_add(1).min(usize::MAX as u64) as usize;
+        let mut buf = vec![0; hint.unwrap_or_default().min(limit as u64) as usize];
+        let mut filled = 0;
+        loop {
+            if filled == buf.len() {
+                let grow = buf.len().clamp(8 * 1024, 4 * 1024 * 1024);
+                buf.resize(limit.min(filled + grow), 0);
+            }
+
+            match r.read(&mut buf[filled..]).await.map_err(read_error)? {
+                0 => break,
+                n => filled += n,
+            }
+
+            if filled as u64 > max_size {
+                return Err(exceeds_max_size(max_size));
+            }
+        }
 
-        Ok(buffer)
+        buf.truncate(filled);
+        check_length(buf)
     }
 
     /// Create a new reader which can read the whole path.
@@ -466,6 +1130,30 @@ impl Operator {
         self.range_reader(path, ..).await
     }
 
+    /// Create a stream of lines which can read the whole path lazily.
+    ///
+    /// This is a shortcut of [`Reader::into_lines`], see its docs for
+    /// more details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use futures::TryStreamExt;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut lines = op.read_lines("path/to/file.ndjson").await?;
+    /// while let Some(line) = lines.try_next().await? {
+    ///     let value: serde_json::Value = serde_json::from_str(&line)?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_lines(&self, path: &str) -> Result<Lines> {
+        Ok(self.reader(path).await?.into_lines())
+    }
+
     /// Create a new reader which can read the specified range.
     ///
     /// # Notes
@@ -496,11 +1184,59 @@ impl Operator {
             );
         }
 
-        let op = OpRead::new().with_range(range.into());
+        let op = OpRead::new().with_range(range);
 
         Reader::create(self.inner().clone(), &path, op).await
     }
 
+    /// Create a new reader with extra options.
+    ///
+    /// # Notes
+    ///
+    /// - With [`OpRead::with_concurrent`], reader will prefetch chunks
+    ///   concurrently and return them in order.
+    /// - With [`OpRead::with_decompress`], reader will decompress content
+    ///   according to its `Content-Encoding`, the returned reader can't seek.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// use opendal::ops::OpRead;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let args = OpRead::new().with_concurrent(8).with_chunk(8 * 1024 * 1024);
+    /// let r = op.reader_with("path/to/file", args).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn reader_with(&self, path: &str, args: OpRead) -> Result<Reader> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "read path is a directory")
+                    .with_operation("Operator::reader_with")
+                    .with_context("service", self.info().scheme())
+                    .with_context("path", path),
+            );
+        }
+
+        self.check_encryption("Operator::reader_with", &path, args.encryption())?;
+        self.check_headers(
+            "Operator::reader_with",
+            &path,
+            args.headers(),
+            self.info().can_read_with_header(),
+        )?;
+        self.check_version("Operator::reader_with", &path, args.version())?;
+        self.check_decompress("Operator::reader_with", &path, &args)?;
+
+        Reader::create(self.inner().clone(), &path, args).await
+    }
+
     /// Write bytes into path.
     ///
     /// # Notes
@@ -562,47 +1298,581 @@ impl Operator {
             );
         }
 
-        let op = OpWrite::default().with_append();
-        Writer::create(self.inner().clone(), &path, op).await
+        let op = OpWrite::default().with_append();
+        Writer::create(self.inner().clone(), &path, op).await
+    }
+
+    /// Create a new writer with extra options.
+    ///
+    /// # Notes
+    ///
+    /// - Use [`OpWrite::with_resume`] to continue an interrupted upload,
+    ///   which requires [`OperatorInfo::can_resumable_write`].
+    /// - Use [`OpWrite::with_content_length`] to declare the total size
+    ///   up front, content no larger than 8MiB will be sent in one request.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// use opendal::ops::OpWrite;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let args = OpWrite::new().with_content_type("text/plain");
+    /// let mut w = op.writer_with("path/to/file", args).await?;
+    /// w.append(vec![0; 4096]).await?;
+    /// w.close().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn writer_with(&self, path: &str, args: OpWrite) -> Result<Writer> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "write path is a directory")
+                    .with_operation("Operator::writer_with")
+                    .with_context("service", self.inner().info().scheme().into_static())
+                    .with_context("path", &path),
+            );
+        }
+
+        if args.resume().is_some() && !self.info().can_resumable_write() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "resumable write is not supported by this service",
+            )
+            .with_operation("Operator::writer_with")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
+        self.check_write_conditions("Operator::writer_with", &path, &args)?;
+
+        if args.checksum() == Some(ChecksumAlgorithm::Md5)
+            && !self.info().can_write_with_content_md5()
+        {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "write with content md5 is not supported by this service",
+            )
+            .with_operation("Operator::writer_with")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
+        self.check_concurrent("Operator::writer_with", &path, args.concurrent())?;
+        self.check_encryption("Operator::writer_with", &path, args.encryption())?;
+        self.check_headers(
+            "Operator::writer_with",
+            &path,
+            args.headers(),
+            self.info().can_write_with_header(),
+        )?;
+
+        // Content will be uploaded in parts, the digest of whole content
+        // can't be verified by services.
+        if args.content_md5().is_some() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "pre-computed content md5 is not supported by writer, use write_with instead",
+            )
+            .with_operation("Operator::writer_with")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
+        let args = match args.content_length() {
+            // Content that fits in a single part will be sent in one request.
+            Some(n) if n <= DEFAULT_WRITE_PART_SIZE as u64 && args.resume().is_none() => args,
+            _ => args.with_append(),
+        };
+        Writer::create(self.inner().clone(), &path, args).await
+    }
+
+    /// Write data with extra options.
+    ///
+    /// # Notes
+    ///
+    /// - Write will make sure all bytes has been written, or an error will be returned.
+    /// - Conditions like [`OpWrite::with_if_match`] require the service to
+    ///   support them, an `Unsupported` error will be returned otherwise.
+    ///   If the condition is not met, an error with
+    ///   `ErrorKind::ConditionNotMatch` will be returned.
+    /// - With [`OpWrite::with_concurrent`], content larger than 8MiB will be
+    ///   uploaded in parts of 8MiB with at most `n` parts in flight.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// use bytes::Bytes;
+    /// use opendal::ops::OpWrite;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let bs = b"hello, world!".to_vec();
+    /// let args = OpWrite::new().with_content_type("text/plain");
+    /// let _ = op.write_with("path/to/file", args, bs).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_with(&self, path: &str, args: OpWrite, bs: impl Into<Bytes>) -> Result<()> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "write path is a directory")
+                    .with_operation("Operator::write_with")
+                    .with_context("service", self.info().scheme().into_static())
+                    .with_context("path", &path),
+            );
+        }
+
+        self.check_write_conditions("Operator::write_with", &path, &args)?;
+
+        if (args.checksum() == Some(ChecksumAlgorithm::Md5) || args.content_md5().is_some())
+            && !self.info().can_write_with_content_md5()
+        {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "write with content md5 is not supported by this service",
+            )
+            .with_operation("Operator::write_with")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
+        if let Some(v) = args.content_md5() {
+            if !is_valid_content_md5(v) {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "content md5 must be base64 encoded 16 bytes digest",
+                )
+                .with_operation("Operator::write_with")
+                .with_context("service", self.info().scheme().into_static())
+                .with_context("path", &path)
+                .with_context("content_md5", v));
+            }
+        }
+
+        self.check_encryption("Operator::write_with", &path, args.encryption())?;
+        self.check_headers(
+            "Operator::write_with",
+            &path,
+            args.headers(),
+            self.info().can_write_with_header(),
+        )?;
+        self.check_concurrent("Operator::write_with", &path, args.concurrent())?;
+
+        let bs = bs.into();
+        if let Some(n) = args.content_length() {
+            if n != bs.len() as u64 {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "content doesn't match the declared content length",
+                )
+                .with_operation("Operator::write_with")
+                .with_context("service", self.info().scheme().into_static())
+                .with_context("path", &path)
+                .with_context("content_length", n.to_string())
+                .with_context("size", bs.len().to_string()));
+            }
+        }
+        if args.concurrent() > 1 && bs.len() > DEFAULT_WRITE_PART_SIZE {
+            return self.write_with_concurrent(&path, args, bs).await;
+        }
+
+        let (_, mut w) = self.inner().write(&path, args).await?;
+        w.write(bs).await?;
+        w.close().await?;
+
+        Ok(())
+    }
+
+    /// Upload content via multipart upload with parts uploaded
+    /// concurrently.
+    ///
+    /// Parts are sliced from the content without copying, and the writer
+    /// will abort the upload if any part failed.
+    async fn write_with_concurrent(&self, path: &str, args: OpWrite, bs: Bytes) -> Result<()> {
+        // Content will be uploaded in parts, the digest of whole content
+        // can't be verified by services.
+        if args.content_md5().is_some() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "checksum of whole content is not supported with concurrent write",
+            )
+            .with_operation("Operator::write_with")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", path));
+        }
+
+        let (_, mut w) = self.inner().write(path, args.with_append()).await?;
+        let res = async {
+            for offset in (0..bs.len()).step_by(DEFAULT_WRITE_PART_SIZE) {
+                let end = cmp::min(offset + DEFAULT_WRITE_PART_SIZE, bs.len());
+                w.append(bs.slice(offset..end)).await?;
+            }
+            w.close().await
+        }
+        .await;
+
+        // The whole content is owned by this call, abort the upload to
+        // avoid orphaned parts.
+        if let Err(err) = res {
+            if let Err(e) = w.abort().await {
+                warn!("abort write of {path} failed: {e:?}");
+            }
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Write many small objects via given stream.
+    ///
+    /// Returns the result of every path in the order of completion. An
+    /// error on one path will not abort the others, only errors of a whole
+    /// batch request will be returned directly. Paths in results are
+    /// normalized.
+    ///
+    /// # Notes
+    ///
+    /// If underlying services support write in batch (check
+    /// [`OperatorInfo::can_batch_write`]), we will write by chunks with
+    /// given batch limit on the stream. Otherwise, we will write objects
+    /// one by one with at most `limit` writes in flight.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use bytes::Bytes;
+    /// # use opendal::Operator;
+    /// use futures::stream;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let stream = stream::iter(vec![
+    ///     ("abc".to_string(), Bytes::from("Hello")),
+    ///     ("def".to_string(), Bytes::from("World")),
+    /// ]);
+    /// for (path, res) in op.write_many(stream).await? {
+    ///     if let Err(err) = res {
+    ///         println!("write {path} failed: {err}");
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_many(
+        &self,
+        input: impl Stream<Item = (String, Bytes)> + Unpin,
+    ) -> Result<Vec<(String, Result<()>)>> {
+        let mut results = Vec::new();
+
+        if self.info().can_batch_write() {
+            let mut input = input.chunks(self.limit());
+
+            while let Some(items) = input.next().await {
+                results.extend(self.write_batch(items).await?);
+            }
+        } else {
+            let written: Vec<_> = input
+                .map(|(path, bs)| async move {
+                    let res = self.write(&path, bs).await;
+                    (normalize_path(&path), res)
+                })
+                .buffer_unordered(self.limit)
+                .collect()
+                .await;
+            results.extend(written);
+        }
+
+        Ok(results)
+    }
+
+    /// Append bytes to the end of path.
+    ///
+    /// # Notes
+    ///
+    /// - The object will be created if it doesn't exist.
+    /// - Only services with native append support like `fs` and `azblob`
+    ///   (via append blobs) can be used, an `Unsupported` error will be
+    ///   returned otherwise. Please check [`OperatorInfo::can_write_can_append`]
+    ///   before calling.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.append("path/to/file", "hello, ").await?;
+    /// op.append("path/to/file", "world!").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn append(&self, path: &str, bs: impl Into<Bytes>) -> Result<()> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "append path is a directory")
+                    .with_operation("Operator::append")
+                    .with_context("service", self.info().scheme().into_static())
+                    .with_context("path", &path),
+            );
+        }
+
+        if !self.info().can_write_can_append() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "append is not supported by this service",
+            )
+            .with_operation("Operator::append")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
+        let (_, mut w) = self
+            .inner()
+            .write(&path, OpWrite::new().with_append_existing())
+            .await?;
+        w.write(bs.into()).await?;
+        w.close().await?;
+
+        Ok(())
+    }
+
+    /// Write data from a stream into path without buffering the whole
+    /// content in memory.
+    ///
+    /// # Notes
+    ///
+    /// - Chunks will be coalesced into parts of 8MiB (except the last one)
+    ///   to satisfy the minimum part size of services like s3.
+    /// - If the whole stream is smaller than one part, it will be sent via
+    ///   a single `write` instead of multipart upload.
+    /// - The first error returned by the stream will abort the writer, so
+    ///   no partial content will be committed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// use bytes::Bytes;
+    /// use futures::stream;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let s = stream::iter(vec![Ok(Bytes::from("Hello, ")), Ok(Bytes::from("World!"))]);
+    /// op.write_from_stream("path/to/file", s).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_from_stream(
+        &self,
+        path: &str,
+        stream: impl Stream<Item = Result<Bytes>>,
+    ) -> Result<()> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "write path is a directory")
+                    .with_operation("Operator::write_from_stream")
+                    .with_context("service", self.info().scheme().into_static())
+                    .with_context("path", &path),
+            );
+        }
+
+        futures::pin_mut!(stream);
+
+        let part_size = DEFAULT_WRITE_PART_SIZE;
+        let mut buf = BytesMut::new();
+        let mut done = false;
+
+        // Fill the first part to decide whether multipart upload is needed.
+        while !done && buf.len() < part_size {
+            match stream.try_next().await? {
+                Some(bs) => buf.extend_from_slice(&bs),
+                None => done = true,
+            }
+        }
+
+        if done {
+            let (_, mut w) = self.inner().write(&path, OpWrite::new()).await?;
+            w.write(buf.freeze()).await?;
+            w.close().await?;
+
+            return Ok(());
+        }
+
+        let (_, mut w) = self
+            .inner()
+            .write(&path, OpWrite::new().with_append())
+            .await?;
+
+        let result = async {
+            loop {
+                while buf.len() >= part_size {
+                    w.append(buf.split_to(part_size).freeze()).await?;
+                }
+
+                if done {
+                    if !buf.is_empty() {
+                        w.append(buf.split().freeze()).await?;
+                    }
+                    return Ok(());
+                }
+
+                match stream.try_next().await? {
+                    Some(bs) => buf.extend_from_slice(&bs),
+                    None => done = true,
+                }
+            }
+        }
+        .await;
+
+        match result {
+            Ok(()) => w.close().await,
+            Err(err) => {
+                if let Err(e) = w.abort().await {
+                    warn!("abort writer of {path} failed: {e:?}");
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// Copy a file from `from` to `to`.
+    ///
+    /// # Notes
+    ///
+    /// - `from` and `to` must be file paths.
+    /// - `to` will be overwritten if it already exists.
+    /// - Services that don't support copy will return `Unsupported`. Use
+    ///   [`CopyLayer`][crate::layers::CopyLayer] to fall back to streaming
+    ///   read and write instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.copy("path/to/file", "path/to/file2").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy(&self, from: &str, to: &str) -> Result<()> {
+        self.copy_with(from, to, OpCopy::new()).await
+    }
+
+    /// Copy a file from `from` to `to` with extra options.
+    ///
+    /// # Notes
+    ///
+    /// Conditions like [`OpCopy::with_if_none_match`] require the service
+    /// to support them, an `Unsupported` error will be returned otherwise.
+    /// If the condition is not match, for example the destination already
+    /// exists while copying with `if_none_match("*")`, an error with
+    /// `ErrorKind::ConditionNotMatch` will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// use opendal::ops::OpCopy;
+    /// use opendal::ErrorKind;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let args = OpCopy::new().with_if_none_match("*");
+    /// match op.copy_with("path/to/file", "path/to/file2", args).await {
+    ///     Ok(()) => println!("copied"),
+    ///     Err(e) if e.kind() == ErrorKind::ConditionNotMatch => println!("file2 already exists"),
+    ///     Err(e) => return Err(e.into()),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy_with(&self, from: &str, to: &str, args: OpCopy) -> Result<()> {
+        let from = normalize_path(from);
+        let to = normalize_path(to);
+
+        for path in [&from, &to] {
+            if !validate_path(path, EntryMode::FILE) {
+                return Err(
+                    Error::new(ErrorKind::IsADirectory, "copy path is a directory")
+                        .with_operation("Operator::copy_with")
+                        .with_context("service", self.info().scheme().into_static())
+                        .with_context("path", path),
+                );
+            }
+        }
+
+        if args.if_none_match().is_some() && !self.info().can_copy_with_if_none_match() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "copy with if_none_match is not supported by this service",
+            )
+            .with_operation("Operator::copy_with")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &to));
+        }
+
+        let _ = self.inner().copy(&from, &to, args).await?;
+
+        Ok(())
     }
 
-    /// Write data with extra options.
+    /// Rename a file from `from` to `to`.
     ///
     /// # Notes
     ///
-    /// - Write will make sure all bytes has been written, or an error will be returned.
+    /// - `from` and `to` must be file paths.
+    /// - `to` will be overwritten if it already exists.
+    /// - Only services that support rename natively (like `fs` and `webdav`)
+    ///   can be used. Others will return `Unsupported` instead of emulating
+    ///   rename via non-atomic copy and delete. Use [`OperatorInfo::can_rename`]
+    ///   to check.
     ///
     /// # Examples
     ///
-    /// ```no_run
-    /// # use std::io::Result;
+    /// ```
+    /// # use anyhow::Result;
     /// # use opendal::Operator;
-    /// use bytes::Bytes;
-    /// use opendal::ops::OpWrite;
-    ///
     /// # #[tokio::main]
     /// # async fn test(op: Operator) -> Result<()> {
-    /// let bs = b"hello, world!".to_vec();
-    /// let args = OpWrite::new().with_content_type("text/plain");
-    /// let _ = op.write_with("path/to/file", args, bs).await?;
+    /// op.rename("path/to/file", "path/to/file2").await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn write_with(&self, path: &str, args: OpWrite, bs: impl Into<Bytes>) -> Result<()> {
-        let path = normalize_path(path);
-
-        if !validate_path(&path, EntryMode::FILE) {
-            return Err(
-                Error::new(ErrorKind::IsADirectory, "write path is a directory")
-                    .with_operation("Operator::write_with")
-                    .with_context("service", self.info().scheme().into_static())
-                    .with_context("path", &path),
-            );
+    pub async fn rename(&self, from: &str, to: &str) -> Result<()> {
+        let from = normalize_path(from);
+        let to = normalize_path(to);
+
+        for path in [&from, &to] {
+            if !validate_path(path, EntryMode::FILE) {
+                return Err(
+                    Error::new(ErrorKind::IsADirectory, "rename path is a directory")
+                        .with_operation("Operator::rename")
+                        .with_context("service", self.info().scheme().into_static())
+                        .with_context("path", path),
+                );
+            }
         }
 
-        let (_, mut w) = self.inner().write(&path, args).await?;
-        w.write(bs.into()).await?;
-        w.close().await?;
+        let _ = self.inner().rename(&from, &to, OpRename::new()).await?;
 
         Ok(())
     }
@@ -626,11 +1896,73 @@ impl Operator {
     /// # }
     /// ```
     pub async fn delete(&self, path: &str) -> Result<()> {
+        self.delete_with(path, OpDelete::new()).await
+    }
+
+    /// Delete the given path with extra options.
+    ///
+    /// # Notes
+    ///
+    /// - Delete not existing path won't return errors, `NotFound` returned
+    ///   by services will be treated as success.
+    /// - With [`OpDelete::with_strict`], `NotFound` will be returned if the
+    ///   path doesn't exist. Services that can't tell it from the delete
+    ///   response will `stat` the path first, see
+    ///   [`OperatorInfo::can_delete_with_strict`].
+    /// - [`OpDelete::with_if_unmodified_since`] requires the service to
+    ///   support it, an `Unsupported` error will be returned otherwise.
+    ///   If the path has been modified since given time, an error with
+    ///   `ErrorKind::ConditionNotMatch` will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// use opendal::ops::OpDelete;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.delete_with("test", OpDelete::new().with_strict(true))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_with(&self, path: &str, args: OpDelete) -> Result<()> {
         let path = normalize_path(path);
 
-        let _ = self.inner().delete(&path, OpDelete::new()).await?;
+        if args.if_unmodified_since().is_some()
+            && !self.info().can_delete_with_if_unmodified_since()
+        {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "conditional delete is not supported by this service",
+            )
+            .with_operation("Operator::delete_with")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
+        // Most services won't tell us whether the path existed while
+        // deleting, so we need to check it first. This check is racy and
+        // costs an extra request, so services that can tell it from the
+        // delete response will handle strict mode by themselves.
+        //
+        // Only `NotFound` is returned here, other errors like denied stat
+        // should not block the delete.
+        if args.strict() && !self.info().can_delete_with_strict() {
+            if let Err(err) = self.inner().stat(&path, OpStat::new()).await {
+                if err.kind() == ErrorKind::NotFound {
+                    return Err(err);
+                }
+            }
+        }
 
-        Ok(())
+        match self.inner().delete(&path, args.clone()).await {
+            Ok(_) => Ok(()),
+            Err(err) if err.kind() == ErrorKind::NotFound && !args.strict() => Ok(()),
+            Err(err) => Err(err),
+        }
     }
 
     ///
@@ -684,33 +2016,31 @@ impl Operator {
     /// # }
     /// ```
     pub async fn remove_via(&self, input: impl Stream<Item = String> + Unpin) -> Result<()> {
-        if self.info().can_batch() {
-            let mut input = input.map(|v| (v, OpDelete::default())).chunks(self.limit());
+        let mut failed = Vec::new();
 
-            while let Some(batches) = input.next().await {
-                let results = self
-                    .inner()
-                    .batch(OpBatch::new(BatchOperations::Delete(batches)))
-                    .await?;
-
-                let BatchedResults::Delete(results) = results.into_results();
+        if self.info().can_batch() {
+            let mut input = input.chunks(self.limit());
 
-                // TODO: return error here directly seems not a good idea?
-                for (_, result) in results {
-                    let _ = result?;
-                }
+            while let Some(paths) = input.next().await {
+                failed.extend(self.remove_batch(paths).await?);
             }
         } else {
-            input
-                .map(Ok)
-                .try_for_each_concurrent(self.limit, |path| async move {
-                    let _ = self.inner().delete(&path, OpDelete::default()).await?;
-                    Ok::<(), Error>(())
+            let results: Vec<_> = input
+                .map(|path| async move {
+                    let res = self.inner().delete(&path, OpDelete::default()).await;
+                    (path, res)
                 })
-                .await?;
+                .buffer_unordered(self.limit)
+                .collect()
+                .await;
+            failed.extend(
+                results
+                    .into_iter()
+                    .filter_map(|(path, res)| res.err().map(|err| (path, err))),
+            );
         }
 
-        Ok(())
+        aggregate_remove_errors("Operator::remove_via", failed)
     }
 
     /// Remove the path and all nested dirs and files recursively.
@@ -749,36 +2079,114 @@ impl Operator {
             return self.delete(path).await;
         }
 
-        let obs = self.scan(path).await?;
+        let mut obs = self.scan(path).await?;
+        let mut failed = Vec::new();
 
         if self.info().can_batch() {
+            // Lister handles pagination for us, we only need to make sure
+            // every batch doesn't exceed the limit.
             let mut obs = obs.try_chunks(self.limit());
 
-            while let Some(batches) = obs.next().await {
-                let batches = batches
+            while let Some(entries) = obs.next().await {
+                let paths = entries
                     .map_err(|err| err.1)?
                     .into_iter()
-                    .map(|v| (v.path().to_string(), OpDelete::default()))
+                    .map(|v| v.path().to_string())
                     .collect();
 
-                let results = self
-                    .inner()
-                    .batch(OpBatch::new(BatchOperations::Delete(batches)))
-                    .await?;
+                failed.extend(self.remove_batch(paths).await?);
+            }
+        } else {
+            while let Some(de) = obs.try_next().await? {
+                if let Err(err) = self.inner().delete(de.path(), OpDelete::new()).await {
+                    failed.push((de.path().to_string(), err));
+                }
+            }
+        }
 
-                let BatchedResults::Delete(results) = results.into_results();
+        // Remove the dir itself which could be left by services that
+        // don't return it while scanning.
+        if failed.is_empty() {
+            let path = normalize_path(path);
+            if let Err(err) = self.inner().delete(&path, OpDelete::new()).await {
+                failed.push((path, err));
+            }
+        }
 
-                // TODO: return error here directly seems not a good idea?
-                for (_, result) in results {
-                    let _ = result?;
-                }
+        aggregate_remove_errors("Operator::remove_all", failed)
+    }
+
+    /// Delete paths in one batch request.
+    ///
+    /// Returns paths that failed to delete along with their errors.
+    async fn remove_batch(&self, paths: Vec<String>) -> Result<Vec<(String, Error)>> {
+        let batches = paths.into_iter().map(|v| (v, OpDelete::new())).collect();
+
+        let results = self
+            .inner()
+            .batch(OpBatch::new(BatchOperations::Delete(batches)))
+            .await?;
+
+        let results = match results.into_results() {
+            BatchedResults::Delete(results) => results,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "batch delete returns results of other operations",
+                ))
             }
-        } else {
-            obs.try_for_each(|v| async move { self.delete(v.path()).await })
-                .await?;
+        };
+        Ok(results
+            .into_iter()
+            .filter_map(|(path, res)| res.err().map(|err| (path, err)))
+            .collect())
+    }
+
+    /// Write objects in one batch request.
+    ///
+    /// Returns the result of every path.
+    async fn write_batch(&self, items: Vec<(String, Bytes)>) -> Result<Vec<(String, Result<()>)>> {
+        let mut results = Vec::new();
+        let mut ops = Vec::with_capacity(items.len());
+
+        for (path, bs) in items {
+            let path = normalize_path(&path);
+            if !validate_path(&path, EntryMode::FILE) {
+                let err = Error::new(ErrorKind::IsADirectory, "write path is a directory")
+                    .with_operation("Operator::write_many")
+                    .with_context("service", self.info().scheme().into_static())
+                    .with_context("path", &path);
+                results.push((path, Err(err)));
+                continue;
+            }
+
+            ops.push((path, OpWrite::new(), bs));
         }
 
-        Ok(())
+        if ops.is_empty() {
+            return Ok(results);
+        }
+
+        let rp = self
+            .inner()
+            .batch(OpBatch::new(BatchOperations::Write(ops)))
+            .await?;
+
+        match rp.into_results() {
+            BatchedResults::Write(written) => results.extend(
+                written
+                    .into_iter()
+                    .map(|(path, res)| (path, res.map(|_| ()))),
+            ),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "batch write returns results of other operations",
+                ))
+            }
+        }
+
+        Ok(results)
     }
 
     /// List given path.
@@ -808,13 +2216,49 @@ impl Operator {
     ///         EntryMode::DIR => {
     ///             println!("Handling dir like start a new list via meta.path()")
     ///         }
-    ///         EntryMode::Unknown => continue,
+    ///         EntryMode::SYMLINK | EntryMode::Unknown => continue,
     ///     }
     /// }
     /// # Ok(())
     /// # }
     /// ```
     pub async fn list(&self, path: &str) -> Result<Lister> {
+        self.list_with(path, OpList::new()).await
+    }
+
+    /// List current dir path with extra options.
+    ///
+    /// Entries returned by lister are guaranteed to carry metadata listed
+    /// in [`OpList::with_metakey`]. Metakeys included in
+    /// [`OperatorInfo::list_metakeys`] come from the list response directly,
+    /// others will be fetched by `stat` per entry.
+    ///
+    /// Entries can also be filtered by name via [`OpList::with_pattern`], and returned
+    /// in lexicographic order via [`OpList::with_sorted`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use futures::io;
+    /// # use opendal::Operator;
+    /// # use futures::TryStreamExt;
+    /// use opendal::ops::OpList;
+    /// use opendal::Metakey;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut ds = op
+    ///     .list_with("path/to/dir/", OpList::new().with_metakey(Metakey::ContentLength))
+    ///     .await?;
+    /// while let Some(mut de) = ds.try_next().await? {
+    ///     // No extra requests will be sent here.
+    ///     let meta = op.metadata(&de, Metakey::ContentLength).await?;
+    ///     println!("{} has {} bytes", de.path(), meta.content_length());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_with(&self, path: &str, args: OpList) -> Result<Lister> {
         let path = normalize_path(path);
 
         if !validate_path(&path, EntryMode::DIR) {
@@ -827,9 +2271,30 @@ impl Operator {
             .with_context("path", &path));
         }
 
-        let (_, pager) = self.inner().list(&path, OpList::new()).await?;
+        if args.start_after().is_some() && !self.info().can_list_with_start_after() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "list with start after is not supported by this service",
+            )
+            .with_operation("Operator::list")
+            .with_context("service", self.info().scheme().into_static())
+            .with_context("path", &path));
+        }
+
+        let metakey = args.metakey();
+        let pattern = args.pattern().map(|v| ListPattern::new(&path, v));
+        let sorted = args.sorted();
+        let (_, mut pager) = self.inner().list(&path, args).await?;
+        if sorted {
+            let ordered_pages = self
+                .inner()
+                .info()
+                .hints()
+                .contains(AccessorHint::ListOrderedPages);
+            pager = Box::new(oio::to_sorted_pager(pager).with_ordered_pages(ordered_pages));
+        }
 
-        Ok(Lister::new(pager))
+        Ok(Lister::new(self.inner().clone(), pager, metakey).with_pattern(pattern))
     }
 
     /// List dir in flat way.
@@ -860,13 +2325,47 @@ impl Operator {
     ///         EntryMode::DIR => {
     ///             println!("Handling dir like start a new list via meta.path()")
     ///         }
-    ///         EntryMode::Unknown => continue,
+    ///         EntryMode::SYMLINK | EntryMode::Unknown => continue,
     ///     }
     /// }
     /// # Ok(())
     /// # }
     /// ```
     pub async fn scan(&self, path: &str) -> Result<Lister> {
+        self.scan_with(path, OpScan::new()).await
+    }
+
+    /// List dir in flat way with extra options.
+    ///
+    /// Entries returned by lister are guaranteed to carry metadata listed
+    /// in [`OpScan::with_metakey`]. See [`Operator::list_with`] for details.
+    ///
+    /// Entries can also be filtered via [`OpScan::with_pattern`]. Leading
+    /// dirs of the pattern without wildcards will be scanned directly, for
+    /// example `logs/2023/*.csv` will only scan `logs/2023/`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use futures::io;
+    /// # use opendal::Operator;
+    /// # use futures::TryStreamExt;
+    /// use opendal::ops::OpScan;
+    /// use opendal::Metakey;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut ds = op
+    ///     .scan_with("path/to/dir/", OpScan::new().with_metakey(Metakey::ContentLength))
+    ///     .await?;
+    /// while let Some(mut de) = ds.try_next().await? {
+    ///     let meta = op.metadata(&de, Metakey::ContentLength).await?;
+    ///     println!("{} has {} bytes", de.path(), meta.content_length());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn scan_with(&self, path: &str, args: OpScan) -> Result<Lister> {
         let path = normalize_path(path);
 
         if !validate_path(&path, EntryMode::DIR) {
@@ -879,14 +2378,50 @@ impl Operator {
             .with_context("path", &path));
         }
 
-        let (_, pager) = self.inner().scan(&path, OpScan::new()).await?;
+        let metakey = args.metakey();
+        let files_only = args.files_only();
+        let pattern = args
+            .pattern()
+            .map(|v| ListPattern::new(&path, v).push_down());
+        let scan_path = pattern.as_ref().map_or(path.as_str(), |v| v.base());
+
+        let pager = match self.inner().scan(scan_path, args).await {
+            Ok((_, pager)) => pager,
+            // The pushed down dir doesn't exist, so nothing could match.
+            Err(err) if err.kind() == ErrorKind::NotFound && scan_path != path => {
+                Box::new(()) as oio::Pager
+            }
+            Err(err) => return Err(err),
+        };
 
-        Ok(Lister::new(pager))
+        Ok(Lister::new(self.inner().clone(), pager, metakey)
+            .with_pattern(pattern)
+            .with_files_only(files_only))
     }
 }
 
 /// Operator presign API.
+///
+/// Only services with [`AccessorCapability::Presign`] support presign,
+/// check it via [`OperatorInfo::can_presign`]. Others will return
+/// [`ErrorKind::Unsupported`].
 impl Operator {
+    /// Make sure current service supports presign.
+    fn check_presign(&self, op: &'static str, path: &str) -> Result<()> {
+        let info = self.info();
+        if !info.can_presign() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "presign is not supported by this service",
+            )
+            .with_operation(op)
+            .with_context("service", info.scheme().into_static())
+            .with_context("path", path));
+        }
+
+        Ok(())
+    }
+
     /// Presign an operation for stat(head).
     ///
     /// # Example
@@ -910,6 +2445,7 @@ impl Operator {
     /// ```
     pub fn presign_stat(&self, path: &str, expire: Duration) -> Result<PresignedRequest> {
         let path = normalize_path(path);
+        self.check_presign("Operator::presign_stat", &path)?;
 
         let op = OpPresign::new(OpStat::new(), expire);
 
@@ -945,6 +2481,7 @@ impl Operator {
     /// ```
     pub fn presign_read(&self, path: &str, expire: Duration) -> Result<PresignedRequest> {
         let path = normalize_path(path);
+        self.check_presign("Operator::presign_read", &path)?;
 
         let op = OpPresign::new(OpRead::new(), expire);
 
@@ -1014,6 +2551,7 @@ impl Operator {
         expire: Duration,
     ) -> Result<PresignedRequest> {
         let path = normalize_path(path);
+        self.check_presign("Operator::presign_write_with", &path)?;
 
         let op = OpPresign::new(op, expire);
 
@@ -1021,3 +2559,782 @@ impl Operator {
         Ok(rp.into_presigned_request())
     }
 }
+
+/// Build an error that lists all paths failed to remove.
+///
+/// The first error will be used as the source.
+fn aggregate_remove_errors(op: &'static str, failed: Vec<(String, Error)>) -> Result<()> {
+    if failed.is_empty() {
+        return Ok(());
+    }
+
+    let paths = failed
+        .iter()
+        .map(|(path, _)| path.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let (_, source) = failed.into_iter().next().expect("failed must not be empty");
+
+    Err(Error::new(
+        // Use source's kind so that callers can still handle errors
+        // like `PermissionDenied` directly.
+        source.kind(),
+        "some paths failed to remove",
+    )
+    .with_operation(op)
+    .with_context("failed", paths)
+    .set_source(source))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::layers::MockLayer;
+    use crate::services;
+
+    #[test]
+    fn test_presign_unsupported() {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .finish();
+        assert!(!op.info().can_presign());
+
+        let expire = Duration::hours(1);
+        let err = op.presign_read("test", expire).expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        let err = op.presign_write("test", expire).expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        let err = op.presign_stat("test", expire).expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[tokio::test]
+    async fn test_stat_many() {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .finish()
+            .with_limit(2);
+        op.write("a", "Hello").await.expect("must write");
+        op.write("b", "Hello, World!").await.expect("must write");
+
+        let paths = ["a", "missing", "b", "dir/missing"].map(|v| v.to_string());
+        let results: HashMap<String, Result<Metadata>> = op.stat_many(paths).collect().await;
+        assert_eq!(results.len(), 4);
+        assert_eq!(results["a"].as_ref().unwrap().content_length(), 5);
+        assert_eq!(results["b"].as_ref().unwrap().content_length(), 13);
+        for path in ["missing", "dir/missing"] {
+            let err = results[path].as_ref().expect_err("must fail");
+            assert_eq!(err.kind(), ErrorKind::NotFound, "{path}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stat_with_recursive_size() {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .finish();
+        op.create_dir("dir/b/").await.expect("must create");
+        op.write("dir/a", vec![0; 5]).await.expect("must write");
+        op.write("dir/b/c", vec![0; 13]).await.expect("must write");
+        op.write("dir/b/d/e", vec![0; 100])
+            .await
+            .expect("must write");
+        op.write("other", vec![0; 1000]).await.expect("must write");
+
+        let meta = op.stat("dir/").await.expect("must stat");
+        assert_eq!(meta.object_count(), None);
+
+        let args = OpStat::new().with_recursive_size(true);
+        let meta = op.stat_with("dir/", args.clone()).await.expect("must stat");
+        assert_eq!(meta.mode(), EntryMode::DIR);
+        assert_eq!(meta.content_length(), 118);
+        assert_eq!(meta.object_count(), Some(3));
+
+        let meta = op
+            .stat_with("dir/b/", args.clone())
+            .await
+            .expect("must stat");
+        assert_eq!(meta.content_length(), 113);
+        assert_eq!(meta.object_count(), Some(2));
+
+        // Files are not affected.
+        let meta = op
+            .stat_with("dir/a", args.clone())
+            .await
+            .expect("must stat");
+        assert_eq!(meta.content_length(), 5);
+        assert_eq!(meta.object_count(), None);
+
+        let meta = op
+            .stat_with("dir/", args.clone().with_recursive_size_limit(3))
+            .await
+            .expect("must stat");
+        assert_eq!(meta.object_count(), Some(3));
+        let err = op
+            .stat_with("dir/", args.with_recursive_size_limit(2))
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+
+    #[tokio::test]
+    async fn test_append_unsupported() {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .finish();
+        assert!(!op.info().can_write_can_append());
+
+        let err = op
+            .append("test", "Hello, World!")
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert!(!op.exists("test").await.expect("must check"));
+    }
+
+    #[tokio::test]
+    async fn test_if_unmodified_since_unsupported() {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .finish();
+        assert!(!op.info().can_write_with_if_unmodified_since());
+        assert!(!op.info().can_delete_with_if_unmodified_since());
+        op.write("test", "Hello, World!").await.expect("must write");
+
+        let now = OffsetDateTime::now_utc();
+        let err = op
+            .write_with(
+                "test",
+                OpWrite::new().with_if_unmodified_since(now),
+                "Hello, World!",
+            )
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        let err = op
+            .writer_with("test", OpWrite::new().with_if_unmodified_since(now))
+            .await
+            .err()
+            .expect("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        let err = op
+            .blocking()
+            .write_with(
+                "test",
+                OpWrite::new().with_if_unmodified_since(now),
+                "Hello, World!",
+            )
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        let err = op
+            .delete_with("test", OpDelete::new().with_if_unmodified_since(now))
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert!(op.exists("test").await.expect("must check"));
+    }
+
+    #[tokio::test]
+    async fn test_write_with_checksum_unsupported() {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .finish();
+        assert!(!op.info().can_write_with_content_md5());
+
+        let args = OpWrite::new().with_checksum(ChecksumAlgorithm::Md5);
+        let err = op
+            .write_with("test", args, "Hello, World!")
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert!(!op.is_exist("test").await.expect("must check"));
+
+        let args = OpWrite::new().with_content_md5("ZajifYh5KDgxtmS9i38K1A==");
+        let err = op
+            .write_with("test", args, "Hello, World!")
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    /// Deny stat on paths that contain `denied`, and return `NotFound` while
+    /// deleting paths that contain `missing`.
+    fn mock_denied_layer() -> MockLayer {
+        MockLayer::default().with_error(|op, path| match op {
+            Operation::Stat | Operation::BlockingStat if path.contains("denied") => {
+                Some(Error::new(ErrorKind::PermissionDenied, "mock denied"))
+            }
+            Operation::Delete | Operation::BlockingDelete if path.contains("missing") => {
+                Some(Error::new(ErrorKind::NotFound, "mock missing"))
+            }
+            _ => None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_exists() {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .layer(mock_denied_layer())
+            .finish();
+        op.write("test", "Hello, World!").await.expect("must write");
+
+        assert!(op.exists("test").await.expect("must check"));
+        assert!(!op.exists("not_exist").await.expect("must check"));
+        let err = op.exists("denied").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        let op = op.blocking();
+        assert!(op.exists("test").expect("must check"));
+        assert!(!op.exists("not_exist").expect("must check"));
+        let err = op.exists("denied").expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+    }
+
+    #[tokio::test]
+    async fn test_last_modified() {
+        let last_modified = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .layer(
+                MockLayer::default().with_metadata(move |op, path, mut meta| {
+                    if op == Operation::Stat && !path.contains("unknown") {
+                        meta.set_last_modified(last_modified);
+                    }
+                    meta
+                }),
+            )
+            .finish();
+        op.write("test", "Hello, World!").await.expect("must write");
+        op.write("unknown", "Hello, World!")
+            .await
+            .expect("must write");
+
+        assert_eq!(
+            op.last_modified("test").await.expect("must stat"),
+            Some(last_modified)
+        );
+        assert_eq!(op.last_modified("unknown").await.expect("must stat"), None);
+        let err = op.last_modified("not_exist").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        let fresher = |path: &'static str, time: OffsetDateTime| {
+            let op = op.clone();
+            async move { op.is_fresher_than(path, time).await.expect("must stat") }
+        };
+        assert_eq!(
+            fresher("test", last_modified - Duration::seconds(1)).await,
+            Some(true)
+        );
+        assert_eq!(fresher("test", last_modified).await, Some(false));
+        assert_eq!(
+            fresher("test", last_modified + Duration::seconds(1)).await,
+            Some(false)
+        );
+        assert_eq!(fresher("unknown", last_modified).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_check() {
+        let new_op = |list: Option<ErrorKind>, stat: Option<ErrorKind>| {
+            Operator::new(services::Memory::default())
+                .expect("must init")
+                .layer(MockLayer::default().with_error(move |op, _| match op {
+                    Operation::List => {
+                        list.map(|kind| Error::new(kind, "mock list error").set_temporary())
+                    }
+                    Operation::Stat => stat.map(|kind| Error::new(kind, "mock stat error")),
+                    _ => None,
+                }))
+                .finish()
+        };
+
+        new_op(None, None).check().await.expect("must succeed");
+
+        // Listing is denied on write-only destinations.
+        new_op(Some(ErrorKind::PermissionDenied), None)
+            .check()
+            .await
+            .expect("must succeed");
+
+        // Bad credentials.
+        let err = new_op(
+            Some(ErrorKind::PermissionDenied),
+            Some(ErrorKind::PermissionDenied),
+        )
+        .check()
+        .await
+        .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        // Unreachable endpoints.
+        let err = new_op(Some(ErrorKind::Unexpected), None)
+            .check()
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.is_temporary());
+        assert!(err.to_string().contains("mock list error"));
+        assert!(err.to_string().contains("Operator::check"));
+    }
+
+    #[tokio::test]
+    async fn test_list_with_sorted() {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .finish();
+        for name in ["dir/b", "dir/a-b", "dir/c/d", "dir/a/e", "dir/a"] {
+            op.write(name, "Hello, World!").await.expect("must write");
+        }
+        let expected = vec!["dir/a", "dir/a-b", "dir/a/", "dir/b", "dir/c/"];
+
+        let paths = op
+            .list_with("dir/", OpList::new().with_sorted(true))
+            .await
+            .expect("must list")
+            .map_ok(|v| v.path().to_string())
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("must collect");
+        assert_eq!(paths, expected);
+
+        let paths = op
+            .blocking()
+            .list_with("dir/", OpList::new().with_sorted(true))
+            .expect("must list")
+            .map(|v| v.map(|v| v.path().to_string()))
+            .collect::<Result<Vec<_>>>()
+            .expect("must collect");
+        assert_eq!(paths, expected);
+    }
+
+    #[tokio::test]
+    async fn test_encryption_unsupported() {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .finish();
+        assert!(!op.info().can_write_with_encryption());
+
+        let err = op
+            .write_with(
+                "test",
+                OpWrite::new().with_encryption(Encryption::S3),
+                "Hello, World!",
+            )
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        let err = op
+            .read_with(
+                "test",
+                OpRead::new().with_encryption(Encryption::customer_key(b"key")),
+            )
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[tokio::test]
+    async fn test_custom_header_unsupported() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        assert!(!op.info().can_read_with_header());
+        assert!(!op.info().can_write_with_header());
+
+        let err = op
+            .write_with(
+                "test",
+                OpWrite::new().with_header("x-tenant-id", "a"),
+                "Hello",
+            )
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        let err = op
+            .read_with("test", OpRead::new().with_header("x-tenant-id", "a"))
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[tokio::test]
+    async fn test_write_with_concurrent_unsupported() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        assert!(!op.info().can_write_with_concurrent());
+
+        let args = OpWrite::new().with_concurrent(4);
+        let err = op
+            .write_with("test", args.clone(), "Hello")
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        let err = match op.writer_with("test", args).await {
+            Ok(_) => panic!("must fail"),
+            Err(err) => err,
+        };
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        let err = op
+            .write_with("test", OpWrite::new().with_concurrent(0), "Hello")
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        // Sequential write is always supported.
+        op.write_with("test", OpWrite::new().with_concurrent(1), "Hello")
+            .await
+            .expect("must write");
+    }
+
+    #[tokio::test]
+    async fn test_read_with_version_unsupported() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        assert!(!op.info().can_read_with_version());
+
+        let err = op
+            .read_with("test", OpRead::new().with_version("v1"))
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        let err = op
+            .stat_with("test", OpStat::new().with_version("v1"))
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[tokio::test]
+    async fn test_create_dir_unsupported() {
+        let mut builder = services::Http::default();
+        builder.endpoint("http://127.0.0.1");
+        let op = Operator::new(builder).expect("must init").finish();
+        assert!(!op.info().can_create_dir());
+
+        let err = op.create_dir("a/b/").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[tokio::test]
+    async fn test_delete_is_idempotent() {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .layer(mock_denied_layer())
+            .finish();
+        op.write("test", "Hello, World!").await.expect("must write");
+
+        op.delete("test")
+            .await
+            .expect("delete present key must succeed");
+        assert!(!op.exists("test").await.expect("must check"));
+        op.delete("test")
+            .await
+            .expect("delete absent key must succeed");
+        op.delete("missing")
+            .await
+            .expect("NotFound must be ignored");
+
+        let err = op
+            .delete_with("test", OpDelete::new().with_strict(true))
+            .await
+            .expect_err("strict delete must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        op.write("missing", "Hello, World!")
+            .await
+            .expect("must write");
+        let err = op
+            .delete_with("missing", OpDelete::new().with_strict(true))
+            .await
+            .expect_err("strict delete must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        // Denied stat should not block strict delete.
+        op.write("denied", "Hello, World!")
+            .await
+            .expect("must write");
+        op.delete_with("denied", OpDelete::new().with_strict(true))
+            .await
+            .expect("strict delete must succeed");
+
+        let op = op.blocking();
+        op.write("test", "Hello, World!").expect("must write");
+        op.delete("test").expect("delete present key must succeed");
+        op.delete("test").expect("delete absent key must succeed");
+        op.delete("missing").expect("NotFound must be ignored");
+        op.write("denied", "Hello, World!").expect("must write");
+        op.delete_with("denied", OpDelete::new().with_strict(true))
+            .expect("strict delete must succeed");
+        let err = op
+            .delete_with("test", OpDelete::new().with_strict(true))
+            .expect_err("strict delete must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+
+    /// Hide content length from stat and replace the one returned by read,
+    /// so that we can test services that don't return `Content-Length` or
+    /// return a wrong one.
+    fn mock_length_layer(length: Option<u64>) -> MockLayer {
+        MockLayer::default().with_metadata(move |op, _, meta| {
+            let mut new = Metadata::new(meta.mode());
+            if let (Operation::Read, Some(length)) = (op, length) {
+                new.set_content_length(length);
+            }
+            new
+        })
+    }
+
+    #[tokio::test]
+    async fn test_read_with_max_size() {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .finish();
+        op.write("test", "Hello, World!").await.expect("must write");
+
+        let bs = op
+            .read_with("test", OpRead::new().with_max_size(13))
+            .await
+            .expect("must read");
+        assert_eq!(bs, b"Hello, World!");
+
+        // Content-Length is known, so read will be rejected up front.
+        let err = op
+            .read_with("test", OpRead::new().with_max_size(12))
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.to_string().contains("object exceeds max size"));
+
+        let err = op
+            .read_with("test", OpRead::new().with_max_size(0))
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
+    #[tokio::test]
+    async fn test_reader_with_concurrent() {
+        use futures::AsyncReadExt;
+
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .finish();
+        let content: Vec<u8> = (0..10_000).map(|v| (v % 251) as u8).collect();
+        op.write("test", content.clone()).await.expect("must write");
+
+        let cases = vec![
+            ("full", BytesRange::from(..), &content[..]),
+            ("offset", BytesRange::from(100..), &content[100..]),
+            ("range", BytesRange::from(100..5100), &content[100..5100]),
+            ("tail", BytesRange::from(..100), &content[9900..]),
+        ];
+        for (name, range, expected) in cases {
+            let args = OpRead::new()
+                .with_range(range)
+                .with_concurrent(4)
+                .with_chunk(1000);
+            let mut r = op.reader_with("test", args).await.expect("must create");
+
+            let mut bs = Vec::new();
+            r.read_to_end(&mut bs).await.expect("must read");
+            assert_eq!(bs, expected, "{name}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_with_max_size_unknown_length() {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .layer(mock_length_layer(None))
+            .finish();
+        let content = vec![1; 1024 * 1024];
+        op.write("test", content.clone()).await.expect("must write");
+
+        let bs = op
+            .read_with("test", OpRead::new().with_max_size(1024 * 1024))
+            .await
+            .expect("must read");
+        assert_eq!(bs, content);
+
+        // Content-Length is unknown, so read will be aborted mid-stream.
+        let err = op
+            .read_with("test", OpRead::new().with_max_size(512 * 1024))
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.to_string().contains("object exceeds max size"));
+    }
+
+    #[tokio::test]
+    async fn test_read_with_length_mismatch() {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .layer(mock_length_layer(Some(20)))
+            .finish();
+        op.write("test", "Hello, World!").await.expect("must write");
+
+        let err = op.read("test").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.to_string().contains("read content length is not match"));
+
+        let err = op
+            .read_with("test", OpRead::new().with_max_size(1024))
+            .await
+            .expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.to_string().contains("read content length is not match"));
+    }
+
+    /// Add batch delete and write support for memory so that we can test
+    /// both paths, keys that contain `denied` will fail in batch.
+    fn mock_batch_layer() -> MockLayer {
+        MockLayer::default().with_batch().with_error(|op, path| {
+            if op == Operation::Batch && path.contains("denied") {
+                Some(Error::new(ErrorKind::PermissionDenied, "mock denied"))
+            } else {
+                None
+            }
+        })
+    }
+
+    async fn write_files(op: &Operator, paths: &[&str]) {
+        for path in paths {
+            op.write(path, "test_remove_all").await.expect("must write");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_remove_all_without_batch() {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .finish();
+        assert!(!op.info().can_batch());
+
+        let paths = ["dir/a", "dir/b", "dir/sub/c", "other"];
+        write_files(&op, &paths).await;
+
+        op.remove_all("dir/").await.expect("must remove");
+
+        for path in &paths[..3] {
+            assert!(!op.is_exist(path).await.unwrap(), "{path} must be removed");
+        }
+        assert!(op.is_exist("other").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_remove_all_with_batch() {
+        let layer = mock_batch_layer();
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .layer(layer.clone())
+            .finish()
+            .with_limit(2);
+        assert!(op.info().can_batch());
+
+        let paths = ["dir/a", "dir/b", "dir/c", "dir/sub/d", "dir/sub/e"];
+        write_files(&op, &paths).await;
+
+        op.remove_all("dir/").await.expect("must remove");
+
+        for path in paths {
+            assert!(!op.is_exist(path).await.unwrap(), "{path} must be removed");
+        }
+        // 5 files with limit 2 should be removed in 3 batches.
+        assert_eq!(layer.batch_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_remove_all_with_batch_partial_failure() {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .layer(mock_batch_layer())
+            .finish();
+
+        let paths = ["dir/a", "dir/denied_a", "dir/b", "dir/denied_b"];
+        write_files(&op, &paths).await;
+
+        let err = op.remove_all("dir/").await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let msg = err.to_string();
+        assert!(msg.contains("dir/denied_a"), "{msg}");
+        assert!(msg.contains("dir/denied_b"), "{msg}");
+        assert!(!msg.contains("dir/a,"), "{msg}");
+
+        assert!(!op.is_exist("dir/a").await.unwrap());
+        assert!(!op.is_exist("dir/b").await.unwrap());
+        assert!(op.is_exist("dir/denied_a").await.unwrap());
+        assert!(op.is_exist("dir/denied_b").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_write_many_without_batch() {
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .finish()
+            .with_limit(2);
+        assert!(!op.info().can_batch_write());
+
+        let input: Vec<_> = (0..5)
+            .map(|i| (format!("dir/{i}"), Bytes::from(format!("content {i}"))))
+            .chain([("/dir/bad/".to_string(), Bytes::from("bad"))])
+            .collect();
+
+        let mut results = op
+            .write_many(stream::iter(input))
+            .await
+            .expect("must succeed");
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(results.len(), 6);
+
+        // One failing key doesn't abort the rest.
+        for (i, (path, res)) in results[..5].iter().enumerate() {
+            assert_eq!(path, &format!("dir/{i}"));
+            assert!(res.is_ok(), "{path} must succeed");
+            let bs = op.read(path).await.expect("must exist");
+            assert_eq!(bs, format!("content {i}").as_bytes());
+        }
+        let (path, res) = &results[5];
+        assert_eq!(path, "dir/bad/");
+        assert_eq!(
+            res.as_ref().map_err(|err| err.kind()),
+            Err(ErrorKind::IsADirectory)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_many_with_batch() {
+        let layer = mock_batch_layer();
+        let op = Operator::new(services::Memory::default())
+            .expect("must init")
+            .layer(layer.clone())
+            .finish()
+            .with_limit(2);
+        assert!(op.info().can_batch_write());
+
+        let input = ["a", "denied_b", "c", "d/"]
+            .into_iter()
+            .map(|path| (path.to_string(), Bytes::from(path)));
+
+        let results = op
+            .write_many(stream::iter(input))
+            .await
+            .expect("must succeed");
+        let results: HashMap<_, _> = results
+            .into_iter()
+            .map(|(path, res)| (path, res.map_err(|err| err.kind())))
+            .collect();
+
+        assert_eq!(results.len(), 4);
+        assert_eq!(results["a"], Ok(()));
+        assert_eq!(results["c"], Ok(()));
+        assert_eq!(results["denied_b"], Err(ErrorKind::PermissionDenied));
+        assert_eq!(results["d/"], Err(ErrorKind::IsADirectory));
+        // 4 items with limit 2 should be written in 2 batches.
+        assert_eq!(layer.batch_count(), 2);
+
+        assert_eq!(op.read("a").await.unwrap(), b"a");
+        assert_eq!(op.read("c").await.unwrap(), b"c");
+        assert!(!op.is_exist("denied_b").await.unwrap());
+    }
+}
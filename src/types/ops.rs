@@ -19,7 +19,13 @@
 //!
 //! By using ops, users can add more context for operation.
 
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use bytes::Bytes;
+use flagset::FlagSet;
 use time::Duration;
+use time::OffsetDateTime;
 
 use crate::raw::*;
 use crate::*;
@@ -44,16 +50,89 @@ impl OpCreate {
     }
 }
 
+/// Args for `copy` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpCopy {
+    if_none_match: Option<String>,
+}
+
+impl OpCopy {
+    /// Create a new `OpCopy`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the `If-None-Match` condition of destination.
+    ///
+    /// Use `*` to make sure copy only succeeds if the destination doesn't
+    /// exist. `ErrorKind::ConditionNotMatch` will be returned otherwise.
+    pub fn with_if_none_match(mut self, etag: &str) -> Self {
+        self.if_none_match = Some(etag.to_string());
+        self
+    }
+
+    /// Get the `If-None-Match` condition of destination.
+    pub fn if_none_match(&self) -> Option<&str> {
+        self.if_none_match.as_deref()
+    }
+}
+
+/// Args for `rename` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpRename {}
+
+impl OpRename {
+    /// Create a new `OpRename`.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
 /// Args for `delete` operation.
 ///
 /// The path must be normalized.
 #[derive(Debug, Clone, Default)]
-pub struct OpDelete {}
+pub struct OpDelete {
+    strict: bool,
+    if_unmodified_since: Option<OffsetDateTime>,
+}
 
 impl OpDelete {
     /// Create a new `OpDelete`.
     pub fn new() -> Self {
-        Self {}
+        Self::default()
+    }
+
+    /// Set the strict mode of delete.
+    ///
+    /// Deleting a not existing path will succeed by default. In strict
+    /// mode, a `NotFound` error will be returned instead.
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Get the strict mode of delete.
+    pub fn strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Set the `If-Unmodified-Since` condition of delete.
+    ///
+    /// `ErrorKind::ConditionNotMatch` will be returned if the path has
+    /// been modified since given time.
+    pub fn with_if_unmodified_since(mut self, t: OffsetDateTime) -> Self {
+        self.if_unmodified_since = Some(t);
+        self
+    }
+
+    /// Get the `If-Unmodified-Since` condition of delete.
+    pub fn if_unmodified_since(&self) -> Option<OffsetDateTime> {
+        self.if_unmodified_since
     }
 }
 
@@ -63,6 +142,16 @@ pub struct OpList {
     /// The limit passed to underlying service to specify the max results
     /// that could return.
     limit: Option<usize>,
+    /// The metakey that entries returned by lister must contain.
+    metakey: FlagSet<Metakey>,
+    /// The glob pattern that entries returned by lister must match.
+    pattern: Option<String>,
+    /// The key that entries returned by lister must be greater than.
+    start_after: Option<String>,
+    /// Whether entries returned by lister must be sorted.
+    sorted: bool,
+    /// Whether symlinks returned by lister must be followed.
+    follow_symlink: bool,
 }
 
 impl OpList {
@@ -81,6 +170,94 @@ impl OpList {
     pub fn limit(&self) -> Option<usize> {
         self.limit
     }
+
+    /// Change the metakey that entries returned by lister must contain.
+    ///
+    /// Entries whose metadata from the list response doesn't cover the
+    /// metakey will be stat-ed by lister before being returned.
+    pub fn with_metakey(mut self, metakey: impl Into<FlagSet<Metakey>>) -> Self {
+        self.metakey = metakey.into();
+        self
+    }
+
+    /// Get the metakey of list operation.
+    pub fn metakey(&self) -> FlagSet<Metakey> {
+        self.metakey | Metakey::Mode
+    }
+
+    /// Change the glob pattern that entries returned by lister must match.
+    ///
+    /// The pattern will be matched against the name of entries, for
+    /// example `*.parquet`. Supported syntax:
+    ///
+    /// - `?` matches any single character except `/`.
+    /// - `*` matches any sequence of characters except `/`.
+    /// - `**` matches any number of directories, only useful for `scan`.
+    pub fn with_pattern(mut self, pattern: &str) -> Self {
+        self.pattern = Some(pattern.to_string());
+        self
+    }
+
+    /// Get the glob pattern of list operation.
+    pub fn pattern(&self) -> Option<&str> {
+        self.pattern.as_deref()
+    }
+
+    /// Change the key that list operation starts after.
+    ///
+    /// Only entries whose path are lexicographically greater than `key`
+    /// will be returned. Combined with [`Lister::continuation_token`], we
+    /// can resume a listing from where the previous one left off.
+    ///
+    /// Require [`OperatorInfo::can_list_with_start_after`].
+    pub fn with_start_after(mut self, key: &str) -> Self {
+        self.start_after = Some(key.to_string());
+        self
+    }
+
+    /// Get the key that list operation starts after.
+    pub fn start_after(&self) -> Option<&str> {
+        self.start_after.as_deref()
+    }
+
+    /// Change whether entries returned by lister must be sorted in
+    /// lexicographic order of their paths.
+    ///
+    /// # Notes
+    ///
+    /// For services that return pages in order (like s3), only entries
+    /// within every page will be sorted, which is cheap.
+    ///
+    /// For other services, the whole listing will be buffered in memory
+    /// before returning the first entry. Please avoid using it on large
+    /// dirs.
+    pub fn with_sorted(mut self, sorted: bool) -> Self {
+        self.sorted = sorted;
+        self
+    }
+
+    /// Get whether entries returned by lister must be sorted.
+    pub fn sorted(&self) -> bool {
+        self.sorted
+    }
+
+    /// Change whether symlinks returned by lister must be followed.
+    ///
+    /// By default, symlinks are returned as `EntryMode::SYMLINK`. If
+    /// `follow` is `true`, they will be returned with the mode of their
+    /// targets instead. Dangling symlinks are always returned as
+    /// `EntryMode::SYMLINK`.
+    ///
+    /// Only works for services that support symlinks like fs.
+    pub fn with_follow_symlink(mut self, follow: bool) -> Self {
+        self.follow_symlink = follow;
+        self
+    }
+
+    /// Get whether symlinks returned by lister must be followed.
+    pub fn follow_symlink(&self) -> bool {
+        self.follow_symlink
+    }
 }
 
 /// Args for `scan` operation.
@@ -89,6 +266,12 @@ pub struct OpScan {
     /// The limit passed to underlying service to specify the max results
     /// that could return.
     limit: Option<usize>,
+    /// The metakey that entries returned by lister must contain.
+    metakey: FlagSet<Metakey>,
+    /// The glob pattern that entries returned by lister must match.
+    pattern: Option<String>,
+    /// Only return file entries.
+    files_only: bool,
 }
 
 impl OpScan {
@@ -107,6 +290,50 @@ impl OpScan {
     pub fn limit(&self) -> Option<usize> {
         self.limit
     }
+
+    /// Change the metakey that entries returned by lister must contain.
+    ///
+    /// Entries whose metadata from the list response doesn't cover the
+    /// metakey will be stat-ed by lister before being returned.
+    pub fn with_metakey(mut self, metakey: impl Into<FlagSet<Metakey>>) -> Self {
+        self.metakey = metakey.into();
+        self
+    }
+
+    /// Get the metakey of list operation.
+    pub fn metakey(&self) -> FlagSet<Metakey> {
+        self.metakey | Metakey::Mode
+    }
+
+    /// Change the glob pattern that entries returned by lister must match.
+    ///
+    /// The pattern will be matched against the path of entries relative
+    /// to the scanned dir, for example `**/data/*.csv`. See
+    /// [`OpList::with_pattern`] for the supported syntax.
+    pub fn with_pattern(mut self, pattern: &str) -> Self {
+        self.pattern = Some(pattern.to_string());
+        self
+    }
+
+    /// Get the glob pattern of scan operation.
+    pub fn pattern(&self) -> Option<&str> {
+        self.pattern.as_deref()
+    }
+
+    /// Only return file entries, dirs (including the markers created by
+    /// `create_dir`) will be skipped.
+    ///
+    /// Disabled by default, so that [`Operator::scan`][crate::Operator::scan]
+    /// returns dirs as well.
+    pub fn with_files_only(mut self, files_only: bool) -> Self {
+        self.files_only = files_only;
+        self
+    }
+
+    /// Get the files only of scan operation.
+    pub fn files_only(&self) -> bool {
+        self.files_only
+    }
 }
 
 /// Args for `presign` operation.
@@ -198,6 +425,10 @@ impl OpBatch {
 pub enum BatchOperations {
     /// Batch delete operations.
     Delete(Vec<(String, OpDelete)>),
+    /// Batch write operations.
+    ///
+    /// Only supported by services that have `AccessorCapability::BatchWrite`.
+    Write(Vec<(String, OpWrite, Bytes)>),
 }
 
 impl BatchOperations {
@@ -206,6 +437,7 @@ impl BatchOperations {
         use BatchOperations::*;
         match self {
             Delete(_) => Operation::Delete,
+            Write(_) => Operation::Write,
         }
     }
 
@@ -214,6 +446,7 @@ impl BatchOperations {
         use BatchOperations::*;
         match self {
             Delete(v) => v.len(),
+            Write(v) => v.len(),
         }
     }
 
@@ -222,6 +455,7 @@ impl BatchOperations {
         use BatchOperations::*;
         match self {
             Delete(v) => v.is_empty(),
+            Write(v) => v.is_empty(),
         }
     }
 }
@@ -230,6 +464,14 @@ impl BatchOperations {
 #[derive(Debug, Clone, Default)]
 pub struct OpRead {
     br: BytesRange,
+    max_size: Option<u64>,
+    concurrent: usize,
+    chunk: Option<usize>,
+    encryption: Option<Encryption>,
+    seek_buffer: usize,
+    headers: Vec<(String, String)>,
+    version: Option<String>,
+    decompress: bool,
 }
 
 impl OpRead {
@@ -239,8 +481,11 @@ impl OpRead {
     }
 
     /// Create a new OpRead with range.
-    pub fn with_range(mut self, range: BytesRange) -> Self {
-        self.br = range;
+    ///
+    /// Accepts [`BytesRange`] or rust native ranges like `0..100` and
+    /// `1000..`, read [`BytesRange`] for the semantic of `..<end>`.
+    pub fn with_range(mut self, range: impl Into<BytesRange>) -> Self {
+        self.br = range.into();
         self
     }
 
@@ -248,16 +493,259 @@ impl OpRead {
     pub fn range(&self) -> BytesRange {
         self.br
     }
+
+    /// Set the max size of content that could be read.
+    ///
+    /// Read will fail if the content is larger than `max_size` so that a
+    /// huge object will not be loaded into memory. `max_size` must be
+    /// greater than 0.
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Get the max size from OpRead.
+    pub fn max_size(&self) -> Option<u64> {
+        self.max_size
+    }
+
+    /// Set the number of chunks that could be fetched concurrently.
+    ///
+    /// Only works with [`Operator::reader_with`][crate::Operator::reader_with].
+    /// Reader will issue up to `concurrent` ranged reads ahead of the
+    /// consumer and return them in order.
+    pub fn with_concurrent(mut self, concurrent: usize) -> Self {
+        self.concurrent = concurrent;
+        self
+    }
+
+    /// Get the concurrent from OpRead.
+    pub fn concurrent(&self) -> usize {
+        self.concurrent
+    }
+
+    /// Set the chunk size of concurrent read.
+    ///
+    /// The memory used by prefetching is bounded by `chunk * concurrent`.
+    pub fn with_chunk(mut self, chunk: usize) -> Self {
+        self.chunk = Some(chunk);
+        self
+    }
+
+    /// Get the chunk size from OpRead.
+    pub fn chunk(&self) -> Option<usize> {
+        self.chunk
+    }
+
+    /// Set the server side encryption of OpRead.
+    ///
+    /// Only [`Encryption::Customer`] needs to be provided while reading,
+    /// the key must be the same as the one used while writing.
+    pub fn with_encryption(mut self, encryption: Encryption) -> Self {
+        self.encryption = Some(encryption);
+        self
+    }
+
+    /// Get the server side encryption from OpRead.
+    pub fn encryption(&self) -> Option<&Encryption> {
+        self.encryption.as_ref()
+    }
+
+    /// Set the size of window that keeps the latest read data.
+    ///
+    /// Only works for services that can't seek natively. Seeking back
+    /// within the window will be served from memory instead of sending a
+    /// new request. Default to `0` which disables the window.
+    pub fn with_seek_buffer(mut self, size: usize) -> Self {
+        self.seek_buffer = size;
+        self
+    }
+
+    /// Get the size of seek buffer from OpRead.
+    pub fn seek_buffer(&self) -> usize {
+        self.seek_buffer
+    }
+
+    /// Attach a custom header to the read request.
+    ///
+    /// Headers that set by OpenDAL itself like `Authorization` and
+    /// `Range` can't be overridden.
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Get the custom headers from OpRead.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Read the given version of path instead of the latest one.
+    ///
+    /// The version is the one returned by [`Metadata::version`][crate::Metadata::version].
+    /// `ErrorKind::NotFound` will be returned if the version doesn't exist.
+    pub fn with_version(mut self, version: &str) -> Self {
+        self.version = Some(version.to_string());
+        self
+    }
+
+    /// Get the version from OpRead.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Decompress the content according to its `Content-Encoding`.
+    ///
+    /// Content that is not encoded will be returned as is, and an
+    /// `Unsupported` error will be returned for unknown encodings. Range
+    /// can't be used with decompress since it applies to the encoded
+    /// content.
+    pub fn with_decompress(mut self, decompress: bool) -> Self {
+        self.decompress = decompress;
+        self
+    }
+
+    /// Get the decompress from OpRead.
+    pub fn decompress(&self) -> bool {
+        self.decompress
+    }
+
+    /// Build the args to stat the same object that this read targets.
+    pub(crate) fn to_stat(&self) -> OpStat {
+        let mut op = OpStat::new();
+        if let Some(v) = &self.version {
+            op = op.with_version(v);
+        }
+        if let Some(v) = &self.encryption {
+            op = op.with_encryption(v.clone());
+        }
+        op
+    }
 }
 
 /// Args for `stat` operation.
 #[derive(Debug, Clone, Default)]
-pub struct OpStat {}
+pub struct OpStat {
+    if_none_match: Option<String>,
+    if_modified_since: Option<OffsetDateTime>,
+    version: Option<String>,
+    follow_symlink: Option<bool>,
+    recursive_size: bool,
+    recursive_size_limit: Option<usize>,
+    encryption: Option<Encryption>,
+}
 
 impl OpStat {
     /// Create a new `OpStat`.
     pub fn new() -> Self {
-        Self {}
+        Self::default()
+    }
+
+    /// Set the `If-None-Match` condition of this stat operation.
+    ///
+    /// `ErrorKind::ConditionNotMatch` will be returned if the etag of
+    /// path is still the same.
+    pub fn with_if_none_match(mut self, etag: &str) -> Self {
+        self.if_none_match = Some(etag.to_string());
+        self
+    }
+
+    /// Get the `If-None-Match` condition of this stat operation.
+    pub fn if_none_match(&self) -> Option<&str> {
+        self.if_none_match.as_deref()
+    }
+
+    /// Set the `If-Modified-Since` condition of this stat operation.
+    ///
+    /// `ErrorKind::ConditionNotMatch` will be returned if the path has not
+    /// been modified since given time.
+    pub fn with_if_modified_since(mut self, t: OffsetDateTime) -> Self {
+        self.if_modified_since = Some(t);
+        self
+    }
+
+    /// Get the `If-Modified-Since` condition of this stat operation.
+    pub fn if_modified_since(&self) -> Option<OffsetDateTime> {
+        self.if_modified_since
+    }
+
+    /// Check if this stat operation carries any condition.
+    pub fn is_conditional(&self) -> bool {
+        self.if_none_match.is_some() || self.if_modified_since.is_some()
+    }
+
+    /// Stat the given version of path instead of the latest one.
+    ///
+    /// `ErrorKind::NotFound` will be returned if the version doesn't exist.
+    pub fn with_version(mut self, version: &str) -> Self {
+        self.version = Some(version.to_string());
+        self
+    }
+
+    /// Get the version from OpStat.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Change whether symlinks must be followed while stat.
+    ///
+    /// By default, stat returns the metadata of the target of a symlink.
+    /// If `follow` is `false`, the metadata of the symlink itself will be
+    /// returned with `EntryMode::SYMLINK`.
+    ///
+    /// Only works for services that support symlinks like fs.
+    pub fn with_follow_symlink(mut self, follow: bool) -> Self {
+        self.follow_symlink = Some(follow);
+        self
+    }
+
+    /// Get whether symlinks must be followed while stat.
+    pub fn follow_symlink(&self) -> bool {
+        self.follow_symlink.unwrap_or(true)
+    }
+
+    /// Change whether to sum the sizes of all files under dir.
+    ///
+    /// Only works for dir paths. Files will be scanned recursively, the
+    /// content length of returned metadata will be set to the total size,
+    /// and [`Metadata::object_count`][crate::Metadata::object_count] will be
+    /// set to the count of files.
+    pub fn with_recursive_size(mut self, recursive_size: bool) -> Self {
+        self.recursive_size = recursive_size;
+        self
+    }
+
+    /// Get whether to sum the sizes of all files under dir.
+    pub fn recursive_size(&self) -> bool {
+        self.recursive_size
+    }
+
+    /// Set the max count of files to visit while summing sizes.
+    ///
+    /// An error will be returned if there are more files under dir, so that
+    /// stat on large dirs will not take forever.
+    pub fn with_recursive_size_limit(mut self, limit: usize) -> Self {
+        self.recursive_size_limit = Some(limit);
+        self
+    }
+
+    /// Get the max count of files to visit while summing sizes.
+    pub fn recursive_size_limit(&self) -> Option<usize> {
+        self.recursive_size_limit
+    }
+
+    /// Set the server side encryption of OpStat.
+    ///
+    /// Only SSE-C is required to stat, and the key must be the same as the
+    /// one used while writing.
+    pub fn with_encryption(mut self, encryption: Encryption) -> Self {
+        self.encryption = Some(encryption);
+        self
+    }
+
+    /// Get the server side encryption from OpStat.
+    pub fn encryption(&self) -> Option<&Encryption> {
+        self.encryption.as_ref()
     }
 }
 
@@ -265,9 +753,20 @@ impl OpStat {
 #[derive(Debug, Clone, Default)]
 pub struct OpWrite {
     append: bool,
+    append_existing: bool,
 
     content_type: Option<String>,
     content_disposition: Option<String>,
+    if_match: Option<String>,
+    if_none_match: Option<String>,
+    if_unmodified_since: Option<OffsetDateTime>,
+    resume: Option<MultipartState>,
+    checksum: Option<ChecksumAlgorithm>,
+    content_md5: Option<String>,
+    encryption: Option<Encryption>,
+    headers: Vec<(String, String)>,
+    concurrent: Option<usize>,
+    content_length: Option<u64>,
 }
 
 impl OpWrite {
@@ -277,9 +776,20 @@ impl OpWrite {
     pub fn new() -> Self {
         Self {
             append: false,
+            append_existing: false,
 
             content_type: None,
             content_disposition: None,
+            if_match: None,
+            if_none_match: None,
+            if_unmodified_since: None,
+            resume: None,
+            checksum: None,
+            content_md5: None,
+            encryption: None,
+            headers: Vec::new(),
+            concurrent: None,
+            content_length: None,
         }
     }
 
@@ -292,6 +802,16 @@ impl OpWrite {
         self.append
     }
 
+    /// Append content to the existing object instead of replacing it.
+    pub(crate) fn with_append_existing(mut self) -> Self {
+        self.append_existing = true;
+        self
+    }
+
+    pub(crate) fn append_existing(&self) -> bool {
+        self.append_existing
+    }
+
     /// Get the content type from option
     pub fn content_type(&self) -> Option<&str> {
         self.content_type.as_deref()
@@ -313,4 +833,209 @@ impl OpWrite {
         self.content_disposition = Some(content_disposition.to_string());
         self
     }
+
+    /// Get the `If-Match` condition from option
+    pub fn if_match(&self) -> Option<&str> {
+        self.if_match.as_deref()
+    }
+
+    /// Set the `If-Match` condition of option
+    ///
+    /// The write will fail with `ErrorKind::ConditionNotMatch` if the etag
+    /// of existing path doesn't match.
+    pub fn with_if_match(mut self, etag: &str) -> Self {
+        self.if_match = Some(etag.to_string());
+        self
+    }
+
+    /// Get the `If-None-Match` condition from option
+    pub fn if_none_match(&self) -> Option<&str> {
+        self.if_none_match.as_deref()
+    }
+
+    /// Set the `If-None-Match` condition of option
+    ///
+    /// Use `*` to only create the path if it doesn't exist. The write will
+    /// fail with `ErrorKind::ConditionNotMatch` if the condition is not met.
+    pub fn with_if_none_match(mut self, etag: &str) -> Self {
+        self.if_none_match = Some(etag.to_string());
+        self
+    }
+
+    /// Get the `If-Unmodified-Since` condition from option
+    pub fn if_unmodified_since(&self) -> Option<OffsetDateTime> {
+        self.if_unmodified_since
+    }
+
+    /// Set the `If-Unmodified-Since` condition of option
+    ///
+    /// The write will fail with `ErrorKind::ConditionNotMatch` if the
+    /// existing path has been modified since given time.
+    pub fn with_if_unmodified_since(mut self, t: OffsetDateTime) -> Self {
+        self.if_unmodified_since = Some(t);
+        self
+    }
+
+    /// Get the multipart state to resume from option
+    pub fn resume(&self) -> Option<&MultipartState> {
+        self.resume.as_ref()
+    }
+
+    /// Set the multipart state to resume of option
+    ///
+    /// Only works with [`Operator::writer_with`][crate::Operator::writer_with].
+    /// The upload id will be verified and the uploaded parts will be skipped.
+    pub fn with_resume(mut self, state: MultipartState) -> Self {
+        self.resume = Some(state);
+        self
+    }
+
+    /// Get the checksum algorithm from option
+    pub fn checksum(&self) -> Option<ChecksumAlgorithm> {
+        self.checksum
+    }
+
+    /// Set the checksum algorithm of option
+    ///
+    /// The checksum of content will be computed and sent along with every
+    /// request so that service can reject corrupted uploads. For multipart
+    /// uploads, every part will carry its own checksum.
+    pub fn with_checksum(mut self, algo: ChecksumAlgorithm) -> Self {
+        self.checksum = Some(algo);
+        self
+    }
+
+    /// Get the user supplied content md5 from option
+    pub fn content_md5(&self) -> Option<&str> {
+        self.content_md5.as_deref()
+    }
+
+    /// Set the pre-computed content md5 of option
+    ///
+    /// The value should be the base64 encoded md5 digest of the whole
+    /// content, and will be sent as `Content-MD5` verbatim instead of
+    /// computing it again. Invalid digest will be rejected by
+    /// [`Operator::write_with`][crate::Operator::write_with].
+    pub fn with_content_md5(mut self, content_md5: &str) -> Self {
+        self.content_md5 = Some(content_md5.to_string());
+        self
+    }
+
+    /// Get the server side encryption from option
+    pub fn encryption(&self) -> Option<&Encryption> {
+        self.encryption.as_ref()
+    }
+
+    /// Set the server side encryption of option
+    ///
+    /// The encryption will override the one configured in services.
+    pub fn with_encryption(mut self, encryption: Encryption) -> Self {
+        self.encryption = Some(encryption);
+        self
+    }
+
+    /// Get the custom headers from option
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Attach a custom header to the write request of option
+    ///
+    /// Headers that set by OpenDAL itself like `Authorization` and
+    /// `Content-Type` can't be overridden.
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Get the number of parts that can be uploaded concurrently from option
+    pub fn concurrent(&self) -> usize {
+        self.concurrent.unwrap_or(1)
+    }
+
+    /// Set the number of parts that can be uploaded concurrently of option
+    ///
+    /// Multipart uploads will have at most `n` parts in flight, so the
+    /// memory usage is bounded by `n * part_size`. Any failed part will
+    /// abort the whole upload.
+    pub fn with_concurrent(mut self, n: usize) -> Self {
+        self.concurrent = Some(n);
+        self
+    }
+
+    /// Get the content length from option
+    pub fn content_length(&self) -> Option<u64> {
+        self.content_length
+    }
+
+    /// Set the total content length that will be written by writer.
+    ///
+    /// Content that fits in a single part will be sent in one request
+    /// instead of multipart upload. Closing the writer will fail if the
+    /// bytes written don't match the declared length: buffered content
+    /// will not be sent, and multipart upload will be aborted.
+    ///
+    /// Bytes of resumed parts via [`OpWrite::with_resume`] are counted
+    /// in the declared length.
+    pub fn with_content_length(mut self, n: u64) -> Self {
+        self.content_length = Some(n);
+        self
+    }
+}
+
+/// Checksum algorithm used by `write` operation.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ChecksumAlgorithm {
+    /// Send `Content-MD5` computed by
+    /// [`format_content_md5`][crate::raw::format_content_md5].
+    Md5,
+}
+
+/// Server side encryption used by `write` and `read` operation.
+#[derive(Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Encryption {
+    /// Encrypt with keys managed by services, a.k.a. SSE-S3.
+    S3,
+    /// Encrypt with keys managed by KMS, a.k.a. SSE-KMS.
+    ///
+    /// The default KMS key will be used if `key_id` is `None`.
+    Kms {
+        /// The id of KMS key.
+        key_id: Option<String>,
+    },
+    /// Encrypt with keys provided by customer, a.k.a. SSE-C.
+    ///
+    /// The same key must be provided while reading.
+    Customer {
+        /// The algorithm of key, like `AES256`.
+        algorithm: String,
+        /// The raw bytes of key.
+        key: Vec<u8>,
+    },
+}
+
+impl Encryption {
+    /// Create a new SSE-C encryption with `AES256` algorithm.
+    pub fn customer_key(key: &[u8]) -> Self {
+        Encryption::Customer {
+            algorithm: "AES256".to_string(),
+            key: key.to_vec(),
+        }
+    }
+}
+
+impl Debug for Encryption {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Encryption::S3 => write!(f, "S3"),
+            Encryption::Kms { key_id } => f.debug_struct("Kms").field("key_id", key_id).finish(),
+            Encryption::Customer { algorithm, .. } => f
+                .debug_struct("Customer")
+                .field("algorithm", algorithm)
+                .field("key", &"<redacted>")
+                .finish(),
+        }
+    }
 }
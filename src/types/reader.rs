@@ -17,19 +17,27 @@
 
 use std::io;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
 
+use bytes::Buf;
 use bytes::Bytes;
+use bytes::BytesMut;
 use futures::ready;
+use futures::AsyncBufRead;
 use futures::AsyncRead;
 use futures::AsyncSeek;
 use futures::Stream;
 
 use crate::ops::OpRead;
+use crate::raw::oio::ReadExt;
 use crate::raw::*;
 use crate::*;
 
+/// Default chunk size of concurrent read.
+const DEFAULT_CONCURRENT_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
 /// Reader is designed to read data from given path in an asynchronous
 /// manner.
 ///
@@ -38,6 +46,7 @@ use crate::*;
 /// Reader implements the following APIs:
 ///
 /// - `AsyncRead`
+/// - `AsyncBufRead`
 /// - `AsyncSeek`
 /// - `Stream<Item = <io::Result<Bytes>>>`
 ///
@@ -54,9 +63,22 @@ use crate::*;
 ///
 /// Besides, `Stream` **COULD** reduce an extra copy if underlying reader is
 /// stream based (like services s3, azure which based on HTTP).
+///
+/// `AsyncBufRead` (both `futures` and `tokio` version) buffers one chunk
+/// returned by the underlying stream at a time, so APIs like `lines()` can
+/// be used directly without reading beyond the requested range.
+///
+/// For formats like parquet that need to check the magic number and footer
+/// before reading, use [`Reader::peek`] and [`Reader::peek_tail`].
 pub struct Reader {
     inner: oio::Reader,
     seek_state: SeekState,
+    /// Buffered bytes that have been filled by `poll_fill_buf` or `peek`
+    /// but not consumed yet.
+    buf: Bytes,
+    /// The accessor, path and args this reader is created from, used to
+    /// send extra requests like `peek_tail`.
+    origin: Option<(FusedAccessor, String, OpRead)>,
 }
 
 impl Reader {
@@ -68,25 +90,362 @@ impl Reader {
     /// We don't want to expose those details to users so keep this function
     /// in crate only.
     pub(crate) async fn create(acc: FusedAccessor, path: &str, op: OpRead) -> Result<Self> {
-        let (_, r) = acc.read(path, op).await?;
+        // Stat first to find out how the content is encoded.
+        let encoding = if op.decompress() {
+            acc.stat(path, op.to_stat())
+                .await?
+                .into_metadata()
+                .content_encoding()
+                .map(|v| v.to_string())
+        } else {
+            None
+        };
+
+        let r = if op.concurrent() > 1 {
+            Self::create_concurrent(acc, path, op).await?
+        } else {
+            let (_, r) = acc.read(path, op.clone()).await?;
+            Reader::new(r).with_origin(acc, path, op)
+        };
+
+        match encoding {
+            // The origin is dropped since requests like `peek_tail` can't
+            // be applied to decompressed content.
+            Some(encoding) => Ok(Reader::new(Box::new(oio::into_decompress_reader(
+                r.inner, &encoding,
+            )?))),
+            None => Ok(r),
+        }
+    }
 
-        Ok(Reader {
+    /// Create a new reader that prefetches chunks concurrently.
+    ///
+    /// We need to stat first to split the range into chunks.
+    async fn create_concurrent(acc: FusedAccessor, path: &str, op: OpRead) -> Result<Self> {
+        let total = acc
+            .stat(path, op.to_stat())
+            .await?
+            .into_metadata()
+            .content_length();
+
+        let br = op.range().resolve_suffix(total);
+        let offset = br.offset().unwrap_or_default().min(total);
+        let size = br.size().map_or(total - offset, |v| v.min(total - offset));
+        let chunk = op.chunk().unwrap_or(DEFAULT_CONCURRENT_CHUNK_SIZE).max(1) as u64;
+
+        let r = oio::into_reader::by_concurrent(
+            Arc::new(acc.clone()),
+            path,
+            offset,
+            size,
+            chunk,
+            op.concurrent(),
+        )
+        .with_args(op.clone());
+
+        Ok(Reader::new(Box::new(r)).with_origin(
+            acc,
+            path,
+            op.with_range(BytesRange::new(Some(offset), Some(size))),
+        ))
+    }
+
+    /// Create a new reader from an opened [`oio::Reader`].
+    pub(crate) fn new(r: oio::Reader) -> Self {
+        Reader {
             inner: r,
             seek_state: SeekState::Init,
-        })
+            buf: Bytes::new(),
+            origin: None,
+        }
+    }
+
+    fn with_origin(mut self, acc: FusedAccessor, path: &str, op: OpRead) -> Self {
+        self.origin = Some((acc, path.to_string(), op));
+        self
+    }
+
+    /// Return the next `len` bytes without advancing the reader.
+    ///
+    /// Peeked bytes are cached and will be returned by the following
+    /// reads, so they won't be fetched twice. Returned bytes will be less
+    /// than `len` only if the reader reaches the end.
+    ///
+    /// # Notes
+    ///
+    /// The response of this reader has already been opened while creating
+    /// it, so peek at the start is served from that response instead of
+    /// sending a new request.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut r = op.reader("path/to/file.parquet").await?;
+    /// let is_parquet = r.peek(4).await? == b"PAR1";
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn peek(&mut self, len: usize) -> Result<&[u8]> {
+        if self.buf.len() < len {
+            let mut buf = BytesMut::from(&self.buf[..]);
+            while buf.len() < len {
+                match self.inner.next().await.transpose()? {
+                    Some(bs) => buf.extend_from_slice(&bs),
+                    None => break,
+                }
+            }
+            self.buf = buf.freeze();
+        }
+
+        Ok(&self.buf[..len.min(self.buf.len())])
+    }
+
+    /// Return the last `len` bytes of the range this reader is created
+    /// for, without affecting the reader.
+    ///
+    /// A new ranged request will be sent for the tail, so it's efficient
+    /// to read the footer of a large file before reading it sequentially.
+    /// Returned bytes will be less than `len` only if the range is smaller
+    /// than `len`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let r = op.reader("path/to/file.parquet").await?;
+    /// let footer = r.peek_tail(8).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn peek_tail(&self, len: usize) -> Result<Vec<u8>> {
+        let (acc, path, op) = self.origin.as_ref().ok_or_else(|| {
+            Error::new(
+                ErrorKind::Unsupported,
+                "peek tail is not supported by this reader",
+            )
+            .with_operation("Reader::peek_tail")
+        })?;
+        let len = len as u64;
+        let range = op.range();
+
+        let tail = match (range.offset(), range.size()) {
+            (Some(offset), Some(size)) => {
+                BytesRange::new(Some(offset + size.saturating_sub(len)), Some(size.min(len)))
+            }
+            (None, Some(size)) => BytesRange::new(None, Some(size.min(len))),
+            (None, None) | (Some(0), None) => BytesRange::new(None, Some(len)),
+            // The suffix could cross the offset, so we need to stat for
+            // the total size first.
+            (Some(offset), None) => {
+                let total = acc
+                    .stat(path, op.to_stat())
+                    .await?
+                    .into_metadata()
+                    .content_length();
+                let start = total.saturating_sub(len).max(offset);
+                BytesRange::new(Some(start), Some(total.saturating_sub(start)))
+            }
+        };
+
+        if tail.size() == Some(0) {
+            return Ok(Vec::new());
+        }
+
+        let (_, r) = acc.read(path, op.clone().with_range(tail)).await?;
+        Reader::new(r).read_to_end_with_capacity(len as usize).await
+    }
+
+    /// Read into given buffer until it's full or the reader is at the end.
+    ///
+    /// Returns the number of bytes read. Unlike `read`, this function will
+    /// not return a short read unless there is no more data, and the rest
+    /// of buffer after it is untouched.
+    pub async fn read_into(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let mut filled = 0;
+        while filled < buf.len() {
+            match self.read(&mut buf[filled..]).await? {
+                0 => break,
+                n => filled += n,
+            }
+        }
+
+        Ok(filled)
+    }
+
+    /// Read all remaining bytes into a new `Vec` allocated with `cap`.
+    ///
+    /// `cap` is a hint instead of a limit: the whole content will be read
+    /// even if `cap` is too small, and the buffer will grow as needed. If
+    /// `cap` matches the remaining size, the buffer will be allocated only
+    /// once.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let meta = op.stat("path/to/file").await?;
+    /// let mut r = op.reader("path/to/file").await?;
+    /// let bs = r
+    ///     .read_to_end_with_capacity(meta.content_length() as usize)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_to_end_with_capacity(&mut self, cap: usize) -> Result<Vec<u8>> {
+        let mut buf = vec![0; cap];
+        let mut filled = 0;
+
+        loop {
+            if filled == buf.len() {
+                // Probe with a small buffer before growing, so that we
+                // don't need to reallocate if the hint is exact.
+                let mut probe = [0; 32];
+                let n = self.read(&mut probe).await?;
+                if n == 0 {
+                    break;
+                }
+
+                buf.truncate(filled);
+                buf.extend_from_slice(&probe[..n]);
+                filled += n;
+                buf.resize(buf.capacity(), 0);
+                continue;
+            }
+
+            match self.read(&mut buf[filled..]).await? {
+                0 => break,
+                n => filled += n,
+            }
+        }
+
+        buf.truncate(filled);
+        Ok(buf)
+    }
+
+    /// Convert reader into a stream that yields chunks of exactly `size`
+    /// bytes, except the final one which could be smaller.
+    ///
+    /// Small chunks from underlying reader will be coalesced and large
+    /// chunks will be split. The stream ends at the end of the reader, so
+    /// it will never read beyond the requested range.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # use futures::TryStreamExt;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let r = op.reader("path/to/file").await?;
+    /// let chunks: Vec<_> = r
+    ///     .into_stream_with_chunk_size(256 * 1024)
+    ///     .try_collect()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_stream_with_chunk_size(self, size: usize) -> ChunkedStream {
+        assert!(size > 0, "chunk size must be larger than 0");
+
+        ChunkedStream {
+            inner: self,
+            size,
+            buf: BytesMut::new(),
+            done: false,
+        }
+    }
+
+    /// Convert reader into a stream of lines.
+    ///
+    /// Lines are split by `\n` and the trailing `\n` or `\r\n` will be
+    /// stripped. The last line will be returned even if it doesn't end
+    /// with a newline.
+    ///
+    /// Only the current line will be buffered in memory, so it's fine to
+    /// read huge files like NDJSON exports in this way. Errors of
+    /// underlying reader and invalid UTF-8 will be returned as items of
+    /// the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use futures::TryStreamExt;
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let r = op.reader("path/to/file.ndjson").await?;
+    /// let mut lines = r.into_lines();
+    /// while let Some(line) = lines.try_next().await? {
+    ///     println!("{line}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_lines(self) -> Lines {
+        Lines {
+            inner: self,
+            line: Vec::new(),
+            done: false,
+        }
+    }
+
+    /// Fill internal buffer with the next chunk if it's empty.
+    fn poll_fill_buf_inner(&mut self, cx: &mut Context<'_>) -> Poll<Result<&[u8]>> {
+        if self.buf.is_empty() {
+            if let Some(bs) = ready!(self.inner.poll_next(cx)).transpose()? {
+                self.buf = bs;
+            }
+        }
+
+        Poll::Ready(Ok(&self.buf))
     }
 }
 
 impl oio::Read for Reader {
     fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize>> {
-        self.inner.poll_read(cx, buf)
+        if self.buf.is_empty() {
+            return self.inner.poll_read(cx, buf);
+        }
+
+        let n = buf.len().min(self.buf.len());
+        buf[..n].copy_from_slice(&self.buf[..n]);
+        self.buf.advance(n);
+        Poll::Ready(Ok(n))
     }
 
     fn poll_seek(&mut self, cx: &mut Context<'_>, pos: io::SeekFrom) -> Poll<Result<u64>> {
-        self.inner.poll_seek(cx, pos)
+        // The underlying reader is ahead of us by the buffered bytes.
+        let pos = match pos {
+            io::SeekFrom::Current(n) => io::SeekFrom::Current(n - self.buf.len() as i64),
+            pos => pos,
+        };
+
+        let n = ready!(self.inner.poll_seek(cx, pos))?;
+        self.buf.clear();
+        Poll::Ready(Ok(n))
     }
 
     fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes>>> {
+        if !self.buf.is_empty() {
+            return Poll::Ready(Some(Ok(self.buf.split_off(0))));
+        }
+
         self.inner.poll_next(cx)
     }
 }
@@ -97,7 +456,20 @@ impl AsyncRead for Reader {
         cx: &mut Context<'_>,
         buf: &mut [u8],
     ) -> Poll<io::Result<usize>> {
-        Pin::new(&mut self.inner).poll_read(cx, buf)
+        oio::Read::poll_read(&mut *self, cx, buf)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+    }
+}
+
+impl AsyncBufRead for Reader {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        self.get_mut()
+            .poll_fill_buf_inner(cx)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+    }
+
+    fn consume(mut self: Pin<&mut Self>, amt: usize) {
+        self.buf.advance(amt)
     }
 }
 
@@ -107,7 +479,8 @@ impl AsyncSeek for Reader {
         cx: &mut Context<'_>,
         pos: io::SeekFrom,
     ) -> Poll<io::Result<u64>> {
-        Pin::new(&mut self.inner).poll_seek(cx, pos)
+        oio::Read::poll_seek(&mut *self, cx, pos)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
     }
 }
 
@@ -118,7 +491,7 @@ impl tokio::io::AsyncRead for Reader {
         buf: &mut tokio::io::ReadBuf<'_>,
     ) -> Poll<io::Result<()>> {
         let b = buf.initialize_unfilled();
-        let n = ready!(self.inner.poll_read(cx, b))?;
+        let n = ready!(oio::Read::poll_read(&mut *self, cx, b))?;
         unsafe {
             buf.assume_init(n);
         }
@@ -151,7 +524,7 @@ impl tokio::io::AsyncSeek for Reader {
                 Poll::Ready(Ok(0))
             }
             SeekState::Start(pos) => {
-                let n = ready!(self.inner.poll_seek(cx, pos))?;
+                let n = ready!(oio::Read::poll_seek(&mut *self, cx, pos))?;
                 self.get_mut().seek_state = SeekState::Init;
                 Poll::Ready(Ok(n))
             }
@@ -168,13 +541,119 @@ enum SeekState {
     Start(io::SeekFrom),
 }
 
+impl tokio::io::AsyncBufRead for Reader {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        self.get_mut()
+            .poll_fill_buf_inner(cx)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+    }
+
+    fn consume(mut self: Pin<&mut Self>, amt: usize) {
+        self.buf.advance(amt)
+    }
+}
+
 impl Stream for Reader {
     type Item = io::Result<Bytes>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        Pin::new(&mut self.inner)
-            .poll_next(cx)
-            .map_err(|err| io::Error::new(io::ErrorKind::Interrupted, err))
+        oio::Read::poll_next(&mut *self, cx)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+    }
+}
+
+/// ChunkedStream is a stream of fixed size chunks returned by
+/// [`Reader::into_stream_with_chunk_size`].
+pub struct ChunkedStream {
+    inner: Reader,
+    size: usize,
+    buf: BytesMut,
+    done: bool,
+}
+
+impl Stream for ChunkedStream {
+    type Item = io::Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        while !this.done && this.buf.len() < this.size {
+            match ready!(Pin::new(&mut this.inner).poll_next(cx)) {
+                Some(Ok(bs)) => this.buf.extend_from_slice(&bs),
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => this.done = true,
+            }
+        }
+
+        if this.buf.is_empty() {
+            return Poll::Ready(None);
+        }
+
+        let n = this.size.min(this.buf.len());
+        Poll::Ready(Some(Ok(this.buf.split_to(n).freeze())))
+    }
+}
+
+/// Lines is a stream of lines returned by [`Reader::into_lines`].
+pub struct Lines {
+    inner: Reader,
+    /// The bytes of current line that have been read.
+    line: Vec<u8>,
+    done: bool,
+}
+
+impl Lines {
+    fn take_line(&mut self) -> Result<String> {
+        let mut line = std::mem::take(&mut self.line);
+        if line.last() == Some(&b'\n') {
+            line.pop();
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+        }
+
+        String::from_utf8(line).map_err(|err| {
+            Error::new(ErrorKind::Unexpected, "line is not valid utf-8")
+                .with_operation("Lines::next")
+                .set_source(err)
+        })
+    }
+}
+
+impl Stream for Lines {
+    type Item = Result<String>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        while !this.done {
+            let buf = match ready!(this.inner.poll_fill_buf_inner(cx)) {
+                Ok(buf) => buf,
+                Err(err) => return Poll::Ready(Some(Err(err))),
+            };
+            if buf.is_empty() {
+                this.done = true;
+                break;
+            }
+
+            match buf.iter().position(|b| *b == b'\n') {
+                Some(idx) => {
+                    this.line.extend_from_slice(&buf[..=idx]);
+                    this.inner.buf.advance(idx + 1);
+                    return Poll::Ready(Some(this.take_line()));
+                }
+                None => {
+                    let n = buf.len();
+                    this.line.extend_from_slice(buf);
+                    this.inner.buf.advance(n);
+                }
+            }
+        }
+
+        if this.line.is_empty() {
+            return Poll::Ready(None);
+        }
+        Poll::Ready(Some(this.take_line()))
     }
 }
 
@@ -193,24 +672,7 @@ impl BlockingReader {
     /// We don't want to expose those details to users so keep this function
     /// in crate only.
     pub(crate) fn create(acc: FusedAccessor, path: &str, op: OpRead) -> Result<Self> {
-        let acc_meta = acc.info();
-
-        let r = if acc_meta.hints().contains(AccessorHint::ReadSeekable) {
-            let (_, r) = acc.blocking_read(path, op)?;
-            r
-        } else {
-            return Err(Error::new(
-                ErrorKind::Unsupported,
-                "non seekable blocking reader is not supported",
-            ));
-        };
-
-        let r = if acc_meta.hints().contains(AccessorHint::ReadStreamable) {
-            r
-        } else {
-            // Make this capacity configurable.
-            Box::new(oio::into_streamable_reader(r, 256 * 1024))
-        };
+        let (_, r) = acc.blocking_read(path, op)?;
 
         Ok(BlockingReader { inner: r })
     }
@@ -254,20 +716,30 @@ impl Iterator for BlockingReader {
     fn next(&mut self) -> Option<Self::Item> {
         self.inner
             .next()
-            .map(|v| v.map_err(|err| io::Error::new(io::ErrorKind::Interrupted, err)))
+            .map(|v| v.map_err(|err| io::Error::new(io::ErrorKind::Other, err)))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use futures::AsyncBufReadExt;
+    use futures::TryStreamExt;
     use rand::rngs::ThreadRng;
     use rand::Rng;
     use rand::RngCore;
     use tokio::io::AsyncReadExt;
     use tokio::io::AsyncSeekExt;
 
+    use bytes::Bytes;
+
+    use crate::ops::OpRead;
+    use crate::raw::BytesRange;
+    use crate::raw::IncomingAsyncBody;
     use crate::services;
+    use crate::Error;
+    use crate::ErrorKind;
     use crate::Operator;
+    use crate::Reader;
 
     fn gen_random_bytes() -> Vec<u8> {
         let mut rng = ThreadRng::default();
@@ -298,6 +770,173 @@ mod tests {
         assert_eq!(buf, content);
     }
 
+    #[tokio::test]
+    async fn test_reader_read_to_end_with_capacity() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+
+        let content = gen_random_bytes();
+        op.write(path, content.clone())
+            .await
+            .expect("write must succeed");
+
+        let mut reader = op.reader(path).await.unwrap();
+        let bs = reader
+            .read_to_end_with_capacity(content.len())
+            .await
+            .expect("read to end must succeed");
+
+        assert_eq!(bs, content);
+        // Exact hint should never trigger reallocation.
+        assert_eq!(bs.capacity(), content.len());
+    }
+
+    #[tokio::test]
+    async fn test_reader_read_to_end_with_small_capacity() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+
+        let content = gen_random_bytes();
+        op.write(path, content.clone())
+            .await
+            .expect("write must succeed");
+
+        for cap in [0, 1, content.len() / 2] {
+            let mut reader = op.reader(path).await.unwrap();
+            let bs = reader
+                .read_to_end_with_capacity(cap)
+                .await
+                .expect("read to end must succeed");
+
+            assert_eq!(bs.len(), content.len(), "cap: {cap}");
+            assert_eq!(bs, content, "cap: {cap}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reader_read_into() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        op.write("long", "Hello, World!")
+            .await
+            .expect("write must succeed");
+        op.write("short", "abc").await.expect("write must succeed");
+
+        let mut buf = vec![0; 32];
+
+        let n = op.read_into("long", &mut buf).await.expect("must read");
+        assert_eq!(&buf[..n], b"Hello, World!");
+
+        // Leftover bytes from the last read must not leak into the result.
+        let n = op.read_into("short", &mut buf).await.expect("must read");
+        assert_eq!(&buf[..n], b"abc");
+        assert_eq!(&buf[n..13], b"lo, World!");
+
+        // Buffer smaller than the object is a short read.
+        let mut small = vec![0; 5];
+        let n = op.read_into("long", &mut small).await.expect("must read");
+        assert_eq!(n, 5);
+        assert_eq!(small, b"Hello");
+
+        let mut reader = op.reader("long").await.unwrap();
+        let n = reader.read_into(&mut small).await.expect("must read");
+        assert_eq!(&small[..n], b"Hello");
+        let n = reader.read_into(&mut buf).await.expect("must read");
+        assert_eq!(&buf[..n], b", World!");
+    }
+
+    #[tokio::test]
+    async fn test_reader_read_into_empty() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        op.write("empty", "").await.expect("write must succeed");
+
+        let mut buf = vec![0; 32];
+        let n = op.read_into("empty", &mut buf).await.expect("must read");
+        assert_eq!(n, 0);
+
+        // Empty buffer will not send any request.
+        let n = op.read_into("not_exist", &mut []).await.expect("must read");
+        assert_eq!(n, 0);
+    }
+
+    #[tokio::test]
+    async fn test_reader_peek() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+
+        let content = gen_random_bytes();
+        op.write(path, content.clone())
+            .await
+            .expect("write must succeed");
+
+        let mut reader = op.reader(path).await.unwrap();
+        assert_eq!(reader.peek(4).await.unwrap(), &content[..4]);
+        // Peek again must not advance the reader.
+        assert_eq!(reader.peek(4).await.unwrap(), &content[..4]);
+        assert_eq!(reader.peek(8).await.unwrap(), &content[..8]);
+
+        let mut buf = Vec::new();
+        reader
+            .read_to_end(&mut buf)
+            .await
+            .expect("read to end must succeed");
+        assert_eq!(buf, content, "no bytes are skipped or duplicated");
+
+        // Peek in the middle and beyond the end.
+        let mut reader = op.reader(path).await.unwrap();
+        let mut buf = vec![0; 3];
+        reader.read_exact(&mut buf).await.unwrap();
+        assert_eq!(reader.peek(4).await.unwrap(), &content[3..7]);
+        assert_eq!(
+            reader.peek(content.len()).await.unwrap(),
+            &content[3..],
+            "peek beyond the end must return the rest"
+        );
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(buf, &content[3..]);
+    }
+
+    #[tokio::test]
+    async fn test_reader_peek_tail() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+
+        let content = gen_random_bytes();
+        let len = content.len();
+        op.write(path, content.clone())
+            .await
+            .expect("write must succeed");
+
+        let mut reader = op.reader(path).await.unwrap();
+        assert_eq!(reader.peek_tail(8).await.unwrap(), &content[len - 8..]);
+        assert_eq!(reader.peek_tail(len + 1).await.unwrap(), content);
+        assert!(reader.peek_tail(0).await.unwrap().is_empty());
+
+        // Tail doesn't affect the reader.
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(buf, content);
+
+        // Tail is relative to the range of the reader.
+        let reader = op.range_reader(path, 2..10).await.unwrap();
+        assert_eq!(reader.peek_tail(4).await.unwrap(), &content[6..10]);
+        assert_eq!(reader.peek_tail(16).await.unwrap(), &content[2..10]);
+
+        let reader = op.range_reader(path, 4..).await.unwrap();
+        assert_eq!(reader.peek_tail(4).await.unwrap(), &content[len - 4..]);
+        assert_eq!(reader.peek_tail(len).await.unwrap(), &content[4..]);
+
+        let reader = op
+            .reader_with(
+                path,
+                OpRead::new().with_range(BytesRange::new(None, Some(6))),
+            )
+            .await
+            .unwrap();
+        assert_eq!(reader.peek_tail(4).await.unwrap(), &content[len - 4..]);
+        assert_eq!(reader.peek_tail(8).await.unwrap(), &content[len - 6..]);
+    }
+
     #[tokio::test]
     async fn test_reader_async_seek() {
         let op = Operator::new(services::Memory::default()).unwrap().finish();
@@ -326,4 +965,227 @@ mod tests {
             .expect("read to end must succeed");
         assert_eq!(buf, content);
     }
+
+    #[tokio::test]
+    async fn test_reader_async_buf_read_lines() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+
+        op.write(path, "hello\nworld\n\nlast line without newline")
+            .await
+            .expect("write must succeed");
+
+        let reader = op.reader(path).await.unwrap();
+        let lines: Vec<String> = reader
+            .lines()
+            .try_collect()
+            .await
+            .expect("read lines must succeed");
+
+        assert_eq!(
+            lines,
+            vec!["hello", "world", "", "last line without newline"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reader_tokio_async_buf_read_lines() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+
+        op.write(path, "hello\nworld\nlast")
+            .await
+            .expect("write must succeed");
+
+        let reader = op.reader(path).await.unwrap();
+        let mut lines = tokio::io::AsyncBufReadExt::lines(reader);
+        let mut actual = Vec::new();
+        while let Some(line) = lines.next_line().await.expect("read line must succeed") {
+            actual.push(line);
+        }
+
+        assert_eq!(actual, vec!["hello", "world", "last"]);
+    }
+
+    #[tokio::test]
+    async fn test_reader_async_buf_read_range() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+
+        op.write(path, "hello\nworld\nnext")
+            .await
+            .expect("write must succeed");
+
+        // Only `world\nnex` is in range.
+        let reader = op.range_reader(path, 6..15).await.unwrap();
+        let lines: Vec<String> = reader
+            .lines()
+            .try_collect()
+            .await
+            .expect("read lines must succeed");
+
+        assert_eq!(lines, vec!["world", "nex"]);
+    }
+
+    #[tokio::test]
+    async fn test_reader_mixed_buf_read_and_read() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+
+        op.write(path, "hello\nworld")
+            .await
+            .expect("write must succeed");
+
+        let mut reader = op.reader(path).await.unwrap();
+        let mut line = String::new();
+        reader
+            .read_line(&mut line)
+            .await
+            .expect("read line must succeed");
+        assert_eq!(line, "hello\n");
+
+        // Buffered data must be returned before reading more.
+        let mut buf = Vec::new();
+        reader
+            .read_to_end(&mut buf)
+            .await
+            .expect("read to end must succeed");
+        assert_eq!(buf, b"world");
+    }
+
+    #[tokio::test]
+    async fn test_reader_into_stream_with_chunk_size() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+
+        let mut content = vec![0; 1024 * 1024];
+        ThreadRng::default().fill_bytes(&mut content);
+        op.write(path, content.clone())
+            .await
+            .expect("write must succeed");
+
+        let reader = op.reader(path).await.unwrap();
+        let chunks: Vec<_> = reader
+            .into_stream_with_chunk_size(256 * 1024)
+            .try_collect()
+            .await
+            .expect("read must succeed");
+
+        assert_eq!(chunks.len(), 4);
+        for chunk in &chunks {
+            assert_eq!(chunk.len(), 256 * 1024);
+        }
+        assert_eq!(chunks.concat(), content);
+    }
+
+    #[tokio::test]
+    async fn test_reader_into_stream_with_chunk_size_range() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+
+        let mut content = vec![0; 1024 * 1024];
+        ThreadRng::default().fill_bytes(&mut content);
+        op.write(path, content.clone())
+            .await
+            .expect("write must succeed");
+
+        let reader = op.range_reader(path, 1024..600 * 1024).await.unwrap();
+        let chunks: Vec<_> = reader
+            .into_stream_with_chunk_size(256 * 1024)
+            .try_collect()
+            .await
+            .expect("read must succeed");
+
+        let sizes: Vec<_> = chunks.iter().map(|v| v.len()).collect();
+        assert_eq!(sizes, vec![256 * 1024, 256 * 1024, 87 * 1024]);
+        assert_eq!(chunks.concat(), content[1024..600 * 1024]);
+    }
+
+    #[tokio::test]
+    async fn test_read_lines() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+
+        op.write(path, "a\r\nb\n\nc").await.unwrap();
+        let lines: Vec<_> = op
+            .read_lines(path)
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(lines, vec!["a", "b", "", "c"]);
+
+        op.write(path, "a\n").await.unwrap();
+        let lines: Vec<_> = op
+            .read_lines(path)
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(lines, vec!["a"]);
+
+        op.write(path, "").await.unwrap();
+        let lines: Vec<_> = op
+            .read_lines(path)
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert!(lines.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reader_into_lines_across_chunks() {
+        let mut rng = ThreadRng::default();
+        let mut expected = Vec::new();
+        let mut content = Vec::new();
+        while content.len() < 4 * 1024 * 1024 {
+            let line = format!(
+                r#"{{"id":{},"data":"{}"}}"#,
+                expected.len(),
+                "x".repeat(rng.gen_range(0..8192))
+            );
+            content.extend_from_slice(line.as_bytes());
+            content.push(b'\n');
+            expected.push(line);
+        }
+        // The last line doesn't end with newline.
+        content.pop();
+
+        // Split content into chunks of odd size so that lines will cross
+        // the edges of chunks.
+        let chunks: Vec<_> = content
+            .chunks(4093)
+            .map(|v| Ok(Bytes::copy_from_slice(v)))
+            .collect();
+        let body = IncomingAsyncBody::new(Box::new(futures::stream::iter(chunks)), None);
+        let lines: Vec<_> = Reader::new(Box::new(body))
+            .into_lines()
+            .try_collect()
+            .await
+            .expect("read must succeed");
+        assert_eq!(lines, expected);
+    }
+
+    #[tokio::test]
+    async fn test_reader_into_lines_with_error() {
+        let chunks = vec![
+            Ok(Bytes::from("a\nb")),
+            Err(Error::new(ErrorKind::Unexpected, "connection reset")),
+            Ok(Bytes::from_static(b"c\n\xff\n")),
+        ];
+        let body = IncomingAsyncBody::new(Box::new(futures::stream::iter(chunks)), None);
+        let mut lines = Reader::new(Box::new(body)).into_lines();
+
+        assert_eq!(lines.try_next().await.unwrap(), Some("a".to_string()));
+        let err = lines.try_next().await.expect_err("must fail");
+        assert!(err.to_string().contains("connection reset"), "{err}");
+        assert_eq!(lines.try_next().await.unwrap(), Some("bc".to_string()));
+        let err = lines.try_next().await.expect_err("must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert_eq!(lines.try_next().await.unwrap(), None);
+    }
 }
@@ -35,6 +35,9 @@ pub enum Scheme {
     Azblob,
     /// [azdfs][crate::services::Azdfs]: Azure Data Lake Storage Gen2.
     Azdfs,
+    /// [chaos][crate::services::Chaos]: In memory backend with latency and error injection.
+    #[cfg(feature = "services-chaos")]
+    Chaos,
     /// [dashmap][crate::services::Dashmap]: dashmap backend support.
     #[cfg(feature = "services-dashmap")]
     Dashmap,
@@ -120,6 +123,8 @@ impl FromStr for Scheme {
         match s.as_str() {
             "azblob" => Ok(Scheme::Azblob),
             "azdfs" => Ok(Scheme::Azdfs),
+            #[cfg(feature = "services-chaos")]
+            "chaos" => Ok(Scheme::Chaos),
             #[cfg(feature = "services-dashmap")]
             "dashmap" => Ok(Scheme::Dashmap),
             "fs" => Ok(Scheme::Fs),
@@ -159,6 +164,8 @@ impl From<Scheme> for &'static str {
         match v {
             Scheme::Azblob => "azblob",
             Scheme::Azdfs => "azdfs",
+            #[cfg(feature = "services-chaos")]
+            Scheme::Chaos => "chaos",
             #[cfg(feature = "services-dashmap")]
             Scheme::Dashmap => "dashmap",
             Scheme::Fs => "fs",
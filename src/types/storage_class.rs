@@ -0,0 +1,60 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+/// StorageClass represents the storage tier of an object.
+///
+/// Services use different names for their tiers, OpenDAL will map them
+/// into the following classes. Unknown values will be returned as
+/// [`StorageClass::Other`] AS-IS.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StorageClass {
+    /// Standard storage, like `STANDARD` of s3/gcs and `Hot` of azblob.
+    Standard,
+    /// Infrequent access storage, like `STANDARD_IA` and `ONEZONE_IA` of s3.
+    InfrequentAccess,
+    /// Glacier storage, like `GLACIER` and `GLACIER_IR` of s3.
+    Glacier,
+    /// Archive storage, like `DEEP_ARCHIVE` of s3, `ARCHIVE` of gcs and
+    /// `Archive` of azblob.
+    Archive,
+    /// Cold storage, like `Cool` of azblob.
+    Cold,
+    /// Nearline storage, like `NEARLINE` of gcs.
+    Nearline,
+    /// Coldline storage, like `COLDLINE` of gcs.
+    Coldline,
+    /// Other storage class that OpenDAL doesn't know.
+    Other(String),
+}
+
+impl Display for StorageClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StorageClass::Standard => write!(f, "standard"),
+            StorageClass::InfrequentAccess => write!(f, "infrequent_access"),
+            StorageClass::Glacier => write!(f, "glacier"),
+            StorageClass::Archive => write!(f, "archive"),
+            StorageClass::Cold => write!(f, "cold"),
+            StorageClass::Nearline => write!(f, "nearline"),
+            StorageClass::Coldline => write!(f, "coldline"),
+            StorageClass::Other(v) => write!(f, "{v}"),
+        }
+    }
+}
@@ -21,13 +21,17 @@ use std::pin::Pin;
 use std::task::Context;
 use std::task::Poll;
 
+use async_trait::async_trait;
 use bytes::Bytes;
+use bytes::BytesMut;
 use futures::future::BoxFuture;
 use futures::ready;
 use futures::AsyncWrite;
 use futures::FutureExt;
+use futures::Sink;
 
 use crate::ops::OpWrite;
+use crate::raw::oio::WriteOperation;
 use crate::raw::*;
 use crate::*;
 
@@ -52,7 +56,18 @@ impl Writer {
     /// We don't want to expose those details to users so keep this function
     /// in crate only.
     pub(crate) async fn create(acc: FusedAccessor, path: &str, op: OpWrite) -> Result<Self> {
-        let (_, w) = acc.write(path, op).await?;
+        let w: oio::Writer = match op.content_length() {
+            // Content will be sent while closing, open it later so that
+            // nothing will be touched if the length doesn't match.
+            Some(n) if !op.append() => Box::new(ExactSizeWriter::buffered(acc, path, op, n)),
+            Some(n) => {
+                let (_, w) = acc.write(path, op.clone()).await?;
+                // Bytes of resumed parts have been written before.
+                let written = op.resume().map(|v| v.uploaded_size()).unwrap_or_default();
+                Box::new(ExactSizeWriter::new(w, n, written))
+            }
+            None => acc.write(path, op).await?.1,
+        };
 
         Ok(Writer {
             state: State::Idle(Some(w)),
@@ -86,6 +101,149 @@ impl Writer {
             );
         }
     }
+
+    /// Abort the writer and clean up all written data.
+    ///
+    /// Writer MUST NOT be used after abort.
+    pub async fn abort(&mut self) -> Result<()> {
+        if let State::Idle(Some(w)) = &mut self.state {
+            w.abort().await
+        } else {
+            unreachable!(
+                "writer state invalid while abort, expect Idle, actual {}",
+                self.state
+            );
+        }
+    }
+
+    /// Fetch the multipart state of this writer.
+    ///
+    /// The state can be persisted and passed to
+    /// [`OpWrite::with_resume`] to continue the upload later. Services that
+    /// don't support [`OperatorInfo::can_resumable_write`] will return
+    /// `Unsupported`.
+    pub fn state(&self) -> Result<MultipartState> {
+        if let State::Idle(Some(w)) = &self.state {
+            w.state()
+        } else {
+            unreachable!(
+                "writer state invalid while state, expect Idle, actual {}",
+                self.state
+            );
+        }
+    }
+
+    /// Convert writer into a [`WriterSink`] which implements
+    /// `Sink<Bytes, Error = Error>`.
+    ///
+    /// Every item sent into the sink will be appended into the writer,
+    /// and closing the sink will close the writer.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// use bytes::Bytes;
+    /// use futures::stream;
+    /// use futures::SinkExt;
+    /// use futures::StreamExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut sink = op.writer("path/to/file").await?.into_sink();
+    /// let mut chunks = stream::iter(vec![Bytes::from("hello"), Bytes::from("world")]).map(Ok);
+    /// sink.send_all(&mut chunks).await?;
+    /// sink.close().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_sink(self) -> WriterSink {
+        WriterSink { inner: self }
+    }
+
+    /// Wait for the in-progress append to finish.
+    fn poll_append_done(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        match &mut self.state {
+            State::Idle(_) => Poll::Ready(Ok(())),
+            State::Write(fut) => {
+                let (_, w) = ready!(fut.poll_unpin(cx))?;
+                self.state = State::Idle(Some(w));
+                Poll::Ready(Ok(()))
+            }
+            State::Close(_) => {
+                unreachable!("invalid state of writer: poll_append_done with State::Close")
+            }
+        }
+    }
+}
+
+/// WriterSink is a [`Sink`] returned by [`Writer::into_sink`].
+///
+/// Only one append will be in progress at the same time, so `poll_ready`
+/// will be pending until the previous item has been appended.
+pub struct WriterSink {
+    inner: Writer,
+}
+
+impl Sink<Bytes> for WriterSink {
+    type Error = Error;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_append_done(cx)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: Bytes) -> Result<()> {
+        let state = &mut self.inner.state;
+        let mut w = match state {
+            State::Idle(w) => w
+                .take()
+                .expect("invalid state of writer: Idle state with empty write"),
+            _ => unreachable!(
+                "writer state invalid while start_send, expect Idle, actual {}",
+                state
+            ),
+        };
+
+        let size = item.len();
+        let fut = async move {
+            w.append(item).await?;
+            Ok((size, w))
+        };
+        *state = State::Write(Box::pin(fut));
+        Ok(())
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_append_done(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        ready!(self.inner.poll_append_done(cx))?;
+
+        loop {
+            match &mut self.inner.state {
+                State::Idle(w) => {
+                    let mut w = w
+                        .take()
+                        .expect("invalid state of writer: Idle state with empty write");
+                    let fut = async move {
+                        w.close().await?;
+                        Ok(w)
+                    };
+                    self.inner.state = State::Close(Box::pin(fut));
+                }
+                State::Write(_) => {
+                    unreachable!("invalid state of writer: poll_close with State::Write")
+                }
+                State::Close(fut) => {
+                    let w = ready!(fut.poll_unpin(cx))?;
+                    self.inner.state = State::Idle(Some(w));
+                    return Poll::Ready(Ok(()));
+                }
+            }
+        }
+    }
 }
 
 enum State {
@@ -171,6 +329,151 @@ impl AsyncWrite for Writer {
     }
 }
 
+/// ExactSizeWriter checks the bytes written against the declared content
+/// length.
+///
+/// For buffered writer, content will be buffered and sent in one request
+/// while closing, so nothing will be written if the length doesn't match.
+/// Otherwise, the inner writer will be aborted if closing with mismatched
+/// length.
+struct ExactSizeWriter {
+    content_length: u64,
+    written: u64,
+    target: ExactSizeTarget,
+}
+
+enum ExactSizeTarget {
+    Buffered {
+        acc: FusedAccessor,
+        path: String,
+        op: Box<OpWrite>,
+        buf: BytesMut,
+    },
+    Writer(oio::Writer),
+}
+
+impl ExactSizeWriter {
+    fn new(inner: oio::Writer, content_length: u64, written: u64) -> Self {
+        Self {
+            content_length,
+            written,
+            target: ExactSizeTarget::Writer(inner),
+        }
+    }
+
+    fn buffered(acc: FusedAccessor, path: &str, op: OpWrite, content_length: u64) -> Self {
+        Self {
+            content_length,
+            written: 0,
+            target: ExactSizeTarget::Buffered {
+                acc,
+                path: path.to_string(),
+                op: Box::new(op),
+                buf: BytesMut::with_capacity(content_length as usize),
+            },
+        }
+    }
+
+    fn new_size_error(&self, op: WriteOperation, msg: &'static str) -> Error {
+        Error::new(ErrorKind::Unexpected, msg)
+            .with_operation(op)
+            .with_context("content_length", self.content_length.to_string())
+            .with_context("written", self.written.to_string())
+    }
+
+    /// Open the inner writer if content is buffered.
+    async fn writer(&mut self) -> Result<&mut oio::Writer> {
+        if let ExactSizeTarget::Buffered { acc, path, op, .. } = &self.target {
+            let (_, w) = acc.write(path, op.as_ref().clone()).await?;
+            self.target = ExactSizeTarget::Writer(w);
+        }
+
+        match &mut self.target {
+            ExactSizeTarget::Writer(w) => Ok(w),
+            ExactSizeTarget::Buffered { .. } => unreachable!("writer must be opened"),
+        }
+    }
+}
+
+#[async_trait]
+impl oio::Write for ExactSizeWriter {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        if self.written + bs.len() as u64 != self.content_length {
+            return Err(self
+                .new_size_error(
+                    WriteOperation::Write,
+                    "content doesn't match the declared content length",
+                )
+                .with_context("size", bs.len().to_string()));
+        }
+        self.written += bs.len() as u64;
+
+        self.writer().await?.write(bs).await
+    }
+
+    async fn append(&mut self, bs: Bytes) -> Result<()> {
+        if self.written + bs.len() as u64 > self.content_length {
+            return Err(self.new_size_error(
+                WriteOperation::Append,
+                "content is longer than the declared content length",
+            ));
+        }
+        self.written += bs.len() as u64;
+
+        match &mut self.target {
+            ExactSizeTarget::Buffered { buf, .. } => {
+                buf.extend_from_slice(&bs);
+                Ok(())
+            }
+            ExactSizeTarget::Writer(w) => w.append(bs).await,
+        }
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        if self.written != self.content_length {
+            let err = self.new_size_error(
+                WriteOperation::Close,
+                "content is shorter than the declared content length",
+            );
+            // Clean up the parts that have been uploaded, the size error
+            // is more important than the error of abort.
+            if let ExactSizeTarget::Writer(w) = &mut self.target {
+                if let Err(e) = w.abort().await {
+                    return Err(err.set_source(e));
+                }
+            }
+            return Err(err);
+        }
+
+        if let ExactSizeTarget::Buffered { buf, .. } = &mut self.target {
+            let bs = buf.split().freeze();
+            self.writer().await?.write(bs).await?;
+        }
+        self.writer().await?.close().await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        match &mut self.target {
+            // Nothing has been sent for buffered content.
+            ExactSizeTarget::Buffered { buf, .. } => {
+                buf.clear();
+                Ok(())
+            }
+            ExactSizeTarget::Writer(w) => w.abort().await,
+        }
+    }
+
+    fn state(&self) -> Result<MultipartState> {
+        match &self.target {
+            ExactSizeTarget::Buffered { .. } => Err(Error::new(
+                ErrorKind::Unsupported,
+                "buffered writer doesn't support state",
+            )),
+            ExactSizeTarget::Writer(w) => w.state(),
+        }
+    }
+}
+
 /// BlockingWriter is designed to write data into given path in an blocking
 /// manner.
 pub struct BlockingWriter {
@@ -204,6 +507,13 @@ impl BlockingWriter {
     pub fn close(&mut self) -> Result<()> {
         self.inner.close()
     }
+
+    /// Abort the writer and clean up all written data.
+    ///
+    /// Writer MUST NOT be used after abort.
+    pub fn abort(&mut self) -> Result<()> {
+        self.inner.abort()
+    }
 }
 
 impl io::Write for BlockingWriter {
@@ -218,3 +528,89 @@ impl io::Write for BlockingWriter {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+    use futures::SinkExt;
+    use futures::StreamExt;
+
+    use super::*;
+    use crate::services;
+
+    #[tokio::test]
+    async fn test_writer_into_sink() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+
+        let chunks = vec![
+            Bytes::from("Hello, "),
+            Bytes::from("World"),
+            Bytes::new(),
+            Bytes::from("!"),
+        ];
+
+        let mut sink = op.writer(path).await.unwrap().into_sink();
+        let mut items = stream::iter(chunks).map(Ok);
+        sink.send_all(&mut items).await.expect("send must succeed");
+        sink.close().await.expect("close must succeed");
+
+        let bs = op.read(path).await.expect("read must succeed");
+        assert_eq!(bs, b"Hello, World!");
+    }
+
+    #[tokio::test]
+    async fn test_writer_with_content_length() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+
+        // Both buffered and multipart writes.
+        for size in [13, 9 * 1024 * 1024] {
+            let content = vec![1; size];
+            let args = OpWrite::new().with_content_length(size as u64);
+            let mut w = op.writer_with("test_file", args).await.unwrap();
+            for chunk in content.chunks(4096) {
+                w.append(chunk.to_vec()).await.expect("append must succeed");
+            }
+            w.close().await.expect("close must succeed");
+
+            let bs = op.read("test_file").await.expect("read must succeed");
+            assert_eq!(bs, content, "{size}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_writer_with_content_length_mismatch() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+
+        let args = OpWrite::new().with_content_length(13);
+        let mut w = op.writer_with("short_file", args).await.unwrap();
+        w.append("Hello").await.expect("append must succeed");
+        let err = w.close().await.expect_err("close must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(!op.exists("short_file").await.unwrap());
+
+        // Existing content must be kept if length mismatched.
+        op.write("short_file", "Old").await.unwrap();
+        let args = OpWrite::new().with_content_length(13);
+        let mut w = op.writer_with("short_file", args).await.unwrap();
+        w.append("Hello").await.expect("append must succeed");
+        w.close().await.expect_err("close must fail");
+        assert_eq!(op.read("short_file").await.unwrap(), b"Old");
+
+        let args = OpWrite::new().with_content_length(13);
+        let err = op
+            .write_with("short_file", args, "Hello")
+            .await
+            .expect_err("write must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert_eq!(op.read("short_file").await.unwrap(), b"Old");
+
+        let args = OpWrite::new().with_content_length(5);
+        let mut w = op.writer_with("long_file", args).await.unwrap();
+        let err = w
+            .append("Hello, World!")
+            .await
+            .expect_err("append must fail");
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+}
@@ -15,11 +15,14 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::io::BufReader;
 use std::io::Read;
 use std::io::Seek;
+use std::io::SeekFrom;
 
 use anyhow::Result;
 use log::debug;
+use opendal::ops::OpDelete;
 use opendal::BlockingOperator;
 use opendal::EntryMode;
 use opendal::ErrorKind;
@@ -83,11 +86,13 @@ macro_rules! behavior_blocking_write_tests {
                 test_read_full,
                 test_read_range,
                 test_read_large_range,
+                test_reader_buf_read_and_seek,
                 test_read_not_exist,
                 test_fuzz_range_reader,
                 test_fuzz_offset_reader,
                 test_fuzz_part_reader,
                 test_delete,
+                test_delete_with_strict,
             );
         )*
     };
@@ -97,6 +102,12 @@ macro_rules! behavior_blocking_write_tests {
 pub fn test_create_dir(op: BlockingOperator) -> Result<()> {
     let path = format!("{}/", uuid::Uuid::new_v4());
 
+    if !op.info().can_create_dir() {
+        let res = op.create_dir(&path);
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+        return Ok(());
+    }
+
     op.create_dir(&path)?;
 
     let meta = op.stat(&path)?;
@@ -110,6 +121,12 @@ pub fn test_create_dir(op: BlockingOperator) -> Result<()> {
 pub fn test_create_dir_existing(op: BlockingOperator) -> Result<()> {
     let path = format!("{}/", uuid::Uuid::new_v4());
 
+    if !op.info().can_create_dir() {
+        let res = op.create_dir(&path);
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+        return Ok(());
+    }
+
     op.create_dir(&path)?;
 
     op.create_dir(&path)?;
@@ -291,6 +308,48 @@ pub fn test_read_large_range(op: BlockingOperator) -> Result<()> {
     Ok(())
 }
 
+/// Read content through BufReader and seek should match.
+pub fn test_reader_buf_read_and_seek(op: BlockingOperator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    // Make sure the content spans multiple chunks.
+    let content = gen_fixed_bytes(4 * 1024 * 1024 + 1024);
+    let size = content.len();
+    let (offset, _) = gen_offset_length(size);
+
+    op.write(&path, content.clone())
+        .expect("write must succeed");
+
+    let mut r = BufReader::new(op.reader(&path)?);
+    let mut bs = Vec::new();
+    r.read_to_end(&mut bs)?;
+    assert_eq!(size, bs.len(), "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content)),
+        "read content"
+    );
+
+    let n = r.seek(SeekFrom::Start(offset))?;
+    assert_eq!(n, offset, "seek position");
+
+    let mut bs = Vec::new();
+    r.read_to_end(&mut bs)?;
+    assert_eq!(
+        size as u64 - offset,
+        bs.len() as u64,
+        "read size after seek"
+    );
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content[offset as usize..])),
+        "read content after seek"
+    );
+
+    op.delete(&path).expect("delete must succeed");
+    Ok(())
+}
+
 /// Read not exist file should return NotFound
 pub fn test_read_not_exist(op: BlockingOperator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
@@ -417,3 +476,21 @@ pub fn test_delete(op: BlockingOperator) -> Result<()> {
 
     Ok(())
 }
+
+// Delete with strict should fail on not existing file.
+pub fn test_delete_with_strict(op: BlockingOperator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    op.write(&path, content).expect("write must succeed");
+
+    op.delete_with(&path, OpDelete::new().with_strict(true))?;
+    assert!(!op.is_exist(&path)?);
+
+    let err = op
+        .delete_with(&path, OpDelete::new().with_strict(true))
+        .expect_err("delete not existing file with strict must fail");
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+
+    Ok(())
+}
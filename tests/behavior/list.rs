@@ -23,6 +23,8 @@ use futures::stream::FuturesUnordered;
 use futures::StreamExt;
 use futures::TryStreamExt;
 use log::debug;
+use opendal::ops::OpScan;
+use opendal::ops::OpStat;
 use opendal::EntryMode;
 use opendal::ErrorKind;
 use opendal::Operator;
@@ -82,6 +84,8 @@ macro_rules! behavior_list_tests {
                 test_list_nested_dir,
                 test_list_dir_with_file_path,
                 test_scan,
+                test_scan_with_pattern,
+                test_stat_with_recursive_size,
                 test_remove_all,
             );
         )*
@@ -308,6 +312,63 @@ pub async fn test_scan(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Scan with pattern should only return matched entries.
+pub async fn test_scan_with_pattern(op: Operator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+    for path in ["data/a.csv", "data/b.json", "x/data/c.csv", "x/y/d.csv"] {
+        op.write(&format!("{parent}/{path}"), "test_scan_with_pattern")
+            .await?;
+    }
+
+    let args = OpScan::new().with_pattern("**/data/*.csv");
+    let actual = op
+        .scan_with(&format!("{parent}/"), args)
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?
+        .into_iter()
+        .map(|v| v.path().to_string())
+        .collect::<HashSet<_>>();
+    assert_eq!(
+        actual,
+        HashSet::from([
+            format!("{parent}/data/a.csv"),
+            format!("{parent}/x/data/c.csv")
+        ])
+    );
+
+    let args = OpScan::new().with_pattern("not_exist/*.csv");
+    let actual = op
+        .scan_with(&format!("{parent}/"), args)
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert!(actual.is_empty());
+
+    op.remove_all(&format!("{parent}/")).await?;
+    Ok(())
+}
+
+/// Stat with recursive size should sum the sizes of all nested files,
+/// no matter whether dir path ends with `/` or not.
+pub async fn test_stat_with_recursive_size(op: Operator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+    for (path, size) in [("a", 5), ("b/c", 13), ("b/d/e", 100)] {
+        op.write(&format!("{parent}/{path}"), vec![0; size]).await?;
+    }
+
+    let args = OpStat::new().with_recursive_size(true);
+    for path in [format!("{parent}/"), parent.clone()] {
+        let meta = op.stat_with(&path, args.clone()).await?;
+        assert_eq!(meta.mode(), EntryMode::DIR, "{path}");
+        assert_eq!(meta.content_length(), 118, "{path}");
+        assert_eq!(meta.object_count(), Some(3), "{path}");
+    }
+
+    op.remove_all(&format!("{parent}/")).await?;
+    Ok(())
+}
+
 // Remove all should remove all in this path.
 pub async fn test_remove_all(op: Operator) -> Result<()> {
     let expected = vec![
@@ -30,6 +30,8 @@ mod presign;
 #[macro_use]
 mod read_only;
 #[macro_use]
+mod rename;
+#[macro_use]
 mod write;
 
 mod utils;
@@ -51,6 +53,8 @@ macro_rules! behavior_tests {
             behavior_list_tests!($service);
             // can_read && can_write && can_presign
             behavior_presign_tests!($service);
+            // can_read && can_write && can_rename
+            behavior_rename_tests!($service);
             // can_read && can_write && can_blocking && can_list
             behavior_blocking_list_tests!($service);
             // can_list && !can_write
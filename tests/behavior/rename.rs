@@ -0,0 +1,146 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use anyhow::Result;
+use opendal::ErrorKind;
+use opendal::Operator;
+
+use super::utils::*;
+
+/// Test services that meet the following capability:
+///
+/// - can_read
+/// - can_write
+/// - can_rename
+macro_rules! behavior_rename_test {
+    ($service:ident, $($(#[$meta:meta])* $test:ident),*,) => {
+        paste::item! {
+            mod [<services_ $service:lower _rename>] {
+                $(
+                    #[tokio::test]
+                    $(
+                        #[$meta]
+                    )*
+                    async fn [< $test >]() -> anyhow::Result<()> {
+                        let op = $crate::utils::init_service::<opendal::services::$service>(true);
+                        match op {
+                            Some(op) if op.info().can_read() && op.info().can_write() && op.info().can_rename() => $crate::rename::$test(op).await,
+                            Some(_) => {
+                                log::warn!("service {} doesn't support rename, ignored", opendal::Scheme::$service);
+                                Ok(())
+                            },
+                            None => {
+                                log::warn!("service {} not initiated, ignored", opendal::Scheme::$service);
+                                Ok(())
+                            }
+                        }
+                    }
+                )*
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! behavior_rename_tests {
+     ($($service:ident),*) => {
+        $(
+            behavior_rename_test!(
+                $service,
+
+                test_rename_file,
+                test_rename_overwrite_existing,
+                test_rename_not_exist,
+                test_rename_to_nested_path,
+            );
+        )*
+    };
+}
+
+/// Rename a file should move content to target and remove source.
+pub async fn test_rename_file(op: Operator) -> Result<()> {
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let target_path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    op.write(&source_path, content.clone()).await?;
+
+    op.rename(&source_path, &target_path).await?;
+
+    assert!(!op.is_exist(&source_path).await?);
+    let bs = op.read(&target_path).await?;
+    assert_eq!(bs, content);
+
+    op.delete(&target_path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Rename a file over an existing target should overwrite it.
+pub async fn test_rename_overwrite_existing(op: Operator) -> Result<()> {
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let target_path = uuid::Uuid::new_v4().to_string();
+    let (source_content, _) = gen_bytes();
+    let (target_content, _) = gen_bytes();
+    assert_ne!(source_content, target_content);
+
+    op.write(&source_path, source_content.clone()).await?;
+    op.write(&target_path, target_content).await?;
+
+    op.rename(&source_path, &target_path).await?;
+
+    assert!(!op.is_exist(&source_path).await?);
+    let bs = op.read(&target_path).await?;
+    assert_eq!(bs, source_content);
+
+    op.delete(&target_path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Rename a not existing file should return `NotFound` and leave target untouched.
+pub async fn test_rename_not_exist(op: Operator) -> Result<()> {
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let target_path = uuid::Uuid::new_v4().to_string();
+
+    let err = op
+        .rename(&source_path, &target_path)
+        .await
+        .expect_err("rename must fail");
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+    assert!(!op.is_exist(&target_path).await?);
+
+    Ok(())
+}
+
+/// Rename a file into a nested path should succeed.
+pub async fn test_rename_to_nested_path(op: Operator) -> Result<()> {
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let target_dir = format!("{}/", uuid::Uuid::new_v4());
+    let target_path = format!("{}{}", target_dir, uuid::Uuid::new_v4());
+    let (content, _) = gen_bytes();
+
+    op.write(&source_path, content.clone()).await?;
+    op.create_dir(&target_dir).await?;
+
+    op.rename(&source_path, &target_path).await?;
+
+    let bs = op.read(&target_path).await?;
+    assert_eq!(bs, content);
+
+    op.delete(&target_path).await.expect("delete must succeed");
+    op.delete(&target_dir).await.expect("delete must succeed");
+    Ok(())
+}
@@ -16,16 +16,23 @@
 // under the License.
 
 use anyhow::Result;
+use bytes::Bytes;
+use futures::stream;
 use futures::AsyncReadExt;
 use futures::AsyncSeekExt;
 use futures::StreamExt;
 use log::debug;
 use log::warn;
+use opendal::ops::OpDelete;
+use opendal::ops::OpRead;
+use opendal::ops::OpStat;
+use opendal::ops::OpWrite;
 use opendal::EntryMode;
 use opendal::ErrorKind;
 use opendal::Operator;
 use sha2::Digest;
 use sha2::Sha256;
+use time::Duration;
 
 use super::utils::*;
 
@@ -74,18 +81,24 @@ macro_rules! behavior_write_tests {
                 test_write,
                 test_write_with_dir_path,
                 test_write_with_special_chars,
+                test_write_with_if_match,
+                test_write_with_if_none_match,
+                test_append_existing,
                 test_stat,
                 test_stat_dir,
                 test_stat_with_special_chars,
                 test_stat_not_cleaned_path,
                 test_stat_not_exist,
                 test_stat_root,
+                test_stat_with_if_none_match,
+                test_stat_with_if_modified_since,
                 test_read_full,
                 test_read_range,
                 test_read_large_range,
                 test_reader_range,
                 test_reader_from,
                 test_reader_tail,
+                test_read_with_suffix_range,
                 test_read_not_exist,
                 test_fuzz_range_reader,
                 test_fuzz_offset_reader,
@@ -96,8 +109,11 @@ macro_rules! behavior_write_tests {
                 test_delete_empty_dir,
                 test_delete_with_special_chars,
                 test_delete_not_existing,
+                test_delete_with_strict,
                 test_delete_stream,
                 test_append,
+                test_write_from_stream,
+                test_write_from_stream_abort,
             );
         )*
     };
@@ -107,6 +123,12 @@ macro_rules! behavior_write_tests {
 pub async fn test_create_dir(op: Operator) -> Result<()> {
     let path = format!("{}/", uuid::Uuid::new_v4());
 
+    if !op.info().can_create_dir() {
+        let res = op.create_dir(&path).await;
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+        return Ok(());
+    }
+
     op.create_dir(&path).await?;
 
     let meta = op.stat(&path).await?;
@@ -120,6 +142,12 @@ pub async fn test_create_dir(op: Operator) -> Result<()> {
 pub async fn test_create_dir_existing(op: Operator) -> Result<()> {
     let path = format!("{}/", uuid::Uuid::new_v4());
 
+    if !op.info().can_create_dir() {
+        let res = op.create_dir(&path).await;
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+        return Ok(());
+    }
+
     op.create_dir(&path).await?;
 
     op.create_dir(&path).await?;
@@ -171,6 +199,107 @@ pub async fn test_write_with_special_chars(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Write with stale etag should be rejected while fresh one succeeds.
+pub async fn test_write_with_if_match(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    if !op.info().can_write_with_if_match() {
+        let res = op
+            .write_with(&path, OpWrite::new().with_if_match("\"etag\""), content)
+            .await;
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+
+        op.delete(&path).await.expect("delete must succeed");
+        return Ok(());
+    }
+
+    let stale = op
+        .stat(&path)
+        .await?
+        .etag()
+        .expect("etag must exist")
+        .to_string();
+
+    let (content, _) = gen_bytes();
+    op.write_with(&path, OpWrite::new().with_if_match(&stale), content)
+        .await
+        .expect("write with fresh etag must succeed");
+
+    let (content, _) = gen_bytes();
+    let res = op
+        .write_with(&path, OpWrite::new().with_if_match(&stale), content)
+        .await;
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::ConditionNotMatch);
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Write with if_none_match `*` should only create new file.
+pub async fn test_write_with_if_none_match(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    if !op.info().can_write_with_if_none_match() {
+        let res = op
+            .write_with(&path, OpWrite::new().with_if_none_match("*"), content)
+            .await;
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+
+        let res = op
+            .writer_with(&path, OpWrite::new().with_if_none_match("*"))
+            .await;
+        assert_eq!(res.err().unwrap().kind(), ErrorKind::Unsupported);
+        return Ok(());
+    }
+
+    op.write_with(&path, OpWrite::new().with_if_none_match("*"), content)
+        .await
+        .expect("create only write must succeed");
+
+    let (content, _) = gen_bytes();
+    let res = op
+        .write_with(&path, OpWrite::new().with_if_none_match("*"), content)
+        .await;
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::ConditionNotMatch);
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Append twice should produce concatenated content.
+pub async fn test_append_existing(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content_one, size_one) = gen_bytes();
+    let (content_two, size_two) = gen_bytes();
+
+    if !op.info().can_write_can_append() {
+        let res = op.append(&path, content_one).await;
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+        return Ok(());
+    }
+
+    op.append(&path, content_one.clone())
+        .await
+        .expect("append must succeed");
+    op.append(&path, content_two.clone())
+        .await
+        .expect("append must succeed");
+
+    let bs = op.read(&path).await?;
+    assert_eq!(bs.len(), size_one + size_two, "read size");
+    assert_eq!(&bs[..size_one], content_one, "read first content");
+    assert_eq!(&bs[size_one..], content_two, "read second content");
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
 /// Stat existing file should return metadata
 pub async fn test_stat(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
@@ -186,6 +315,74 @@ pub async fn test_stat(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Stat with if_none_match should return ConditionNotMatch if etag matched.
+pub async fn test_stat_with_if_none_match(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    op.write(&path, content).await.expect("write must succeed");
+
+    if !op.info().can_conditional_stat() {
+        let res = op
+            .stat_with(&path, OpStat::new().with_if_none_match("\"etag\""))
+            .await;
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+
+        op.delete(&path).await.expect("delete must succeed");
+        return Ok(());
+    }
+
+    let meta = op.stat(&path).await?;
+    let etag = meta.etag().expect("etag must exist");
+
+    let res = op
+        .stat_with(&path, OpStat::new().with_if_none_match(etag))
+        .await;
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::ConditionNotMatch);
+
+    let meta = op
+        .stat_with(&path, OpStat::new().with_if_none_match("\"invalid_etag\""))
+        .await?;
+    assert_eq!(meta.mode(), EntryMode::FILE);
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Stat with if_modified_since should return ConditionNotMatch if not modified.
+pub async fn test_stat_with_if_modified_since(op: Operator) -> Result<()> {
+    if !op.info().can_conditional_stat() {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    op.write(&path, content).await.expect("write must succeed");
+
+    let meta = op.stat(&path).await?;
+    let last_modified = meta.last_modified().expect("last_modified must exist");
+
+    let res = op
+        .stat_with(
+            &path,
+            OpStat::new().with_if_modified_since(last_modified + Duration::hours(1)),
+        )
+        .await;
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::ConditionNotMatch);
+
+    let meta = op
+        .stat_with(
+            &path,
+            OpStat::new().with_if_modified_since(last_modified - Duration::hours(1)),
+        )
+        .await?;
+    assert_eq!(meta.mode(), EntryMode::FILE);
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
 /// Stat existing file should return metadata
 pub async fn test_stat_dir(op: Operator) -> Result<()> {
     let path = format!("{}/", uuid::Uuid::new_v4());
@@ -418,6 +615,24 @@ pub async fn test_reader_tail(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Read with suffix range should return the last N bytes.
+pub async fn test_read_with_suffix_range(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let content: Vec<u8> = (0..1000).map(|v| v as u8).collect();
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    let bs = op.read_with(&path, OpRead::new().with_range(..100)).await?;
+    assert_eq!(bs.len(), 100, "read size");
+    assert_eq!(bs, content[900..], "read content");
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
 /// Read not exist file should return NotFound
 pub async fn test_read_not_exist(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
@@ -629,6 +844,26 @@ pub async fn test_delete_not_existing(op: Operator) -> Result<()> {
     Ok(())
 }
 
+// Delete with strict should fail on not existing file.
+pub async fn test_delete_with_strict(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let (content, _) = gen_bytes();
+
+    op.write(&path, content).await.expect("write must succeed");
+
+    op.delete_with(&path, OpDelete::new().with_strict(true))
+        .await?;
+    assert!(!op.is_exist(&path).await?);
+
+    let err = op
+        .delete_with(&path, OpDelete::new().with_strict(true))
+        .await
+        .expect_err("delete not existing file with strict must fail");
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+
+    Ok(())
+}
+
 // Delete via stream.
 pub async fn test_delete_stream(op: Operator) -> Result<()> {
     let dir = uuid::Uuid::new_v4().to_string();
@@ -694,3 +929,60 @@ pub async fn test_append(op: Operator) -> Result<()> {
     op.delete(&path).await.expect("delete must succeed");
     Ok(())
 }
+
+/// Write from stream with multiple parts should succeed.
+pub async fn test_write_from_stream(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let size = 4 * 1024 * 1024;
+    let chunks: Vec<_> = (0..3).map(|_| gen_fixed_bytes(size)).collect();
+
+    let s = stream::iter(chunks.clone().into_iter().map(|v| Ok(Bytes::from(v))));
+    match op.write_from_stream(&path, s).await {
+        Ok(_) => (),
+        Err(err) if err.kind() == ErrorKind::Unsupported => {
+            warn!("service doesn't support write with append");
+            return Ok(());
+        }
+        Err(err) => return Err(err.into()),
+    }
+
+    let bs = op.read(&path).await?;
+    assert_eq!(bs.len(), size * 3, "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(chunks.concat())),
+        "read content"
+    );
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Write from stream should be aborted if stream returns an error.
+pub async fn test_write_from_stream_abort(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    let size = 9 * 1024 * 1024;
+
+    let s = stream::iter(vec![
+        Ok(Bytes::from(gen_fixed_bytes(size))),
+        Err(opendal::Error::new(
+            ErrorKind::Unexpected,
+            "stream failed in the middle",
+        )),
+    ]);
+    let err = op
+        .write_from_stream(&path, s)
+        .await
+        .expect_err("write must fail");
+    if err.kind() == ErrorKind::Unsupported {
+        warn!("service doesn't support write with append");
+        return Ok(());
+    }
+    assert_eq!(err.kind(), ErrorKind::Unexpected);
+
+    assert!(
+        !op.is_exist(&path).await?,
+        "aborted write must not create object"
+    );
+    Ok(())
+}
@@ -0,0 +1,199 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! LoggingLayer tests need to install a global logger, so they live in
+//! their own test binary.
+
+use std::sync::Mutex;
+use std::sync::Once;
+
+#[cfg(feature = "layers-logging-kv")]
+use log::kv;
+use log::Log;
+use log::Metadata;
+use log::Record;
+use once_cell::sync::Lazy;
+use opendal::layers::LoggingLayer;
+use opendal::services;
+use opendal::Operator;
+use tokio::sync::MutexGuard;
+
+static LOGGER: Lazy<CaptureLogger> = Lazy::new(|| CaptureLogger {
+    records: Mutex::new(Vec::new()),
+});
+
+static INIT: Once = Once::new();
+static TEST_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+struct CaptureLogger {
+    records: Mutex<Vec<CapturedRecord>>,
+}
+
+struct CapturedRecord {
+    message: String,
+    #[cfg(feature = "layers-logging-kv")]
+    kvs: Vec<(String, String)>,
+}
+
+#[cfg(feature = "layers-logging-kv")]
+impl CapturedRecord {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.kvs
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+#[cfg(feature = "layers-logging-kv")]
+struct KvCollector<'a>(&'a mut Vec<(String, String)>);
+
+#[cfg(feature = "layers-logging-kv")]
+impl<'kvs, 'a> kv::Visitor<'kvs> for KvCollector<'a> {
+    fn visit_pair(&mut self, key: kv::Key<'kvs>, value: kv::Value<'kvs>) -> Result<(), kv::Error> {
+        self.0.push((key.to_string(), value.to_string()));
+        Ok(())
+    }
+}
+
+/// Collect structured fields of given record.
+#[cfg(feature = "layers-logging-kv")]
+fn collect_kvs(record: &Record) -> Vec<(String, String)> {
+    let mut kvs = Vec::new();
+    record
+        .key_values()
+        .visit(&mut KvCollector(&mut kvs))
+        .expect("visit key values must succeed");
+    kvs
+}
+
+/// Install the capture logger and take the test lock.
+///
+/// All tests share the same global logger, so they are run one by one
+/// with the captured records cleared in between.
+async fn setup() -> MutexGuard<'static, ()> {
+    INIT.call_once(|| {
+        log::set_logger(&*LOGGER).expect("logger must be set");
+        log::set_max_level(log::LevelFilter::Trace);
+    });
+
+    let guard = TEST_LOCK.lock().await;
+    LOGGER
+        .records
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .clear();
+    guard
+}
+
+impl Log for CaptureLogger {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        self.records
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .push(CapturedRecord {
+                message: record.args().to_string(),
+                #[cfg(feature = "layers-logging-kv")]
+                kvs: collect_kvs(record),
+            });
+    }
+
+    fn flush(&self) {}
+}
+
+#[tokio::test]
+async fn test_logging_path_redactor() {
+    let _guard = setup().await;
+
+    let op = Operator::new(services::Memory::default())
+        .expect("must init")
+        .layer(LoggingLayer::default().with_path_redactor(|_| "***".to_string()))
+        .finish();
+
+    op.write("tenant-42/secret", "Hello, World!")
+        .await
+        .expect("write must succeed");
+    op.stat("tenant-42/secret")
+        .await
+        .expect("stat must succeed");
+    op.read("tenant-42/secret")
+        .await
+        .expect("read must succeed");
+    let _ = op.list("tenant-42/").await.expect("list must succeed");
+
+    let records = LOGGER.records.lock().unwrap_or_else(|err| err.into_inner());
+    let records: Vec<_> = records
+        .iter()
+        .filter(|v| v.message.starts_with("service=memory") && v.message.contains("path="))
+        .collect();
+
+    assert!(!records.is_empty());
+    for record in records {
+        let message = &record.message;
+        assert!(!message.contains("tenant-42"), "path leaked: {message}");
+        assert!(message.contains("path=***"), "path not redacted: {message}");
+        #[cfg(feature = "layers-logging-kv")]
+        assert_eq!(record.get("path"), Some("***"), "path not redacted");
+    }
+}
+
+#[cfg(feature = "layers-logging-kv")]
+#[tokio::test]
+async fn test_logging_structured_fields() {
+    let _guard = setup().await;
+
+    let op = Operator::new(services::Memory::default())
+        .expect("must init")
+        .layer(LoggingLayer::default())
+        .finish();
+
+    op.write("structured", "Hello, World!")
+        .await
+        .expect("write must succeed");
+    let _ = op.stat("not_exist").await.expect_err("stat must fail");
+
+    let records = LOGGER.records.lock().unwrap_or_else(|err| err.into_inner());
+
+    let write = records
+        .iter()
+        .find(|v| v.get("operation") == Some("write") && v.message.ends_with("-> started"))
+        .expect("write started record must exist");
+    assert_eq!(write.get("service"), Some("memory"));
+    assert_eq!(write.get("path"), Some("structured"));
+    assert!(
+        write.message.contains("path=structured"),
+        "human readable message must be kept: {}",
+        write.message
+    );
+
+    let written = records
+        .iter()
+        .find(|v| v.message.contains("data write") && v.get("path") == Some("structured"))
+        .expect("data write record must exist");
+    assert_eq!(written.get("bytes"), Some("13"));
+
+    let stat = records
+        .iter()
+        .find(|v| v.get("operation") == Some("stat") && v.get("error_kind").is_some())
+        .expect("stat error record must exist");
+    assert_eq!(stat.get("path"), Some("not_exist"));
+    assert_eq!(stat.get("error_kind"), Some("NotFound"));
+}